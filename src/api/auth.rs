@@ -1,5 +1,6 @@
 //! Authentication API types
 
+use crate::storage::Credentials;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -35,7 +36,8 @@ pub enum QrcodePollStatus {
 impl From<i32> for QrcodePollStatus {
     fn from(code: i32) -> Self {
         match code {
-            86101 => QrcodePollStatus::Waiting,
+            // 86039 is the TV login's "not scanned yet"
+            86101 | 86039 => QrcodePollStatus::Waiting,
             86090 => QrcodePollStatus::Scanned,
             0 => QrcodePollStatus::Success,
             86038 => QrcodePollStatus::Expired,
@@ -44,7 +46,201 @@ impl From<i32> for QrcodePollStatus {
     }
 }
 
+/// Logged-in user info from the nav endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct NavInfo {
+    #[serde(rename = "isLogin", default)]
+    pub is_login: bool,
+    pub mid: Option<i64>,
+    pub uname: Option<String>,
+    pub face: Option<String>,
+    /// Coin balance
+    pub money: Option<f64>,
+    #[serde(rename = "vipStatus")]
+    pub vip_status: Option<i32>,
+    #[serde(rename = "vipType")]
+    pub vip_type: Option<i32>,
+    /// 大会员 expiry in milliseconds
+    #[serde(rename = "vipDueDate")]
+    pub vip_due_date: Option<i64>,
+    pub level_info: Option<LevelInfo>,
+}
+
+impl NavInfo {
+    /// Whether the account currently has an active 大会员
+    pub fn is_vip(&self) -> bool {
+        self.vip_status == Some(1)
+    }
+}
+
+/// Account level and experience
+#[derive(Debug, Clone, Deserialize)]
+pub struct LevelInfo {
+    pub current_level: i32,
+    /// Experience needed to reach the current level
+    #[serde(default)]
+    pub current_min: i64,
+    pub current_exp: i64,
+    /// Experience needed for the next level, `"--"` at the top level
+    #[serde(default, deserialize_with = "exp_or_none")]
+    pub next_exp: Option<i64>,
+}
+
+impl LevelInfo {
+    /// Progress towards the next level in `0.0..=1.0`, full at the top level
+    pub fn progress(&self) -> f64 {
+        match self.next_exp {
+            Some(next) if next > self.current_min => {
+                let gained = (self.current_exp - self.current_min) as f64;
+                (gained / (next - self.current_min) as f64).clamp(0.0, 1.0)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+fn exp_or_none<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(value.as_i64())
+}
+
+/// Follow and post counts from `/x/web-interface/nav/stat`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NavStat {
+    #[serde(default)]
+    pub following: i64,
+    #[serde(default)]
+    pub follower: i64,
+    #[serde(default)]
+    pub dynamic_count: i64,
+}
+
 pub struct QrcodePollResult {
     pub data: Option<QrcodePollData>,
     pub cookies: Vec<(String, String)>,
 }
+
+/// TV-client QR code data
+#[derive(Debug, Deserialize)]
+pub struct TvQrcodeData {
+    pub url: String,
+    pub auth_code: String,
+}
+
+/// Tokens returned by a successful TV-client QR login
+#[derive(Debug, Clone, Deserialize)]
+pub struct TvLoginData {
+    pub mid: i64,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Token lifetime in seconds
+    pub expires_in: i64,
+    pub cookie_info: Option<TvCookieInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TvCookieInfo {
+    pub cookies: Vec<TvCookie>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TvCookie {
+    pub name: String,
+    pub value: String,
+}
+
+impl TvLoginData {
+    /// Build credentials from the web cookies plus the long-lived app tokens
+    pub fn into_credentials(self) -> Option<Credentials> {
+        let cookies: Vec<(String, String)> = self
+            .cookie_info
+            .map(|info| {
+                info.cookies
+                    .into_iter()
+                    .map(|c| (c.name, c.value))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut creds = Credentials::from_cookies(&cookies, None)?;
+        creds.access_token = Some(self.access_token);
+        creds.app_refresh_token = Some(self.refresh_token);
+        creds.token_expires_at = Some(chrono::Utc::now().timestamp() + self.expires_in);
+        Some(creds)
+    }
+}
+
+/// Device id cookies handed out by `/x/frontend/finger/spi`
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceIds {
+    pub b_3: String,
+    pub b_4: String,
+}
+
+/// Whether the web cookies should be refreshed (`/x/passport-login/web/cookie/info`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CookieRefreshInfo {
+    pub refresh: bool,
+    /// Server time in milliseconds
+    pub timestamp: i64,
+}
+
+/// Token part of an app token refresh response
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppTokenInfo {
+    pub mid: i64,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+}
+
+/// Response of `/x/passport-login/oauth2/refresh_token`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppTokenRefreshData {
+    pub token_info: AppTokenInfo,
+    pub cookie_info: Option<TvCookieInfo>,
+}
+
+impl AppTokenRefreshData {
+    /// Same layout as a TV login once the token part is flattened
+    pub fn into_credentials(self) -> Option<Credentials> {
+        TvLoginData {
+            mid: self.token_info.mid,
+            access_token: self.token_info.access_token,
+            refresh_token: self.token_info.refresh_token,
+            expires_in: self.token_info.expires_in,
+            cookie_info: self.cookie_info,
+        }
+        .into_credentials()
+    }
+}
+
+pub struct TvPollResult {
+    /// Top-level response code, see `QrcodePollStatus`
+    pub code: i32,
+    pub data: Option<TvLoginData>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_info_progress() {
+        let info: LevelInfo = serde_json::from_str(
+            r#"{"current_level":4,"current_min":4500,"current_exp":7200,"next_exp":10800}"#,
+        )
+        .unwrap();
+        assert!((info.progress() - 2700.0 / 6300.0).abs() < 1e-9);
+
+        let top: LevelInfo = serde_json::from_str(
+            r#"{"current_level":6,"current_min":28800,"current_exp":30000,"next_exp":"--"}"#,
+        )
+        .unwrap();
+        assert_eq!(top.next_exp, None);
+        assert_eq!(top.progress(), 1.0);
+    }
+}
@@ -0,0 +1,150 @@
+//! 追番/追剧 list of the logged-in user
+
+use crate::util::normalize_image_url;
+use serde::Deserialize;
+
+/// Which follow list to load, the `type` parameter of the follow API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FollowType {
+    /// 番剧
+    #[default]
+    Anime = 1,
+    /// 电影、电视剧、纪录片
+    Drama = 2,
+}
+
+impl FollowType {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FollowType::Anime => "追番",
+            FollowType::Drama => "追剧",
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            FollowType::Anime => FollowType::Drama,
+            FollowType::Drama => FollowType::Anime,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BangumiFollowData {
+    #[serde(default)]
+    pub list: Option<Vec<BangumiFollowItem>>,
+    pub pn: Option<i32>,
+    pub ps: Option<i32>,
+    pub total: Option<i32>,
+}
+
+impl BangumiFollowData {
+    pub fn items(self) -> Vec<BangumiFollowItem> {
+        self.list.unwrap_or_default()
+    }
+
+    /// Total number of followed series
+    pub fn total(&self) -> i32 {
+        self.total.unwrap_or(0)
+    }
+}
+
+/// One followed series
+#[derive(Debug, Clone, Deserialize)]
+pub struct BangumiFollowItem {
+    pub season_id: i64,
+    pub title: Option<String>,
+    pub cover: Option<String>,
+    /// e.g. "番剧" or "电视剧"
+    pub season_type_name: Option<String>,
+    /// Newest released episode
+    pub new_ep: Option<NewEpisode>,
+    /// Where the user left off, e.g. "看到第3话 12:34", empty before the first watch
+    pub progress: Option<String>,
+    /// e.g. "会员专享"
+    pub badge: Option<String>,
+    /// 1 once every episode is out
+    pub is_finish: Option<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewEpisode {
+    pub id: i64,
+    /// e.g. "更新至第12话" or "全12话"
+    pub index_show: Option<String>,
+    pub title: Option<String>,
+    pub long_title: Option<String>,
+}
+
+impl BangumiFollowItem {
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or("无标题")
+    }
+
+    /// Newest episode indicator, "更新至第12话" style
+    pub fn latest_label(&self) -> String {
+        self.new_ep
+            .as_ref()
+            .and_then(|ep| ep.index_show.clone())
+            .unwrap_or_else(|| "暂无剧集".to_string())
+    }
+
+    pub fn progress_label(&self) -> &str {
+        match self.progress.as_deref() {
+            Some(progress) if !progress.is_empty() => progress,
+            _ => "尚未观看",
+        }
+    }
+
+    /// Page of the newest episode, what Enter plays
+    pub fn latest_episode_url(&self) -> Option<String> {
+        let ep = self.new_ep.as_ref()?;
+        Some(format!("https://www.bilibili.com/bangumi/play/ep{}", ep.id))
+    }
+
+    /// Label shown in the player banner, the series title with the episode
+    ///
+    /// Anime episodes are titled by their number ("12"), others by name ("正片").
+    pub fn playing_label(&self) -> String {
+        match self.new_ep.as_ref().and_then(|ep| ep.title.as_deref()) {
+            Some(ep) if !ep.is_empty() && ep.chars().all(|c| c.is_ascii_digit()) => {
+                format!("{} 第{}话", self.display_title(), ep)
+            }
+            Some(ep) if !ep.is_empty() => format!("{} {}", self.display_title(), ep),
+            _ => self.display_title().to_string(),
+        }
+    }
+
+    pub fn cover_url(&self) -> Option<String> {
+        self.cover.as_deref().map(normalize_image_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_item_labels() {
+        let item: BangumiFollowItem = serde_json::from_str(
+            r#"{"season_id":1,"title":"示例番剧","new_ep":{"id":733,"index_show":"更新至第12话","title":"12"},"progress":""}"#,
+        )
+        .unwrap();
+        assert_eq!(item.latest_label(), "更新至第12话");
+        assert_eq!(item.progress_label(), "尚未观看");
+        assert_eq!(
+            item.latest_episode_url().as_deref(),
+            Some("https://www.bilibili.com/bangumi/play/ep733")
+        );
+        assert_eq!(item.playing_label(), "示例番剧 第12话");
+
+        let item: BangumiFollowItem =
+            serde_json::from_str(r#"{"season_id":2,"new_ep":null}"#).unwrap();
+        assert_eq!(item.latest_label(), "暂无剧集");
+        assert_eq!(item.latest_episode_url(), None);
+    }
+}
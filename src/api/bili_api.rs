@@ -0,0 +1,189 @@
+//! The slice of the Bilibili API that pages load their lists from
+//!
+//! Pages take `&impl BiliApi` instead of [`ApiClient`] so their load paths can be
+//! tested against [`MockApi`](super::test_api::MockApi) without the network.
+
+use super::bangumi::{BangumiFollowData, FollowType};
+use super::client::ApiClient;
+use super::comment::{CommentData, CommentSort};
+use super::dynamic::DynamicFeedData;
+use super::favorite::{FavFoldersData, FavResourceData};
+use super::history::HistoryData;
+use super::recommend::{FreshType, VideoItem};
+use super::search::SearchData;
+use super::space::{CollectionArchivesData, CollectionKind, CollectionsData, SpaceVideoData};
+use super::tag::TagDetailData;
+use anyhow::Result;
+use std::future::Future;
+
+pub trait BiliApi {
+    /// Whether requests carry a login session, the feed falls back to 热门 otherwise
+    fn is_logged_in(&self) -> bool;
+
+    /// Page `fresh_idx` of the recommendation feed
+    fn get_recommendations_paged(
+        &self,
+        fresh_idx: i32,
+        fresh_type: FreshType,
+    ) -> impl Future<Output = Result<Vec<VideoItem>>> + Send;
+
+    fn search_videos(
+        &self,
+        keyword: &str,
+        page: i32,
+    ) -> impl Future<Output = Result<SearchData>> + Send;
+
+    fn get_dynamic_feed(
+        &self,
+        offset: Option<&str>,
+        feed_type: Option<&str>,
+        host_mid: Option<i64>,
+    ) -> impl Future<Output = Result<DynamicFeedData>> + Send;
+
+    /// Watch history after the given cursor, the first page when all are `None`
+    fn get_history(
+        &self,
+        max: Option<i64>,
+        view_at: Option<i64>,
+        business: Option<&str>,
+    ) -> impl Future<Output = Result<HistoryData>> + Send;
+
+    /// Page `page` of the uploads of user `mid`
+    fn get_space_videos(
+        &self,
+        mid: i64,
+        page: i32,
+    ) -> impl Future<Output = Result<SpaceVideoData>> + Send;
+
+    /// 合集 and 系列 of user `mid`
+    fn get_user_collections(
+        &self,
+        mid: i64,
+    ) -> impl Future<Output = Result<CollectionsData>> + Send;
+
+    /// Videos of the 合集 or 系列 `id` of user `mid`
+    fn get_collection_videos(
+        &self,
+        mid: i64,
+        kind: CollectionKind,
+        id: i64,
+    ) -> impl Future<Output = Result<CollectionArchivesData>> + Send;
+
+    /// Page `page` of the newest videos filed under tag `tag_id`
+    fn get_tag_videos(
+        &self,
+        tag_id: i64,
+        page: i32,
+    ) -> impl Future<Output = Result<TagDetailData>> + Send;
+
+    /// Page `page` of the comments on item `oid` of reply type `comment_type`
+    fn get_comments_by_type(
+        &self,
+        oid: i64,
+        comment_type: i32,
+        page: i32,
+        sort: CommentSort,
+    ) -> impl Future<Output = Result<CommentData>> + Send;
+
+    /// Page `page` of the 追番 or 追剧 list of user `mid`
+    fn get_bangumi_follow(
+        &self,
+        mid: i64,
+        follow_type: FollowType,
+        page: i32,
+    ) -> impl Future<Output = Result<BangumiFollowData>> + Send;
+
+    /// Favorite folders created by user `mid`
+    fn get_fav_folders(&self, mid: i64) -> impl Future<Output = Result<FavFoldersData>> + Send;
+
+    /// Page `page` of the items in favorite folder `media_id`
+    fn get_fav_videos(
+        &self,
+        media_id: i64,
+        page: i32,
+    ) -> impl Future<Output = Result<FavResourceData>> + Send;
+}
+
+impl BiliApi for ApiClient {
+    fn is_logged_in(&self) -> bool {
+        ApiClient::is_logged_in(self)
+    }
+
+    async fn get_recommendations_paged(
+        &self,
+        fresh_idx: i32,
+        fresh_type: FreshType,
+    ) -> Result<Vec<VideoItem>> {
+        ApiClient::get_recommendations_paged(self, fresh_idx, fresh_type).await
+    }
+
+    async fn search_videos(&self, keyword: &str, page: i32) -> Result<SearchData> {
+        ApiClient::search_videos(self, keyword, page).await
+    }
+
+    async fn get_dynamic_feed(
+        &self,
+        offset: Option<&str>,
+        feed_type: Option<&str>,
+        host_mid: Option<i64>,
+    ) -> Result<DynamicFeedData> {
+        ApiClient::get_dynamic_feed(self, offset, feed_type, host_mid).await
+    }
+
+    async fn get_history(
+        &self,
+        max: Option<i64>,
+        view_at: Option<i64>,
+        business: Option<&str>,
+    ) -> Result<HistoryData> {
+        ApiClient::get_history(self, max, view_at, business).await
+    }
+
+    async fn get_space_videos(&self, mid: i64, page: i32) -> Result<SpaceVideoData> {
+        ApiClient::get_space_videos(self, mid, page).await
+    }
+
+    async fn get_user_collections(&self, mid: i64) -> Result<CollectionsData> {
+        ApiClient::get_user_collections(self, mid).await
+    }
+
+    async fn get_collection_videos(
+        &self,
+        mid: i64,
+        kind: CollectionKind,
+        id: i64,
+    ) -> Result<CollectionArchivesData> {
+        ApiClient::get_collection_videos(self, mid, kind, id).await
+    }
+
+    async fn get_tag_videos(&self, tag_id: i64, page: i32) -> Result<TagDetailData> {
+        ApiClient::get_tag_videos(self, tag_id, page).await
+    }
+
+    async fn get_comments_by_type(
+        &self,
+        oid: i64,
+        comment_type: i32,
+        page: i32,
+        sort: CommentSort,
+    ) -> Result<CommentData> {
+        ApiClient::get_comments_by_type(self, oid, comment_type, page, sort).await
+    }
+
+    async fn get_bangumi_follow(
+        &self,
+        mid: i64,
+        follow_type: FollowType,
+        page: i32,
+    ) -> Result<BangumiFollowData> {
+        ApiClient::get_bangumi_follow(self, mid, follow_type, page).await
+    }
+
+    async fn get_fav_folders(&self, mid: i64) -> Result<FavFoldersData> {
+        ApiClient::get_fav_folders(self, mid).await
+    }
+
+    async fn get_fav_videos(&self, media_id: i64, page: i32) -> Result<FavResourceData> {
+        ApiClient::get_fav_videos(self, media_id, page).await
+    }
+}
@@ -0,0 +1,236 @@
+//! av/BV id conversion and video reference parsing
+//! See: https://socialsisteryi.github.io/bilibili-API-collect/docs/misc/bvid_desc.html
+
+const XOR_CODE: u64 = 23442827791579;
+const MASK_CODE: u64 = 2251799813685247;
+const MAX_AID: u64 = 1 << 51;
+const BASE: u64 = 58;
+const ALPHABET: &[u8] = b"FcwAPNKTMug3GV5Lj7EJnHpWsx4tb8haYeviqBz6rkCy12mUSDQX9RdoZf";
+const BVID_LEN: usize = 12;
+
+/// Convert an av number to its bvid
+pub fn av_to_bv(aid: i64) -> String {
+    let mut bytes = *b"BV1000000000";
+    let mut idx = BVID_LEN - 1;
+    let mut tmp = (MAX_AID | aid as u64) ^ XOR_CODE;
+    while tmp > 0 && idx >= 3 {
+        bytes[idx] = ALPHABET[(tmp % BASE) as usize];
+        tmp /= BASE;
+        idx -= 1;
+    }
+    bytes.swap(3, 9);
+    bytes.swap(4, 7);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Convert a bvid to its av number, returns None for malformed input
+pub fn bv_to_av(bvid: &str) -> Option<i64> {
+    let mut bytes = bvid.as_bytes().to_vec();
+    if bytes.len() != BVID_LEN || !bytes.starts_with(b"BV1") {
+        return None;
+    }
+    bytes.swap(3, 9);
+    bytes.swap(4, 7);
+    let tmp = bytes[3..].iter().try_fold(0u64, |acc, c| {
+        ALPHABET
+            .iter()
+            .position(|a| a == c)
+            .map(|pos| acc * BASE + pos as u64)
+    })?;
+    Some(((tmp & MASK_CODE) ^ XOR_CODE) as i64)
+}
+
+/// A video reference parsed from user input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VideoRef {
+    /// A video with both ids resolved
+    Video { bvid: String, aid: i64 },
+    /// A b23.tv short link that needs to be followed first
+    ShortLink(String),
+}
+
+/// Parse a bvid, av number, bilibili URL or b23.tv short link
+pub fn parse_video_ref(input: &str) -> Option<VideoRef> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    if let Some(bvid) = find_bvid(input) {
+        let aid = bv_to_av(&bvid)?;
+        return Some(VideoRef::Video { bvid, aid });
+    }
+
+    if let Some(aid) = find_aid(input) {
+        return Some(VideoRef::Video {
+            bvid: av_to_bv(aid),
+            aid,
+        });
+    }
+
+    // Shared text usually looks like "【title】 https://b23.tv/xxxx"
+    input
+        .split_whitespace()
+        .find(|part| part.contains("b23.tv/"))
+        .map(|part| {
+            if part.starts_with("http://") || part.starts_with("https://") {
+                part.to_string()
+            } else {
+                format!("https://{}", part)
+            }
+        })
+        .map(VideoRef::ShortLink)
+}
+
+/// Like [`parse_video_ref`], but only when the whole input is a single id or link
+///
+/// For the search box: keywords that merely mention an id and bare numbers stay
+/// keyword searches, an av number needs its `av` prefix.
+pub fn parse_exact_video_ref(input: &str) -> Option<VideoRef> {
+    let input = input.trim();
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return None;
+    }
+    let is_link = input.contains("bilibili.com/") || input.contains("b23.tv/");
+    let is_bvid = input.len() == BVID_LEN && find_bvid(input).is_some();
+    let is_aid = input.len() > 2
+        && input
+            .get(..2)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("av"))
+        && input[2..].bytes().all(|b| b.is_ascii_digit());
+    if is_link || is_bvid || is_aid {
+        parse_video_ref(input)
+    } else {
+        None
+    }
+}
+
+fn is_boundary(byte: Option<&u8>) -> bool {
+    byte.is_none_or(|b| !b.is_ascii_alphanumeric())
+}
+
+// Find a standalone "BV1xxxxxxxxx" token (case-insensitive prefix)
+fn find_bvid(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    if bytes.len() < BVID_LEN {
+        return None;
+    }
+    (0..=bytes.len() - BVID_LEN).find_map(|i| {
+        let cand = &bytes[i..i + BVID_LEN];
+        if !cand[..2].eq_ignore_ascii_case(b"BV")
+            || !cand[2..].iter().all(|b| b.is_ascii_alphanumeric())
+            || !is_boundary(i.checked_sub(1).and_then(|j| bytes.get(j)))
+            || !is_boundary(bytes.get(i + BVID_LEN))
+        {
+            return None;
+        }
+        let bvid = format!("BV{}", std::str::from_utf8(&cand[2..]).ok()?);
+        bv_to_av(&bvid).map(|_| bvid)
+    })
+}
+
+// Find a standalone "av123" token, or accept a bare number
+fn find_aid(s: &str) -> Option<i64> {
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        return s.parse().ok().filter(|aid| *aid > 0);
+    }
+    let bytes = s.as_bytes();
+    (0..bytes.len().saturating_sub(2)).find_map(|i| {
+        if !bytes[i..i + 2].eq_ignore_ascii_case(b"av")
+            || !is_boundary(i.checked_sub(1).and_then(|j| bytes.get(j)))
+        {
+            return None;
+        }
+        let digits: String = s[i + 2..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if digits.is_empty() || !is_boundary(bytes.get(i + 2 + digits.len())) {
+            return None;
+        }
+        digits.parse().ok().filter(|aid: &i64| *aid > 0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_av_bv_roundtrip() {
+        assert_eq!(av_to_bv(170001), "BV17x411w7KC");
+        assert_eq!(bv_to_av("BV17x411w7KC"), Some(170001));
+        assert_eq!(bv_to_av("BV1L9Uoa9EUx"), Some(111298867365120));
+        assert_eq!(av_to_bv(111298867365120), "BV1L9Uoa9EUx");
+    }
+
+    #[test]
+    fn test_bv_to_av_rejects_invalid() {
+        assert_eq!(bv_to_av("BV17x411w7K"), None);
+        assert_eq!(bv_to_av("BV17x411w70C"), None);
+        assert_eq!(bv_to_av("av170001"), None);
+    }
+
+    #[test]
+    fn test_parse_video_ref() {
+        let expected = Some(VideoRef::Video {
+            bvid: "BV17x411w7KC".to_string(),
+            aid: 170001,
+        });
+        assert_eq!(parse_video_ref("BV17x411w7KC"), expected);
+        assert_eq!(parse_video_ref("bv17x411w7KC"), expected);
+        assert_eq!(parse_video_ref("av170001"), expected);
+        assert_eq!(parse_video_ref("AV170001"), expected);
+        assert_eq!(parse_video_ref(" 170001 "), expected);
+        assert_eq!(
+            parse_video_ref("https://www.bilibili.com/video/BV17x411w7KC/?p=2&t=30"),
+            expected
+        );
+        assert_eq!(
+            parse_video_ref("https://m.bilibili.com/video/av170001"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_short_link() {
+        assert_eq!(
+            parse_video_ref("【标题】 https://b23.tv/abcdEFG"),
+            Some(VideoRef::ShortLink("https://b23.tv/abcdEFG".to_string()))
+        );
+        assert_eq!(
+            parse_video_ref("b23.tv/abcdEFG"),
+            Some(VideoRef::ShortLink("https://b23.tv/abcdEFG".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_exact_video_ref() {
+        let expected = Some(VideoRef::Video {
+            bvid: "BV17x411w7KC".to_string(),
+            aid: 170001,
+        });
+        assert_eq!(parse_exact_video_ref(" av170001 "), expected);
+        assert_eq!(parse_exact_video_ref("BV17x411w7KC"), expected);
+        assert_eq!(
+            parse_exact_video_ref("https://www.bilibili.com/video/av170001"),
+            expected
+        );
+        assert_eq!(
+            parse_exact_video_ref("b23.tv/abcdEFG"),
+            Some(VideoRef::ShortLink("https://b23.tv/abcdEFG".to_string()))
+        );
+        // Keyword searches
+        assert_eq!(parse_exact_video_ref("170001"), None);
+        assert_eq!(parse_exact_video_ref("avatar"), None);
+        assert_eq!(parse_exact_video_ref("av170001 解说"), None);
+        assert_eq!(parse_exact_video_ref("av"), None);
+    }
+
+    #[test]
+    fn test_parse_unrecognized() {
+        assert_eq!(parse_video_ref(""), None);
+        assert_eq!(parse_video_ref("hello world"), None);
+        assert_eq!(parse_video_ref("https://www.bilibili.com/"), None);
+    }
+}
@@ -1,14 +1,39 @@
 //! Bilibili API Client with cookie management and WBI signing
 
 use super::wbi;
-use crate::storage::Credentials;
+use crate::storage::{AppConfig, Credentials};
 use anyhow::{anyhow, Result};
 use reqwest::header::{HeaderMap, HeaderValue, COOKIE, REFERER, USER_AGENT};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::RwLock;
+use std::time::Duration;
 
-const UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+/// Desktop Chrome UA, Bilibili answers unknown clients with more -412 (风控) rejections
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+
+/// Configured User-Agent, empty for [`DEFAULT_USER_AGENT`]
+static USER_AGENT_OVERRIDE: RwLock<String> = RwLock::new(String::new());
+
+/// Use `user_agent` for API, image and stream requests from now on, empty restores the default
+pub fn set_user_agent(user_agent: &str) {
+    *USER_AGENT_OVERRIDE
+        .write()
+        .expect("user agent lock poisoned") = user_agent.trim().to_string();
+}
+
+/// User-Agent sent with every request, also handed to mpv and yt-dlp
+pub fn user_agent() -> String {
+    let configured = USER_AGENT_OVERRIDE
+        .read()
+        .expect("user agent lock poisoned");
+    if configured.is_empty() {
+        DEFAULT_USER_AGENT.to_string()
+    } else {
+        configured.clone()
+    }
+}
 
 pub enum BilibiliApiDomain {
     Main,
@@ -33,6 +58,106 @@ pub struct ApiResponse<T> {
     pub data: Option<T>,
 }
 
+/// Whether the cookie string carries a login session
+fn has_session(cookie_str: &str) -> bool {
+    cookie_str.split(';').any(|part| {
+        part.trim()
+            .split_once('=')
+            .is_some_and(|(name, value)| name == "SESSDATA" && !value.is_empty())
+    })
+}
+
+fn csrf_from_cookies(cookie_str: &str) -> Option<String> {
+    cookie_str.split(';').find_map(|part| {
+        part.trim()
+            .split_once('=')
+            .filter(|(name, _)| *name == "bili_jct")
+            .map(|(_, value)| value.to_string())
+    })
+}
+
+/// Returned when a request runs past the configured timeout
+#[derive(Debug)]
+pub struct RequestTimeout;
+
+impl std::fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("请求超时")
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+/// Whether an error came from a request timing out
+pub fn is_timeout(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<RequestTimeout>())
+}
+
+/// Keep timeouts apart from other network failures
+fn network_error(err: reqwest::Error) -> anyhow::Error {
+    if err.is_timeout() {
+        anyhow::Error::new(RequestTimeout)
+    } else {
+        err.into()
+    }
+}
+
+/// How much of an undecodable body is written to the log file
+const LOG_BODY_BYTES: usize = 512;
+
+/// Loose envelope used to recover `code`/`message` when the typed decode fails
+#[derive(Deserialize)]
+struct RawEnvelope {
+    code: Option<i64>,
+    message: Option<String>,
+}
+
+/// Endpoint path of a URL, without scheme, host or query
+fn endpoint_name(url: &str) -> &str {
+    let path = url.split('?').next().unwrap_or(url);
+    match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => path,
+    }
+}
+
+/// Longest prefix of `s` that fits in `max` bytes without splitting a char
+fn prefix_bytes(s: &str, max: usize) -> &str {
+    let mut end = max.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Build the error message for a body that failed to decode
+fn decode_error_message(url: &str, body: &str, err: &serde_json::Error) -> String {
+    let mut msg = format!("Failed to decode {}: {}", endpoint_name(url), err);
+    if let Ok(env) = serde_json::from_str::<RawEnvelope>(body) {
+        if let Some(code) = env.code {
+            msg.push_str(&format!(" (code {}", code));
+            if let Some(message) = env.message.filter(|m| !m.is_empty()) {
+                msg.push_str(&format!(": {}", message));
+            }
+            msg.push(')');
+        }
+    }
+    msg
+}
+
+/// Decode a response body, reporting the endpoint and API status on failure
+fn decode_json<T: for<'de> Deserialize<'de>>(url: &str, body: &str) -> Result<T> {
+    serde_json::from_str(body).map_err(|e| {
+        let msg = decode_error_message(url, body, &e);
+        let _ = crate::storage::append_log(&format!(
+            "{}\n  body: {}",
+            msg,
+            prefix_bytes(body, LOG_BODY_BYTES)
+        ));
+        anyhow!(msg)
+    })
+}
+
 /// WBI keys for signing requests
 #[derive(Debug, Clone)]
 pub struct WbiKeys {
@@ -44,6 +169,8 @@ pub struct ApiClient {
     client: Client,
     cookies: RwLock<Option<String>>,
     wbi_keys: RwLock<Option<WbiKeys>>,
+    /// Per-request timeout in milliseconds, can be changed while running
+    timeout_ms: AtomicU64,
 }
 
 impl ApiClient {
@@ -55,6 +182,11 @@ impl ApiClient {
                 .expect("Failed to create HTTP client"),
             cookies: RwLock::new(None),
             wbi_keys: RwLock::new(None),
+            timeout_ms: AtomicU64::new(
+                crate::storage::NetworkConfig::default()
+                    .request_timeout()
+                    .as_millis() as u64,
+            ),
         }
     }
 
@@ -64,9 +196,21 @@ impl ApiClient {
         client
     }
 
+    /// Client for `credentials`, anonymous without, using the configured timeout and User-Agent
+    ///
+    /// Every client the app talks to the API with is built here, so none misses a setting.
+    pub fn from_config(credentials: Option<&Credentials>, config: &AppConfig) -> Self {
+        set_user_agent(&config.user_agent);
+        let client = match credentials {
+            Some(credentials) => Self::with_cookies(credentials),
+            None => Self::new(),
+        };
+        client.set_timeout(config.network.request_timeout());
+        client
+    }
+
     fn default_headers() -> HeaderMap {
         let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static(UA));
         headers.insert(
             REFERER,
             HeaderValue::from_static("https://www.bilibili.com/"),
@@ -75,26 +219,78 @@ impl ApiClient {
     }
 
     pub fn set_credentials(&self, credentials: &Credentials) {
-        let cookie_str = format!(
-            "SESSDATA={}; bili_jct={}; DedeUserID={}",
-            credentials.sessdata, credentials.bili_jct, credentials.dede_user_id
-        );
+        let cookie_str = credentials
+            .cookies()
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
         *self.cookies.write().expect("cookies lock poisoned") = Some(cookie_str);
     }
 
+    /// The `bili_jct` cookie, sent as `csrf` by write endpoints
+    pub fn get_csrf(&self) -> Option<String> {
+        self.cookies
+            .read()
+            .expect("cookies lock poisoned")
+            .as_deref()
+            .and_then(csrf_from_cookies)
+    }
+
+    /// Whether requests are sent with a login session, the feed is personalized only then
+    pub fn is_logged_in(&self) -> bool {
+        self.cookies
+            .read()
+            .expect("cookies lock poisoned")
+            .as_deref()
+            .is_some_and(has_session)
+    }
+
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.timeout_ms
+            .store(timeout.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.load(Ordering::Relaxed))
+    }
+
+    fn http_get(&self, url: &str) -> RequestBuilder {
+        self.client
+            .get(url)
+            .timeout(self.timeout())
+            .header(USER_AGENT, user_agent())
+    }
+
+    fn http_post(&self, url: &str) -> RequestBuilder {
+        self.client
+            .post(url)
+            .timeout(self.timeout())
+            .header(USER_AGENT, user_agent())
+    }
+
     fn build_url(&self, domain: BilibiliApiDomain, endpoint: &str) -> String {
         format!("{}{}", domain.as_str(), endpoint)
     }
 
     /// Make a GET request
     pub async fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<ApiResponse<T>> {
-        let mut req = self.client.get(url);
+        #[cfg(feature = "mock")]
+        if super::mock::enabled() {
+            return super::mock::response(url);
+        }
+        let mut req = self.http_get(url);
         if let Some(ref cookies) = *self.cookies.read().expect("cookies lock poisoned") {
             req = req.header(COOKIE, cookies.as_str());
         }
-        let resp = req.send().await?;
-        let api_resp: ApiResponse<T> = resp.json().await?;
-        Ok(api_resp)
+        let body = req
+            .send()
+            .await
+            .map_err(network_error)?
+            .text()
+            .await
+            .map_err(network_error)?;
+        decode_json(url, &body)
     }
 
     /// Make a POST request with form data
@@ -103,7 +299,11 @@ impl ApiClient {
         url: &str,
         form_data: Vec<(&str, String)>,
     ) -> Result<ApiResponse<T>> {
-        let mut req = self.client.post(url);
+        #[cfg(feature = "mock")]
+        if super::mock::enabled() {
+            return super::mock::response(url);
+        }
+        let mut req = self.http_post(url);
 
         // 使用块作用域确保锁在 await 之前释放
         let params = {
@@ -112,56 +312,98 @@ impl ApiClient {
                 req = req.header(COOKIE, cookie_str.as_str());
             }
 
-            let has_csrf = cookies
-                .as_ref()
-                .map(|c| c.contains("bili_jct"))
-                .unwrap_or(false);
-
             let mut params: Vec<(String, String)> = form_data
                 .into_iter()
                 .map(|(k, v)| (k.to_string(), v))
                 .collect();
 
-            if has_csrf && !params.iter().any(|(k, _)| k == "csrf") {
-                if let Some(cookie_str) = cookies.as_ref() {
-                    if let Some(csrf) = cookie_str.split(';').find_map(|part| {
-                        let part = part.trim();
-                        part.split_once('=')
-                            .filter(|(name, _)| *name == "bili_jct")
-                            .map(|(_, value)| value.to_string())
-                    }) {
-                        params.push(("csrf".to_string(), csrf));
-                    }
+            if !params.iter().any(|(k, _)| k == "csrf") {
+                if let Some(csrf) = cookies.as_deref().and_then(csrf_from_cookies) {
+                    params.push(("csrf".to_string(), csrf));
                 }
             }
             params
         }; // 锁在此处释放
 
         req = req.form(&params);
-        let resp = req.send().await?;
-        let api_resp: ApiResponse<T> = resp.json().await?;
-        Ok(api_resp)
+        let body = req
+            .send()
+            .await
+            .map_err(network_error)?
+            .text()
+            .await
+            .map_err(network_error)?;
+        decode_json(url, &body)
     }
 
-    /// Make a WBI-signed GET request
-    pub async fn get_with_wbi<T: for<'de> Deserialize<'de>>(
+    /// Make a WBI-signed GET request to `path` on the main API host
+    ///
+    /// Every endpoint under `/wbi/` goes through here. The keys rotate daily, so a
+    /// rejected signature (-403) drops the cached keys and signs once more with fresh ones.
+    pub async fn signed_get<T: for<'de> Deserialize<'de>>(
         &self,
-        base_url: &str,
+        path: &str,
         params: Vec<(&str, String)>,
     ) -> Result<ApiResponse<T>> {
-        // Ensure we have WBI keys
+        let url = self.build_url(BilibiliApiDomain::Main, path);
+        let query = self.signed_query(params.clone()).await?;
+        let resp: ApiResponse<T> = self.get(&format!("{}?{}", url, query)).await?;
+        if resp.code != -403 {
+            return Ok(resp);
+        }
+
+        *self.wbi_keys.write().expect("wbi_keys lock poisoned") = None;
+        let query = self.signed_query(params).await?;
+        self.get(&format!("{}?{}", url, query)).await
+    }
+
+    /// `params` as a query string signed with the cached WBI keys, adding `wts` and `w_rid`
+    async fn signed_query(&self, params: Vec<(&str, String)>) -> Result<String> {
         self.ensure_wbi_keys().await?;
+        let keys = self.wbi_keys.read().expect("wbi_keys lock poisoned");
+        let keys = keys
+            .as_ref()
+            .ok_or_else(|| anyhow!("No WBI keys in nav response"))?;
+        Ok(wbi::encode_wbi(params, &keys.img_key, &keys.sub_key))
+    }
 
-        let query = {
-            let keys = self.wbi_keys.read().expect("wbi_keys lock poisoned");
-            let keys = keys
-                .as_ref()
-                .expect("WBI keys should be set after ensure_wbi_keys");
-            wbi::encode_wbi(params, &keys.img_key, &keys.sub_key)
-        };
-        let url = format!("{}?{}", base_url, query);
+    /// Get info about the current login state
+    /// Not-logged-in responses carry `is_login = false` rather than an error
+    pub async fn get_nav_info(&self) -> Result<super::auth::NavInfo> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/web-interface/nav");
+        let resp: ApiResponse<super::auth::NavInfo> = self.get(&url).await?;
+        resp.data
+            .ok_or_else(|| anyhow!("Failed to get nav info: {}", resp.message))
+    }
 
-        self.get(&url).await
+    /// Follow, follower and post counts of the logged-in user
+    pub async fn get_nav_stat(&self) -> Result<super::auth::NavStat> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/web-interface/nav/stat");
+        let resp: ApiResponse<super::auth::NavStat> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("Nav stat error: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in nav stat response"))
+    }
+
+    /// Unread replies, @s, likes and private messages of the logged-in user
+    pub async fn get_unread_counts(&self) -> Result<super::message::UnreadCounts> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/msgfeed/unread");
+        let resp: ApiResponse<super::message::FeedUnread> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("Unread count error: {}", resp.message));
+        }
+        let feed = resp.data.unwrap_or_default();
+
+        // Private messages live on another host and are optional for the badge
+        let chat_url =
+            "https://api.vc.bilibili.com/session_svr/v1/session_svr/single_unread?build=0&mobi_app=web";
+        let chat = match self.get::<super::message::ChatUnread>(chat_url).await {
+            Ok(resp) if resp.code == 0 => resp.data,
+            _ => None,
+        };
+        Ok(super::message::UnreadCounts::new(feed, chat))
     }
 
     /// Fetch WBI keys from nav API
@@ -220,12 +462,12 @@ impl ApiClient {
             qrcode_key
         );
 
-        let mut req = self.client.get(&url);
+        let mut req = self.http_get(&url);
         if let Some(ref cookies) = *self.cookies.read().unwrap() {
             req = req.header(COOKIE, cookies.as_str());
         }
 
-        let resp = req.send().await?;
+        let resp = req.send().await.map_err(network_error)?;
 
         // Extract cookies from response headers
         let mut new_cookies = Vec::new();
@@ -233,7 +475,8 @@ impl ApiClient {
             new_cookies.push((cookie.name().to_string(), cookie.value().to_string()));
         }
 
-        let api_resp: ApiResponse<super::auth::QrcodePollData> = resp.json().await?;
+        let body = resp.text().await.map_err(network_error)?;
+        let api_resp: ApiResponse<super::auth::QrcodePollData> = decode_json(&url, &body)?;
 
         Ok(super::auth::QrcodePollResult {
             data: api_resp.data,
@@ -241,27 +484,180 @@ impl ApiClient {
         })
     }
 
-    // Recommendation API
-    pub async fn get_recommendations(&self) -> Result<Vec<super::recommend::VideoItem>> {
+    /// Get a TV-client login QR code, its auth code is stored as `qrcode_key`
+    pub async fn get_tv_qrcode_data(&self) -> Result<super::auth::QrcodeData> {
         let url = self.build_url(
-            BilibiliApiDomain::Main,
-            "/x/web-interface/wbi/index/top/feed/rcmd",
+            BilibiliApiDomain::Passport,
+            "/x/passport-tv-login/qrcode/auth_code",
         );
+        let params = super::appsign::sign_tv_params(vec![("local_id", "0".to_string())]);
+
+        // Signed requests must not carry the extra csrf field added by `post`
+        let body = self
+            .http_post(&url)
+            .form(&params)
+            .send()
+            .await?
+            .text()
+            .await?;
+        let api_resp: ApiResponse<super::auth::TvQrcodeData> = decode_json(&url, &body)?;
+        let data = api_resp
+            .data
+            .ok_or_else(|| anyhow!("No data in TV QR code response: {}", api_resp.message))?;
 
-        let params = vec![
-            ("fresh_type", "4".to_string()),
-            ("ps", "20".to_string()),
-            ("fresh_idx", "1".to_string()),
-            ("fresh_idx_1h", "1".to_string()),
-        ];
+        Ok(super::auth::QrcodeData {
+            url: data.url,
+            qrcode_key: data.auth_code,
+        })
+    }
 
-        let resp: ApiResponse<super::recommend::RecommendData> =
-            self.get_with_wbi(&url, params).await?;
+    pub async fn poll_tv_qrcode(&self, auth_code: &str) -> Result<super::auth::TvPollResult> {
+        let url = self.build_url(
+            BilibiliApiDomain::Passport,
+            "/x/passport-tv-login/qrcode/poll",
+        );
+        let params = super::appsign::sign_tv_params(vec![
+            ("auth_code", auth_code.to_string()),
+            ("local_id", "0".to_string()),
+        ]);
+
+        let body = self
+            .http_post(&url)
+            .form(&params)
+            .send()
+            .await?
+            .text()
+            .await?;
+        let api_resp: ApiResponse<super::auth::TvLoginData> = decode_json(&url, &body)?;
+
+        Ok(super::auth::TvPollResult {
+            code: api_resp.code,
+            data: api_resp.data,
+        })
+    }
 
-        Ok(resp
+    /// Request a fresh pair of device ids (`buvid3`/`buvid4`) for a login that has none
+    pub async fn get_device_ids(&self) -> Result<super::auth::DeviceIds> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/frontend/finger/spi");
+        let resp: ApiResponse<super::auth::DeviceIds> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("Device id error: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in device id response"))
+    }
+
+    /// Fill in missing device id cookies and apply them, returns whether anything changed
+    pub async fn ensure_device_ids(&self, creds: &mut Credentials) -> Result<bool> {
+        if creds.buvid3.is_some() && creds.buvid4.is_some() {
+            return Ok(false);
+        }
+        let ids = self.get_device_ids().await?;
+        creds.buvid3.get_or_insert(ids.b_3);
+        creds.buvid4.get_or_insert(ids.b_4);
+        creds
+            .b_nut
+            .get_or_insert_with(|| chrono::Utc::now().timestamp().to_string());
+        self.set_credentials(creds);
+        Ok(true)
+    }
+
+    /// Ask whether the web cookies are close to expiry and should be refreshed
+    pub async fn get_cookie_refresh_info(&self) -> Result<super::auth::CookieRefreshInfo> {
+        let csrf = self
+            .get_csrf()
+            .ok_or_else(|| anyhow!("Not logged in (missing bili_jct)"))?;
+        let url = format!(
+            "{}?csrf={}",
+            self.build_url(
+                BilibiliApiDomain::Passport,
+                "/x/passport-login/web/cookie/info"
+            ),
+            csrf
+        );
+        let resp: ApiResponse<super::auth::CookieRefreshInfo> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("Cookie info error: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in cookie info response"))
+    }
+
+    /// Exchange the TV-client tokens for fresh tokens and web cookies
+    pub async fn refresh_app_token(
+        &self,
+        access_token: &str,
+        refresh_token: &str,
+    ) -> Result<Credentials> {
+        let url = self.build_url(
+            BilibiliApiDomain::Passport,
+            "/x/passport-login/oauth2/refresh_token",
+        );
+        let params = super::appsign::sign_tv_params(vec![
+            ("access_key", access_token.to_string()),
+            ("refresh_token", refresh_token.to_string()),
+        ]);
+
+        let body = self
+            .http_post(&url)
+            .form(&params)
+            .send()
+            .await?
+            .text()
+            .await?;
+        let api_resp: ApiResponse<super::auth::AppTokenRefreshData> = decode_json(&url, &body)?;
+        if api_resp.code != 0 {
+            return Err(anyhow!("Token refresh error: {}", api_resp.message));
+        }
+        api_resp
             .data
-            .map(|d| d.item.into_iter().filter(|v| v.bvid.is_some()).collect())
-            .unwrap_or_default())
+            .and_then(|data| data.into_credentials())
+            .ok_or_else(|| anyhow!("Token refresh returned no cookies"))
+    }
+
+    /// Refresh the credentials when the app token or the web cookies are about to expire
+    /// Returns `None` when nothing needed refreshing, the client itself is left untouched
+    pub async fn refresh_credentials_if_needed(
+        &self,
+        creds: &Credentials,
+    ) -> Result<Option<Credentials>> {
+        let needs_refresh = creds.app_token_expiring(chrono::Utc::now().timestamp())
+            || self.get_cookie_refresh_info().await?.refresh;
+        if !needs_refresh {
+            return Ok(None);
+        }
+        if !creds.can_refresh() {
+            return Err(anyhow!("登录状态即将过期，请重新扫码登录"));
+        }
+        self.refresh_credentials(creds).await.map(Some)
+    }
+
+    /// Refresh the credentials now, whether or not they are about to expire
+    pub async fn refresh_credentials(&self, creds: &Credentials) -> Result<Credentials> {
+        let (Some(access_token), Some(refresh_token)) =
+            (&creds.access_token, &creds.app_refresh_token)
+        else {
+            return Err(anyhow!("没有可用的刷新令牌，请重新扫码登录"));
+        };
+        let mut refreshed = self.refresh_app_token(access_token, refresh_token).await?;
+        refreshed.refresh_token = creds.refresh_token.clone();
+        refreshed.sid.clone_from(&creds.sid);
+        refreshed.inherit_device_ids(creds);
+        Ok(refreshed)
+    }
+
+    // Recommendation API
+    pub async fn get_recommendations(&self) -> Result<Vec<super::recommend::VideoItem>> {
+        self.get_recommendations_with(super::recommend::FreshType::default())
+            .await
+    }
+
+    /// Fetch the first page of recommendations using the given feed algorithm
+    pub async fn get_recommendations_with(
+        &self,
+        fresh_type: super::recommend::FreshType,
+    ) -> Result<Vec<super::recommend::VideoItem>> {
+        self.get_recommendations_paged(1, fresh_type).await
     }
 
     // Video API
@@ -276,14 +672,53 @@ impl ApiClient {
             .ok_or_else(|| anyhow::anyhow!("No data in video info response"))
     }
 
+    /// Fetch the available stream qualities of a video part
+    pub async fn get_play_url(&self, bvid: &str, cid: i64) -> Result<super::video::PlayUrlData> {
+        let params = vec![
+            ("bvid", bvid.to_string()),
+            ("cid", cid.to_string()),
+            ("qn", "127".to_string()),
+            ("fnval", "4048".to_string()),
+            ("fourk", "1".to_string()),
+        ];
+
+        let resp: ApiResponse<super::video::PlayUrlData> =
+            self.signed_get("/x/player/wbi/playurl", params).await?;
+        resp.data
+            .ok_or_else(|| anyhow!("获取播放地址失败: {}", resp.message))
+    }
+
+    /// Fetch the danmaku XML for a video part (the response is deflate-compressed)
+    pub async fn get_danmaku_xml(&self, cid: i64) -> Result<String> {
+        let url = format!(
+            "{}/x/v1/dm/list.so?oid={}",
+            BilibiliApiDomain::Main.as_str(),
+            cid
+        );
+        let mut req = self.http_get(&url);
+        if let Some(ref cookies) = *self.cookies.read().expect("cookies lock poisoned") {
+            req = req.header(COOKIE, cookies.as_str());
+        }
+        let resp = req
+            .send()
+            .await
+            .map_err(network_error)?
+            .error_for_status()?;
+        resp.text().await.map_err(network_error)
+    }
+
+    /// Follow a b23.tv short link and return the final URL
+    pub async fn resolve_short_link(&self, url: &str) -> Result<String> {
+        let resp = self.http_get(url).send().await.map_err(network_error)?;
+        Ok(resp.url().to_string())
+    }
+
     // Search API
     pub async fn search_videos(
         &self,
         keyword: &str,
         page: i32,
     ) -> Result<super::search::SearchData> {
-        let url = self.build_url(BilibiliApiDomain::Main, "/x/web-interface/wbi/search/type");
-
         let params = vec![
             ("search_type", "video".to_string()),
             ("keyword", keyword.to_string()),
@@ -291,27 +726,128 @@ impl ApiClient {
             ("order", "totalrank".to_string()),
         ];
 
-        let resp: ApiResponse<super::search::SearchData> = self.get_with_wbi(&url, params).await?;
+        let resp: ApiResponse<super::search::SearchData> = self
+            .signed_get("/x/web-interface/wbi/search/type", params)
+            .await?;
         Ok(resp.data.unwrap_or(super::search::SearchData {
             result: None,
             num_results: Some(0),
             page: Some(page),
             pagesize: Some(20),
+            num_pages: Some(0),
         }))
     }
 
+    /// Uploads of user `mid`, newest first
+    pub async fn get_space_videos(
+        &self,
+        mid: i64,
+        page: i32,
+    ) -> Result<super::space::SpaceVideoData> {
+        let params = vec![
+            ("mid", mid.to_string()),
+            ("pn", page.to_string()),
+            ("ps", "30".to_string()),
+            ("order", "pubdate".to_string()),
+        ];
+
+        let resp: ApiResponse<super::space::SpaceVideoData> =
+            self.signed_get("/x/space/wbi/arc/search", params).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取投稿列表失败: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in space video response"))
+    }
+
+    /// 合集 and 系列 of user `mid`, each with a preview of its newest videos
+    pub async fn get_user_collections(&self, mid: i64) -> Result<super::space::CollectionsData> {
+        let url = format!(
+            "{}/x/polymer/web-space/seasons_series_list?mid={}&page_num=1&page_size=20",
+            BilibiliApiDomain::Main.as_str(),
+            mid
+        );
+
+        let resp: ApiResponse<super::space::CollectionsData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取合集列表失败: {}", resp.message));
+        }
+        Ok(resp.data.unwrap_or_default())
+    }
+
+    /// Videos of a 合集 or 系列 of user `mid`, up to the first 100
+    pub async fn get_collection_videos(
+        &self,
+        mid: i64,
+        kind: super::space::CollectionKind,
+        id: i64,
+    ) -> Result<super::space::CollectionArchivesData> {
+        let domain = BilibiliApiDomain::Main.as_str();
+        let url = match kind {
+            super::space::CollectionKind::Season => format!(
+                "{}/x/polymer/web-space/seasons_archives_list?mid={}&season_id={}&page_num=1&page_size=100",
+                domain, mid, id
+            ),
+            super::space::CollectionKind::Series => format!(
+                "{}/x/series/archives?mid={}&series_id={}&pn=1&ps=100",
+                domain, mid, id
+            ),
+        };
+
+        let resp: ApiResponse<super::space::CollectionArchivesData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取合集视频失败: {}", resp.message));
+        }
+        Ok(resp.data.unwrap_or_default())
+    }
+
+    /// Page `page` of the 追番 (or 追剧) list of user `mid`
+    pub async fn get_bangumi_follow(
+        &self,
+        mid: i64,
+        follow_type: super::bangumi::FollowType,
+        page: i32,
+    ) -> Result<super::bangumi::BangumiFollowData> {
+        let url = format!(
+            "{}/x/space/bangumi/follow/list?type={}&follow_status=0&pn={}&ps=24&vmid={}",
+            BilibiliApiDomain::Main.as_str(),
+            follow_type.as_i32(),
+            page,
+            mid
+        );
+
+        let resp: ApiResponse<super::bangumi::BangumiFollowData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取追番列表失败: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in bangumi follow response"))
+    }
+
     /// Fetch hot search keywords (web)
     pub async fn get_hot_search(&self) -> Result<Vec<super::search::HotwordItem>> {
         const HOTWORD_URL: &str = "https://s.search.bilibili.com/main/hotword";
 
-        let mut req = self.client.get(HOTWORD_URL);
+        #[cfg(feature = "mock")]
+        if super::mock::enabled() {
+            let data: super::search::HotwordResponse = super::mock::raw(HOTWORD_URL)?;
+            return Ok(data.list.unwrap_or_default());
+        }
+
+        let mut req = self.http_get(HOTWORD_URL);
 
         if let Some(ref cookies) = *self.cookies.read().expect("cookies lock poisoned") {
             req = req.header(COOKIE, cookies.as_str());
         }
 
-        let resp = req.send().await?;
-        let data: super::search::HotwordResponse = resp.json().await?;
+        let body = req
+            .send()
+            .await
+            .map_err(network_error)?
+            .text()
+            .await
+            .map_err(network_error)?;
+        let data: super::search::HotwordResponse = decode_json(HOTWORD_URL, &body)?;
 
         if let Some(code) = data.code {
             if code != 0 {
@@ -421,20 +957,36 @@ impl ApiClient {
     }
 
     // Comments API
-    pub async fn get_comments(&self, oid: i64, pn: i32) -> Result<super::comment::CommentData> {
+    pub async fn get_comments(
+        &self,
+        oid: i64,
+        pn: i32,
+        sort: super::comment::CommentSort,
+    ) -> Result<super::comment::CommentData> {
+        self.get_comments_by_type(oid, super::comment::CommentType::Video.as_i32(), pn, sort)
+            .await
+    }
+
+    /// Page `pn` of the comments on item `oid` of reply type `comment_type`
+    pub async fn get_comments_by_type(
+        &self,
+        oid: i64,
+        comment_type: i32,
+        pn: i32,
+        sort: super::comment::CommentSort,
+    ) -> Result<super::comment::CommentData> {
         let url = format!(
-            "{}/x/v2/reply?type=1&oid={}&sort=1&ps=20&pn={}",
+            "{}/x/v2/reply?type={}&oid={}&sort={}&ps={}&pn={}",
             BilibiliApiDomain::Main.as_str(),
+            comment_type,
             oid,
+            sort.as_param(),
+            super::comment::PAGE_SIZE,
             pn
         );
 
         let resp: ApiResponse<super::comment::CommentData> = self.get(&url).await?;
-        Ok(resp.data.unwrap_or(super::comment::CommentData {
-            page: None,
-            replies: None,
-            hots: None,
-        }))
+        Ok(resp.data.unwrap_or_default())
     }
 
     // Dynamic Comments API
@@ -455,34 +1007,28 @@ impl ApiClient {
         );
 
         let resp: ApiResponse<super::comment::CommentData> = self.get(&url).await?;
-        Ok(resp.data.unwrap_or(super::comment::CommentData {
-            page: None,
-            replies: None,
-            hots: None,
-        }))
+        Ok(resp.data.unwrap_or_default())
     }
 
     // Comment replies API
     pub async fn get_comment_replies(
         &self,
         oid: i64,
+        comment_type: i32,
         root: i64,
         pn: i32,
     ) -> Result<super::comment::CommentData> {
         let url = format!(
-            "{}/x/v2/reply/reply?type=1&oid={}&root={}&ps=20&pn={}",
+            "{}/x/v2/reply/reply?type={}&oid={}&root={}&ps=20&pn={}",
             BilibiliApiDomain::Main.as_str(),
+            comment_type,
             oid,
             root,
             pn
         );
 
         let resp: ApiResponse<super::comment::CommentData> = self.get(&url).await?;
-        Ok(resp.data.unwrap_or(super::comment::CommentData {
-            page: None,
-            replies: None,
-            hots: None,
-        }))
+        Ok(resp.data.unwrap_or_default())
     }
 
     // Related Videos API
@@ -500,25 +1046,102 @@ impl ApiClient {
         Ok(resp.data.unwrap_or_default())
     }
 
-    // Extended Recommendations API with pagination
+    /// Tags attached to video `bvid`, empty for videos without any
+    pub async fn get_video_tags(&self, bvid: &str) -> Result<Vec<super::tag::VideoTag>> {
+        let url = format!(
+            "{}/x/tag/archive/tags?bvid={}",
+            BilibiliApiDomain::Main.as_str(),
+            bvid
+        );
+
+        let resp: ApiResponse<Vec<super::tag::VideoTag>> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取视频标签失败: {}", resp.message));
+        }
+        Ok(resp.data.unwrap_or_default())
+    }
+
+    /// Page `page` of the newest videos filed under tag `tag_id`
+    pub async fn get_tag_videos(
+        &self,
+        tag_id: i64,
+        page: i32,
+    ) -> Result<super::tag::TagDetailData> {
+        let url = format!(
+            "{}/x/tag/detail?tag_id={}&pn={}&ps=20",
+            BilibiliApiDomain::Main.as_str(),
+            tag_id,
+            page
+        );
+
+        let resp: ApiResponse<super::tag::TagDetailData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取标签视频失败: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in tag detail response"))
+    }
+
+    /// Favorite folders created by user `mid`
+    pub async fn get_fav_folders(&self, mid: i64) -> Result<super::favorite::FavFoldersData> {
+        let url = format!(
+            "{}/x/v3/fav/folder/created/list-all?up_mid={}",
+            BilibiliApiDomain::Main.as_str(),
+            mid
+        );
+
+        let resp: ApiResponse<super::favorite::FavFoldersData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取收藏夹失败: {}", resp.message));
+        }
+        // Users without any folder get `data: null`
+        Ok(resp.data.unwrap_or_default())
+    }
+
+    /// Page `page` of the items in favorite folder `media_id`
+    pub async fn get_fav_videos(
+        &self,
+        media_id: i64,
+        page: i32,
+    ) -> Result<super::favorite::FavResourceData> {
+        let url = format!(
+            "{}/x/v3/fav/resource/list?media_id={}&pn={}&ps={}&platform=web",
+            BilibiliApiDomain::Main.as_str(),
+            media_id,
+            page,
+            super::favorite::FAV_PAGE_SIZE
+        );
+
+        let resp: ApiResponse<super::favorite::FavResourceData> = self.get(&url).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("获取收藏内容失败: {}", resp.message));
+        }
+        resp.data
+            .ok_or_else(|| anyhow!("No data in favorite resource response"))
+    }
+
+    /// Fetch page `fresh_idx` of the feed, higher pages return items not shown before
     pub async fn get_recommendations_paged(
         &self,
         fresh_idx: i32,
+        fresh_type: super::recommend::FreshType,
     ) -> Result<Vec<super::recommend::VideoItem>> {
-        let url = self.build_url(
-            BilibiliApiDomain::Main,
-            "/x/web-interface/wbi/index/top/feed/rcmd",
-        );
+        // Without a session the feed is generic and soon repeats, use 热门 instead
+        if !self.is_logged_in() {
+            return self.get_popular(fresh_idx).await;
+        }
 
         let params = vec![
-            ("fresh_type", "4".to_string()),
+            ("fresh_type", fresh_type.value().to_string()),
             ("ps", "20".to_string()),
             ("fresh_idx", fresh_idx.to_string()),
             ("fresh_idx_1h", fresh_idx.to_string()),
+            ("brush", fresh_idx.to_string()),
         ];
 
-        let resp: ApiResponse<super::recommend::RecommendData> =
-            self.get_with_wbi(&url, params).await?;
+        let resp: ApiResponse<super::recommend::RecommendData> = self
+            .signed_get("/x/web-interface/wbi/index/top/feed/rcmd", params)
+            .await?;
 
         Ok(resp
             .data
@@ -526,6 +1149,18 @@ impl ApiClient {
             .unwrap_or_default())
     }
 
+    /// Page `page` of the public 热门 list, empty past the last page
+    pub async fn get_popular(&self, page: i32) -> Result<Vec<super::recommend::VideoItem>> {
+        let url = format!(
+            "{}/x/web-interface/popular?ps=20&pn={}",
+            BilibiliApiDomain::Main.as_str(),
+            page
+        );
+
+        let resp: ApiResponse<super::recommend::PopularData> = self.get(&url).await?;
+        Ok(resp.data.map(|d| d.list).unwrap_or_default())
+    }
+
     pub async fn get_history(
         &self,
         max: Option<i64>,
@@ -560,6 +1195,29 @@ impl ApiClient {
             .ok_or_else(|| anyhow::anyhow!("No data in history response"))
     }
 
+    /// Remove one entry from the watch history, `kid` as from [`HistoryItem::delete_kid`]
+    ///
+    /// [`HistoryItem::delete_kid`]: super::history::HistoryItem::delete_kid
+    pub async fn delete_history(&self, kid: &str) -> Result<()> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/v2/history/delete");
+        let resp: ApiResponse<serde_json::Value> =
+            self.post(&url, vec![("kid", kid.to_string())]).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("{}", resp.message));
+        }
+        Ok(())
+    }
+
+    /// Remove the whole watch history
+    pub async fn clear_history(&self) -> Result<()> {
+        let url = self.build_url(BilibiliApiDomain::Main, "/x/v2/history/clear");
+        let resp: ApiResponse<serde_json::Value> = self.post(&url, Vec::new()).await?;
+        if resp.code != 0 {
+            return Err(anyhow!("{}", resp.message));
+        }
+        Ok(())
+    }
+
     // ========== Comment Action APIs ==========
 
     /// Add a comment (发表评论)
@@ -568,7 +1226,7 @@ impl ApiClient {
     /// - `message`: Comment content
     /// - `root`: Root comment rpid for reply (None for top-level comment)
     /// - `parent`: Parent comment rpid for reply (None for top-level comment)
-    pub async fn add_comment(
+    pub async fn post_comment(
         &self,
         oid: i64,
         comment_type: i32,
@@ -592,11 +1250,12 @@ impl ApiClient {
             form_data.push(("parent", p.to_string()));
         }
 
+        // `post` adds the csrf token from bili_jct
         let resp: ApiResponse<super::comment::AddCommentResponse> =
             self.post(&url, form_data).await?;
 
         if resp.code != 0 {
-            return Err(anyhow::anyhow!("Failed to add comment: {}", resp.message));
+            return Err(anyhow::anyhow!("{}", resp.message));
         }
 
         resp.data
@@ -694,3 +1353,142 @@ impl Default for ApiClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        #[allow(dead_code)]
+        aid: i64,
+    }
+
+    #[test]
+    fn test_endpoint_name() {
+        assert_eq!(
+            endpoint_name("https://api.bilibili.com/x/web-interface/nav?a=1"),
+            "/x/web-interface/nav"
+        );
+        assert_eq!(endpoint_name("https://api.bilibili.com"), "/");
+        assert_eq!(endpoint_name("/x/nav"), "/x/nav");
+    }
+
+    #[test]
+    fn test_decode_error_includes_code_and_field() {
+        let url = "https://api.bilibili.com/x/web-interface/view?bvid=BV1";
+        let body = r#"{"code":-352,"message":"风控校验失败","data":{"aid":"oops"}}"#;
+        let err = decode_json::<ApiResponse<Item>>(url, body)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("/x/web-interface/view"), "{}", err);
+        assert!(err.contains("code -352: 风控校验失败"), "{}", err);
+        assert!(err.contains("line 1 column"), "{}", err);
+    }
+
+    #[test]
+    fn test_decode_error_non_json_body() {
+        let err = decode_json::<ApiResponse<Item>>("https://x.com/y", "<html>")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Failed to decode /y:"), "{}", err);
+        assert!(!err.contains("(code"), "{}", err);
+    }
+
+    #[test]
+    fn test_has_session() {
+        assert!(has_session("buvid3=x; SESSDATA=abc; bili_jct=y"));
+        assert!(!has_session("buvid3=x; SESSDATA=; bili_jct=y"));
+        assert!(!has_session("buvid3=x"));
+        assert!(!ApiClient::new().is_logged_in());
+    }
+
+    #[tokio::test]
+    async fn test_slow_response_is_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Accept the connection but never answer
+        let server = tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let client = ApiClient::new();
+        client.set_timeout(Duration::from_millis(200));
+        let err = client
+            .get::<Item>(&format!("http://{}/x/slow", addr))
+            .await
+            .unwrap_err();
+        assert!(is_timeout(&err), "{}", err);
+        assert_eq!(err.to_string(), "请求超时");
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_request_carries_configured_user_agent() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let len = socket.read(&mut request).await.unwrap();
+            let body = r#"{"code":0,"message":"0","data":{"aid":1}}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..len]).to_lowercase()
+        });
+
+        set_user_agent("bilibili-tui-test/1.0");
+        let client = ApiClient::new();
+        let result = client.get::<Item>(&format!("http://{}/x/ua", addr)).await;
+        set_user_agent("");
+        result.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(
+            request.contains("user-agent: bilibili-tui-test/1.0"),
+            "{}",
+            request
+        );
+        assert_eq!(user_agent(), DEFAULT_USER_AGENT);
+    }
+
+    #[tokio::test]
+    async fn test_signed_query_carries_w_rid_and_wts() {
+        let client = ApiClient::new();
+        *client.wbi_keys.write().unwrap() = Some(WbiKeys {
+            img_key: "7cd084941338484aae1ad9425b84077c".to_string(),
+            sub_key: "4932caff0ff746eab6f01bf08b70ac45".to_string(),
+        });
+        let query = client
+            .signed_query(vec![
+                ("search_type", "video".to_string()),
+                ("keyword", "rust".to_string()),
+            ])
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = query
+            .split('&')
+            .filter_map(|pair| Some(pair.split_once('=')?.0))
+            .collect();
+        assert_eq!(
+            names,
+            ["keyword", "search_type", "wts", "w_rid"],
+            "{}",
+            query
+        );
+    }
+
+    #[test]
+    fn test_prefix_bytes_char_boundary() {
+        assert_eq!(prefix_bytes("哔哩", 4), "哔");
+        assert_eq!(prefix_bytes("abc", 10), "abc");
+    }
+}
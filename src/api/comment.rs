@@ -1,13 +1,73 @@
 //! Comment API types and functions
 
+use super::bvid::bv_to_av;
 use serde::Deserialize;
 
+/// Top-level comments per page of the reply API
+pub const PAGE_SIZE: usize = 20;
+
+/// Order of a comment list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentSort {
+    /// 热门, ranked by likes and replies
+    #[default]
+    Hot,
+    /// 最新, newest first
+    Time,
+}
+
+impl CommentSort {
+    /// Value of the reply API's `sort` parameter
+    pub fn as_param(&self) -> i32 {
+        match self {
+            CommentSort::Hot => 2,
+            CommentSort::Time => 0,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommentSort::Hot => "热门",
+            CommentSort::Time => "最新",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            CommentSort::Hot => CommentSort::Time,
+            CommentSort::Time => CommentSort::Hot,
+        }
+    }
+}
+
 /// Comment list response
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CommentData {
     pub page: Option<CommentPage>,
     pub replies: Option<Vec<CommentItem>>,
     pub hots: Option<Vec<CommentItem>>,
+    /// Uploader's section, holds the pinned comment
+    #[serde(default)]
+    pub upper: Option<CommentUpper>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentUpper {
+    /// Comment pinned by the uploader, not repeated in `replies`
+    pub top: Option<CommentItem>,
+}
+
+impl CommentData {
+    /// Comments of the page with the pinned one, if any, marked and moved to the front
+    pub fn into_comments(self) -> Vec<CommentItem> {
+        let mut comments = self.replies.unwrap_or_default();
+        if let Some(mut top) = self.upper.and_then(|upper| upper.top) {
+            top.pinned = true;
+            comments.retain(|comment| comment.rpid != top.rpid);
+            comments.insert(0, top);
+        }
+        comments
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,6 +93,9 @@ pub struct CommentItem {
     pub member: Option<CommentMember>,
     pub content: Option<CommentContent>,
     pub replies: Option<Vec<CommentItem>>,
+    /// Pinned by the uploader, shown with a 置顶 tag
+    #[serde(skip)]
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -135,6 +198,12 @@ impl CommentType {
     }
 }
 
+/// Comment area of a video, its aid, worked out from the bvid when a list only has that
+pub fn video_oid(bvid: Option<&str>, aid: Option<i64>) -> Option<i64> {
+    aid.filter(|&aid| aid > 0)
+        .or_else(|| bvid.and_then(bv_to_av))
+}
+
 /// Response for adding a comment
 #[derive(Debug, Deserialize)]
 pub struct AddCommentResponse {
@@ -147,3 +216,35 @@ pub struct AddCommentResponse {
     pub parent: Option<i64>,
     pub reply: Option<CommentItem>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_comment_comes_first() {
+        let data: CommentData = serde_json::from_str(
+            r#"{"replies":[{"rpid":1,"oid":9,"mid":0,"parent":0},{"rpid":2,"oid":9,"mid":0,"parent":0}],
+                "upper":{"top":{"rpid":2,"oid":9,"mid":0,"parent":0}}}"#,
+        )
+        .unwrap();
+        let comments = data.into_comments();
+        assert_eq!(
+            comments
+                .iter()
+                .map(|c| (c.rpid, c.pinned))
+                .collect::<Vec<_>>(),
+            vec![(2, true), (1, false)]
+        );
+
+        let data: CommentData = serde_json::from_str(r#"{"upper":{"top":null}}"#).unwrap();
+        assert!(data.into_comments().is_empty());
+    }
+
+    #[test]
+    fn test_video_oid() {
+        assert_eq!(video_oid(Some("BV17x411w7KC"), Some(0)), Some(170001));
+        assert_eq!(video_oid(None, Some(42)), Some(42));
+        assert_eq!(video_oid(Some("not a bvid"), None), None);
+    }
+}
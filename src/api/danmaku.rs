@@ -0,0 +1,129 @@
+//! Danmaku (bullet comment) XML parsing
+//! See: https://socialsisteryi.github.io/bilibili-API-collect/docs/danmaku/danmaku_xml.html
+
+use std::cmp::Ordering;
+
+/// Danmaku display mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DanmakuMode {
+    /// Scrolls from right to left
+    Scroll,
+    /// Fixed at the bottom
+    Bottom,
+    /// Fixed at the top
+    Top,
+}
+
+impl DanmakuMode {
+    /// Map the XML mode number, advanced/code danmaku are not supported
+    fn from_code(code: i32) -> Option<Self> {
+        match code {
+            1..=3 | 6 => Some(DanmakuMode::Scroll),
+            4 => Some(DanmakuMode::Bottom),
+            5 => Some(DanmakuMode::Top),
+            _ => None,
+        }
+    }
+}
+
+/// A single danmaku entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct DanmakuItem {
+    /// Appearance time in seconds
+    pub time: f64,
+    pub mode: DanmakuMode,
+    pub font_size: u32,
+    /// RGB color as 0xRRGGBB
+    pub color: u32,
+    pub text: String,
+}
+
+impl DanmakuItem {
+    /// Parse from the `p` attribute: "time,mode,size,color,..."
+    fn from_attrs(attrs: &str, text: String) -> Option<Self> {
+        let mut parts = attrs.split(',');
+        let time = parts.next()?.parse().ok()?;
+        let mode = DanmakuMode::from_code(parts.next()?.parse().ok()?)?;
+        let font_size = parts.next()?.parse().ok()?;
+        let color = parts.next()?.parse().ok()?;
+        if text.trim().is_empty() {
+            return None;
+        }
+        Some(Self {
+            time,
+            mode,
+            font_size,
+            color,
+            text,
+        })
+    }
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Parse danmaku XML into entries sorted by time
+/// Returns an empty list when the video has danmaku disabled
+pub fn parse_danmaku_xml(xml: &str) -> Vec<DanmakuItem> {
+    let mut items = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find("<d p=\"") {
+        rest = &rest[start + 6..];
+        let Some(attr_end) = rest.find('"') else {
+            break;
+        };
+        let attrs = &rest[..attr_end];
+        rest = &rest[attr_end..];
+        let Some(text_start) = rest.find('>') else {
+            break;
+        };
+        rest = &rest[text_start + 1..];
+        let Some(text_end) = rest.find("</d>") else {
+            break;
+        };
+        let text = unescape_xml(&rest[..text_end]);
+        rest = &rest[text_end + 4..];
+
+        if let Some(item) = DanmakuItem::from_attrs(attrs, text) {
+            items.push(item);
+        }
+    }
+
+    items.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_danmaku_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?><i><chatserver>chat.bilibili.com</chatserver><chatid>170001</chatid>
+<d p="12.5,5,25,16711680,1700000000,0,abcd,1,10">顶部 &amp; 红色</d>
+<d p="3.0,1,25,16777215,1700000000,0,abcd,2,10">hello</d>
+<d p="4.0,7,25,16777215,1700000000,0,abcd,3,10">[advanced]</d>
+</i>"#;
+        let items = parse_danmaku_xml(xml);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "hello");
+        assert_eq!(items[0].mode, DanmakuMode::Scroll);
+        assert_eq!(items[1].text, "顶部 & 红色");
+        assert_eq!(items[1].mode, DanmakuMode::Top);
+        assert_eq!(items[1].color, 0xFF0000);
+        assert_eq!(items[1].time, 12.5);
+    }
+
+    #[test]
+    fn test_parse_disabled_danmaku() {
+        let xml =
+            r#"<?xml version="1.0" encoding="UTF-8"?><i><chatid>1</chatid><state>1</state></i>"#;
+        assert!(parse_danmaku_xml(xml).is_empty());
+    }
+}
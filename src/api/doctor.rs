@@ -0,0 +1,143 @@
+//! Login self-test behind `bilibili-tui doctor` and the settings page
+//!
+//! Each check hits one endpoint, so the report tells apart a dead network, expired
+//! cookies and broken WBI signing.
+
+use super::client::{ApiClient, ApiResponse};
+use super::recommend::FreshType;
+use anyhow::Result;
+
+const NAV: &str = "登录状态 (nav)";
+const FEED: &str = "推荐接口";
+const WBI: &str = "WBI 签名 (搜索)";
+
+/// Outcome of one endpoint check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn from_result<T>(
+        name: &'static str,
+        result: Result<T>,
+        describe: impl FnOnce(T) -> std::result::Result<String, String>,
+    ) -> Self {
+        let (ok, detail) = match result.map(describe) {
+            Ok(Ok(detail)) => (true, detail),
+            Ok(Err(detail)) => (false, detail),
+            Err(e) => (false, format!("请求失败: {}", e)),
+        };
+        Self { name, ok, detail }
+    }
+}
+
+/// Most likely cause given the check results, `None` when everything passed
+pub fn diagnosis(results: &[CheckResult]) -> Option<&'static str> {
+    if results.iter().all(|check| check.ok) {
+        return None;
+    }
+    if results.iter().all(|check| !check.ok) {
+        return Some("所有请求都失败了，请检查网络或代理设置");
+    }
+    match results
+        .iter()
+        .find(|check| !check.ok)
+        .map(|check| check.name)
+    {
+        Some(NAV) => Some("登录已失效，请在设置中退出后重新扫码登录"),
+        Some(WBI) => Some("WBI 签名请求被拒绝，搜索和 UP 主页可能无法加载，可稍后重试"),
+        _ => Some("部分接口不可用，可稍后重试"),
+    }
+}
+
+/// Run every check in order
+pub async fn run_checks(client: &ApiClient) -> Vec<CheckResult> {
+    let nav = CheckResult::from_result(NAV, client.get_nav_info().await, |nav| {
+        if nav.is_login {
+            Ok(format!("已登录: {}", nav.uname.unwrap_or_default()))
+        } else {
+            Err("未登录或 Cookie 已失效".to_string())
+        }
+    });
+    let feed = CheckResult::from_result(
+        FEED,
+        client
+            .get_recommendations_paged(1, FreshType::default())
+            .await,
+        |videos| Ok(format!("返回 {} 条视频", videos.len())),
+    );
+    let wbi = CheckResult::from_result(WBI, signed_search(client).await, |resp| {
+        if resp.code == 0 {
+            Ok("签名有效".to_string())
+        } else {
+            Err(format!("code {}: {}", resp.code, resp.message))
+        }
+    });
+    vec![nav, feed, wbi]
+}
+
+/// Raw signed search, so a rejected signature shows up as a non-zero code
+async fn signed_search(client: &ApiClient) -> Result<ApiResponse<serde_json::Value>> {
+    let params = vec![
+        ("search_type", "video".to_string()),
+        ("keyword", "bilibili".to_string()),
+        ("page", "1".to_string()),
+    ];
+    client
+        .signed_get("/x/web-interface/wbi/search/type", params)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    fn check(name: &'static str, ok: bool) -> CheckResult {
+        CheckResult {
+            name,
+            ok,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_result_from_result() {
+        let passed = CheckResult::from_result(FEED, Ok(3), |n| Ok(format!("{} 条", n)));
+        assert!(passed.ok);
+        assert_eq!(passed.detail, "3 条");
+
+        let rejected =
+            CheckResult::from_result(WBI, Ok(-412), |code| Err(format!("code {}", code)));
+        assert!(!rejected.ok);
+        assert_eq!(rejected.detail, "code -412");
+
+        let failed =
+            CheckResult::from_result(NAV, Err::<(), _>(anyhow!("timeout")), |_| Ok(String::new()));
+        assert!(!failed.ok);
+        assert_eq!(failed.detail, "请求失败: timeout");
+    }
+
+    #[test]
+    fn test_diagnosis_names_the_failing_part() {
+        assert_eq!(
+            diagnosis(&[check(NAV, true), check(FEED, true), check(WBI, true)]),
+            None
+        );
+        assert!(
+            diagnosis(&[check(NAV, false), check(FEED, false), check(WBI, false)])
+                .is_some_and(|msg| msg.contains("网络"))
+        );
+        assert!(
+            diagnosis(&[check(NAV, false), check(FEED, true), check(WBI, true)])
+                .is_some_and(|msg| msg.contains("重新扫码登录"))
+        );
+        assert!(
+            diagnosis(&[check(NAV, true), check(FEED, true), check(WBI, false)])
+                .is_some_and(|msg| msg.contains("WBI"))
+        );
+    }
+}
@@ -131,7 +131,38 @@ pub struct RichTextNode {
     pub node_type: Option<String>,
 }
 
+/// Content type of a feed item, used for the card badge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicKind {
+    Video,
+    Draw,
+    Opus,
+}
+
+impl DynamicKind {
+    pub fn badge(&self) -> &'static str {
+        match self {
+            DynamicKind::Video => "🎬 视频",
+            DynamicKind::Draw => "🖼 图文",
+            DynamicKind::Opus => "📝 动态",
+        }
+    }
+}
+
 impl DynamicItem {
+    /// Kind of a supported item, None for types the feed does not render
+    pub fn kind(&self) -> Option<DynamicKind> {
+        if self.is_video() {
+            Some(DynamicKind::Video)
+        } else if self.is_draw() {
+            Some(DynamicKind::Draw)
+        } else if self.is_opus() {
+            Some(DynamicKind::Opus)
+        } else {
+            None
+        }
+    }
+
     pub fn is_video(&self) -> bool {
         self.modules
             .as_ref()
@@ -150,6 +181,13 @@ impl DynamicItem {
             .unwrap_or("未知")
     }
 
+    pub fn author_mid(&self) -> Option<i64> {
+        self.modules
+            .as_ref()
+            .and_then(|m| m.module_author.as_ref())
+            .and_then(|a| a.mid)
+    }
+
     pub fn author_face(&self) -> Option<&str> {
         self.modules
             .as_ref()
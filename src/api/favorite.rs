@@ -0,0 +1,151 @@
+//! Favorite folders (收藏夹) of a user and the videos in them
+
+use crate::util::normalize_image_url;
+use serde::Deserialize;
+
+/// Videos per page of a folder, the most the resource list hands out at once
+pub const FAV_PAGE_SIZE: i32 = 20;
+
+/// Folders created by a user from `/x/v3/fav/folder/created/list-all`
+#[derive(Debug, Default, Deserialize)]
+pub struct FavFoldersData {
+    pub count: Option<i32>,
+    #[serde(default)]
+    pub list: Option<Vec<FavFolder>>,
+}
+
+impl FavFoldersData {
+    pub fn folders(self) -> Vec<FavFolder> {
+        self.list.unwrap_or_default()
+    }
+}
+
+/// One folder, `id` is the media id its contents are listed by
+#[derive(Debug, Clone, Deserialize)]
+pub struct FavFolder {
+    pub id: i64,
+    #[serde(default)]
+    pub title: String,
+    /// Items in the folder, including ones that are no longer available
+    #[serde(default)]
+    pub media_count: i32,
+}
+
+/// One page of a folder from `/x/v3/fav/resource/list`
+#[derive(Debug, Deserialize)]
+pub struct FavResourceData {
+    pub info: Option<FavFolderInfo>,
+    #[serde(default)]
+    pub medias: Option<Vec<FavMedia>>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FavFolderInfo {
+    pub id: i64,
+    pub title: Option<String>,
+    pub media_count: Option<i32>,
+}
+
+impl FavResourceData {
+    /// Total number of items in the folder
+    pub fn total(&self) -> i32 {
+        self.info
+            .as_ref()
+            .and_then(|info| info.media_count)
+            .unwrap_or(0)
+    }
+
+    pub fn medias(self) -> Vec<FavMedia> {
+        self.medias.unwrap_or_default()
+    }
+}
+
+/// One favorited item, a video unless `kind` says otherwise
+#[derive(Debug, Clone, Deserialize)]
+pub struct FavMedia {
+    /// aid for videos
+    pub id: i64,
+    /// 2 for videos, 12 for audio, 21 for video collections
+    #[serde(rename = "type")]
+    pub kind: i32,
+    pub title: Option<String>,
+    pub cover: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<i64>,
+    pub upper: Option<FavUpper>,
+    pub cnt_info: Option<FavCount>,
+    pub bvid: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FavUpper {
+    pub mid: i64,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FavCount {
+    pub play: Option<i64>,
+}
+
+impl FavMedia {
+    /// Videos open on the detail page, other kinds have nowhere to go
+    pub fn is_video(&self) -> bool {
+        self.kind == 2
+    }
+
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or("无标题")
+    }
+
+    pub fn author_name(&self) -> &str {
+        self.upper
+            .as_ref()
+            .and_then(|upper| upper.name.as_deref())
+            .unwrap_or("-")
+    }
+
+    pub fn format_play(&self) -> String {
+        match self.cnt_info.as_ref().and_then(|count| count.play) {
+            Some(n) if n >= 10000 => format!("{:.1}万", n as f64 / 10000.0),
+            Some(n) => n.to_string(),
+            None => "-".to_string(),
+        }
+    }
+
+    pub fn format_duration(&self) -> String {
+        match self.duration {
+            Some(duration) => format!("{:02}:{:02}", duration / 60, duration % 60),
+            None => "--:--".to_string(),
+        }
+    }
+
+    pub fn cover_url(&self) -> Option<String> {
+        self.cover.as_deref().map(normalize_image_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_folder_page() {
+        let data: FavResourceData = serde_json::from_str(
+            r#"{"info":{"id":1,"title":"默认收藏夹","media_count":0},"medias":null,"has_more":false}"#,
+        )
+        .unwrap();
+        assert_eq!(data.total(), 0);
+        assert!(data.medias().is_empty());
+
+        let media: FavMedia = serde_json::from_str(
+            r#"{"id":1,"type":12,"title":"一首歌","cnt_info":{"play":123456}}"#,
+        )
+        .unwrap();
+        assert!(!media.is_video());
+        assert_eq!(media.format_play(), "12.3万");
+        assert_eq!(media.format_duration(), "--:--");
+    }
+}
@@ -10,6 +10,7 @@ pub struct HeartbeatResponse {
 /// Report video watch start to Bilibili
 pub async fn report_watch_start(
     client: &super::client::ApiClient,
+    mid: &str,
     aid: i64,
     cid: i64,
     bvid: &str,
@@ -24,7 +25,7 @@ pub async fn report_watch_start(
         ("aid", aid.to_string()),
         ("cid", cid.to_string()),
         ("bvid", bvid.to_string()),
-        ("mid", "0".to_string()),
+        ("mid", mid.to_string()),
         ("type", "3".to_string()),
         ("dt", "2".to_string()),
         ("auto_continued_play", "0".to_string()),
@@ -45,6 +46,7 @@ pub async fn report_watch_start(
 #[allow(clippy::too_many_arguments)]
 pub async fn report_heartbeat(
     client: &super::client::ApiClient,
+    mid: &str,
     aid: i64,
     cid: i64,
     bvid: &str,
@@ -63,7 +65,7 @@ pub async fn report_heartbeat(
         ("aid", aid.to_string()),
         ("cid", cid.to_string()),
         ("bvid", bvid.to_string()),
-        ("mid", "0".to_string()),
+        ("mid", mid.to_string()),
         ("played_time", played_time.to_string()),
         ("realtime", realtime.to_string()),
         ("real_played_time", real_played_time.to_string()),
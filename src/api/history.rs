@@ -189,4 +189,100 @@ impl HistoryItem {
     pub fn get_bvid(&self) -> Option<&str> {
         self.history.bvid.as_deref().filter(|s| !s.is_empty())
     }
+
+    /// Bangumi / 国创 episode, played through its `ep` page rather than a bvid
+    pub fn is_bangumi(&self) -> bool {
+        self.history.business == "pgc"
+    }
+
+    /// Id for `/x/v2/history/delete`, e.g. `archive_170001` or `pgc_<season id>`
+    pub fn delete_kid(&self) -> String {
+        let id = if self.kid > 0 {
+            self.kid
+        } else {
+            self.history.oid
+        };
+        format!("{}_{}", self.history.business, id)
+    }
+
+    /// Page URL handed to the player
+    pub fn play_url(&self) -> Option<String> {
+        match self.history.business.as_str() {
+            "pgc" if self.history.epid > 0 => Some(format!(
+                "https://www.bilibili.com/bangumi/play/ep{}",
+                self.history.epid
+            )),
+            "archive" => self
+                .get_bvid()
+                .map(|bvid| format!("https://www.bilibili.com/video/{}", bvid)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(business: &str, bvid: Option<&str>, epid: i64) -> HistoryItem {
+        serde_json::from_value(serde_json::json!({
+            "title": "t",
+            "history": {
+                "oid": 1,
+                "epid": epid,
+                "bvid": bvid,
+                "page": 1,
+                "cid": 2,
+                "business": business,
+                "dt": 2
+            },
+            "videos": 1,
+            "author_name": "up",
+            "author_mid": 3,
+            "view_at": 0,
+            "progress": 0,
+            "duration": 0,
+            "total": 0,
+            "is_finish": 0,
+            "is_fav": 0,
+            "kid": 0,
+            "live_status": 0
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_archive_play_url() {
+        let archive = item("archive", Some("BV1xx411c7mD"), 0);
+        assert!(!archive.is_bangumi());
+        assert_eq!(
+            archive.play_url().as_deref(),
+            Some("https://www.bilibili.com/video/BV1xx411c7mD")
+        );
+        assert_eq!(item("archive", Some(""), 0).play_url(), None);
+    }
+
+    #[test]
+    fn test_pgc_play_url() {
+        // Pure bangumi entries have no bvid
+        let pgc = item("pgc", Some(""), 733316);
+        assert!(pgc.is_bangumi());
+        assert_eq!(
+            pgc.play_url().as_deref(),
+            Some("https://www.bilibili.com/bangumi/play/ep733316")
+        );
+        assert_eq!(item("pgc", None, 0).play_url(), None);
+        assert_eq!(item("live", None, 0).play_url(), None);
+    }
+
+    #[test]
+    fn test_delete_kid() {
+        assert_eq!(
+            item("archive", Some("BV1xx411c7mD"), 0).delete_kid(),
+            "archive_1"
+        );
+        let mut pgc = item("pgc", None, 733316);
+        pgc.kid = 28747;
+        assert_eq!(pgc.delete_kid(), "pgc_28747");
+    }
 }
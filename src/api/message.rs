@@ -0,0 +1,74 @@
+//! Unread message counts
+//!
+//! API endpoints:
+//! - GET https://api.bilibili.com/x/msgfeed/unread (replies, @s, likes)
+//! - GET https://api.vc.bilibili.com/session_svr/v1/session_svr/single_unread (private messages)
+//!
+//! Authentication: Cookie (SESSDATA)
+
+use serde::Deserialize;
+
+/// Unread replies, @s, likes and system notices
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FeedUnread {
+    #[serde(default)]
+    pub at: u64,
+    #[serde(default)]
+    pub like: u64,
+    #[serde(default)]
+    pub reply: u64,
+    #[serde(default)]
+    pub sys_msg: u64,
+}
+
+/// Unread private message sessions
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChatUnread {
+    #[serde(default)]
+    pub follow_unread: u64,
+    #[serde(default)]
+    pub unfollow_unread: u64,
+}
+
+/// Combined counts shown as the sidebar badge
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnreadCounts {
+    pub reply: u64,
+    pub at: u64,
+    pub like: u64,
+    pub chat: u64,
+}
+
+impl UnreadCounts {
+    pub fn new(feed: FeedUnread, chat: Option<ChatUnread>) -> Self {
+        Self {
+            reply: feed.reply,
+            at: feed.at,
+            like: feed.like,
+            chat: chat.map_or(0, |c| c.follow_unread + c.unfollow_unread),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.reply + self.at + self.like + self.chat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unread_counts_total() {
+        let feed: FeedUnread =
+            serde_json::from_str(r#"{"at":1,"chat":0,"like":5,"reply":2,"sys_msg":3,"up":0}"#)
+                .unwrap();
+        let chat: ChatUnread =
+            serde_json::from_str(r#"{"unfollow_unread":1,"follow_unread":2}"#).unwrap();
+        let counts = UnreadCounts::new(feed.clone(), Some(chat));
+        assert_eq!(counts.chat, 3);
+        // System notices are not counted
+        assert_eq!(counts.total(), 11);
+        assert_eq!(UnreadCounts::new(feed, None).total(), 8);
+    }
+}
@@ -0,0 +1,259 @@
+//! Offline fixture backend for UI development
+//!
+//! Built with `--features mock` and enabled at runtime with `BILIBILI_TUI_MOCK=1`.
+//! Requests are answered from the JSON files in `fixtures/` instead of the network.
+
+use super::client::ApiResponse;
+use crate::storage::Credentials;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Environment variable that switches the client to fixtures
+pub const MOCK_ENV: &str = "BILIBILI_TUI_MOCK";
+
+const NAV: &str = include_str!("fixtures/nav.json");
+const RECOMMEND: &str = include_str!("fixtures/recommend.json");
+const POPULAR: &str = include_str!("fixtures/popular.json");
+const SEARCH: &str = include_str!("fixtures/search.json");
+const SEARCH_EMPTY: &str = include_str!("fixtures/search_empty.json");
+const COMMENTS: &str = include_str!("fixtures/comments.json");
+const HOTWORD: &str = include_str!("fixtures/hotword.json");
+const DYNAMIC: &str = include_str!("fixtures/dynamic.json");
+const HISTORY: &str = include_str!("fixtures/history.json");
+const SPACE: &str = include_str!("fixtures/space.json");
+const BANGUMI_FOLLOW: &str = include_str!("fixtures/bangumi_follow.json");
+const VIDEO_TAGS: &str = include_str!("fixtures/video_tags.json");
+const TAG_DETAIL: &str = include_str!("fixtures/tag_detail.json");
+const FAV_FOLDERS: &str = include_str!("fixtures/fav_folders.json");
+const FAV_RESOURCES: &str = include_str!("fixtures/fav_resources.json");
+const SPACE_COLLECTIONS: &str = include_str!("fixtures/space_collections.json");
+const COLLECTION_ARCHIVES: &str = include_str!("fixtures/collection_archives.json");
+const NAV_STAT: &str =
+    r#"{"code":0,"message":"0","data":{"following":128,"follower":16,"dynamic_count":3}}"#;
+const UNREAD: &str =
+    r#"{"code":0,"message":"0","data":{"at":1,"chat":0,"like":4,"reply":2,"sys_msg":0,"up":0}}"#;
+const COOKIE_INFO: &str = r#"{"code":0,"message":"0","data":{"refresh":false,"timestamp":0}}"#;
+const DEVICE_IDS: &str =
+    r#"{"code":0,"message":"0","data":{"b_3":"mock-buvid3","b_4":"mock-buvid4"}}"#;
+/// Reply for endpoints without a fixture, e.g. comment actions
+const EMPTY_OK: &str = r#"{"code":0,"message":"0","data":null}"#;
+
+/// Whether mock mode is switched on, read once per process
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        std::env::var(MOCK_ENV).is_ok_and(|v| !v.is_empty() && v != "0" && v != "false")
+    })
+}
+
+/// Fake credentials so the app starts on the home page without logging in
+pub fn credentials() -> Credentials {
+    Credentials {
+        sessdata: "mock".to_string(),
+        bili_jct: "mock".to_string(),
+        dede_user_id: "10000".to_string(),
+        dede_user_id_ckmd5: None,
+        refresh_token: None,
+        access_token: None,
+        app_refresh_token: None,
+        token_expires_at: None,
+        sid: None,
+        buvid3: None,
+        buvid4: None,
+        b_nut: None,
+    }
+}
+
+fn query_param<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+    url.split_once('?')?
+        .1
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+}
+
+/// Pick the fixture for a request URL
+fn fixture(url: &str) -> &'static str {
+    let path = url.split('?').next().unwrap_or(url);
+    if path.ends_with("/x/web-interface/nav") {
+        NAV
+    } else if path.ends_with("/x/web-interface/nav/stat") {
+        NAV_STAT
+    } else if path.ends_with("/index/top/feed/rcmd") {
+        RECOMMEND
+    } else if path.ends_with("/x/web-interface/popular") {
+        POPULAR
+    } else if path.ends_with("/search/type") {
+        // Search for "empty" (or past the first page) to get an empty result set
+        let keyword = query_param(url, "keyword").unwrap_or_default();
+        let page = query_param(url, "page").unwrap_or("1");
+        if keyword.contains("empty") || page != "1" {
+            SEARCH_EMPTY
+        } else {
+            SEARCH
+        }
+    } else if path.ends_with("/main/hotword") {
+        HOTWORD
+    } else if path.ends_with("/web-dynamic/v1/feed/all") {
+        DYNAMIC
+    } else if path.ends_with("/history/cursor") {
+        HISTORY
+    } else if path.ends_with("/x/space/wbi/arc/search") {
+        SPACE
+    } else if path.ends_with("/web-space/seasons_series_list") {
+        SPACE_COLLECTIONS
+    } else if path.ends_with("/web-space/seasons_archives_list")
+        || path.ends_with("/x/series/archives")
+    {
+        COLLECTION_ARCHIVES
+    } else if path.ends_with("/x/space/bangumi/follow/list") {
+        BANGUMI_FOLLOW
+    } else if path.ends_with("/x/tag/archive/tags") {
+        VIDEO_TAGS
+    } else if path.ends_with("/x/tag/detail") {
+        TAG_DETAIL
+    } else if path.ends_with("/x/v3/fav/folder/created/list-all") {
+        FAV_FOLDERS
+    } else if path.ends_with("/x/v3/fav/resource/list") {
+        FAV_RESOURCES
+    } else if path.ends_with("/x/v2/reply") {
+        COMMENTS
+    } else if path.ends_with("/x/msgfeed/unread") {
+        UNREAD
+    } else if path.ends_with("/web/cookie/info") {
+        COOKIE_INFO
+    } else if path.ends_with("/x/frontend/finger/spi") {
+        DEVICE_IDS
+    } else {
+        EMPTY_OK
+    }
+}
+
+/// Answer a request from fixtures
+pub fn response<T: for<'de> Deserialize<'de>>(url: &str) -> Result<ApiResponse<T>> {
+    serde_json::from_str(fixture(url))
+        .map_err(|e| anyhow!("Invalid mock fixture for {}: {}", url, e))
+}
+
+/// Raw fixture body for endpoints that don't use the common response envelope
+pub fn raw<T: for<'de> Deserialize<'de>>(url: &str) -> Result<T> {
+    serde_json::from_str(fixture(url))
+        .map_err(|e| anyhow!("Invalid mock fixture for {}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::dynamic::{DynamicFeedData, DynamicKind};
+    use crate::api::favorite::{FavFoldersData, FavResourceData};
+    use crate::api::history::HistoryData;
+    use crate::api::recommend::{PopularData, RecommendData};
+    use crate::api::search::{HotwordResponse, SearchData};
+    use crate::api::space::{CollectionArchivesData, CollectionsData, SpaceVideoData};
+    use crate::api::tag::{TagDetailData, VideoTag};
+
+    const API: &str = "https://api.bilibili.com";
+
+    #[test]
+    fn test_fixtures_deserialize() {
+        let rcmd: ApiResponse<RecommendData> = response(&format!(
+            "{}/x/web-interface/wbi/index/top/feed/rcmd?ps=20",
+            API
+        ))
+        .unwrap();
+        let items = rcmd.data.unwrap().item;
+        assert!(items.iter().any(|v| v.is_playable()));
+        assert!(items.iter().any(|v| !v.is_playable()));
+
+        let popular: ApiResponse<PopularData> =
+            response(&format!("{}/x/web-interface/popular?ps=20&pn=1", API)).unwrap();
+        assert!(popular.data.unwrap().list.iter().all(|v| v.is_playable()));
+
+        let feed: ApiResponse<DynamicFeedData> =
+            response(&format!("{}/x/polymer/web-dynamic/v1/feed/all", API)).unwrap();
+        let kinds: Vec<_> = feed
+            .data
+            .unwrap()
+            .items
+            .unwrap()
+            .iter()
+            .filter_map(|item| item.kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![DynamicKind::Video, DynamicKind::Draw, DynamicKind::Opus]
+        );
+
+        let history: ApiResponse<HistoryData> =
+            response(&format!("{}/x/web-interface/history/cursor?ps=20", API)).unwrap();
+        assert_eq!(history.data.unwrap().list.len(), 1);
+
+        let space: ApiResponse<SpaceVideoData> =
+            response(&format!("{}/x/space/wbi/arc/search?mid=10001", API)).unwrap();
+        assert_eq!(space.data.unwrap().videos().len(), 2);
+
+        let collections: ApiResponse<CollectionsData> = response(&format!(
+            "{}/x/polymer/web-space/seasons_series_list?mid=10001",
+            API
+        ))
+        .unwrap();
+        assert_eq!(collections.data.unwrap().collections().len(), 2);
+
+        let archives: ApiResponse<CollectionArchivesData> =
+            response(&format!("{}/x/series/archives?mid=10001&series_id=1", API)).unwrap();
+        assert_eq!(archives.data.unwrap().archives.len(), 3);
+
+        let tags: ApiResponse<Vec<VideoTag>> =
+            response(&format!("{}/x/tag/archive/tags?bvid=BV1mock0001", API)).unwrap();
+        assert_eq!(tags.data.unwrap().len(), 3);
+
+        let tag: ApiResponse<TagDetailData> =
+            response(&format!("{}/x/tag/detail?tag_id=1&pn=1&ps=20", API)).unwrap();
+        assert_eq!(tag.data.unwrap().videos().len(), 2);
+
+        let folders: ApiResponse<FavFoldersData> = response(&format!(
+            "{}/x/v3/fav/folder/created/list-all?up_mid=10001",
+            API
+        ))
+        .unwrap();
+        assert_eq!(folders.data.unwrap().folders().len(), 2);
+
+        let favs: ApiResponse<FavResourceData> = response(&format!(
+            "{}/x/v3/fav/resource/list?media_id=90001&pn=1&ps=20&platform=web",
+            API
+        ))
+        .unwrap();
+        let favs = favs.data.unwrap();
+        assert_eq!(favs.total(), 45);
+        assert_eq!(favs.medias().len(), 2);
+
+        let hot: HotwordResponse = raw("https://s.search.bilibili.com/main/hotword").unwrap();
+        assert!(!hot.list.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_empty_search_fixture() {
+        let url = format!(
+            "{}/x/web-interface/wbi/search/type?keyword=empty&page=1",
+            API
+        );
+        let resp: ApiResponse<SearchData> = response(&url).unwrap();
+        let data = resp.data.unwrap();
+        assert!(data.result.as_ref().unwrap().is_empty());
+        assert_eq!(data.total_pages(), 0);
+
+        let url = format!(
+            "{}/x/web-interface/wbi/search/type?keyword=rust&page=1",
+            API
+        );
+        let resp: ApiResponse<SearchData> = response(&url).unwrap();
+        assert_eq!(resp.data.unwrap().result.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_query_param() {
+        assert_eq!(query_param("/a?page=2&keyword=x", "page"), Some("2"));
+        assert_eq!(query_param("/a?pages=2", "page"), None);
+        assert_eq!(query_param("/a", "page"), None);
+    }
+}
@@ -1,12 +1,27 @@
+pub mod appsign;
 pub mod auth;
+pub mod bangumi;
+pub mod bili_api;
+pub mod bvid;
 pub mod client;
 pub mod comment;
+pub mod danmaku;
+pub mod doctor;
 pub mod dynamic;
+pub mod favorite;
 pub mod heartbeat;
 pub mod history;
+pub mod message;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod recommend;
 pub mod search;
+pub mod space;
+pub mod tag;
+#[cfg(test)]
+pub mod test_api;
 pub mod video;
 pub mod wbi;
 
+pub use bili_api::BiliApi;
 pub use client::ApiClient;
@@ -1,17 +1,96 @@
 //! Video recommendation API types
 
+use super::bvid::{av_to_bv, parse_video_ref, VideoRef};
 use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// Feed algorithm used by the recommend endpoint (`fresh_type` parameter)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FreshType {
+    /// Default web feed (fresh_type=4)
+    #[default]
+    Default,
+    /// Higher relevance, closer to the user's interests (大数据推荐)
+    BigData,
+    /// Lower relevance, more variety (探索)
+    Explore,
+}
+
+impl FreshType {
+    pub fn next(&self) -> Self {
+        match self {
+            FreshType::Default => FreshType::BigData,
+            FreshType::BigData => FreshType::Explore,
+            FreshType::Explore => FreshType::Default,
+        }
+    }
+
+    /// Value passed as `fresh_type` (larger means more relevant)
+    pub fn value(&self) -> i32 {
+        match self {
+            FreshType::Default => 4,
+            FreshType::BigData => 10,
+            FreshType::Explore => 1,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FreshType::Default => "默认",
+            FreshType::BigData => "大数据推荐",
+            FreshType::Explore => "探索",
+        }
+    }
+}
+
+impl fmt::Display for FreshType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FreshType::Default => "Default",
+            FreshType::BigData => "BigData",
+            FreshType::Explore => "Explore",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for FreshType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Default" => Ok(FreshType::Default),
+            "BigData" => Ok(FreshType::BigData),
+            "Explore" => Ok(FreshType::Explore),
+            _ => Err(()),
+        }
+    }
+}
 
 #[derive(Debug, Deserialize)]
 pub struct RecommendData {
     pub item: Vec<VideoItem>,
 }
 
+/// Page of the public 热门 list, shown instead of the feed when not logged in
+#[derive(Debug, Deserialize)]
+pub struct PopularData {
+    #[serde(default)]
+    pub list: Vec<VideoItem>,
+    #[serde(default)]
+    pub no_more: bool,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct VideoItem {
+    /// av number, named `aid` in the 热门 list
+    #[serde(alias = "aid")]
     pub id: i64,
     pub bvid: Option<String>,
     pub cid: Option<i64>,
+    /// Target kind, missing in the 热门 list which only holds videos
+    #[serde(default = "default_goto")]
     pub goto: String,
     pub uri: Option<String>,
     pub pic: Option<String>,
@@ -36,6 +115,32 @@ pub struct VideoStat {
     pub danmaku: Option<i64>,
 }
 
+fn default_goto() -> String {
+    "av".to_string()
+}
+
+/// What opening a feed item leads to, decided by its `goto`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedTarget {
+    Video {
+        bvid: String,
+        aid: i64,
+    },
+    /// Bangumi season or episode page, played from its URL like the 追番 list does
+    Bangumi(String),
+    /// Live room page
+    Live(String),
+}
+
+/// Counts from 10000 on in 万 with one decimal, "-" when missing
+fn format_count(count: Option<i64>) -> String {
+    match count {
+        Some(n) if n >= 10000 => format!("{:.1}万", n as f64 / 10000.0),
+        Some(n) => n.to_string(),
+        None => "-".to_string(),
+    }
+}
+
 impl VideoItem {
     /// Format duration as mm:ss
     pub fn format_duration(&self) -> String {
@@ -50,23 +155,193 @@ impl VideoItem {
 
     /// Format view count (e.g., 1.2万)
     pub fn format_views(&self) -> String {
-        if let Some(stat) = &self.stat {
-            if let Some(view) = stat.view {
-                if view >= 10000 {
-                    format!("{:.1}万", view as f64 / 10000.0)
-                } else {
-                    view.to_string()
-                }
-            } else {
-                "-".to_string()
-            }
-        } else {
-            "-".to_string()
-        }
+        format_count(self.stat.as_ref().and_then(|s| s.view))
+    }
+
+    /// Format like count, same style as views
+    pub fn format_likes(&self) -> String {
+        format_count(self.stat.as_ref().and_then(|s| s.like))
+    }
+
+    /// Format danmaku count, same style as views
+    pub fn format_danmaku(&self) -> String {
+        format_count(self.stat.as_ref().and_then(|s| s.danmaku))
     }
 
     /// Get author name
     pub fn author_name(&self) -> &str {
         self.owner.as_ref().map(|o| o.name.as_str()).unwrap_or("-")
     }
+
+    /// Uploader mid, None for ads and live rooms without an owner
+    pub fn owner_mid(&self) -> Option<i64> {
+        self.owner.as_ref().map(|o| o.mid).filter(|&mid| mid > 0)
+    }
+
+    /// Resolve the (bvid, aid) to open, None for live rooms, ads and other non-video targets
+    pub fn playable_target(&self) -> Option<(String, i64)> {
+        if self.goto != "av" {
+            return None;
+        }
+        match self.bvid.as_deref() {
+            Some(bvid) if !bvid.is_empty() => Some((bvid.to_string(), self.id)),
+            // Some items only carry the av number
+            _ if self.id > 0 => Some((av_to_bv(self.id), self.id)),
+            _ => match self.uri.as_deref().and_then(parse_video_ref)? {
+                VideoRef::Video { bvid, aid } => Some((bvid, aid)),
+                VideoRef::ShortLink(_) => None,
+            },
+        }
+    }
+
+    pub fn is_playable(&self) -> bool {
+        self.playable_target().is_some()
+    }
+
+    /// Ads are dropped from the feed before they reach the grid
+    pub fn is_ad(&self) -> bool {
+        self.goto == "ad"
+    }
+
+    /// Where opening the item leads, None for ads and kinds the client can't open
+    pub fn target(&self) -> Option<FeedTarget> {
+        let uri = self.uri.as_deref().filter(|uri| uri.starts_with("http"));
+        match self.goto.as_str() {
+            "av" => self
+                .playable_target()
+                .map(|(bvid, aid)| FeedTarget::Video { bvid, aid }),
+            "bangumi" => uri.map(|uri| FeedTarget::Bangumi(uri.to_string())),
+            "live" => uri
+                .map(str::to_string)
+                // The item id is the room id when the link is missing
+                .or_else(|| (self.id > 0).then(|| format!("https://live.bilibili.com/{}", self.id)))
+                .map(FeedTarget::Live),
+            _ => None,
+        }
+    }
+
+    /// Card badge for items that aren't plain videos
+    pub fn kind_badge(&self) -> Option<&'static str> {
+        match self.goto.as_str() {
+            "bangumi" => Some("番剧"),
+            "live" => Some("直播"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::client::ApiResponse;
+
+    fn item(json: &str) -> VideoItem {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let video =
+            item(r#"{"id":1,"goto":"av","stat":{"view":123456,"like":9999,"danmaku":null}}"#);
+        assert_eq!(video.format_views(), "12.3万");
+        assert_eq!(video.format_likes(), "9999");
+        assert_eq!(video.format_danmaku(), "-");
+    }
+
+    #[test]
+    fn test_fresh_type_roundtrip() {
+        for fresh_type in [FreshType::Default, FreshType::BigData, FreshType::Explore] {
+            assert_eq!(fresh_type.to_string().parse(), Ok(fresh_type));
+        }
+        assert!("unknown".parse::<FreshType>().is_err());
+    }
+
+    #[test]
+    fn test_regular_video_is_playable() {
+        let video = item(r#"{"id":170001,"bvid":"BV17x411w7KC","goto":"av"}"#);
+        assert_eq!(
+            video.playable_target(),
+            Some(("BV17x411w7KC".to_string(), 170001))
+        );
+    }
+
+    #[test]
+    fn test_video_without_bvid_uses_aid() {
+        let video = item(r#"{"id":170001,"bvid":"","goto":"av"}"#);
+        assert_eq!(
+            video.playable_target(),
+            Some(("BV17x411w7KC".to_string(), 170001))
+        );
+
+        let video =
+            item(r#"{"id":0,"goto":"av","uri":"https://www.bilibili.com/video/BV17x411w7KC"}"#);
+        assert_eq!(
+            video.playable_target(),
+            Some(("BV17x411w7KC".to_string(), 170001))
+        );
+    }
+
+    #[test]
+    fn test_live_and_ad_items_are_not_playable() {
+        let live = item(
+            r#"{"id":21452505,"bvid":"","goto":"live","uri":"https://live.bilibili.com/21452505"}"#,
+        );
+        assert!(!live.is_playable());
+
+        let ad = item(r#"{"id":0,"bvid":"","goto":"ad","uri":"https://www.bilibili.com"}"#);
+        assert!(!ad.is_playable());
+    }
+
+    #[test]
+    fn test_mixed_feed_targets() {
+        let resp: ApiResponse<RecommendData> =
+            serde_json::from_str(include_str!("fixtures/recommend.json")).unwrap();
+        let data = resp.data.unwrap();
+        let targets: Vec<_> = data.item.iter().map(VideoItem::target).collect();
+        assert!(
+            matches!(&targets[0], Some(FeedTarget::Video { bvid, .. }) if bvid == "BV1mock0001")
+        );
+        assert_eq!(
+            targets[3],
+            Some(FeedTarget::Live(
+                "https://live.bilibili.com/22001".to_string()
+            ))
+        );
+        assert!(data.item[4].is_ad());
+        assert_eq!(targets[4], None);
+        assert_eq!(
+            targets[5],
+            Some(FeedTarget::Bangumi(
+                "https://www.bilibili.com/bangumi/play/ss28001".to_string()
+            ))
+        );
+        let badges: Vec<_> = data.item.iter().map(VideoItem::kind_badge).collect();
+        assert_eq!(badges[3..], [Some("直播"), None, Some("番剧")]);
+
+        let live = item(r#"{"id":21452505,"goto":"live"}"#);
+        assert_eq!(
+            live.target(),
+            Some(FeedTarget::Live(
+                "https://live.bilibili.com/21452505".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_popular_item_is_playable() {
+        let data: PopularData = serde_json::from_str(
+            r#"{"list":[{"aid":170001,"bvid":"BV17x411w7KC","title":"热门视频"}],"no_more":false}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            data.list[0].playable_target(),
+            Some(("BV17x411w7KC".to_string(), 170001))
+        );
+    }
+
+    #[test]
+    fn test_video_without_any_id_is_not_playable() {
+        let video = item(r#"{"id":0,"bvid":null,"goto":"av"}"#);
+        assert!(!video.is_playable());
+    }
 }
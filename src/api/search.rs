@@ -1,5 +1,6 @@
 //! Search API types and functions
 
+use crate::util::normalize_image_url;
 use serde::Deserialize;
 
 /// Search result for video type
@@ -10,6 +11,30 @@ pub struct SearchData {
     pub num_results: Option<i32>,
     pub page: Option<i32>,
     pub pagesize: Option<i32>,
+    #[serde(rename = "numPages")]
+    pub num_pages: Option<i32>,
+}
+
+/// Bilibili only serves the first 50 pages of any search
+pub const MAX_SEARCH_PAGES: i32 = 50;
+
+impl SearchData {
+    fn uncapped_pages(&self) -> i32 {
+        match (self.num_results, self.pagesize) {
+            (Some(total), Some(size)) if size > 0 => (total + size - 1) / size,
+            _ => self.num_pages.unwrap_or(0),
+        }
+    }
+
+    /// Total pages from `numResults`/`pagesize`, capped at [`MAX_SEARCH_PAGES`]
+    pub fn total_pages(&self) -> i32 {
+        self.uncapped_pages().clamp(0, MAX_SEARCH_PAGES)
+    }
+
+    /// Whether the result set reaches the page cap, so later results are unreachable
+    pub fn is_capped(&self) -> bool {
+        self.uncapped_pages() >= MAX_SEARCH_PAGES
+    }
 }
 
 /// Individual video search result
@@ -50,13 +75,7 @@ impl SearchVideoItem {
     }
 
     pub fn cover_url(&self) -> Option<String> {
-        self.pic.as_ref().map(|url| {
-            if url.starts_with("//") {
-                format!("https:{}", url)
-            } else {
-                url.clone()
-            }
-        })
+        self.pic.as_deref().map(normalize_image_url)
     }
 }
 
@@ -109,3 +128,36 @@ pub struct HotwordResponse {
     pub message: Option<String>,
     pub list: Option<Vec<HotwordItem>>, // Top 10 hot words
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(num_results: i32, pagesize: Option<i32>, num_pages: Option<i32>) -> SearchData {
+        SearchData {
+            result: None,
+            num_results: Some(num_results),
+            page: Some(1),
+            pagesize,
+            num_pages,
+        }
+    }
+
+    #[test]
+    fn test_total_pages() {
+        assert_eq!(data(0, Some(20), Some(0)).total_pages(), 0);
+        assert_eq!(data(41, Some(20), None).total_pages(), 3);
+        assert_eq!(data(40, None, Some(2)).total_pages(), 2);
+    }
+
+    #[test]
+    fn test_total_pages_is_capped() {
+        assert_eq!(
+            data(1000, Some(20), Some(50)).total_pages(),
+            MAX_SEARCH_PAGES
+        );
+        assert_eq!(data(5000, Some(20), None).total_pages(), MAX_SEARCH_PAGES);
+        assert!(data(1000, Some(20), Some(50)).is_capped());
+        assert!(!data(41, Some(20), None).is_capped());
+    }
+}
@@ -0,0 +1,257 @@
+//! User space (UP主空间) API types
+
+use crate::util::normalize_image_url;
+use serde::Deserialize;
+
+/// Uploads of a user from `/x/space/wbi/arc/search`
+#[derive(Debug, Deserialize)]
+pub struct SpaceVideoData {
+    pub list: Option<SpaceVideoList>,
+    pub page: Option<SpaceVideoPage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpaceVideoList {
+    #[serde(default)]
+    pub vlist: Vec<SpaceVideoItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpaceVideoPage {
+    pub pn: Option<i32>,
+    pub ps: Option<i32>,
+    pub count: Option<i32>,
+}
+
+/// One upload, newest first
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpaceVideoItem {
+    pub aid: i64,
+    pub bvid: String,
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub mid: Option<i64>,
+    pub pic: Option<String>,
+    /// View count, `"--"` while the video is under review
+    #[serde(default, deserialize_with = "count_or_none")]
+    pub play: Option<i64>,
+    /// Duration as `mm:ss`
+    pub length: Option<String>,
+    pub created: Option<i64>,
+}
+
+fn count_or_none<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(value.as_i64())
+}
+
+impl SpaceVideoData {
+    pub fn videos(self) -> Vec<SpaceVideoItem> {
+        self.list.map(|l| l.vlist).unwrap_or_default()
+    }
+
+    /// Total number of uploads
+    pub fn total(&self) -> i32 {
+        self.page.as_ref().and_then(|p| p.count).unwrap_or(0)
+    }
+}
+
+impl SpaceVideoItem {
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or("无标题")
+    }
+
+    pub fn author_name(&self) -> &str {
+        self.author.as_deref().unwrap_or("未知")
+    }
+
+    pub fn format_play(&self) -> String {
+        match self.play {
+            Some(n) if n >= 10000 => format!("{:.1}万", n as f64 / 10000.0),
+            Some(n) => n.to_string(),
+            None => "-".to_string(),
+        }
+    }
+
+    pub fn cover_url(&self) -> Option<String> {
+        self.pic.as_deref().map(normalize_image_url)
+    }
+}
+
+/// 合集 and 系列 of a user from `/x/polymer/web-space/seasons_series_list`
+#[derive(Debug, Default, Deserialize)]
+pub struct CollectionsData {
+    pub items_lists: Option<CollectionLists>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CollectionLists {
+    #[serde(default)]
+    pub seasons_list: Vec<CollectionEntry>,
+    #[serde(default)]
+    pub series_list: Vec<CollectionEntry>,
+}
+
+/// One 合集 or 系列 with a preview of its newest videos
+#[derive(Debug, Deserialize)]
+pub struct CollectionEntry {
+    pub meta: CollectionMeta,
+    #[serde(default)]
+    pub archives: Vec<CollectionArchive>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CollectionMeta {
+    /// Set for 合集
+    pub season_id: Option<i64>,
+    /// Set for 系列
+    pub series_id: Option<i64>,
+    #[serde(default)]
+    pub name: String,
+    pub total: Option<i32>,
+}
+
+/// Videos of one collection from `seasons_archives_list` or `/x/series/archives`
+#[derive(Debug, Default, Deserialize)]
+pub struct CollectionArchivesData {
+    #[serde(default)]
+    pub archives: Vec<CollectionArchive>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionArchive {
+    pub aid: i64,
+    pub bvid: String,
+    pub title: Option<String>,
+    pub pic: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<i64>,
+    pub stat: Option<CollectionArchiveStat>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CollectionArchiveStat {
+    pub view: Option<i64>,
+}
+
+/// Which endpoint lists a collection's videos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionKind {
+    /// 合集, episodes in the creator's order
+    Season,
+    /// 系列, a looser grouping of uploads
+    Series,
+}
+
+/// A 合集 or 系列 ready for display, `episodes` may only be a preview until fetched
+#[derive(Debug, Clone)]
+pub struct Collection {
+    pub kind: CollectionKind,
+    pub id: i64,
+    pub name: String,
+    pub total: i32,
+    pub episodes: Vec<CollectionArchive>,
+}
+
+impl CollectionsData {
+    /// 合集 first, then 系列, as the web space lists them
+    pub fn collections(self) -> Vec<Collection> {
+        let lists = self.items_lists.unwrap_or_default();
+        let seasons = lists.seasons_list.into_iter().filter_map(|entry| {
+            let id = entry.meta.season_id?;
+            Some(Collection::from_entry(CollectionKind::Season, id, entry))
+        });
+        let series = lists.series_list.into_iter().filter_map(|entry| {
+            let id = entry.meta.series_id?;
+            Some(Collection::from_entry(CollectionKind::Series, id, entry))
+        });
+        seasons.chain(series).collect()
+    }
+}
+
+impl Collection {
+    fn from_entry(kind: CollectionKind, id: i64, entry: CollectionEntry) -> Self {
+        Self {
+            kind,
+            id,
+            total: entry.meta.total.unwrap_or(entry.archives.len() as i32),
+            name: entry.meta.name,
+            episodes: entry.archives,
+        }
+    }
+}
+
+impl CollectionArchive {
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or("无标题")
+    }
+
+    pub fn format_duration(&self) -> String {
+        match self.duration {
+            Some(duration) => format!("{:02}:{:02}", duration / 60, duration % 60),
+            None => "--:--".to_string(),
+        }
+    }
+
+    pub fn format_views(&self) -> String {
+        match self.stat.as_ref().and_then(|stat| stat.view) {
+            Some(n) if n >= 10000 => format!("{:.1}万", n as f64 / 10000.0),
+            Some(n) => n.to_string(),
+            None => "-".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_under_review_is_none() {
+        let data: SpaceVideoData = serde_json::from_value(serde_json::json!({
+            "list": {"vlist": [
+                {"aid": 1, "bvid": "BV1", "title": "a", "play": 23456, "length": "03:21"},
+                {"aid": 2, "bvid": "BV2", "title": "b", "play": "--", "length": "00:10"},
+            ]},
+            "page": {"pn": 1, "ps": 30, "count": 42},
+        }))
+        .expect("valid space data");
+
+        assert_eq!(data.total(), 42);
+        let videos = data.videos();
+        assert_eq!(videos[0].format_play(), "2.3万");
+        assert_eq!(videos[1].play, None);
+        assert_eq!(videos[1].format_play(), "-");
+    }
+
+    #[test]
+    fn test_collections_skip_entries_without_id() {
+        let data: CollectionsData = serde_json::from_value(serde_json::json!({
+            "items_lists": {
+                "seasons_list": [
+                    {"meta": {"season_id": 7, "name": "合集", "total": 12}, "archives": []},
+                    {"meta": {"name": "坏数据"}},
+                ],
+                "series_list": [
+                    {"meta": {"series_id": 9, "name": "系列"}, "archives": [
+                        {"aid": 1, "bvid": "BV1", "duration": 75, "stat": {"view": 12000}},
+                    ]},
+                ],
+            },
+        }))
+        .expect("valid collections data");
+
+        let collections = data.collections();
+        assert_eq!(collections.len(), 2);
+        assert_eq!(collections[0].kind, CollectionKind::Season);
+        assert_eq!(collections[0].total, 12);
+        assert_eq!(collections[1].kind, CollectionKind::Series);
+        // Without a total the preview is all there is
+        assert_eq!(collections[1].total, 1);
+        assert_eq!(collections[1].episodes[0].format_duration(), "01:15");
+        assert_eq!(collections[1].episodes[0].format_views(), "1.2万");
+    }
+}
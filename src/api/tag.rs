@@ -0,0 +1,64 @@
+//! Video tags and the videos filed under a tag
+
+use super::video::RelatedVideoItem;
+use serde::Deserialize;
+
+/// One tag of a video from `/x/tag/archive/tags`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct VideoTag {
+    pub tag_id: i64,
+    #[serde(default)]
+    pub tag_name: String,
+}
+
+/// Newest videos under a tag from `/x/tag/detail`
+#[derive(Debug, Deserialize)]
+pub struct TagDetailData {
+    pub info: Option<TagInfo>,
+    pub news: Option<TagNews>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagInfo {
+    pub tag_id: Option<i64>,
+    pub tag_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagNews {
+    /// Videos filed under the tag
+    pub count: Option<i32>,
+    /// Archives in the same shape as related videos
+    #[serde(default)]
+    pub archives: Vec<RelatedVideoItem>,
+}
+
+impl TagDetailData {
+    /// Tag name as the API spells it, `None` when the tag info is missing
+    pub fn tag_name(&self) -> Option<&str> {
+        self.info.as_ref()?.tag_name.as_deref()
+    }
+
+    /// Total number of videos under the tag
+    pub fn total(&self) -> i32 {
+        self.news.as_ref().and_then(|news| news.count).unwrap_or(0)
+    }
+
+    pub fn videos(self) -> Vec<RelatedVideoItem> {
+        self.news.map(|news| news.archives).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_detail_without_news() {
+        let data: TagDetailData =
+            serde_json::from_str(r#"{"info":{"tag_id":1,"tag_name":"Rust"},"news":null}"#).unwrap();
+        assert_eq!(data.tag_name(), Some("Rust"));
+        assert_eq!(data.total(), 0);
+        assert!(data.videos().is_empty());
+    }
+}
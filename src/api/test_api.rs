@@ -0,0 +1,151 @@
+//! Canned [`BiliApi`] for page tests, answering from the fixtures in `fixtures/`
+
+use super::bangumi::{BangumiFollowData, FollowType};
+use super::bili_api::BiliApi;
+use super::client::ApiResponse;
+use super::comment::{CommentData, CommentSort};
+use super::dynamic::DynamicFeedData;
+use super::favorite::{FavFoldersData, FavResourceData};
+use super::history::HistoryData;
+use super::recommend::{FreshType, PopularData, RecommendData, VideoItem};
+use super::search::SearchData;
+use super::space::{CollectionArchivesData, CollectionKind, CollectionsData, SpaceVideoData};
+use super::tag::TagDetailData;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn fixture<T: for<'de> Deserialize<'de>>(json: &str) -> T {
+    let resp: ApiResponse<T> = serde_json::from_str(json).expect("invalid fixture");
+    resp.data.expect("fixture without data")
+}
+
+#[derive(Default)]
+pub struct MockApi {
+    /// Answer every request with an error
+    pub fail: bool,
+    /// Act as if no one is logged in, the feed answers with 热门
+    pub anonymous: bool,
+    /// Number of requests made so far
+    pub calls: AtomicUsize,
+}
+
+impl MockApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn failing() -> Self {
+        Self {
+            fail: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn anonymous() -> Self {
+        Self {
+            anonymous: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    fn answer<T>(&self, data: impl FnOnce() -> T) -> Result<T> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(anyhow!("mock failure"));
+        }
+        Ok(data())
+    }
+}
+
+impl BiliApi for MockApi {
+    fn is_logged_in(&self) -> bool {
+        !self.anonymous
+    }
+
+    async fn get_recommendations_paged(
+        &self,
+        _fresh_idx: i32,
+        _fresh_type: FreshType,
+    ) -> Result<Vec<VideoItem>> {
+        if self.anonymous {
+            return self
+                .answer(|| fixture::<PopularData>(include_str!("fixtures/popular.json")).list);
+        }
+        self.answer(|| fixture::<RecommendData>(include_str!("fixtures/recommend.json")).item)
+    }
+
+    async fn search_videos(&self, _keyword: &str, _page: i32) -> Result<SearchData> {
+        self.answer(|| fixture(include_str!("fixtures/search.json")))
+    }
+
+    async fn get_dynamic_feed(
+        &self,
+        _offset: Option<&str>,
+        _feed_type: Option<&str>,
+        _host_mid: Option<i64>,
+    ) -> Result<DynamicFeedData> {
+        self.answer(|| fixture(include_str!("fixtures/dynamic.json")))
+    }
+
+    async fn get_history(
+        &self,
+        _max: Option<i64>,
+        _view_at: Option<i64>,
+        _business: Option<&str>,
+    ) -> Result<HistoryData> {
+        self.answer(|| fixture(include_str!("fixtures/history.json")))
+    }
+
+    async fn get_space_videos(&self, _mid: i64, _page: i32) -> Result<SpaceVideoData> {
+        self.answer(|| fixture(include_str!("fixtures/space.json")))
+    }
+
+    async fn get_user_collections(&self, _mid: i64) -> Result<CollectionsData> {
+        self.answer(|| fixture(include_str!("fixtures/space_collections.json")))
+    }
+
+    async fn get_collection_videos(
+        &self,
+        _mid: i64,
+        _kind: CollectionKind,
+        _id: i64,
+    ) -> Result<CollectionArchivesData> {
+        self.answer(|| fixture(include_str!("fixtures/collection_archives.json")))
+    }
+
+    async fn get_tag_videos(&self, _tag_id: i64, _page: i32) -> Result<TagDetailData> {
+        self.answer(|| fixture(include_str!("fixtures/tag_detail.json")))
+    }
+
+    async fn get_comments_by_type(
+        &self,
+        _oid: i64,
+        _comment_type: i32,
+        _page: i32,
+        _sort: CommentSort,
+    ) -> Result<CommentData> {
+        self.answer(|| fixture(include_str!("fixtures/comments.json")))
+    }
+
+    async fn get_bangumi_follow(
+        &self,
+        _mid: i64,
+        _follow_type: FollowType,
+        _page: i32,
+    ) -> Result<BangumiFollowData> {
+        self.answer(|| fixture(include_str!("fixtures/bangumi_follow.json")))
+    }
+
+    async fn get_fav_folders(&self, _mid: i64) -> Result<FavFoldersData> {
+        self.answer(|| fixture(include_str!("fixtures/fav_folders.json")))
+    }
+
+    async fn get_fav_videos(&self, _media_id: i64, _page: i32) -> Result<FavResourceData> {
+        self.answer(|| fixture(include_str!("fixtures/fav_resources.json")))
+    }
+}
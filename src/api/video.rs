@@ -1,5 +1,6 @@
 //! Video info API types
 
+use crate::util::normalize_image_url;
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -15,6 +16,8 @@ pub struct VideoInfo {
     pub owner: VideoOwner,
     pub stat: VideoStat,
     pub pages: Option<Vec<VideoPage>>,
+    /// The UGC season (合集) this video belongs to
+    pub ugc_season: Option<UgcSeason>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,6 +46,59 @@ pub struct VideoPage {
     pub duration: i64,
 }
 
+/// A UGC season (合集) an uploader grouped their videos into
+#[derive(Debug, Clone, Deserialize)]
+pub struct UgcSeason {
+    pub id: i64,
+    pub title: String,
+    #[serde(default)]
+    pub sections: Vec<UgcSection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UgcSection {
+    #[serde(default)]
+    pub episodes: Vec<UgcEpisode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UgcEpisode {
+    pub aid: i64,
+    pub cid: i64,
+    pub bvid: String,
+    pub title: String,
+    pub arc: Option<UgcEpisodeArc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UgcEpisodeArc {
+    pub duration: Option<i64>,
+}
+
+impl UgcSeason {
+    /// All episodes in play order, sections one after another
+    pub fn episodes(&self) -> Vec<UgcEpisode> {
+        self.sections
+            .iter()
+            .flat_map(|section| section.episodes.iter().cloned())
+            .collect()
+    }
+
+    /// Index of `bvid` among [`Self::episodes`]
+    pub fn position(&self, bvid: &str) -> Option<usize> {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.episodes)
+            .position(|episode| episode.bvid == bvid)
+    }
+}
+
+impl UgcEpisode {
+    pub fn duration(&self) -> i64 {
+        self.arc.as_ref().and_then(|arc| arc.duration).unwrap_or(0)
+    }
+}
+
 /// Related video item from /x/web-interface/archive/related
 #[derive(Debug, Clone, Deserialize)]
 pub struct RelatedVideoItem {
@@ -69,6 +125,80 @@ pub struct RelatedVideoStat {
     pub danmaku: Option<i64>,
 }
 
+/// Stream info from /x/player/wbi/playurl, only the quality list is used
+#[derive(Debug, Deserialize)]
+pub struct PlayUrlData {
+    #[serde(default)]
+    pub accept_quality: Vec<u32>,
+    #[serde(default)]
+    pub accept_description: Vec<String>,
+    pub support_formats: Option<Vec<SupportFormat>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SupportFormat {
+    pub quality: u32,
+    pub new_description: Option<String>,
+    pub need_vip: Option<bool>,
+}
+
+/// A selectable stream quality (qn)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityOption {
+    pub qn: u32,
+    pub label: String,
+    pub need_vip: bool,
+}
+
+/// Qualities above 1080P (1080P+, 1080P60, 4K, HDR, Dolby, 8K) are 大会员 only
+pub const MAX_FREE_QUALITY: u32 = 80;
+
+impl PlayUrlData {
+    /// Quality options from highest to lowest
+    pub fn quality_options(&self) -> Vec<QualityOption> {
+        let mut options: Vec<QualityOption> = match &self.support_formats {
+            Some(formats) if !formats.is_empty() => formats
+                .iter()
+                .map(|f| QualityOption {
+                    qn: f.quality,
+                    label: f
+                        .new_description
+                        .clone()
+                        .unwrap_or_else(|| f.quality.to_string()),
+                    need_vip: f.need_vip.unwrap_or(f.quality > MAX_FREE_QUALITY),
+                })
+                .collect(),
+            _ => self
+                .accept_quality
+                .iter()
+                .enumerate()
+                .map(|(idx, &qn)| QualityOption {
+                    qn,
+                    label: self
+                        .accept_description
+                        .get(idx)
+                        .cloned()
+                        .unwrap_or_else(|| qn.to_string()),
+                    need_vip: qn > MAX_FREE_QUALITY,
+                })
+                .collect(),
+        };
+        options.sort_by_key(|o| std::cmp::Reverse(o.qn));
+        options
+    }
+}
+
+/// Pick the highest playable quality not above `preferred`
+/// Falls back to the lowest playable one when everything is above it
+pub fn default_quality(options: &[QualityOption], preferred: u32, is_vip: bool) -> Option<u32> {
+    let playable = || options.iter().filter(|o| is_vip || !o.need_vip);
+    playable()
+        .filter(|o| o.qn <= preferred)
+        .map(|o| o.qn)
+        .max()
+        .or_else(|| playable().map(|o| o.qn).min())
+}
+
 impl RelatedVideoItem {
     pub fn author_name(&self) -> &str {
         self.owner
@@ -102,13 +232,7 @@ impl RelatedVideoItem {
     }
 
     pub fn cover_url(&self) -> Option<String> {
-        self.pic.as_ref().map(|url| {
-            if url.starts_with("//") {
-                format!("https:{}", url)
-            } else {
-                url.clone()
-            }
-        })
+        self.pic.as_deref().map(normalize_image_url)
     }
 }
 
@@ -161,3 +285,77 @@ impl VideoStat {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play_url() -> PlayUrlData {
+        serde_json::from_str(
+            r#"{
+                "accept_quality": [116, 80, 64, 32],
+                "accept_description": ["高清 1080P60", "高清 1080P", "高清 720P", "清晰 480P"],
+                "support_formats": [
+                    {"quality": 116, "new_description": "1080P 60帧", "need_vip": true},
+                    {"quality": 80, "new_description": "1080P 高清"},
+                    {"quality": 64, "new_description": "720P 准高清"},
+                    {"quality": 32, "new_description": "480P 标清"}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ugc_season_episode_order() {
+        let season: UgcSeason = serde_json::from_str(
+            r#"{
+                "id": 1, "title": "教程合集",
+                "sections": [
+                    {"episodes": [
+                        {"aid": 1, "cid": 11, "bvid": "BV1", "title": "第一集", "arc": {"duration": 60}},
+                        {"aid": 2, "cid": 22, "bvid": "BV2", "title": "第二集"}
+                    ]},
+                    {"episodes": [
+                        {"aid": 3, "cid": 33, "bvid": "BV3", "title": "番外", "arc": {"duration": 90}}
+                    ]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let titles: Vec<_> = season.episodes().into_iter().map(|ep| ep.title).collect();
+        assert_eq!(titles, ["第一集", "第二集", "番外"]);
+        assert_eq!(season.position("BV3"), Some(2));
+        assert_eq!(season.position("BV9"), None);
+        assert_eq!(season.episodes()[1].duration(), 0);
+    }
+
+    #[test]
+    fn test_quality_options_mark_vip() {
+        let options = play_url().quality_options();
+        assert_eq!(options.len(), 4);
+        assert_eq!(options[0].label, "1080P 60帧");
+        assert!(options[0].need_vip);
+        assert!(!options[1].need_vip);
+    }
+
+    #[test]
+    fn test_quality_options_fallback_to_accept_quality() {
+        let mut data = play_url();
+        data.support_formats = None;
+        let options = data.quality_options();
+        assert_eq!(options[0].label, "高清 1080P60");
+        assert!(options[0].need_vip);
+        assert!(!options[3].need_vip);
+    }
+
+    #[test]
+    fn test_default_quality_respects_vip() {
+        let options = play_url().quality_options();
+        assert_eq!(default_quality(&options, 120, false), Some(80));
+        assert_eq!(default_quality(&options, 120, true), Some(116));
+        assert_eq!(default_quality(&options, 64, true), Some(64));
+        assert_eq!(default_quality(&options, 16, false), Some(32));
+        assert_eq!(default_quality(&[], 80, false), None);
+    }
+}
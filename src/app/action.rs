@@ -1,4 +1,4 @@
-use crate::storage::{Credentials, Keybindings};
+use crate::storage::{Credentials, DownloadConfig, Keybindings, NetworkConfig};
 use crate::ui::ThemeVariant;
 
 /// Actions that can be triggered from UI components
@@ -8,22 +8,50 @@ pub enum AppAction {
     Quit,
     /// Switch to home page
     SwitchToHome,
-    /// Refresh home page recommendations (force reload)
-    RefreshHome,
+    /// Reload recommendations in place, keeping the home page's feed mode
+    ReloadRecommendations,
+    /// Swap in the next batch of recommendations, keeping the selection and scroll
+    RefreshRecommendations,
+    /// Toggle hiding locally watched videos in the home and search grids
+    ToggleHideWatched,
+    /// Switch the home page between the cover grid and the text list
+    ToggleHomeView,
     /// Switch to login page
     SwitchToLogin,
+    /// Leave the first-run welcome screen for the login page
+    DismissWelcome,
+    /// Reload the login QR code, keeping the selected QR source
+    ReloadLoginQrcode,
     /// Switch to settings page
     SwitchToSettings,
     /// Switch to history page
     SwitchToHistory,
+    /// Reload account info on the profile page
+    RefreshProfile,
     /// Login was successful with credentials
     LoginSuccess(Credentials),
+    /// Validate manually entered cookies and log in if they are valid
+    CookieLogin(Credentials),
     /// Play a video with metadata (bvid, aid, cid, duration)
     PlayVideo {
         bvid: String,
+        /// UP who uploaded it, whose intro skip applies
+        mid: i64,
         aid: i64,
         cid: i64,
         duration: i64,
+        /// Selected stream quality (qn), None lets the player decide
+        quality: Option<u32>,
+    },
+    /// Play a bangumi episode by its page URL, `label` is shown while it plays
+    PlayEpisode {
+        url: String,
+        label: String,
+        aid: i64,
+        cid: i64,
+        duration: i64,
+        /// The play key already answered the dialog of `AppConfig::confirm_before_play`
+        confirmed: bool,
     },
     /// Navigate to next sidebar item
     NavNext,
@@ -31,14 +59,42 @@ pub enum AppAction {
     NavPrev,
     /// Search for videos
     Search(String),
+    /// Fetch the hot search list again, e.g. when it failed to load with the page
+    LoadHotwords,
     /// Refresh dynamic feed
     RefreshDynamic,
     /// Open video detail page (bvid, aid)
     OpenVideoDetail(String, i64),
+    /// Resolve a bvid, av number or URL typed into the open prompt
+    OpenByInput(String),
+    /// Close the open prompt
+    CloseOpenPrompt,
+    /// Quit after stopping background playback, chosen in the quit confirmation
+    ConfirmQuit,
+    /// Dismiss the confirmation dialog without acting
+    CloseConfirm,
     /// Open dynamic detail page for image/text dynamics (dynamic_id)
     OpenDynamicDetail(String),
     /// Go back to previous page
     BackToList,
+    /// Re-issue the request that failed on the current page
+    RetryFailed,
+    /// Copy the playing video's link with its current position
+    CopyPlayingLink,
+    /// Play an item picked from the recently played list again
+    ReplayRecent(Box<crate::storage::RecentItem>),
+    /// Close the recently played picker
+    CloseRecentPicker,
+    /// Close the command palette
+    CloseCommandPalette,
+    /// Act as if `KeyCode` was pressed, e.g. a key-bound command picked from the palette
+    RunKey(ratatui::crossterm::event::KeyCode),
+    /// Switch to a sidebar section
+    SwitchSection(crate::ui::NavItem),
+    /// Step the seconds skipped at the start of videos by UP `mid`
+    CycleIntroSkip { mid: i64, name: String },
+    /// Play the video offered for resuming, from where it stopped or from the start
+    ResumePlayback { from_start: bool },
     /// Load more recommendations
     LoadMoreRecommendations,
     /// Load more search results
@@ -47,10 +103,65 @@ pub enum AppAction {
     LoadMoreDynamic,
     /// Load more history items
     LoadMoreHistory,
-    /// Load more comments in video detail page
+    /// Load more comments in the video detail or comments page
     LoadMoreComments,
     /// Toggle comment replies expansion
     ToggleCommentReplies,
+    /// Load the next page of the expanded reply thread
+    LoadMoreReplies,
+    /// Switch comments between hot and newest first
+    CycleCommentSort,
+    /// Open the comments of item `oid` of reply type `comment_type` without its detail page
+    OpenComments {
+        oid: i64,
+        comment_type: i32,
+        title: String,
+    },
+    /// Reload the comments page
+    RefreshComments,
+    /// Open the uploads of UP `mid`, `name` is shown while loading
+    OpenUserSpace { mid: i64, name: String },
+    /// Load more series in the 追番 page
+    LoadMoreBangumi,
+    /// Switch the 追番 page between anime and drama and reload it
+    ToggleBangumiType,
+    /// Reload the 追番 page
+    RefreshBangumi,
+    /// Load the next page of the folder shown in the 收藏 page
+    LoadMoreFavorites,
+    /// Move the 收藏 page this many folders along
+    SwitchFavFolder(isize),
+    /// Reload the folders of the 收藏 page, dropping the loaded pages
+    RefreshFavorites,
+    /// Load more uploads in the UP space page
+    LoadMoreSpaceVideos,
+    /// Load the 合集 and 系列 of the UP space page
+    LoadSpaceCollections,
+    /// Fetch the full episode list of the collection at this index in the UP space page
+    LoadCollectionEpisodes(usize),
+    /// Play a video picked from a list without opening its detail page
+    PlayFromList {
+        bvid: String,
+        /// UP who uploaded it, whose intro skip applies
+        mid: i64,
+        aid: i64,
+        title: String,
+        duration: i64,
+    },
+    /// Open the newest videos under tag `tag_id`, `name` is shown while loading
+    OpenTag { tag_id: i64, name: String },
+    /// Load more videos in the tag page
+    LoadMoreTagVideos,
+    /// Delete a watch history entry already removed from the list, by its `kid`
+    DeleteHistory(String),
+    /// Ask before deleting the marked watch history entries, by their `kid`s
+    RequestDeleteHistoryBatch(Vec<String>),
+    /// Delete several watch history entries, by their `kid`s
+    DeleteHistoryBatch(Vec<String>),
+    /// Ask before clearing the whole watch history
+    RequestClearHistory,
+    /// Clear the whole watch history
+    ClearHistory,
     /// Switch dynamic tab
     SwitchDynamicTab(crate::ui::DynamicTab),
     /// Select UP master (0 = all, 1+ = specific UP)
@@ -61,20 +172,43 @@ pub enum AppAction {
     SetTheme(ThemeVariant),
     /// Save keybindings to config
     SaveKeybindings(Box<Keybindings>),
+    /// Apply and save network settings
+    SaveNetworkConfig(NetworkConfig),
+    /// Apply and save the recommendation column count, `None` for automatic
+    SaveGridColumns(Option<usize>),
+    /// Persist the recommendation filter and apply it to the home feed
+    SaveRecommendFilter(crate::storage::RecommendFilter),
+    /// Save the image protocol, used from the next launch
+    SaveImageProtocol(crate::storage::ImageProtocol),
+    /// Save whether `h`/`l` wrap around the ends of video lists
+    SaveWrapNavigation(bool),
+    /// Save the download directory and format
+    SaveDownloadConfig(DownloadConfig),
+    /// Save a video (bvid) to disk with yt-dlp in the background
+    DownloadVideo(String),
+    /// Download several videos (bvids), e.g. the marked history entries
+    DownloadVideos(Vec<String>),
     /// Logout and return to login page
     Logout,
+    /// Run the login self-test and show the report on the settings page
+    RunDiagnostics,
+    /// Refresh the login cookies now instead of waiting for them to near expiry
+    RefreshCredentials,
+    /// Ask the server whether the saved cookies still log in
+    ValidateLogin,
     /// Like or unlike a comment (oid, rpid, comment_type)
     LikeComment {
         oid: i64,
         rpid: i64,
         comment_type: i32,
     },
-    /// Add a comment (oid, comment_type, message, optional root rpid for replies)
+    /// Add a comment, replies carry the thread `root` and the `parent` they answer
     AddComment {
         oid: i64,
         comment_type: i32,
         message: String,
         root: Option<i64>,
+        parent: Option<i64>,
     },
     /// No action
     None,
@@ -0,0 +1,48 @@
+use ratatui::crossterm::event::{self, Event};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+/// Events driving the main loop
+#[derive(Debug)]
+pub enum AppEvent {
+    /// Terminal input (key, mouse, resize)
+    Input(Event),
+    /// Periodic tick for background work (QR polling, cover loading)
+    Tick,
+}
+
+/// Merges terminal input and the tick interval into one async stream
+pub struct EventHandler {
+    input_rx: mpsc::UnboundedReceiver<Event>,
+    tick: Interval,
+}
+
+impl EventHandler {
+    pub fn new(tick_rate: Duration) -> Self {
+        let (tx, input_rx) = mpsc::unbounded_channel();
+
+        // crossterm's read is blocking, so forward events from a dedicated thread
+        std::thread::spawn(move || {
+            while let Ok(event) = event::read() {
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut tick = interval(tick_rate);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        Self { input_rx, tick }
+    }
+
+    /// Wait for the next input event or tick
+    /// Returns None once the input reader has stopped
+    pub async fn next(&mut self) -> Option<AppEvent> {
+        tokio::select! {
+            event = self.input_rx.recv() => event.map(AppEvent::Input),
+            _ = self.tick.tick() => Some(AppEvent::Tick),
+        }
+    }
+}
@@ -1,28 +1,49 @@
 mod action;
+mod event;
+mod task;
 
 pub use action::AppAction;
+pub use event::{AppEvent, EventHandler};
+pub use task::next_request_id;
 
+use crate::api::auth::NavInfo;
 use crate::api::client::ApiClient;
-use crate::storage::{AppConfig, Credentials, Keybindings};
+use crate::api::message::UnreadCounts;
+use crate::api::video::UgcEpisode;
+use crate::storage::{
+    AppConfig, Credentials, Keybindings, RecentItem, RecentlyPlayed, WatchedVideos,
+};
+use crate::ui::util::centered_fixed;
+use crate::ui::{
+    BangumiPage, CommandPalette, CommentsPage, Component, ConfirmDialog, DynamicPage,
+    FavoritesPage, HistoryPage, HomePage, LoginCheck, LoginPage, LoginRequiredPage, NavItem,
+    OpenPrompt, Page, ProfilePage, RecentPicker, SearchPage, SettingsPage, Sidebar, TagPage, Theme,
+    ThemeVariant, Toast, TokenStatus, UserSpacePage, VideoDetailPage, WelcomePage,
+};
 use crate::ui::{
-    Component, DynamicPage, HistoryPage, HomePage, LoginPage, NavItem, Page, SearchPage,
-    SettingsPage, Sidebar, Theme, ThemeVariant, VideoDetailPage,
+    BangumiRequest, CommentsRequest, DynamicRequest, FavoritesRequest, HomeRequest, SearchRequest,
+    SpaceRequest, TagRequest,
 };
 use ratatui::{
-    crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseEvent},
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseEvent},
     prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
     DefaultTerminal, Frame,
 };
 use std::io;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 
 /// Previous page for back navigation
-#[derive(Clone)]
+/// List pages are kept as-is so returning restores their scroll position and covers,
+/// the home page lives in `cached_home` since tab switches reuse it too
 pub enum PreviousPage {
     Home,
-    Search,
-    Dynamic,
-    History,
+    Search(SearchPage),
+    Dynamic(DynamicPage),
+    History(HistoryPage),
+    Favorites(Box<FavoritesPage>),
 }
 
 /// Main application state
@@ -31,6 +52,8 @@ pub struct App {
     pub should_quit: bool,
     pub api_client: Arc<ApiClient>,
     pub credentials: Option<Credentials>,
+    /// Account info of the logged-in user, refreshed on startup and login
+    pub nav_info: Option<NavInfo>,
     pub sidebar: Sidebar,
     pub show_sidebar: bool,
 
@@ -39,32 +62,278 @@ pub struct App {
     pub theme_variant: ThemeVariant,
     pub config: AppConfig,
     pub keybindings: Keybindings,
+    /// Videos launched from this client, dimmed or hidden in grids
+    pub watched: WatchedVideos,
 
     /// Cached home page to avoid refresh when switching tabs
     pub cached_home: Option<HomePage>,
+
+    /// "Open by id" prompt shown over the current page
+    pub open_prompt: Option<OpenPrompt>,
+    /// Yes/no dialog shown over everything else, e.g. before quitting during playback
+    confirm: Option<ConfirmDialog>,
+
+    /// Long-lived task keeping the login fresh, polled on tick
+    credential_refresh: Option<CredentialRefresh>,
+    /// Whether the saved cookies logged in when last checked
+    login_check: LoginCheck,
+    /// Unix time the server last answered a login check
+    login_checked_at: Option<i64>,
+    /// App-wide notice drawn above the current page
+    toast: Option<Toast>,
+    /// Video open in the external player
+    now_playing: Option<NowPlaying>,
+    /// Items played from this client, newest first
+    recent: RecentlyPlayed,
+    /// Quick-pick over `recent`, drawn above the current page
+    recent_picker: Option<RecentPicker>,
+    /// Actions searchable by name, drawn above the current page
+    command_palette: Option<CommandPalette>,
+    /// Page URL and final position in seconds of the video that played last
+    last_played: Option<(String, i64)>,
+    /// Where the last played video stopped, offered when it is played again
+    resume_point: Option<ResumePoint>,
+    /// Play request held while the resume dialog is open
+    resume_offer: Option<ResumeOffer>,
+    /// Next season episode waiting out its cancel window before it plays
+    pending_autoplay: Option<PendingAutoplay>,
+    /// yt-dlp downloads running in the background
+    downloads: Vec<Download>,
+    /// Section from the last session to open once home has loaded
+    restore_section: Option<NavItem>,
+    /// Latest unread message counts of the logged-in user
+    unread: Option<UnreadCounts>,
+    /// Pending unread count fetch, polled on tick
+    unread_refresh: Option<oneshot::Receiver<Result<UnreadCounts, String>>>,
+    /// When the unread counts were last requested
+    unread_checked_at: Option<Instant>,
+    /// Last terminal resize not yet applied to the covers
+    pending_resize: Option<Instant>,
+    /// Page requests running in the background, applied on tick
+    tasks: task::Tasks,
+}
+
+/// Covers are rebuilt once the terminal size has been stable this long
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Smallest terminal the layout fits in, below it only a resize hint is drawn
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
+fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Apply the wrap navigation setting to the list or grid of `page`
+fn set_wrap_navigation(page: &mut Page, wrap: bool) {
+    match page {
+        Page::Home(page) => page.set_wrap_navigation(wrap),
+        Page::Search(page) => page.grid.wrap = wrap,
+        Page::Dynamic(page) => page.grid.wrap = wrap,
+        Page::VideoDetail(page) => page.related_card_grid.wrap = wrap,
+        Page::Bangumi(page) => page.grid.wrap = wrap,
+        Page::Favorites(page) => page.grid.wrap = wrap,
+        Page::UserSpace(page) => page.grid.wrap = wrap,
+        Page::Tag(page) => page.grid.wrap = wrap,
+        _ => {}
+    }
+}
+
+/// Dialog running `action` once the play key is pressed, marked so it plays then
+fn play_key_dialog(label: String, mut action: AppAction) -> ConfirmDialog {
+    if let AppAction::PlayEpisode { confirmed, .. } = &mut action {
+        *confirmed = true;
+    }
+    ConfirmDialog::new("播放", vec![label], action).play_key_only()
+}
+
+/// Whether `page` takes typed characters, so global keys must not fire on it
+fn captures_text(page: &Page) -> bool {
+    match page {
+        Page::Search(page) => page.input_mode || page.filter_input,
+        Page::Dynamic(page) => page.filter_mode,
+        Page::VideoDetail(page) => page.input_mode,
+        Page::DynamicDetail(page) => page.input_mode,
+        Page::Settings(page) => page.editing_keybind || page.editing_download.is_some(),
+        _ => false,
+    }
+}
+
+/// How often unread message counts are refreshed
+const UNREAD_REFRESH_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Playback running in the background until mpv exits
+struct NowPlaying {
+    /// Bvid or episode name shown in the banner
+    label: String,
+    /// Page URL handed to mpv, the base of the copied timestamped link
+    url: String,
+    /// Empty for episodes played by URL
+    bvid: String,
+    /// Identifies the mpv IPC socket the position is read from
+    cid: i64,
+    duration: i64,
+    /// Last position in seconds once mpv has exited
+    done: oneshot::Receiver<Result<i64, String>>,
+    /// Kills mpv when sent, dropped unused when playback ends on its own
+    stop: oneshot::Sender<()>,
+    /// Season the video belongs to, for playing the next episode afterwards
+    season: Option<SeasonQueue>,
+}
+
+/// Part of a video that stopped early, kept for this session only
+struct ResumePoint {
+    bvid: String,
+    cid: i64,
+    /// Last position mpv reported, in seconds
+    position: i64,
+}
+
+/// Positions this close to the start or the end are not worth resuming from
+const RESUME_MARGIN: i64 = 10;
+
+impl ResumePoint {
+    /// Resume point for a video that stopped at `position`, none once it played to the end
+    fn after(bvid: &str, cid: i64, position: i64, duration: i64) -> Option<Self> {
+        let finished = duration > 0 && position >= duration - RESUME_MARGIN;
+        (!bvid.is_empty() && position > RESUME_MARGIN && !finished).then(|| Self {
+            bvid: bvid.to_string(),
+            cid,
+            position,
+        })
+    }
+}
+
+/// `play_video` arguments waiting for the answer to the resume dialog
+struct ResumeOffer {
+    bvid: String,
+    mid: i64,
+    title: String,
+    aid: i64,
+    cid: i64,
+    duration: i64,
+    quality: Option<u32>,
+    season: Option<SeasonQueue>,
+    position: i64,
+}
+
+/// Episodes of a UGC season (合集) and the one being played
+struct SeasonQueue {
+    /// UP who made the season, whose intro skip applies to every episode
+    mid: i64,
+    episodes: Vec<UgcEpisode>,
+    index: usize,
+    /// Quality picked for the first episode, kept for the rest
+    quality: Option<u32>,
+}
+
+impl SeasonQueue {
+    /// The queue moved on to the following episode, `None` after the last one
+    fn advance(self) -> Option<Self> {
+        let index = self.index + 1;
+        (index < self.episodes.len()).then_some(Self { index, ..self })
+    }
+
+    fn current(&self) -> &UgcEpisode {
+        &self.episodes[self.index]
+    }
+}
+
+/// Next episode announced in a toast, played once `starts_at` passes unless cancelled
+struct PendingAutoplay {
+    queue: SeasonQueue,
+    starts_at: Instant,
+}
+
+/// How long the "next episode" toast stays up before the episode starts
+const AUTOPLAY_DELAY: Duration = Duration::from_secs(5);
+
+/// How often the background task checks whether the login needs refreshing
+const CREDENTIAL_CHECK_INTERVAL: Duration = Duration::from_secs(4 * 60 * 60);
+
+/// Background task refreshing the login for as long as the app runs
+struct CredentialRefresh {
+    /// Refreshed credentials, already saved and applied to the client, or the error of a check
+    updates: mpsc::UnboundedReceiver<Result<Credentials, String>>,
+    /// Ends the task on logout and shutdown
+    stop: oneshot::Sender<()>,
+}
+
+/// A yt-dlp download running in the background
+struct Download {
+    bvid: String,
+    /// Last reported percentage
+    percent: f32,
+    progress: mpsc::UnboundedReceiver<f32>,
+    done: oneshot::Receiver<Result<std::path::PathBuf, String>>,
+    stop: oneshot::Sender<()>,
 }
 
+/// Download progress is announced each time it passes another step of this many percent
+const DOWNLOAD_TOAST_STEP: f32 = 25.0;
+
+/// How long quitting waits for background tasks to finish their cleanup
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
 impl App {
     pub fn new() -> Self {
-        let credentials = crate::storage::load_credentials().ok();
-        let api_client = if let Some(ref creds) = credentials {
-            ApiClient::with_cookies(creds)
+        let credentials = match crate::storage::load_credentials() {
+            Ok(credentials) => Some(credentials),
+            Err(e) => {
+                // No file just means nobody has logged in yet
+                let missing = e
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::NotFound);
+                if !missing {
+                    let _ = crate::storage::append_log(&format!("读取登录信息失败: {}", e));
+                }
+                None
+            }
+        };
+        #[cfg(feature = "mock")]
+        let credentials = if crate::api::mock::enabled() {
+            Some(crate::api::mock::credentials())
         } else {
-            ApiClient::new()
+            credentials
         };
 
         // Load config and apply saved theme
         let config = crate::storage::load_config().unwrap_or_default();
+        let api_client = ApiClient::from_config(credentials.as_ref(), &config);
         let keybindings = config.keybindings.clone();
-        let theme_variant = config
-            .theme
-            .parse()
-            .unwrap_or(ThemeVariant::CatppuccinMocha);
+        let theme_variant = config.theme.parse().unwrap_or(ThemeVariant::Auto);
         let theme = Theme::from_variant(theme_variant);
+        crate::ui::set_download_timeout(config.network.request_timeout());
+        crate::ui::image_protocol::init(config.image_protocol);
+        // Query the background now: once the event reader owns stdin it would eat the
+        // reply when the theme is later switched to Auto
+        crate::ui::terminal_bg::is_light();
+        let watched = crate::storage::load_watched().unwrap_or_default();
+        let recent = if config.remember_recent {
+            crate::storage::load_recent().unwrap_or_default()
+        } else {
+            RecentlyPlayed::default()
+        };
 
         // Start on login page if no credentials, otherwise go to home
+        let mut restore_section = None;
         let current_page = if credentials.is_some() {
-            Page::Home(HomePage::new())
+            let mut home = HomePage::new();
+            if config.restore_session {
+                let session = &config.session;
+                home.restore(
+                    session.fresh_type.parse().unwrap_or_default(),
+                    session.home_selected,
+                );
+                restore_section = session
+                    .section
+                    .parse()
+                    .ok()
+                    .filter(|section| *section != NavItem::Home);
+            }
+            Page::Home(home)
+        } else if crate::storage::is_first_run() {
+            Page::Welcome(WelcomePage::detect())
         } else {
             Page::Login(LoginPage::new())
         };
@@ -74,6 +343,7 @@ impl App {
             should_quit: false,
             api_client: Arc::new(api_client),
             credentials,
+            nav_info: None,
             sidebar: Sidebar::new(),
             show_sidebar: true,
             previous_page: None,
@@ -81,25 +351,69 @@ impl App {
             theme_variant,
             config,
             keybindings,
+            watched,
             cached_home: None,
+            open_prompt: None,
+            confirm: None,
+            credential_refresh: None,
+            login_check: LoginCheck::Unknown,
+            login_checked_at: None,
+            toast: None,
+            now_playing: None,
+            recent,
+            recent_picker: None,
+            command_palette: None,
+            last_played: None,
+            resume_point: None,
+            resume_offer: None,
+            pending_autoplay: None,
+            downloads: Vec::new(),
+            restore_section,
+            unread: None,
+            unread_refresh: None,
+            unread_checked_at: None,
+            pending_resize: None,
+            tasks: task::Tasks::new(),
         }
     }
 
-    /// 记录当前页面以便返回导航
-    fn save_previous_page(&mut self) {
-        self.previous_page = match &self.current_page {
-            Page::Home(_) => Some(PreviousPage::Home),
-            Page::Search(_) => Some(PreviousPage::Search),
-            Page::Dynamic(_) => Some(PreviousPage::Dynamic),
-            Page::History(_) => Some(PreviousPage::History),
-            _ => None,
+    /// 进入详情页，保留当前列表页以便返回时恢复原状
+    fn enter_detail_page(&mut self, detail: Page) {
+        let mut detail = detail;
+        set_wrap_navigation(&mut detail, self.config.wrap_navigation);
+        let previous = std::mem::replace(&mut self.current_page, detail);
+        self.previous_page = match previous {
+            Page::Home(page) => {
+                self.cached_home = Some(page);
+                Some(PreviousPage::Home)
+            }
+            Page::Search(page) => Some(PreviousPage::Search(page)),
+            Page::Dynamic(page) => Some(PreviousPage::Dynamic(page)),
+            Page::History(mut page) => {
+                page.clear_marks();
+                Some(PreviousPage::History(page))
+            }
+            Page::Favorites(page) => Some(PreviousPage::Favorites(page)),
+            // Detail opened from another detail page: go back to the original list
+            _ => self.previous_page.take(),
         };
     }
 
     /// Main run loop
     pub async fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
         // Initialize the first page
+        // Logins saved before the device ids were captured get them here
+        self.ensure_device_ids().await;
+        self.start_credential_refresh();
+        // Old covers leave the disk cache off the render path
+        tokio::task::spawn_blocking(crate::ui::prune_disk_cache);
+        self.refresh_nav_info().await;
         self.init_current_page().await;
+        if let Some(section) = self.restore_section.take() {
+            // Home stays cached underneath, like a normal tab switch
+            self.sidebar.select(section);
+            self.switch_to_nav_page().await;
+        }
 
         // Store the last content area for mouse handling
         let mut last_content_area = Rect::default();
@@ -109,58 +423,114 @@ impl App {
         const SCROLL_THRESHOLD: i32 = 15; // Accumulate 15 events before scrolling
         let mut scroll_accumulator: i32 = 0;
 
+        // Input is handled as soon as it arrives, background work runs on each tick
+        let mut events = EventHandler::new(std::time::Duration::from_millis(50));
+
+        // Frames are only drawn after something may have changed, an idle screen costs
+        // nothing but the tick
+        let mut dirty = true;
+
         while !self.should_quit {
-            terminal.draw(|frame| {
-                last_content_area = self.get_content_area(frame.area());
-                self.draw(frame);
-            })?;
-
-            if event::poll(std::time::Duration::from_millis(100))? {
-                match event::read()? {
-                    Event::Key(key) => {
-                        if key.kind == KeyEventKind::Press {
-                            self.handle_input(key.code, key.modifiers).await;
-                        }
+            if dirty {
+                terminal.draw(|frame| {
+                    // Nothing is clickable while the resize hint is shown
+                    last_content_area = if is_too_small(frame.area()) {
+                        Rect::default()
+                    } else {
+                        self.get_content_area(frame.area())
+                    };
+                    self.draw(frame);
+                })?;
+                dirty = false;
+            }
+
+            let Some(app_event) = events.next().await else {
+                break;
+            };
+
+            match app_event {
+                AppEvent::Input(Event::Key(key)) => {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_input(key.code, key.modifiers).await;
+                        dirty = true;
                     }
-                    Event::Mouse(mouse) => {
-                        use crossterm::event::MouseEventKind;
-                        match mouse.kind {
-                            MouseEventKind::ScrollDown => {
-                                scroll_accumulator += 1;
-                                if scroll_accumulator >= SCROLL_THRESHOLD {
-                                    scroll_accumulator = 0;
-                                    self.handle_mouse(mouse, last_content_area).await;
-                                }
-                            }
-                            MouseEventKind::ScrollUp => {
-                                scroll_accumulator -= 1;
-                                if scroll_accumulator <= -SCROLL_THRESHOLD {
-                                    scroll_accumulator = 0;
-                                    self.handle_mouse(mouse, last_content_area).await;
-                                }
+                }
+                AppEvent::Input(Event::Mouse(mouse)) => {
+                    use crossterm::event::MouseEventKind;
+                    match mouse.kind {
+                        MouseEventKind::ScrollDown => {
+                            scroll_accumulator += 1;
+                            if scroll_accumulator >= SCROLL_THRESHOLD {
+                                scroll_accumulator = 0;
+                                self.handle_mouse(mouse, last_content_area).await;
+                                dirty = true;
                             }
-                            _ => {
-                                // Other mouse events (clicks) are handled immediately
+                        }
+                        MouseEventKind::ScrollUp => {
+                            scroll_accumulator -= 1;
+                            if scroll_accumulator <= -SCROLL_THRESHOLD {
+                                scroll_accumulator = 0;
                                 self.handle_mouse(mouse, last_content_area).await;
+                                dirty = true;
                             }
                         }
+                        MouseEventKind::Moved => {
+                            // Nothing reacts to hovering, a moving pointer is no reason to draw
+                            self.handle_mouse(mouse, last_content_area).await;
+                        }
+                        _ => {
+                            // Other mouse events (clicks) are handled immediately
+                            self.handle_mouse(mouse, last_content_area).await;
+                            dirty = true;
+                        }
                     }
-                    _ => {}
+                }
+                AppEvent::Input(Event::Resize(..)) => {
+                    self.pending_resize = Some(Instant::now());
+                    dirty = true;
+                }
+                AppEvent::Input(_) => {}
+                AppEvent::Tick => {
+                    // Handle background tasks (like QR code polling)
+                    dirty |= self.tick().await;
                 }
             }
-
-            // Handle background tasks (like QR code polling)
-            self.tick().await;
         }
+        self.save_session();
         Ok(())
     }
 
+    /// Remember the current section and home state when session restore is enabled
+    fn save_session(&mut self) {
+        if !self.config.restore_session || self.credentials.is_none() {
+            return;
+        }
+        self.config.session.section = self.sidebar.selected.to_string();
+        let home = match &self.current_page {
+            Page::Home(page) => Some(page),
+            _ => self.cached_home.as_ref(),
+        };
+        if let Some(home) = home {
+            self.config.session.fresh_type = home.fresh_type().to_string();
+            self.config.session.home_selected = home.selected_index();
+        }
+        if let Err(e) = crate::storage::save_config(&self.config) {
+            eprintln!("Failed to save config: {}", e);
+        }
+    }
+
     /// Get the content area excluding sidebar
     fn get_content_area(&self, area: Rect) -> Rect {
-        // Login page, VideoDetail, and DynamicDetail use full area
+        // Login page and detail pages use the full area
         if matches!(
             self.current_page,
-            Page::Login(_) | Page::VideoDetail(_) | Page::DynamicDetail(_)
+            Page::Login(_)
+                | Page::Welcome(_)
+                | Page::VideoDetail(_)
+                | Page::DynamicDetail(_)
+                | Page::UserSpace(_)
+                | Page::Tag(_)
+                | Page::Comments(_)
         ) {
             return area;
         }
@@ -181,16 +551,139 @@ impl App {
 
     fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
+        if is_too_small(area) {
+            self.draw_too_small(frame, area);
+            return;
+        }
+        self.draw_layout(frame);
+
+        if let Some(prompt) = &mut self.open_prompt {
+            prompt.draw(frame, area, &self.theme, &self.keybindings);
+        }
+        if let Some(picker) = &mut self.recent_picker {
+            picker.draw(frame, area, &self.theme, &self.keybindings);
+        }
+        if let Some(palette) = &mut self.command_palette {
+            palette.draw(frame, area, &self.theme, &self.keybindings);
+        }
+        self.draw_now_playing(frame, area);
+        if let Some(confirm) = &mut self.confirm {
+            confirm.draw(frame, area, &self.theme, &self.keybindings);
+        }
+        self.draw_toast(frame, area);
+    }
+
+    /// Resize hint drawn instead of the pages when the terminal is too small
+    fn draw_too_small(&self, frame: &mut Frame, area: Rect) {
+        let text = vec![
+            Line::from(Span::styled(
+                "终端太小，请调整窗口大小",
+                Style::default()
+                    .fg(self.theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "当前 {}x{}，至少 {}x{}",
+                    area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+                ),
+                Style::default().fg(self.theme.fg_muted),
+            )),
+        ];
+        let hint = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        // Room for both lines to wrap on very narrow terminals
+        frame.render_widget(hint, centered_fixed(area.width, 4, area));
+    }
+
+    /// Banner shown at the top while a video is open in mpv
+    fn draw_now_playing(&self, frame: &mut Frame, area: Rect) {
+        let Some(playing) = &self.now_playing else {
+            return;
+        };
+        let text = format!(
+            " ▶ 正在外部播放器中播放 {} · 关闭 mpv 后可继续播放 · [{}] 复制时间链接 ",
+            playing.label, self.keybindings.copy_link
+        );
+        let width = (Line::from(text.as_str()).width() as u16 + 2).min(area.width);
+        if area.height < 3 || width == 0 {
+            return;
+        }
+        let banner = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y,
+            width,
+            height: 3,
+        };
+        frame.render_widget(Clear, banner);
+        frame.render_widget(
+            Paragraph::new(text)
+                .style(Style::default().fg(self.theme.success))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(self.theme.success))
+                        .style(Style::default().bg(self.theme.bg_modal)),
+                ),
+            banner,
+        );
+    }
+
+    /// Draw the app-wide toast centered just above the bottom border
+    fn draw_toast(&self, frame: &mut Frame, area: Rect) {
+        let Some(message) = self.toast.as_ref().and_then(Toast::active_message) else {
+            return;
+        };
+        let text = format!(" ℹ {} ", message);
+        let width = (Line::from(text.as_str()).width() as u16 + 2).min(area.width);
+        if area.height < 4 || width == 0 {
+            return;
+        }
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + area.height - 4,
+            width,
+            height: 3,
+        };
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(text)
+                .style(Style::default().fg(self.theme.warning))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(self.theme.warning))
+                        .style(Style::default().bg(self.theme.bg_modal)),
+                ),
+            popup,
+        );
+    }
+
+    fn draw_layout(&mut self, frame: &mut Frame) {
+        let area = frame.area();
 
-        // Login page, VideoDetail, and DynamicDetail don't show sidebar
+        // Login page and detail pages don't show the sidebar
         if matches!(
             self.current_page,
-            Page::Login(_) | Page::VideoDetail(_) | Page::DynamicDetail(_)
+            Page::Login(_)
+                | Page::Welcome(_)
+                | Page::VideoDetail(_)
+                | Page::DynamicDetail(_)
+                | Page::UserSpace(_)
+                | Page::Tag(_)
+                | Page::Comments(_)
         ) {
             match &mut self.current_page {
-                Page::Login(page) => page.draw(frame, area, &self.theme),
-                Page::VideoDetail(page) => page.draw(frame, area, &self.theme),
-                Page::DynamicDetail(page) => page.draw(frame, area, &self.theme),
+                Page::Login(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::Welcome(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::VideoDetail(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::DynamicDetail(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::UserSpace(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::Tag(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+                Page::Comments(page) => page.draw(frame, area, &self.theme, &self.keybindings),
                 _ => {}
             }
             return;
@@ -222,28 +715,142 @@ impl App {
 
     fn draw_page(&mut self, frame: &mut Frame, area: Rect) {
         match &mut self.current_page {
-            Page::Login(page) => page.draw(frame, area, &self.theme),
-            Page::Home(page) => page.draw(frame, area, &self.theme),
-            Page::Search(page) => page.draw(frame, area, &self.theme),
-            Page::Dynamic(page) => page.draw(frame, area, &self.theme),
-            Page::DynamicDetail(page) => page.draw(frame, area, &self.theme),
-            Page::VideoDetail(page) => page.draw(frame, area, &self.theme),
-            Page::History(page) => page.draw(frame, area, &self.theme),
-            Page::Settings(page) => page.draw(frame, area, &self.theme),
+            Page::Login(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::LoginRequired(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Home(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Search(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Dynamic(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::DynamicDetail(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::VideoDetail(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::History(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Bangumi(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Favorites(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::UserSpace(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Tag(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Comments(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Profile(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Settings(page) => page.draw(frame, area, &self.theme, &self.keybindings),
+            Page::Welcome(page) => page.draw(frame, area, &self.theme, &self.keybindings),
         }
     }
 
-    async fn handle_input(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+    /// Whether the current page is capturing text input
+    fn is_text_input_active(&self) -> bool {
+        captures_text(&self.current_page)
+    }
+
+    async fn handle_input(&mut self, mut key: KeyCode, mut modifiers: KeyModifiers) {
+        // Raw mode turns Ctrl-C into a key press instead of SIGINT
+        if key == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+            self.handle_action(AppAction::Quit).await;
+            return;
+        }
+
         let keys = &self.keybindings;
+
+        if let Some(confirm) = &mut self.confirm {
+            if let Some(action) = confirm.handle_input(key, keys) {
+                self.handle_action(action).await;
+            }
+            return;
+        }
+
+        if let Some(prompt) = &mut self.open_prompt {
+            if let Some(action) = prompt.handle_input(key, keys) {
+                self.handle_action(action).await;
+            }
+            return;
+        }
+
+        if let Some(picker) = &mut self.recent_picker {
+            if let Some(action) = picker.handle_input(key, keys) {
+                self.handle_action(action).await;
+            }
+            return;
+        }
+
+        if let Some(palette) = &mut self.command_palette {
+            match palette.handle_input(key, keys) {
+                // Handled below as if the bound key had been pressed on the page
+                Some(AppAction::RunKey(bound)) => {
+                    self.command_palette = None;
+                    key = bound;
+                    modifiers = KeyModifiers::NONE;
+                }
+                Some(AppAction::None) | None => return,
+                Some(action) => {
+                    self.command_palette = None;
+                    self.handle_action(action).await;
+                    return;
+                }
+            }
+        }
+
+        if self.pending_autoplay.is_some() && keys.matches_back(key) {
+            self.pending_autoplay = None;
+            self.toast = Some(Toast::new("已取消自动播放下一集"));
+            return;
+        }
+
+        if keys.matches_open_by_id(key)
+            && !matches!(self.current_page, Page::Login(_) | Page::Welcome(_))
+            && !self.is_text_input_active()
+        {
+            self.open_prompt = Some(OpenPrompt::new());
+            return;
+        }
+
+        if keys.matches_recent_played(key)
+            && !matches!(self.current_page, Page::Login(_) | Page::Welcome(_))
+            && !self.is_text_input_active()
+        {
+            self.open_recent_picker();
+            return;
+        }
+
+        if keys.matches_command_palette(key)
+            && !matches!(self.current_page, Page::Login(_) | Page::Welcome(_))
+            && !self.is_text_input_active()
+        {
+            self.command_palette = Some(CommandPalette::new(keys, &self.current_page));
+            return;
+        }
+
+        if keys.matches_copy_link(key)
+            && (self.now_playing.is_some() || self.last_played.is_some())
+            && !matches!(self.current_page, Page::Login(_) | Page::Welcome(_))
+            && !self.is_text_input_active()
+        {
+            self.handle_action(AppAction::CopyPlayingLink).await;
+            return;
+        }
+
+        // Theme cycling works everywhere except the login page, which uses the key for the QR source
+        if keys.matches_next_theme(key)
+            && !matches!(self.current_page, Page::Login(_) | Page::Welcome(_))
+            && !self.is_text_input_active()
+        {
+            self.handle_action(AppAction::NextTheme).await;
+            return;
+        }
+
         let action = match &mut self.current_page {
             Page::Login(page) => page.handle_input(key, keys),
+            Page::LoginRequired(page) => page.handle_input(key, keys),
             Page::Home(page) => page.handle_input(key, keys),
             Page::Search(page) => page.handle_input(key, keys),
             Page::Dynamic(page) => page.handle_input_with_modifiers(key, modifiers, keys),
             Page::DynamicDetail(page) => page.handle_input(key, keys),
             Page::VideoDetail(page) => page.handle_input(key, keys),
             Page::History(page) => page.handle_input(key, keys),
+            Page::Bangumi(page) => page.handle_input(key, keys),
+            Page::Favorites(page) => page.handle_input(key, keys),
+            Page::UserSpace(page) => page.handle_input(key, keys),
+            Page::Tag(page) => page.handle_input(key, keys),
+            Page::Comments(page) => page.handle_input(key, keys),
+            Page::Profile(page) => page.handle_input(key, keys),
             Page::Settings(page) => page.handle_input(key, keys),
+            Page::Welcome(page) => page.handle_input(key, keys),
         };
 
         if let Some(action) = action {
@@ -252,15 +859,32 @@ impl App {
     }
 
     async fn handle_mouse(&mut self, event: MouseEvent, area: Rect) {
+        if self.open_prompt.is_some()
+            || self.recent_picker.is_some()
+            || self.command_palette.is_some()
+            || self.confirm.is_some()
+            || area.is_empty()
+        {
+            return;
+        }
+
         let action = match &mut self.current_page {
             Page::Login(page) => page.handle_mouse(event, area),
+            Page::LoginRequired(page) => page.handle_mouse(event, area),
             Page::Home(page) => page.handle_mouse(event, area),
             Page::Search(page) => page.handle_mouse(event, area),
             Page::Dynamic(page) => page.handle_mouse(event, area),
             Page::DynamicDetail(page) => page.handle_mouse(event, area),
             Page::VideoDetail(page) => page.handle_mouse(event, area),
             Page::History(page) => page.handle_mouse(event, area),
+            Page::Bangumi(page) => page.handle_mouse(event, area),
+            Page::Favorites(page) => page.handle_mouse(event, area),
+            Page::UserSpace(page) => page.handle_mouse(event, area),
+            Page::Tag(page) => page.handle_mouse(event, area),
+            Page::Comments(page) => page.handle_mouse(event, area),
+            Page::Profile(page) => page.handle_mouse(event, area),
             Page::Settings(page) => page.handle_mouse(event, area),
+            Page::Welcome(page) => page.handle_mouse(event, area),
         };
 
         if let Some(action) = action {
@@ -270,7 +894,47 @@ impl App {
 
     async fn handle_action(&mut self, action: AppAction) {
         match action {
-            AppAction::Quit => self.should_quit = true,
+            AppAction::Quit => {
+                let tasks = self.active_tasks();
+                if tasks.is_empty() || self.confirm.is_some() {
+                    // A second quit while the dialog is open, e.g. Ctrl-C twice, confirms it
+                    self.quit().await;
+                } else {
+                    let mut lines = tasks;
+                    lines.push("退出将停止以上任务".to_string());
+                    self.confirm = Some(ConfirmDialog::new(
+                        "后台任务仍在进行",
+                        lines,
+                        AppAction::ConfirmQuit,
+                    ));
+                }
+            }
+            AppAction::ConfirmQuit => self.quit().await,
+            AppAction::CloseConfirm => {
+                self.confirm = None;
+                self.resume_offer = None;
+            }
+            AppAction::ResumePlayback { from_start } => {
+                self.confirm = None;
+                if let Some(offer) = self.resume_offer.take() {
+                    let start = if from_start {
+                        self.config.intro_skip(offer.mid)
+                    } else {
+                        offer.position
+                    };
+                    self.play_video(
+                        offer.bvid,
+                        offer.mid,
+                        offer.title,
+                        offer.aid,
+                        offer.cid,
+                        offer.duration,
+                        offer.quality,
+                        offer.season,
+                        Some(start),
+                    );
+                }
+            }
             AppAction::SwitchToHome => {
                 self.sidebar.select(NavItem::Home);
                 // Use cached home page if available
@@ -281,50 +945,133 @@ impl App {
                     self.init_current_page().await;
                 }
             }
-            AppAction::RefreshHome => {
-                self.sidebar.select(NavItem::Home);
-                // Clear cache and create fresh home page
-                self.cached_home = None;
-                self.current_page = Page::Home(HomePage::new());
+            AppAction::ReloadRecommendations => self.load_home(HomeRequest::Recommendations),
+            AppAction::RefreshRecommendations => self.load_home(HomeRequest::Refresh),
+            AppAction::ToggleHideWatched => {
+                self.config.hide_watched = !self.config.hide_watched;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save config: {}", e);
+                }
+                // Filter what is loaded, hidden cards stay around to come back
+                let hide = self.config.hide_watched;
+                let home = match &mut self.current_page {
+                    Page::Home(page) => Some(page),
+                    _ => self.cached_home.as_mut(),
+                };
+                if let Some(page) = home {
+                    page.set_hide_watched(hide, &self.watched);
+                }
+                match (&mut self.current_page, &mut self.previous_page) {
+                    (Page::Search(page), _) | (_, Some(PreviousPage::Search(page))) => {
+                        page.set_hide_watched(hide, &self.watched);
+                    }
+                    _ => {}
+                }
+            }
+            AppAction::ToggleHomeView => {
+                self.config.home_view = self.config.home_view.toggled();
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save config: {}", e);
+                }
+                if let Page::Home(page) = &mut self.current_page {
+                    page.set_view_mode(self.config.home_view);
+                }
+            }
+            AppAction::DismissWelcome => {
+                // Saving the config marks the first run as done
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save config: {}", e);
+                }
+                self.current_page = Page::Login(LoginPage::new());
                 self.init_current_page().await;
             }
             AppAction::SwitchToLogin => {
                 self.current_page = Page::Login(LoginPage::new());
                 self.init_current_page().await;
             }
-            AppAction::LoginSuccess(creds) => {
-                // Save credentials
-                if let Err(e) = crate::storage::save_credentials(&creds) {
-                    eprintln!("Failed to save credentials: {}", e);
-                }
-                self.credentials = Some(creds.clone());
-                // Update API client with new cookies
-                {
+            AppAction::ReloadLoginQrcode => {
+                if let Page::Login(page) = &mut self.current_page {
                     let client = self.api_client.clone();
-                    client.set_credentials(&creds);
+                    page.load_qrcode(&client).await;
+                }
+            }
+            AppAction::LoginSuccess(creds) => {
+                self.login_success(creds).await;
+            }
+            AppAction::CookieLogin(creds) => {
+                let client = ApiClient::from_config(Some(&creds), &self.config);
+                let result = match client.get_nav_info().await {
+                    Ok(nav) if nav.is_login => match nav.mid {
+                        Some(mid) if mid.to_string() != creds.dede_user_id => {
+                            Err("DedeUserID 与 SESSDATA 不匹配".to_string())
+                        }
+                        _ => Ok(()),
+                    },
+                    Ok(_) => Err("Cookie 无效或已过期".to_string()),
+                    Err(e) => Err(format!("验证失败: {}", e)),
+                };
+                match result {
+                    Ok(()) => self.login_success(creds).await,
+                    Err(msg) => {
+                        if let Page::Login(page) = &mut self.current_page {
+                            page.set_cookie_error(msg);
+                        }
+                    }
                 }
-                // Switch to home
-                self.current_page = Page::Home(HomePage::new());
-                self.init_current_page().await;
             }
             AppAction::PlayVideo {
                 bvid,
+                mid,
+                aid,
+                cid,
+                duration,
+                quality,
+            } => {
+                let season = self.season_queue(&bvid, quality);
+                let title = match &self.current_page {
+                    Page::VideoDetail(page) => page.video_info.as_ref().map(|v| v.title.clone()),
+                    _ => None,
+                };
+                let title = title.unwrap_or_else(|| bvid.clone());
+                self.play_video(bvid, mid, title, aid, cid, duration, quality, season, None);
+            }
+            AppAction::PlayEpisode {
+                url,
+                label,
                 aid,
                 cid,
                 duration,
+                confirmed,
             } => {
-                let api_client = self.api_client.clone();
-                if let Err(e) = crate::player::play_video(
-                    api_client,
-                    &bvid,
+                // Episodes have no detail page to stop at, ask for the play key instead
+                if self.config.confirm_before_play && !confirmed {
+                    self.ask_play_key(
+                        label.clone(),
+                        AppAction::PlayEpisode {
+                            url,
+                            label,
+                            aid,
+                            cid,
+                            duration,
+                            confirmed,
+                        },
+                    );
+                    return;
+                }
+                if confirmed {
+                    self.confirm = None;
+                }
+                let item = RecentItem {
+                    url: url.clone(),
+                    bvid: String::new(),
+                    mid: 0,
+                    title: label.clone(),
                     aid,
                     cid,
                     duration,
-                    self.credentials.as_ref(),
-                )
-                .await
-                {
-                    eprintln!("Failed to play video: {}", e);
+                };
+                if self.start_playback(url, label, String::new(), aid, cid, duration, None, 0) {
+                    self.remember_recent(item);
                 }
             }
             AppAction::NavNext => {
@@ -340,65 +1087,32 @@ impl App {
                     self.switch_to_nav_page().await;
                 }
             }
-            AppAction::Search(keyword) => {
-                if let Page::Search(page) = &mut self.current_page {
-                    let client = self.api_client.clone();
-                    match client.search_videos(&keyword, 1).await {
-                        Ok(data) => {
-                            let results = data.result.unwrap_or_default();
-                            let total = data.num_results.unwrap_or(0);
-                            page.set_results(results, total);
-                        }
-                        Err(e) => {
-                            page.set_error(format!("搜索失败: {}", e));
-                        }
-                    }
-                }
-            }
-            AppAction::RefreshDynamic => {
-                if let Page::Dynamic(page) = &mut self.current_page {
-                    let client = self.api_client.clone();
-                    let feed_type = page.current_tab.get_feed_type();
-                    let host_mid = page.get_selected_up_mid();
-                    match client.get_dynamic_feed(None, feed_type, host_mid).await {
-                        Ok(data) => {
-                            let items = data.items.unwrap_or_default();
-                            let offset = data.offset;
-                            let has_more = data.has_more.unwrap_or(false);
-                            page.set_feed(items, offset, has_more);
-                        }
-                        Err(e) => {
-                            page.set_error(format!("加载动态失败: {}", e));
-                        }
-                    }
-                }
-            }
+            AppAction::Search(keyword) => self.run_search(keyword),
+            AppAction::LoadHotwords => self.load_hotwords().await,
+            AppAction::RefreshDynamic => self.load_dynamic(DynamicRequest::Feed),
             AppAction::OpenVideoDetail(bvid, aid) => {
-                self.save_previous_page();
-                // Cache home page before navigating to video detail
-                if let Page::Home(home_page) =
-                    std::mem::replace(&mut self.current_page, Page::Home(HomePage::new()))
-                {
-                    self.cached_home = Some(home_page);
+                self.open_video_detail(bvid, aid).await;
+            }
+            AppAction::OpenByInput(input) => match self.resolve_video_input(&input).await {
+                Ok((bvid, aid)) => {
+                    self.open_prompt = None;
+                    self.open_video_detail(bvid, aid).await;
                 }
-                let mut detail_page = VideoDetailPage::new(bvid, aid);
-                let client = &self.api_client;
-                detail_page.load_data(client).await;
-                self.current_page = Page::VideoDetail(Box::new(detail_page));
+                Err(msg) => match &mut self.open_prompt {
+                    Some(prompt) => prompt.set_error(msg),
+                    // Typed into the search box, which has no error line of its own
+                    None => self.toast = Some(Toast::new(msg)),
+                },
+            },
+            AppAction::CloseOpenPrompt => {
+                self.open_prompt = None;
             }
             AppAction::OpenDynamicDetail(dynamic_id) => {
-                self.save_previous_page();
-                // Cache home page before navigating to dynamic detail
-                if let Page::Home(home_page) =
-                    std::mem::replace(&mut self.current_page, Page::Home(HomePage::new()))
-                {
-                    self.cached_home = Some(home_page);
-                }
                 use crate::ui::DynamicDetailPage;
                 let mut detail_page = DynamicDetailPage::new(dynamic_id);
                 let client = &self.api_client;
                 detail_page.load_data(client).await;
-                self.current_page = Page::DynamicDetail(Box::new(detail_page));
+                self.enter_detail_page(Page::DynamicDetail(Box::new(detail_page)));
             }
             AppAction::BackToList => {
                 match self.previous_page.take() {
@@ -412,20 +1126,21 @@ impl App {
                             self.init_current_page().await;
                         }
                     }
-                    Some(PreviousPage::Search) => {
+                    Some(PreviousPage::Search(page)) => {
                         self.sidebar.select(NavItem::Search);
-                        self.current_page = Page::Search(SearchPage::new());
-                        self.init_current_page().await;
+                        self.current_page = Page::Search(page);
                     }
-                    Some(PreviousPage::Dynamic) => {
+                    Some(PreviousPage::Dynamic(page)) => {
                         self.sidebar.select(NavItem::Dynamic);
-                        self.current_page = Page::Dynamic(DynamicPage::new());
-                        self.init_current_page().await;
+                        self.current_page = Page::Dynamic(page);
                     }
-                    Some(PreviousPage::History) => {
+                    Some(PreviousPage::History(page)) => {
                         self.sidebar.select(NavItem::History);
-                        self.current_page = Page::History(HistoryPage::new());
-                        self.init_current_page().await;
+                        self.current_page = Page::History(page);
+                    }
+                    Some(PreviousPage::Favorites(page)) => {
+                        self.sidebar.select(NavItem::Favorites);
+                        self.current_page = Page::Favorites(page);
                     }
                     None => {
                         // Default to home
@@ -439,35 +1154,153 @@ impl App {
                     }
                 }
             }
-            AppAction::LoadMoreRecommendations => {
-                if let Page::Home(page) = &mut self.current_page {
-                    let client = self.api_client.clone();
-                    page.load_more(&client).await;
+            AppAction::RetryFailed => self.retry_failed().await,
+            AppAction::CopyPlayingLink => self.copy_playing_link().await,
+            AppAction::ReplayRecent(item) => {
+                self.recent_picker = None;
+                if self.config.confirm_before_play && !item.bvid.is_empty() {
+                    self.open_video_detail(item.bvid, item.aid).await;
+                } else if self.config.confirm_before_play {
+                    let RecentItem {
+                        url,
+                        title,
+                        aid,
+                        cid,
+                        duration,
+                        ..
+                    } = *item;
+                    self.ask_play_key(
+                        title.clone(),
+                        AppAction::PlayEpisode {
+                            url,
+                            label: title,
+                            aid,
+                            cid,
+                            duration,
+                            confirmed: false,
+                        },
+                    );
+                } else {
+                    self.replay_recent(*item);
                 }
             }
-            AppAction::LoadMoreSearch => {
-                if let Page::Search(page) = &mut self.current_page {
-                    let client = self.api_client.clone();
-                    page.load_more(&client).await;
-                }
+            AppAction::CloseRecentPicker => self.recent_picker = None,
+            AppAction::CloseCommandPalette => self.command_palette = None,
+            // The palette's key commands are fed back through handle_input
+            AppAction::RunKey(_) => {}
+            AppAction::SwitchSection(item) => {
+                self.sidebar.select(item);
+                self.switch_to_nav_page().await;
             }
-            AppAction::LoadMoreDynamic => {
-                if let Page::Dynamic(page) = &mut self.current_page {
-                    let client = self.api_client.clone();
-                    page.load_more(&client).await;
+            AppAction::CycleIntroSkip { mid, name } => {
+                let seconds = self.config.cycle_intro_skip(mid);
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save intro skip: {}", e);
+                }
+                if let Page::VideoDetail(page) = &mut self.current_page {
+                    page.intro_skip = seconds;
                 }
+                self.toast = Some(Toast::new(if seconds > 0 {
+                    format!("{} 的视频将跳过开头 {} 秒", name, seconds)
+                } else {
+                    format!("不再跳过 {} 的视频开头", name)
+                }));
             }
+            AppAction::LoadMoreRecommendations => self.load_home(HomeRequest::More),
+            AppAction::LoadMoreSearch => self.load_more_search(),
+            AppAction::LoadMoreDynamic => self.load_dynamic(DynamicRequest::More),
             AppAction::LoadMoreHistory => {
                 if let Page::History(page) = &mut self.current_page {
                     let client = self.api_client.clone();
-                    page.load_more(&client).await;
+                    page.load_more(client.as_ref()).await;
+                }
+            }
+            AppAction::DeleteHistory(kid) => {
+                let Page::History(page) = &mut self.current_page else {
+                    return;
+                };
+                if self.credentials.is_none() {
+                    page.restore_removed();
+                    self.toast = Some(Toast::new("请先登录再管理历史记录"));
+                    return;
+                }
+                match self.api_client.delete_history(&kid).await {
+                    Ok(()) => page.confirm_removed(),
+                    Err(e) => {
+                        page.restore_removed();
+                        self.toast = Some(Toast::new(format!("删除历史记录失败: {}", e)));
+                    }
+                }
+            }
+            AppAction::RequestDeleteHistoryBatch(kids) => {
+                self.confirm = Some(ConfirmDialog::new(
+                    "删除历史记录",
+                    vec![format!("将删除选中的 {} 条观看历史，无法恢复", kids.len())],
+                    AppAction::DeleteHistoryBatch(kids),
+                ));
+            }
+            AppAction::DeleteHistoryBatch(kids) => {
+                self.confirm = None;
+                if self.credentials.is_none() {
+                    self.toast = Some(Toast::new("请先登录再管理历史记录"));
+                    return;
+                }
+                let mut deleted = Vec::new();
+                let mut last_error = None;
+                for kid in kids {
+                    match self.api_client.delete_history(&kid).await {
+                        Ok(()) => deleted.push(kid),
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                let message = match last_error {
+                    None => format!("已删除 {} 条历史记录", deleted.len()),
+                    Some(e) => format!("已删除 {} 条，其余删除失败: {}", deleted.len(), e),
+                };
+                if let Page::History(page) = &mut self.current_page {
+                    page.remove_entries(&deleted);
+                }
+                self.toast = Some(Toast::new(message));
+            }
+            AppAction::RequestClearHistory => {
+                self.confirm = Some(ConfirmDialog::new(
+                    "清空历史记录",
+                    vec!["将删除全部观看历史，无法恢复".to_string()],
+                    AppAction::ClearHistory,
+                ));
+            }
+            AppAction::ClearHistory => {
+                self.confirm = None;
+                if self.credentials.is_none() {
+                    self.toast = Some(Toast::new("请先登录再管理历史记录"));
+                    return;
+                }
+                match self.api_client.clear_history().await {
+                    Ok(()) => {
+                        if let Page::History(page) = &mut self.current_page {
+                            page.clear_items();
+                        }
+                        self.toast = Some(Toast::new("历史记录已清空"));
+                    }
+                    Err(e) => {
+                        self.toast = Some(Toast::new(format!("清空历史记录失败: {}", e)));
+                    }
                 }
             }
             AppAction::SwitchToHistory => {
                 self.sidebar.select(NavItem::History);
+                if self.show_login_required(NavItem::History) {
+                    return;
+                }
                 self.current_page = Page::History(HistoryPage::new());
                 self.init_current_page().await;
             }
+            AppAction::RefreshProfile => {
+                if let Page::Profile(page) = &mut self.current_page {
+                    page.start_loading();
+                    self.load_profile().await;
+                }
+            }
             AppAction::LoadMoreComments => {
                 if let Page::VideoDetail(page) = &mut self.current_page {
                     let client = self.api_client.clone();
@@ -475,6 +1308,9 @@ impl App {
                 } else if let Page::DynamicDetail(page) = &mut self.current_page {
                     let client = self.api_client.clone();
                     page.load_more_comments(&client).await;
+                } else if let Page::Comments(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more(client.as_ref()).await;
                 }
             }
             AppAction::ToggleCommentReplies => {
@@ -483,48 +1319,153 @@ impl App {
                     page.toggle_comment_replies(&client).await;
                 }
             }
+            AppAction::LoadMoreReplies => {
+                if let Page::VideoDetail(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more_replies(&client).await;
+                }
+            }
+            AppAction::CycleCommentSort => {
+                if let Page::VideoDetail(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.cycle_comment_sort(&client).await;
+                } else if let Page::Comments(page) = &mut self.current_page {
+                    page.toggle_sort();
+                    let client = self.api_client.clone();
+                    page.load(client.as_ref()).await;
+                }
+            }
+            AppAction::OpenComments {
+                oid,
+                comment_type,
+                title,
+            } => {
+                let mut comments_page = CommentsPage::new(oid, comment_type, title);
+                comments_page.load(self.api_client.as_ref()).await;
+                self.enter_detail_page(Page::Comments(Box::new(comments_page)));
+            }
+            AppAction::RefreshComments => {
+                if let Page::Comments(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load(client.as_ref()).await;
+                }
+            }
+            AppAction::OpenUserSpace { mid, name } => {
+                let mut space_page = UserSpacePage::new(mid, name);
+                space_page.load(self.api_client.as_ref()).await;
+                // Stay where we are rather than landing on an empty page
+                if let Some(error) = space_page.error_message.take() {
+                    self.toast = Some(Toast::new(error));
+                    return;
+                }
+                self.enter_detail_page(Page::UserSpace(Box::new(space_page)));
+            }
+            AppAction::LoadMoreBangumi => {
+                if let Page::Bangumi(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more(client.as_ref()).await;
+                }
+            }
+            AppAction::ToggleBangumiType => {
+                if let Page::Bangumi(page) = &mut self.current_page {
+                    page.toggle_follow_type();
+                    let client = self.api_client.clone();
+                    page.load(client.as_ref()).await;
+                }
+            }
+            AppAction::RefreshBangumi => {
+                if let Page::Bangumi(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load(client.as_ref()).await;
+                }
+            }
+            AppAction::LoadMoreFavorites => {
+                if let Page::Favorites(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more(client.as_ref()).await;
+                }
+            }
+            AppAction::SwitchFavFolder(step) => {
+                if let Page::Favorites(page) = &mut self.current_page {
+                    page.switch_folder(step);
+                    let client = self.api_client.clone();
+                    page.load_folder(client.as_ref()).await;
+                }
+            }
+            AppAction::RefreshFavorites => {
+                if let Page::Favorites(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load(client.as_ref()).await;
+                }
+            }
+            AppAction::LoadMoreSpaceVideos => {
+                if let Page::UserSpace(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more(client.as_ref()).await;
+                }
+            }
+            AppAction::LoadSpaceCollections => {
+                if let Page::UserSpace(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_collections(client.as_ref()).await;
+                }
+            }
+            AppAction::LoadCollectionEpisodes(index) => {
+                if let Page::UserSpace(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_episodes(client.as_ref(), index).await;
+                }
+            }
+            AppAction::PlayFromList {
+                bvid,
+                mid,
+                aid,
+                title,
+                duration,
+            } => {
+                // Like the recently played list, confirming goes through the detail page
+                if self.config.confirm_before_play {
+                    self.open_video_detail(bvid, aid).await;
+                } else {
+                    self.play_video(bvid, mid, title, aid, 0, duration, None, None, None);
+                }
+            }
+            AppAction::OpenTag { tag_id, name } => {
+                let mut tag_page = TagPage::new(tag_id, name);
+                tag_page.load(self.api_client.as_ref()).await;
+                // Stay where we are rather than landing on an empty page
+                if let Some(error) = tag_page.error_message.take() {
+                    self.toast = Some(Toast::new(error));
+                    return;
+                }
+                self.enter_detail_page(Page::Tag(Box::new(tag_page)));
+            }
+            AppAction::LoadMoreTagVideos => {
+                if let Page::Tag(page) = &mut self.current_page {
+                    let client = self.api_client.clone();
+                    page.load_more(client.as_ref()).await;
+                }
+            }
             AppAction::SwitchDynamicTab(tab) => {
                 if let Page::Dynamic(page) = &mut self.current_page {
                     page.switch_tab(tab);
-                    let client = self.api_client.clone();
-                    let feed_type = page.current_tab.get_feed_type();
-                    let host_mid = page.get_selected_up_mid();
-                    match client.get_dynamic_feed(None, feed_type, host_mid).await {
-                        Ok(data) => {
-                            let items = data.items.unwrap_or_default();
-                            let offset = data.offset;
-                            let has_more = data.has_more.unwrap_or(false);
-                            page.set_feed(items, offset, has_more);
-                        }
-                        Err(e) => {
-                            page.set_error(format!("加载动态失败: {}", e));
-                        }
-                    }
+                    self.load_dynamic(DynamicRequest::Feed);
                 }
             }
             AppAction::SelectUpMaster(index) => {
                 if let Page::Dynamic(page) = &mut self.current_page {
                     page.select_up(index);
-                    let client = self.api_client.clone();
-                    let feed_type = page.current_tab.get_feed_type();
-                    let host_mid = page.get_selected_up_mid();
-                    match client.get_dynamic_feed(None, feed_type, host_mid).await {
-                        Ok(data) => {
-                            let items = data.items.unwrap_or_default();
-                            let offset = data.offset;
-                            let has_more = data.has_more.unwrap_or(false);
-                            page.set_feed(items, offset, has_more);
-                        }
-                        Err(e) => {
-                            page.set_error(format!("加载动态失败: {}", e));
-                        }
-                    }
+                    self.load_dynamic(DynamicRequest::Feed);
                 }
             }
             AppAction::NextTheme => {
                 self.theme_variant = self.theme_variant.next();
                 self.theme = Theme::from_variant(self.theme_variant);
                 self.save_theme_to_config();
+                if let Page::Settings(page) = &mut self.current_page {
+                    page.select_theme(self.theme_variant);
+                }
+                self.toast = Some(Toast::new(format!("主题: {}", self.theme_variant.label())));
             }
             AppAction::SetTheme(variant) => {
                 self.theme_variant = variant;
@@ -533,17 +1474,45 @@ impl App {
             }
             AppAction::SwitchToSettings => {
                 self.sidebar.select(NavItem::Settings);
-                let page = SettingsPage::new(self.keybindings.clone(), self.theme_variant);
+                let mut page = SettingsPage::new(
+                    self.keybindings.clone(),
+                    self.theme_variant,
+                    self.config.network,
+                    self.config.download.clone(),
+                    self.config.grid_columns,
+                    self.config.recommend_filter,
+                    self.config.image_protocol,
+                );
+                page.wrap_navigation = self.config.wrap_navigation;
+                page.token_status = self.token_status();
                 self.current_page = Page::Settings(Box::new(page));
             }
             AppAction::Logout => {
                 if let Err(e) = crate::storage::delete_credentials() {
                     eprintln!("Failed to delete credentials: {}", e);
                 }
+                self.stop_credential_refresh();
                 self.credentials = None;
+                self.nav_info = None;
+                self.login_check = LoginCheck::Unknown;
+                self.login_checked_at = None;
+                self.set_unread(None);
+                self.unread_refresh = None;
+                self.unread_checked_at = None;
+                self.previous_page = None;
+                self.cached_home = None;
                 self.current_page = Page::Login(LoginPage::new());
                 self.init_current_page().await;
             }
+            AppAction::RefreshCredentials => self.refresh_credentials_now(),
+            AppAction::ValidateLogin => self.validate_login(true),
+            AppAction::RunDiagnostics => {
+                let client = self.api_client.clone();
+                let results = crate::api::doctor::run_checks(&client).await;
+                if let Page::Settings(page) = &mut self.current_page {
+                    page.set_diagnostics(results);
+                }
+            }
             AppAction::LikeComment {
                 oid,
                 rpid,
@@ -592,22 +1561,36 @@ impl App {
                 comment_type,
                 message,
                 root,
+                parent,
             } => {
+                if self.credentials.is_none() {
+                    self.toast = Some(Toast::new("请先登录再发表评论"));
+                    return;
+                }
                 let client = self.api_client.clone();
                 match client
-                    .add_comment(oid, comment_type, &message, root, root)
+                    .post_comment(oid, comment_type, &message, root, parent)
                     .await
                 {
-                    Ok(_response) => {
-                        // Reload comments to show new comment
+                    Ok(response) => {
+                        self.toast = Some(Toast::new(
+                            response
+                                .success_toast
+                                .filter(|t| !t.is_empty())
+                                .unwrap_or_else(|| "发送成功".to_string()),
+                        ));
+                        // Reload comments to show the new one
                         if let Page::VideoDetail(page) = &mut self.current_page {
-                            page.load_data(&client).await;
+                            match root {
+                                Some(root) => page.refresh_thread(&client, root).await,
+                                None => page.reload_comments(&client).await,
+                            }
                         } else if let Page::DynamicDetail(page) = &mut self.current_page {
                             page.load_data(&client).await;
                         }
                     }
                     Err(e) => {
-                        eprintln!("Failed to add comment: {}", e);
+                        self.toast = Some(Toast::new(format!("评论发送失败: {}", e)));
                     }
                 }
             }
@@ -618,10 +1601,921 @@ impl App {
                     eprintln!("Failed to save keybindings: {}", e);
                 }
             }
+            AppAction::SaveNetworkConfig(network) => {
+                self.config.network = network.clamped();
+                self.api_client
+                    .set_timeout(self.config.network.request_timeout());
+                crate::ui::set_download_timeout(self.config.network.request_timeout());
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save network settings: {}", e);
+                }
+            }
+            AppAction::SaveGridColumns(columns) => {
+                self.config.grid_columns = columns;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save display settings: {}", e);
+                }
+                // Home is cached while the settings page is open
+                if let Some(home) = &mut self.cached_home {
+                    home.set_grid_columns(columns);
+                }
+            }
+            AppAction::SaveRecommendFilter(filter) => {
+                self.config.recommend_filter = filter;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save display settings: {}", e);
+                }
+                if let Some(home) = &mut self.cached_home {
+                    home.set_filter(filter);
+                }
+            }
+            AppAction::SaveImageProtocol(protocol) => {
+                self.config.image_protocol = protocol;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save display settings: {}", e);
+                }
+            }
+            AppAction::SaveWrapNavigation(wrap) => {
+                self.config.wrap_navigation = wrap;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save display settings: {}", e);
+                }
+                // Pages kept behind settings get it now, new ones when created
+                if let Some(home) = &mut self.cached_home {
+                    home.set_wrap_navigation(wrap);
+                }
+                match &mut self.previous_page {
+                    Some(PreviousPage::Search(page)) => page.grid.wrap = wrap,
+                    Some(PreviousPage::Dynamic(page)) => page.grid.wrap = wrap,
+                    Some(PreviousPage::Favorites(page)) => page.grid.wrap = wrap,
+                    _ => {}
+                }
+            }
+            AppAction::SaveDownloadConfig(download) => {
+                self.config.download = download;
+                if let Err(e) = crate::storage::save_config(&self.config) {
+                    eprintln!("Failed to save download settings: {}", e);
+                }
+            }
+            AppAction::DownloadVideo(bvid) => self.start_download(bvid),
+            AppAction::DownloadVideos(bvids) => {
+                let count = bvids.len();
+                for bvid in bvids {
+                    self.start_download(bvid);
+                }
+                self.toast = Some(Toast::new(format!("开始下载 {} 个视频", count)));
+            }
             AppAction::None => {}
         }
     }
 
+    async fn login_success(&mut self, creds: Credentials) {
+        // Save credentials
+        if let Err(e) = crate::storage::save_credentials(&creds) {
+            eprintln!("Failed to save credentials: {}", e);
+        }
+        self.credentials = Some(creds);
+        // Updates the API client with the new cookies
+        self.ensure_device_ids().await;
+        self.start_credential_refresh();
+        self.refresh_nav_info().await;
+        // Switch to home
+        self.current_page = Page::Home(HomePage::new());
+        self.init_current_page().await;
+    }
+
+    /// Apply the login to the API client, fetching and saving `buvid3`/`buvid4` when it has none
+    ///
+    /// Logins from before these were captured get them on the next launch.
+    async fn ensure_device_ids(&mut self) {
+        let Some(creds) = self.credentials.as_mut() else {
+            return;
+        };
+        self.api_client.set_credentials(creds);
+        match self.api_client.ensure_device_ids(creds).await {
+            Ok(true) => {
+                if let Err(e) = crate::storage::save_credentials(creds) {
+                    eprintln!("Failed to save credentials: {}", e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                let _ = crate::storage::append_log(&format!("获取 buvid 失败: {}", e));
+            }
+        }
+    }
+
+    /// Start the task that checks the login now and every few hours after, replacing a running one
+    fn start_credential_refresh(&mut self) {
+        self.stop_credential_refresh();
+        let Some(creds) = self.credentials.clone() else {
+            return;
+        };
+        let client = self.api_client.clone();
+        let (tx, updates) = mpsc::unbounded_channel();
+        let (stop, stop_rx) = oneshot::channel();
+        tokio::spawn(refresh_credentials_periodically(client, creds, tx, stop_rx));
+        self.credential_refresh = Some(CredentialRefresh { updates, stop });
+    }
+
+    fn stop_credential_refresh(&mut self) {
+        if let Some(refresh) = self.credential_refresh.take() {
+            let _ = refresh.stop.send(());
+        }
+    }
+
+    /// Refresh the login in the background right away, as asked for from the settings page
+    fn refresh_credentials_now(&mut self) {
+        let Some(creds) = self.credentials.clone() else {
+            return;
+        };
+        // The periodic check must not refresh with the tokens this request replaces
+        self.stop_credential_refresh();
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move {
+                client
+                    .refresh_credentials(&creds)
+                    .await
+                    .map_err(|e| e.to_string())
+            },
+            |app, result| {
+                // Logged out while the request was in flight
+                if app.credentials.is_none() {
+                    return;
+                }
+                match result {
+                    Ok(refreshed) => {
+                        if let Err(e) = crate::storage::save_credentials(&refreshed) {
+                            let _ = crate::storage::append_log(&format!(
+                                "保存刷新后的登录信息失败: {}",
+                                e
+                            ));
+                        }
+                        app.api_client.set_credentials(&refreshed);
+                        app.record_credential_refresh(refreshed);
+                        app.toast = Some(Toast::new("登录状态已刷新"));
+                        // The new cookies are only known to work once the server says so
+                        app.validate_login(false);
+                    }
+                    Err(e) => {
+                        app.toast = Some(Toast::with_duration(
+                            format!("刷新登录状态失败: {}", e),
+                            Duration::from_secs(5),
+                        ));
+                        app.show_token_status();
+                    }
+                }
+                app.start_credential_refresh();
+            },
+        );
+    }
+
+    /// Adopt refreshed credentials and remember when the refresh happened
+    fn record_credential_refresh(&mut self, creds: Credentials) {
+        self.credentials = Some(creds);
+        self.config.last_credential_refresh = Some(chrono::Utc::now().timestamp());
+        if let Err(e) = crate::storage::save_config(&self.config) {
+            eprintln!("Failed to save config: {}", e);
+        }
+        self.show_token_status();
+    }
+
+    /// Ask the nav endpoint in the background whether the cookies still log in
+    ///
+    /// With `refresh_if_expired`, expired cookies are refreshed right away when the
+    /// refresh tokens are stored.
+    fn validate_login(&mut self, refresh_if_expired: bool) {
+        if self.credentials.is_none() {
+            return;
+        }
+        self.login_check = LoginCheck::Checking;
+        self.show_token_status();
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move { client.get_nav_info().await.map_err(|e| e.to_string()) },
+            move |app, result| {
+                // Logged out while the request was in flight
+                if app.credentials.is_none() {
+                    return;
+                }
+                let is_login = result.as_ref().ok().map(|nav| nav.is_login);
+                app.record_login_check(is_login);
+                let can_refresh = app
+                    .credentials
+                    .as_ref()
+                    .is_some_and(Credentials::can_refresh);
+                match result {
+                    Ok(nav) if nav.is_login => {
+                        app.nav_info = Some(nav);
+                        app.toast = Some(Toast::new("登录有效"));
+                    }
+                    Ok(_) if refresh_if_expired && can_refresh => {
+                        app.toast = Some(Toast::new("登录已失效，正在刷新 Cookie..."));
+                        app.refresh_credentials_now();
+                    }
+                    Ok(_) => {
+                        app.toast = Some(Toast::with_duration(
+                            "登录已失效，请重新扫码登录",
+                            Duration::from_secs(5),
+                        ));
+                    }
+                    Err(e) => {
+                        app.toast = Some(Toast::with_duration(
+                            format!("验证登录失败: {}", e),
+                            Duration::from_secs(5),
+                        ));
+                    }
+                }
+            },
+        );
+    }
+
+    /// Remember the server's answer to a login check, `None` when it could not be reached
+    fn record_login_check(&mut self, is_login: Option<bool>) {
+        self.login_check = match is_login {
+            Some(true) => LoginCheck::Valid,
+            Some(false) => LoginCheck::Expired,
+            None => LoginCheck::Unknown,
+        };
+        if is_login.is_some() {
+            self.login_checked_at = Some(chrono::Utc::now().timestamp());
+        }
+        self.show_token_status();
+    }
+
+    /// Refresh token, login check and their times for the settings account section
+    fn token_status(&self) -> TokenStatus {
+        TokenStatus {
+            has_refresh_token: self
+                .credentials
+                .as_ref()
+                .is_some_and(Credentials::can_refresh),
+            last_refresh: self.config.last_credential_refresh,
+            refreshing: false,
+            login: self.login_check,
+            last_validated: self.login_checked_at,
+        }
+    }
+
+    fn show_token_status(&mut self) {
+        let status = self.token_status();
+        if let Page::Settings(page) = &mut self.current_page {
+            page.token_status = status;
+        }
+    }
+
+    /// Play a video by bvid and remember it as watched and recently played
+    ///
+    /// `start` is the position in seconds to start at. Without one, a video that stopped
+    /// early in this session asks whether to resume before anything plays.
+    #[allow(clippy::too_many_arguments)]
+    fn play_video(
+        &mut self,
+        bvid: String,
+        mid: i64,
+        title: String,
+        aid: i64,
+        cid: i64,
+        duration: i64,
+        quality: Option<u32>,
+        season: Option<SeasonQueue>,
+        start: Option<i64>,
+    ) {
+        let skip = self.config.intro_skip(mid);
+        let resume = self.resume_point.as_ref().filter(|point| {
+            point.bvid == bvid && (cid == 0 || point.cid == cid) && point.position > skip
+        });
+        let start = match (start, resume) {
+            (Some(start), _) => start,
+            (None, Some(point)) if self.now_playing.is_none() => {
+                let lines = vec![
+                    crate::util::truncate_display(&title, 54),
+                    format!("上次播放到 {}", crate::util::format_clock(point.position)),
+                ];
+                self.resume_offer = Some(ResumeOffer {
+                    bvid,
+                    mid,
+                    title,
+                    aid,
+                    cid: point.cid,
+                    duration,
+                    quality,
+                    season,
+                    position: point.position,
+                });
+                self.confirm = Some(
+                    ConfirmDialog::new(
+                        "继续播放",
+                        lines,
+                        AppAction::ResumePlayback { from_start: false },
+                    )
+                    .or_else("从头播放", AppAction::ResumePlayback { from_start: true }),
+                );
+                return;
+            }
+            (None, _) => skip,
+        };
+        if self.watched.insert(&bvid) {
+            if let Err(e) = crate::storage::save_watched(&self.watched) {
+                eprintln!("Failed to save watched videos: {}", e);
+            }
+        }
+        let url = format!("https://www.bilibili.com/video/{}", bvid);
+        let item = RecentItem {
+            url: url.clone(),
+            bvid: bvid.clone(),
+            mid,
+            title,
+            aid,
+            cid,
+            duration,
+        };
+        if !self.start_playback(url, bvid.clone(), bvid, aid, cid, duration, quality, start) {
+            return;
+        }
+        self.remember_recent(item);
+        if let Some(playing) = &mut self.now_playing {
+            playing.season = season;
+        }
+    }
+
+    /// Put `item` on top of the recently played list, saved when the config asks for it
+    fn remember_recent(&mut self, item: RecentItem) {
+        self.recent.push(item);
+        if self.config.remember_recent {
+            if let Err(e) = crate::storage::save_recent(&self.recent) {
+                eprintln!("Failed to save recently played: {}", e);
+            }
+        }
+    }
+
+    /// Hold a direct play until the play key is pressed, see `AppConfig::confirm_before_play`
+    fn ask_play_key(&mut self, label: String, action: AppAction) {
+        self.confirm = Some(play_key_dialog(label, action));
+    }
+
+    /// Open the quick-pick list of recently played items
+    fn open_recent_picker(&mut self) {
+        if self.recent.is_empty() {
+            self.toast = Some(Toast::new("还没有播放过视频"));
+            return;
+        }
+        self.recent_picker = Some(RecentPicker::new(self.recent.items().cloned().collect()));
+    }
+
+    /// Play a recently played item again, by bvid for videos and by URL for episodes
+    fn replay_recent(&mut self, item: RecentItem) {
+        if item.bvid.is_empty() {
+            if self.start_playback(
+                item.url.clone(),
+                item.title.clone(),
+                String::new(),
+                item.aid,
+                item.cid,
+                item.duration,
+                None,
+                0,
+            ) {
+                self.remember_recent(item);
+            }
+        } else {
+            let RecentItem {
+                bvid,
+                mid,
+                title,
+                aid,
+                cid,
+                duration,
+                ..
+            } = item;
+            self.play_video(bvid, mid, title, aid, cid, duration, None, None, None);
+        }
+    }
+
+    /// Season of the video open on the detail page, positioned at `bvid`
+    fn season_queue(&self, bvid: &str, quality: Option<u32>) -> Option<SeasonQueue> {
+        let Page::VideoDetail(page) = &self.current_page else {
+            return None;
+        };
+        let info = page.video_info.as_ref()?;
+        let season = info.ugc_season.as_ref()?;
+        Some(SeasonQueue {
+            mid: info.owner.mid,
+            index: season.position(bvid)?,
+            episodes: season.episodes(),
+            quality,
+        })
+    }
+
+    /// Announce the next season episode, it starts after [`AUTOPLAY_DELAY`] unless cancelled
+    fn queue_next_episode(&mut self, queue: SeasonQueue) {
+        if !self.config.autoplay_next {
+            return;
+        }
+        let Some(queue) = queue.advance() else {
+            return;
+        };
+        self.toast = Some(Toast::with_duration(
+            format!(
+                "下一集: {}  [{}] 取消",
+                queue.current().title,
+                self.keybindings.back
+            ),
+            AUTOPLAY_DELAY,
+        ));
+        self.pending_autoplay = Some(PendingAutoplay {
+            queue,
+            starts_at: Instant::now() + AUTOPLAY_DELAY,
+        });
+    }
+
+    /// Play the announced episode once its cancel window has passed, returning whether it did
+    fn start_pending_autoplay(&mut self) -> bool {
+        if self
+            .pending_autoplay
+            .as_ref()
+            .is_none_or(|pending| Instant::now() < pending.starts_at)
+        {
+            return false;
+        }
+        let Some(PendingAutoplay { queue, .. }) = self.pending_autoplay.take() else {
+            return false;
+        };
+        let episode = queue.current();
+        let (bvid, title, aid, cid, duration) = (
+            episode.bvid.clone(),
+            episode.title.clone(),
+            episode.aid,
+            episode.cid,
+            episode.duration(),
+        );
+        let (mid, quality) = (queue.mid, queue.quality);
+        self.play_video(
+            bvid,
+            mid,
+            title,
+            aid,
+            cid,
+            duration,
+            quality,
+            Some(queue),
+            None,
+        );
+        true
+    }
+
+    /// Open `url` in mpv beside the UI so the loop keeps drawing while it plays
+    ///
+    /// Returns false when another video is still playing and nothing was started.
+    #[allow(clippy::too_many_arguments)]
+    fn start_playback(
+        &mut self,
+        url: String,
+        label: String,
+        bvid: String,
+        aid: i64,
+        cid: i64,
+        duration: i64,
+        quality: Option<u32>,
+        start: i64,
+    ) -> bool {
+        if self.now_playing.is_some() {
+            self.toast = Some(Toast::new("已有视频正在外部播放器中播放"));
+            return false;
+        }
+        let api_client = self.api_client.clone();
+        let credentials = self.credentials.clone();
+        let options = crate::player::PlayOptions {
+            show_danmaku: self.config.show_danmaku,
+            quality,
+            start,
+        };
+        let (tx, done) = oneshot::channel();
+        let (stop, stop_rx) = oneshot::channel();
+        let task_url = url.clone();
+        let task_bvid = bvid.clone();
+        tokio::spawn(async move {
+            let result = crate::player::play_url(
+                api_client,
+                &task_url,
+                &task_bvid,
+                aid,
+                cid,
+                duration,
+                credentials.as_ref(),
+                options,
+                stop_rx,
+            )
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.now_playing = Some(NowPlaying {
+            label,
+            url,
+            bvid,
+            cid,
+            duration,
+            done,
+            stop,
+            season: None,
+        });
+        true
+    }
+
+    /// Background work that quitting would cut short, one line per task for the confirmation
+    fn active_tasks(&self) -> Vec<String> {
+        let mut tasks = Vec::new();
+        if let Some(playing) = &self.now_playing {
+            tasks.push(format!("正在外部播放器中播放 {}", playing.label));
+        }
+        for download in &self.downloads {
+            tasks.push(format!(
+                "正在下载 {} ({:.0}%)",
+                download.bvid, download.percent
+            ));
+        }
+        tasks
+    }
+
+    /// Stop background tasks and leave the main loop
+    async fn quit(&mut self) {
+        self.confirm = None;
+        self.stop_background_tasks().await;
+        self.should_quit = true;
+    }
+
+    /// Kill mpv and wait briefly so the final heartbeat and temp file cleanup can run
+    async fn stop_background_tasks(&mut self) {
+        self.stop_credential_refresh();
+        if let Some(playing) = self.now_playing.take() {
+            let _ = playing.stop.send(());
+            let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, playing.done).await;
+        }
+        for download in std::mem::take(&mut self.downloads) {
+            let _ = download.stop.send(());
+            let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, download.done).await;
+        }
+    }
+
+    /// Run yt-dlp for `bvid` beside the UI, progress is reported through toasts
+    fn start_download(&mut self, bvid: String) {
+        if self.downloads.iter().any(|d| d.bvid == bvid) {
+            self.toast = Some(Toast::new(format!("{} 已在下载中", bvid)));
+            return;
+        }
+        let credentials = self.credentials.clone();
+        let config = self.config.download.clone();
+        let (progress_tx, progress) = mpsc::unbounded_channel();
+        let (tx, done) = oneshot::channel();
+        let (stop, stop_rx) = oneshot::channel();
+        let task_bvid = bvid.clone();
+        tokio::spawn(async move {
+            let result = crate::player::download::download_video(
+                &task_bvid,
+                credentials.as_ref(),
+                &config,
+                progress_tx,
+                stop_rx,
+            )
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.toast = Some(Toast::new(format!("开始下载 {}", bvid)));
+        self.downloads.push(Download {
+            bvid,
+            percent: 0.0,
+            progress,
+            done,
+            stop,
+        });
+    }
+
+    /// Announce download progress in steps and report finished downloads
+    ///
+    /// Returns whether a toast was shown, progress below a step changes nothing on screen.
+    fn poll_downloads(&mut self) -> bool {
+        let mut finished = Vec::new();
+        let mut announced = false;
+        for (idx, download) in self.downloads.iter_mut().enumerate() {
+            let before = (download.percent / DOWNLOAD_TOAST_STEP) as u32;
+            while let Ok(percent) = download.progress.try_recv() {
+                download.percent = percent;
+            }
+            let after = (download.percent / DOWNLOAD_TOAST_STEP) as u32;
+            if after > before && download.percent < 100.0 {
+                announced = true;
+                self.toast = Some(Toast::new(format!(
+                    "下载 {} {:.0}%",
+                    download.bvid, download.percent
+                )));
+            }
+            match download.done.try_recv() {
+                Ok(result) => finished.push((idx, result)),
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    finished.push((idx, Err("下载任务异常退出".to_string())))
+                }
+            }
+        }
+        for (idx, result) in finished.into_iter().rev() {
+            let download = self.downloads.remove(idx);
+            self.toast = Some(match result {
+                Ok(path) => Toast::with_duration(
+                    format!("下载完成: {}", path.display()),
+                    Duration::from_secs(5),
+                ),
+                Err(e) => Toast::new(format!("下载 {} 失败: {}", download.bvid, e)),
+            });
+            announced = true;
+        }
+        announced
+    }
+
+    /// Fetch unread message counts in the background once the refresh interval has passed
+    fn start_unread_refresh(&mut self) {
+        if self.credentials.is_none() || self.unread_refresh.is_some() {
+            return;
+        }
+        if self
+            .unread_checked_at
+            .is_some_and(|at| at.elapsed() < UNREAD_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.unread_checked_at = Some(Instant::now());
+        let client = self.api_client.clone();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = client.get_unread_counts().await.map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+        self.unread_refresh = Some(rx);
+    }
+
+    /// Apply finished unread count fetches, failures keep the previous counts
+    ///
+    /// Returns whether new counts were applied.
+    fn poll_unread_refresh(&mut self) -> bool {
+        let Some(rx) = &mut self.unread_refresh else {
+            return false;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return false,
+            Err(oneshot::error::TryRecvError::Closed) => Err(String::new()),
+        };
+        self.unread_refresh = None;
+        if let (Ok(counts), Some(_)) = (result, &self.credentials) {
+            self.set_unread(Some(counts));
+            return true;
+        }
+        false
+    }
+
+    fn set_unread(&mut self, unread: Option<UnreadCounts>) {
+        self.unread = unread;
+        self.sidebar.unread = unread.map_or(0, |counts| counts.total());
+        if let Page::Profile(page) = &mut self.current_page {
+            page.set_unread(unread);
+        }
+    }
+
+    /// Copy the playing (or last played) video's URL at its current position
+    ///
+    /// The position comes from mpv's IPC socket; when it does not answer the plain URL is copied.
+    async fn copy_playing_link(&mut self) {
+        let (url, position) = if let Some(playing) = &self.now_playing {
+            let position = crate::player::playback_position(playing.cid).await;
+            (playing.url.clone(), position.map(|t| t as i64))
+        } else if let Some((url, position)) = &self.last_played {
+            (url.clone(), Some(*position))
+        } else {
+            return;
+        };
+        let link = crate::util::timestamped_url(&url, position);
+        let message = match (crate::clipboard::copy(&link), position) {
+            (Ok(()), Some(t)) if link != url => {
+                format!("已复制 {} 处的链接: {}", crate::util::format_clock(t), link)
+            }
+            (Ok(()), _) => format!("已复制链接 (未获取到播放位置): {}", link),
+            (Err(e), _) => format!("复制链接失败: {}", e),
+        };
+        self.toast = Some(Toast::new(message));
+    }
+
+    /// Clear the playing state once mpv has exited, reporting failures as a toast
+    ///
+    /// Returns whether mpv exited, which takes the now playing banner down.
+    fn poll_now_playing(&mut self) -> bool {
+        let Some(playing) = &mut self.now_playing else {
+            return false;
+        };
+        let result = match playing.done.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return false,
+            Err(oneshot::error::TryRecvError::Closed) => Ok(0),
+        };
+        let Some(playing) = self.now_playing.take() else {
+            return false;
+        };
+        match result {
+            Ok(position) => {
+                self.resume_point =
+                    ResumePoint::after(&playing.bvid, playing.cid, position, playing.duration);
+                self.last_played = Some((playing.url, position));
+                if let Some(queue) = playing.season {
+                    self.queue_next_episode(queue);
+                }
+            }
+            Err(e) => self.toast = Some(Toast::new(format!("播放失败: {}", e))),
+        }
+        true
+    }
+
+    /// Mirror refreshed credentials, returning whether the refresher reported anything
+    fn poll_credential_refresh(&mut self) -> bool {
+        let Some(refresh) = &mut self.credential_refresh else {
+            return false;
+        };
+        let result = match refresh.updates.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return false,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.credential_refresh = None;
+                return false;
+            }
+        };
+        match result {
+            Ok(creds) => self.record_credential_refresh(creds),
+            Err(e) => {
+                self.toast = Some(Toast::with_duration(
+                    format!("刷新登录状态失败: {}", e),
+                    std::time::Duration::from_secs(5),
+                ));
+            }
+        }
+        true
+    }
+
+    /// Fetch account info of the logged-in user, cleared when the session is invalid
+    async fn refresh_nav_info(&mut self) {
+        if self.credentials.is_none() {
+            self.nav_info = None;
+            return;
+        }
+        let nav = self.api_client.get_nav_info().await.ok();
+        self.record_login_check(nav.as_ref().map(|nav| nav.is_login));
+        self.nav_info = nav.filter(|nav| nav.is_login);
+    }
+
+    /// Refresh nav info and fill the profile page with it and the follow counts
+    async fn load_profile(&mut self) {
+        self.refresh_nav_info().await;
+        let stat = self.api_client.get_nav_stat().await.ok();
+        if let Page::Profile(page) = &mut self.current_page {
+            page.set_unread(self.unread);
+            match &self.nav_info {
+                Some(nav) => page.set_data(nav.clone(), stat),
+                None => page.set_error("获取账号信息失败，请检查登录状态"),
+            }
+        }
+    }
+
+    /// Account id of the logged-in user, 0 when logged out
+    fn user_mid(&self) -> i64 {
+        self.credentials
+            .as_ref()
+            .and_then(|creds| creds.dede_user_id.parse().ok())
+            .unwrap_or(0)
+    }
+
+    async fn open_video_detail(&mut self, bvid: String, aid: i64) {
+        let mut detail_page = VideoDetailPage::new(bvid, aid);
+        let client = &self.api_client;
+        detail_page.load_data(client).await;
+        detail_page.intro_skip = detail_page
+            .video_info
+            .as_ref()
+            .map_or(0, |info| self.config.intro_skip(info.owner.mid));
+        let is_vip = self.nav_info.as_ref().is_some_and(NavInfo::is_vip);
+        detail_page
+            .load_qualities(client, self.config.preferred_quality, is_vip)
+            .await;
+        self.enter_detail_page(Page::VideoDetail(Box::new(detail_page)));
+    }
+
+    /// Resolve user input from the open prompt into (bvid, aid)
+    async fn resolve_video_input(&self, input: &str) -> Result<(String, i64), String> {
+        use crate::api::bvid::{parse_video_ref, VideoRef};
+
+        match parse_video_ref(input) {
+            Some(VideoRef::Video { bvid, aid }) => Ok((bvid, aid)),
+            Some(VideoRef::ShortLink(url)) => {
+                let target = self
+                    .api_client
+                    .resolve_short_link(&url)
+                    .await
+                    .map_err(|e| format!("解析短链接失败: {}", e))?;
+                match parse_video_ref(&target) {
+                    Some(VideoRef::Video { bvid, aid }) => Ok((bvid, aid)),
+                    _ => Err("短链接未指向视频".to_string()),
+                }
+            }
+            None => Err("无法识别的输入，请输入 BV号、av号或视频链接".to_string()),
+        }
+    }
+
+    async fn load_hotwords(&mut self) {
+        if let Page::Search(page) = &mut self.current_page {
+            match self.api_client.get_hot_search().await {
+                Ok(list) => page.set_hotwords(list),
+                Err(e) => page.set_hotword_error(format!("加载热搜失败: {}", e)),
+            }
+        }
+    }
+
+    /// Re-issue the request behind the current page's error, keeping its query and position
+    async fn retry_failed(&mut self) {
+        let client = self.api_client.clone();
+        match &mut self.current_page {
+            Page::Home(page) => {
+                if let Some(kind) = page.take_failed() {
+                    self.load_home(kind);
+                }
+            }
+            Page::Search(page) => match page.take_failed() {
+                Some(SearchRequest::Search(keyword)) => {
+                    page.loading = true;
+                    page.error_message = None;
+                    page.page = 1;
+                    self.run_search(keyword);
+                }
+                Some(SearchRequest::More) => self.load_more_search(),
+                None => {}
+            },
+            Page::Dynamic(page) => {
+                if let Some(kind) = page.take_failed() {
+                    self.load_dynamic(kind);
+                }
+            }
+            Page::VideoDetail(page) => {
+                page.retry_failed(client.as_ref()).await;
+                if page.qualities.is_empty() {
+                    let is_vip = self.nav_info.as_ref().is_some_and(NavInfo::is_vip);
+                    page.load_qualities(client.as_ref(), self.config.preferred_quality, is_vip)
+                        .await;
+                }
+            }
+            Page::Bangumi(page) => match page.take_failed() {
+                Some(BangumiRequest::List) => page.load(client.as_ref()).await,
+                Some(BangumiRequest::More) => page.load_more(client.as_ref()).await,
+                None => {}
+            },
+            Page::Favorites(page) => match page.take_failed() {
+                Some(FavoritesRequest::Folders) => page.load(client.as_ref()).await,
+                Some(FavoritesRequest::Videos) => page.load_folder(client.as_ref()).await,
+                Some(FavoritesRequest::More) => page.load_more(client.as_ref()).await,
+                None => {}
+            },
+            Page::UserSpace(page) => match page.take_failed() {
+                Some(SpaceRequest::Uploads) => page.load(client.as_ref()).await,
+                Some(SpaceRequest::More) => page.load_more(client.as_ref()).await,
+                Some(SpaceRequest::Collections) => page.load_collections(client.as_ref()).await,
+                Some(SpaceRequest::Episodes(index)) => {
+                    page.load_episodes(client.as_ref(), index).await
+                }
+                None => {}
+            },
+            Page::Tag(page) => match page.take_failed() {
+                Some(TagRequest::Videos) => page.load(client.as_ref()).await,
+                Some(TagRequest::More) => page.load_more(client.as_ref()).await,
+                None => {}
+            },
+            Page::Comments(page) => match page.take_failed() {
+                Some(CommentsRequest::List) => page.load(client.as_ref()).await,
+                Some(CommentsRequest::More) => page.load_more(client.as_ref()).await,
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Stand in for `section` with a login prompt when it needs an account and nobody is logged in
+    ///
+    /// Returns whether the prompt is shown, in which case the section must not be loaded.
+    fn show_login_required(&mut self, section: NavItem) -> bool {
+        if !section.requires_auth() || self.credentials.is_some() {
+            return false;
+        }
+        if !matches!(&self.current_page, Page::LoginRequired(page) if page.section == section) {
+            self.current_page = Page::LoginRequired(LoginRequiredPage::new(section));
+        }
+        true
+    }
+
     async fn switch_to_nav_page(&mut self) {
         // First, cache home page if we're leaving it
         if matches!(self.current_page, Page::Home(_)) && self.sidebar.selected != NavItem::Home {
@@ -632,6 +2526,10 @@ impl App {
             }
         }
 
+        if self.show_login_required(self.sidebar.selected) {
+            return;
+        }
+
         match self.sidebar.selected {
             NavItem::Home => {
                 if !matches!(self.current_page, Page::Home(_)) {
@@ -656,15 +2554,44 @@ impl App {
                     self.init_current_page().await;
                 }
             }
+            NavItem::Bangumi => {
+                if !matches!(self.current_page, Page::Bangumi(_)) {
+                    self.current_page = Page::Bangumi(Box::new(BangumiPage::new(self.user_mid())));
+                    self.init_current_page().await;
+                }
+            }
+            NavItem::Favorites => {
+                if !matches!(self.current_page, Page::Favorites(_)) {
+                    self.current_page =
+                        Page::Favorites(Box::new(FavoritesPage::new(self.user_mid())));
+                    self.init_current_page().await;
+                }
+            }
             NavItem::History => {
                 if !matches!(self.current_page, Page::History(_)) {
                     self.current_page = Page::History(HistoryPage::new());
                     self.init_current_page().await;
                 }
             }
+            NavItem::Profile => {
+                if !matches!(self.current_page, Page::Profile(_)) {
+                    self.current_page = Page::Profile(ProfilePage::new());
+                    self.init_current_page().await;
+                }
+            }
             NavItem::Settings => {
                 if !matches!(self.current_page, Page::Settings(_)) {
-                    let page = SettingsPage::new(self.keybindings.clone(), self.theme_variant);
+                    let mut page = SettingsPage::new(
+                        self.keybindings.clone(),
+                        self.theme_variant,
+                        self.config.network,
+                        self.config.download.clone(),
+                        self.config.grid_columns,
+                        self.config.recommend_filter,
+                        self.config.image_protocol,
+                    );
+                    page.wrap_navigation = self.config.wrap_navigation;
+                    page.token_status = self.token_status();
                     self.current_page = Page::Settings(Box::new(page));
                 }
             }
@@ -672,54 +2599,28 @@ impl App {
     }
 
     async fn init_current_page(&mut self) {
+        set_wrap_navigation(&mut self.current_page, self.config.wrap_navigation);
         match &mut self.current_page {
             Page::Login(page) => {
                 let client = self.api_client.clone();
                 page.load_qrcode(&client).await;
             }
             Page::Home(page) => {
-                let client = self.api_client.clone();
-                page.load_recommendations(&client).await;
+                page.set_view_mode(self.config.home_view);
+                page.set_grid_columns(self.config.grid_columns);
+                page.set_filter(self.config.recommend_filter);
+                page.set_keep_position(self.config.keep_home_position);
+                self.load_home(HomeRequest::Recommendations);
             }
             Page::Search(page) => {
-                let client = self.api_client.clone();
                 page.start_hotword_loading();
-
-                match client.get_hot_search().await {
-                    Ok(list) => page.set_hotwords(list),
-                    Err(e) => page.set_hotword_error(format!("加载热搜失败: {}", e)),
-                }
+                self.load_hotwords().await;
             }
             Page::Dynamic(page) => {
-                let client = self.api_client.clone();
-
-                // First load portal to get frequently watched UPs
+                // Frequently watched UPs load alongside the feed
                 page.loading_up_list = true;
-                match client.get_dynamic_portal().await {
-                    Ok(portal) => {
-                        if let Some(up_list) = portal.up_list {
-                            page.set_up_list(up_list);
-                        }
-                    }
-                    Err(_) => {
-                        page.loading_up_list = false;
-                    }
-                }
-
-                // Then load dynamic feed
-                let feed_type = page.current_tab.get_feed_type();
-                let host_mid = page.get_selected_up_mid();
-                match client.get_dynamic_feed(None, feed_type, host_mid).await {
-                    Ok(data) => {
-                        let items = data.items.unwrap_or_default();
-                        let offset = data.offset;
-                        let has_more = data.has_more.unwrap_or(false);
-                        page.set_feed(items, offset, has_more);
-                    }
-                    Err(e) => {
-                        page.set_error(format!("加载动态失败: {}", e));
-                    }
-                }
+                self.load_up_list();
+                self.load_dynamic(DynamicRequest::Feed);
             }
             Page::VideoDetail(_) => {
                 // VideoDetail is initialized when created
@@ -727,47 +2628,141 @@ impl App {
             Page::DynamicDetail(_) => {
                 // DynamicDetail is initialized when created
             }
+            Page::UserSpace(_) => {
+                // UserSpace is initialized when created
+            }
+            Page::Tag(_) => {
+                // Tag is initialized when created
+            }
+            Page::Comments(_) => {
+                // Comments are loaded when the page is created
+            }
             Page::History(page) => {
                 let client = self.api_client.clone();
-                page.load_history(&client).await;
+                page.load_history(client.as_ref()).await;
+            }
+            Page::Bangumi(page) => {
+                let client = self.api_client.clone();
+                page.load(client.as_ref()).await;
+            }
+            Page::Favorites(page) => {
+                let client = self.api_client.clone();
+                page.load(client.as_ref()).await;
+            }
+            Page::Profile(_) => {
+                self.load_profile().await;
             }
-            Page::Settings(_) => {
-                // Settings doesn't need async initialization
+            Page::LoginRequired(_) | Page::Settings(_) | Page::Welcome(_) => {
+                // Nothing to load
             }
         }
     }
 
-    async fn tick(&mut self) {
+    /// Rebuild covers for the new card sizes once resizing has settled, returning whether it did
+    fn apply_pending_resize(&mut self) -> bool {
+        if self
+            .pending_resize
+            .is_none_or(|at| at.elapsed() < RESIZE_DEBOUNCE)
+        {
+            return false;
+        }
+        self.pending_resize = None;
+        match &mut self.current_page {
+            Page::Home(page) => page.invalidate_covers(),
+            Page::Search(page) => page.invalidate_covers(),
+            Page::Dynamic(page) => page.invalidate_covers(),
+            Page::VideoDetail(page) => page.invalidate_covers(),
+            Page::History(page) => page.invalidate_covers(),
+            Page::Bangumi(page) => page.invalidate_covers(),
+            Page::Favorites(page) => page.invalidate_covers(),
+            Page::UserSpace(page) => page.invalidate_covers(),
+            Page::Tag(page) => page.invalidate_covers(),
+            _ => {}
+        }
+        if let Some(home) = &mut self.cached_home {
+            home.invalidate_covers();
+        }
+        match &mut self.previous_page {
+            Some(PreviousPage::Search(page)) => page.invalidate_covers(),
+            Some(PreviousPage::Dynamic(page)) => page.invalidate_covers(),
+            Some(PreviousPage::History(page)) => page.invalidate_covers(),
+            Some(PreviousPage::Favorites(page)) => page.invalidate_covers(),
+            _ => {}
+        }
+        true
+    }
+
+    /// Run background work, returning whether anything on screen may have changed
+    async fn tick(&mut self) -> bool {
+        let mut changed = self.apply_finished_tasks();
+        changed |= self.apply_pending_resize();
+        changed |= self.poll_credential_refresh();
+        changed |= self.poll_unread_refresh();
+        self.start_unread_refresh();
+        changed |= self.poll_now_playing();
+        changed |= self.start_pending_autoplay();
+        changed |= self.poll_downloads();
+        // A toast running out needs one more frame to disappear, a cover spinner a new one
+        changed |= crate::ui::toast_expired();
+        changed |= crate::ui::spinner_due();
+        let cover_fit = self.config.cover_fit;
+        let concurrency = self.config.network.cover_download_concurrency();
+        let prefetch_rows = self.config.network.cover_prefetch_rows();
+        let cache_size = self.config.network.cover_cache_size();
         match &mut self.current_page {
             Page::Login(page) => {
-                let client = &self.api_client;
-                if let Some(action) = page.tick(client).await {
-                    self.handle_action(action).await;
+                if let Some(creds) = page.take_credentials() {
+                    self.handle_action(AppAction::LoginSuccess(creds)).await;
+                    changed = true;
+                } else {
+                    self.poll_login();
                 }
             }
             Page::Home(page) => {
                 // Non-blocking: poll completed downloads and start new ones
-                page.poll_cover_results();
-                page.start_cover_downloads();
+                page.mark_watched(&self.watched);
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
             }
             Page::Search(page) => {
-                page.poll_cover_results();
-                page.start_cover_downloads();
+                page.mark_watched(&self.watched);
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
             }
             Page::Dynamic(page) => {
-                page.poll_cover_results();
-                page.start_cover_downloads();
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
             }
             Page::VideoDetail(page) => {
-                page.poll_cover_results();
-                page.start_cover_downloads();
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
             }
             Page::History(page) => {
-                page.poll_cover_results();
-                page.start_cover_downloads();
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+            }
+            Page::Bangumi(page) => {
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+            }
+            Page::Favorites(page) => {
+                page.mark_watched(&self.watched);
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+            }
+            Page::UserSpace(page) => {
+                page.mark_watched(&self.watched);
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+            }
+            Page::Tag(page) => {
+                page.mark_watched(&self.watched);
+                changed |= page.poll_cover_results();
+                page.start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
             }
             _ => {}
         }
+        changed
     }
 
     fn save_theme_to_config(&mut self) {
@@ -783,3 +2778,102 @@ impl Default for App {
         Self::new()
     }
 }
+
+/// Check the login every [`CREDENTIAL_CHECK_INTERVAL`], starting right away, until `stop`
+///
+/// Refreshed credentials are saved and applied to the shared client here, so a long
+/// session keeps working even while the UI is idle; the app only mirrors them.
+async fn refresh_credentials_periodically(
+    client: Arc<ApiClient>,
+    mut creds: Credentials,
+    updates: mpsc::UnboundedSender<Result<Credentials, String>>,
+    mut stop: oneshot::Receiver<()>,
+) {
+    let mut interval = tokio::time::interval(CREDENTIAL_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = &mut stop => return,
+            _ = interval.tick() => {}
+        }
+        let update = match client.refresh_credentials_if_needed(&creds).await {
+            Ok(Some(refreshed)) => {
+                // Logged out while the request was in flight
+                if stop.try_recv() != Err(oneshot::error::TryRecvError::Empty) {
+                    return;
+                }
+                if let Err(e) = crate::storage::save_credentials(&refreshed) {
+                    let _ = crate::storage::append_log(&format!("保存刷新后的登录信息失败: {}", e));
+                }
+                client.set_credentials(&refreshed);
+                let _ = crate::storage::append_log("登录状态已刷新");
+                creds = refreshed.clone();
+                Ok(refreshed)
+            }
+            Ok(None) => continue,
+            Err(e) => {
+                let _ = crate::storage::append_log(&format!("刷新登录状态失败: {}", e));
+                Err(e.to_string())
+            }
+        };
+        if updates.send(update).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_filter_input_blocks_global_keys() {
+        let mut search = SearchPage::new();
+        search.input_mode = false;
+        let mut page = Page::Search(search);
+        assert!(!captures_text(&page));
+
+        // Typing into the result filter must not open the prompt, palette or copy the link
+        if let Page::Search(search) = &mut page {
+            search.filter_input = true;
+        }
+        assert!(captures_text(&page));
+    }
+
+    #[test]
+    fn test_wrap_navigation_is_applied_to_the_page_grid() {
+        let mut page = Page::Search(SearchPage::new());
+        set_wrap_navigation(&mut page, true);
+        assert!(matches!(&page, Page::Search(search) if search.grid.wrap));
+
+        set_wrap_navigation(&mut page, false);
+        assert!(matches!(&page, Page::Search(search) if !search.grid.wrap));
+    }
+
+    #[test]
+    fn test_episode_waits_for_the_play_key() {
+        let keys = Keybindings::default();
+        // What Enter on a bangumi card asks for, it has no detail page to stop at
+        let action = AppAction::PlayEpisode {
+            url: "https://www.bilibili.com/bangumi/play/ss28001".to_string(),
+            label: "番剧".to_string(),
+            aid: 0,
+            cid: 0,
+            duration: 0,
+            confirmed: false,
+        };
+
+        let mut dialog = play_key_dialog("番剧".to_string(), action);
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Char('p'), &keys),
+            Some(AppAction::PlayEpisode {
+                confirmed: true,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+//! Page requests run on spawned tasks so input keeps flowing during slow requests
+//!
+//! A request is split in three: the page records what it asked for, the fetch runs on
+//! its own task, and the result is applied to the app on the next tick.
+
+use super::{App, PreviousPage};
+use crate::ui::{
+    fetch_dynamic, fetch_feed, fetch_qr_poll, fetch_search, DynamicPage, DynamicQuery,
+    DynamicRequest, FeedQuery, HomePage, HomeRequest, Page, SearchPage, SearchQuery,
+};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc;
+
+/// Applies a finished request to whichever page it belongs to, if that page is still around
+type Completion = Box<dyn FnOnce(&mut App) + Send>;
+
+/// Ids are unique across page instances, so a page rebuilt while its old request was in
+/// flight never mistakes that answer for its own
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Id for a new page request, later answers carrying an older id are dropped
+pub fn next_request_id() -> u64 {
+    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Finished requests waiting for the next tick
+pub(super) struct Tasks {
+    tx: mpsc::UnboundedSender<Completion>,
+    rx: mpsc::UnboundedReceiver<Completion>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self { tx, rx }
+    }
+
+    /// Run `request` on its own task and hand its output to `apply` on a later tick
+    pub fn spawn<T: Send + 'static>(
+        &self,
+        request: impl Future<Output = T> + Send + 'static,
+        apply: impl FnOnce(&mut App, T) + Send + 'static,
+    ) {
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let output = request.await;
+            // The app is gone when sending fails, nothing is left to update
+            let _ = tx.send(Box::new(move |app: &mut App| apply(app, output)));
+        });
+    }
+
+    /// Requests finished since the last call, oldest first
+    pub fn finished(&mut self) -> Vec<Completion> {
+        let mut done = Vec::new();
+        while let Ok(completion) = self.rx.try_recv() {
+            done.push(completion);
+        }
+        done
+    }
+}
+
+/// Home page wherever it lives, on screen or cached behind another tab
+fn home_page<'a>(
+    current: &'a mut Page,
+    cached: &'a mut Option<HomePage>,
+) -> Option<&'a mut HomePage> {
+    match current {
+        Page::Home(page) => Some(page),
+        _ => cached.as_mut(),
+    }
+}
+
+/// Search page on screen or kept behind the detail page opened from it
+fn search_page<'a>(
+    current: &'a mut Page,
+    previous: &'a mut Option<PreviousPage>,
+) -> Option<&'a mut SearchPage> {
+    match (current, previous) {
+        (Page::Search(page), _) | (_, Some(PreviousPage::Search(page))) => Some(page),
+        _ => None,
+    }
+}
+
+/// Dynamic page on screen or kept behind the detail page opened from it
+fn dynamic_page<'a>(
+    current: &'a mut Page,
+    previous: &'a mut Option<PreviousPage>,
+) -> Option<&'a mut DynamicPage> {
+    match (current, previous) {
+        (Page::Dynamic(page), _) | (_, Some(PreviousPage::Dynamic(page))) => Some(page),
+        _ => None,
+    }
+}
+
+impl App {
+    /// Apply the requests that finished since the last tick, returning whether any did
+    pub(super) fn apply_finished_tasks(&mut self) -> bool {
+        let finished = self.tasks.finished();
+        let any = !finished.is_empty();
+        for completion in finished {
+            completion(self);
+        }
+        any
+    }
+
+    /// Start a home feed request of `kind` in the background
+    pub(super) fn load_home(&mut self, kind: HomeRequest) {
+        let Some(query) = home_page(&mut self.current_page, &mut self.cached_home)
+            .and_then(|page| page.begin(kind))
+        else {
+            return;
+        };
+        self.spawn_feed(query);
+    }
+
+    /// Run a feed query, then keep fetching while the videos don't fill the screen
+    fn spawn_feed(&mut self, query: FeedQuery) {
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move { fetch_feed(client.as_ref(), query).await },
+            |app, answer| {
+                let Some(page) = home_page(&mut app.current_page, &mut app.cached_home) else {
+                    return;
+                };
+                if page.finish(answer).is_none() {
+                    return;
+                }
+                if app.config.hide_watched {
+                    page.set_hide_watched(true, &app.watched);
+                }
+                if let Some(query) = page.top_up() {
+                    app.spawn_feed(query);
+                }
+            },
+        );
+    }
+
+    /// Search for `keyword` in the background, the results replace the current ones
+    pub(super) fn run_search(&mut self, keyword: String) {
+        let Some(page) = search_page(&mut self.current_page, &mut self.previous_page) else {
+            return;
+        };
+        let query = page.begin_search(keyword);
+        self.spawn_search(query);
+    }
+
+    /// Fetch the next search result page in the background
+    pub(super) fn load_more_search(&mut self) {
+        let Some(query) = search_page(&mut self.current_page, &mut self.previous_page)
+            .and_then(SearchPage::begin_more)
+        else {
+            return;
+        };
+        self.spawn_search(query);
+    }
+
+    /// Run a search query, then keep fetching while the results don't fill the screen
+    fn spawn_search(&mut self, query: SearchQuery) {
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move { fetch_search(client.as_ref(), query).await },
+            |app, answer| {
+                let Some(page) = search_page(&mut app.current_page, &mut app.previous_page) else {
+                    return;
+                };
+                if page.finish(answer).is_none() {
+                    return;
+                }
+                if app.config.hide_watched {
+                    page.set_hide_watched(true, &app.watched);
+                }
+                if let Some(query) = page.top_up() {
+                    app.spawn_search(query);
+                }
+            },
+        );
+    }
+
+    /// Fetch the frequently watched UPs shown above the dynamic feed
+    pub(super) fn load_up_list(&mut self) {
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move { client.get_dynamic_portal().await },
+            |app, portal| {
+                let Some(page) = dynamic_page(&mut app.current_page, &mut app.previous_page) else {
+                    return;
+                };
+                match portal.map(|portal| portal.up_list) {
+                    Ok(Some(up_list)) => page.set_up_list(up_list),
+                    _ => page.loading_up_list = false,
+                }
+            },
+        );
+    }
+
+    /// Start a dynamic feed request of `kind` in the background
+    pub(super) fn load_dynamic(&mut self, kind: DynamicRequest) {
+        let Some(query) = dynamic_page(&mut self.current_page, &mut self.previous_page)
+            .and_then(|page| page.begin(kind))
+        else {
+            return;
+        };
+        self.spawn_dynamic(query);
+    }
+
+    /// Run a dynamic feed query, then keep fetching while the cards don't fill the screen
+    fn spawn_dynamic(&mut self, query: DynamicQuery) {
+        let client = self.api_client.clone();
+        self.tasks.spawn(
+            async move { fetch_dynamic(client.as_ref(), query).await },
+            |app, answer| {
+                let Some(page) = dynamic_page(&mut app.current_page, &mut app.previous_page) else {
+                    return;
+                };
+                page.finish(answer);
+                if let Some(query) = page.top_up() {
+                    app.spawn_dynamic(query);
+                }
+            },
+        );
+    }
+
+    /// Poll the QR login state in the background once the page's poll delay has passed
+    pub(super) fn poll_login(&mut self) {
+        let Page::Login(page) = &mut self.current_page else {
+            return;
+        };
+        let Some(poll) = page.begin_poll() else {
+            return;
+        };
+        let client = self.api_client.clone();
+        let poll_interval = self.config.network.qr_poll_interval();
+        self.tasks.spawn(
+            async move { fetch_qr_poll(client.as_ref(), poll).await },
+            move |app, answer| {
+                if let Page::Login(page) = &mut app.current_page {
+                    page.finish_poll(answer, poll_interval);
+                }
+            },
+        );
+    }
+}
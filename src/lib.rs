@@ -1,5 +1,7 @@
 pub mod api;
 pub mod app;
+pub mod clipboard;
 pub mod player;
 pub mod storage;
 pub mod ui;
+pub mod util;
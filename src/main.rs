@@ -1,14 +1,89 @@
+use bilibili_tui::api::{doctor, ApiClient};
 use bilibili_tui::app::App;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
+    style::Stylize,
 };
 use std::io;
 
+/// Leave raw mode and the alternate screen, and stop capturing the mouse
+fn restore_terminal() {
+    let _ = execute!(std::io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+}
+
+/// Restore the terminal before the panic message is printed
+///
+/// A panic in any task leaves the UI in an unknown state, so the process exits
+/// instead of continuing on a restored (non-raw) terminal.
+fn install_panic_hook() {
+    let original = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        original(info);
+        std::process::exit(101);
+    }));
+}
+
+/// Restore the terminal when the process is interrupted or terminated by a signal
+fn spawn_signal_handler() {
+    tokio::spawn(async {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            match signal(SignalKind::terminate()) {
+                Ok(mut terminate) => {
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {}
+                        _ = terminate.recv() => {}
+                    }
+                }
+                Err(_) => {
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+
+        restore_terminal();
+        std::process::exit(130);
+    });
+}
+
+/// `bilibili-tui doctor`: print the login self-test on the plain terminal and exit
+///
+/// Exits with status 1 when any check fails.
+async fn run_doctor() {
+    let config = bilibili_tui::storage::load_config().unwrap_or_default();
+    let credentials = bilibili_tui::storage::load_credentials().ok();
+    let client = ApiClient::from_config(credentials.as_ref(), &config);
+
+    let results = doctor::run_checks(&client).await;
+    for check in &results {
+        let mark = if check.ok { "✔".green() } else { "✘".red() };
+        println!("{} {}  {}", mark, check.name, check.detail);
+    }
+    if let Some(hint) = doctor::diagnosis(&results) {
+        println!("\n{}", hint);
+        std::process::exit(1);
+    }
+}
+
 #[tokio::main]
 async fn main() -> io::Result<()> {
+    if std::env::args().nth(1).as_deref() == Some("doctor") {
+        run_doctor().await;
+        return Ok(());
+    }
+
     // Initialize terminal
     let mut terminal = ratatui::init();
+    install_panic_hook();
+    spawn_signal_handler();
     terminal.clear()?;
 
     // Enable mouse capture
@@ -18,11 +93,7 @@ async fn main() -> io::Result<()> {
     let app = App::new();
     let result = app.run(&mut terminal).await;
 
-    // Disable mouse capture before restoring
-    let _ = execute!(std::io::stdout(), DisableMouseCapture);
-
-    // Restore terminal
-    ratatui::restore();
+    restore_terminal();
 
     result
 }
@@ -0,0 +1,205 @@
+//! Convert danmaku into an ASS subtitle track for mpv
+
+use crate::api::danmaku::{DanmakuItem, DanmakuMode};
+use std::fmt::Write;
+
+const PLAY_RES_X: f64 = 1920.0;
+const PLAY_RES_Y: f64 = 1080.0;
+/// Font size for a standard (25) danmaku at 1080p
+const BASE_FONT_SIZE: f64 = 48.0;
+const LINE_SPACING: f64 = 4.0;
+const SCROLL_DURATION: f64 = 8.0;
+const FIXED_DURATION: f64 = 4.0;
+/// Only use the upper part of the screen for scrolling danmaku
+const SCROLL_AREA_RATIO: f64 = 0.75;
+
+/// State of a scrolling lane: when the last entry has fully entered, and when it leaves
+#[derive(Clone, Copy, Default)]
+struct ScrollLane {
+    free_at: f64,
+    exit_at: f64,
+}
+
+fn font_px(item: &DanmakuItem) -> f64 {
+    (item.font_size as f64 / 25.0 * BASE_FONT_SIZE).round()
+}
+
+// Rough rendered width: CJK glyphs are full width, ASCII about half
+fn text_width(text: &str, font: f64) -> f64 {
+    text.chars()
+        .map(|c| if c.is_ascii() { 0.5 } else { 1.0 })
+        .sum::<f64>()
+        * font
+}
+
+fn format_time(seconds: f64) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centis / 360_000,
+        centis / 6_000 % 60,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+fn ass_color(rgb: u32) -> String {
+    let r = (rgb >> 16) & 0xFF;
+    let g = (rgb >> 8) & 0xFF;
+    let b = rgb & 0xFF;
+    format!("&H{:02X}{:02X}{:02X}&", b, g, r)
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "＼")
+        .replace('{', "｛")
+        .replace('}', "｝")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Build an ASS subtitle document, entries that cannot find a free lane are dropped
+pub fn danmaku_to_ass(items: &[DanmakuItem]) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "[Script Info]\n\
+        ScriptType: v4.00+\n\
+        PlayResX: {}\n\
+        PlayResY: {}\n\
+        WrapStyle: 2\n\
+        ScaledBorderAndShadow: yes\n\n\
+        [V4+ Styles]\n\
+        Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+        Style: Danmaku,sans-serif,{},&H33FFFFFF,&H33FFFFFF,&H33000000,&H33000000,0,0,0,0,100,100,0,0,1,1.5,0,7,0,0,0,1\n\n\
+        [Events]\n\
+        Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+        PLAY_RES_X, PLAY_RES_Y, BASE_FONT_SIZE
+    );
+
+    let lane_height = BASE_FONT_SIZE + LINE_SPACING;
+    let scroll_lanes = ((PLAY_RES_Y * SCROLL_AREA_RATIO) / lane_height) as usize;
+    let fixed_lanes = (PLAY_RES_Y / 2.0 / lane_height) as usize;
+
+    let mut scroll = vec![ScrollLane::default(); scroll_lanes];
+    let mut top = vec![0.0_f64; fixed_lanes];
+    let mut bottom = vec![0.0_f64; fixed_lanes];
+
+    for item in items {
+        let font = font_px(item);
+        let text = escape_text(&item.text);
+        let color = if item.color == 0xFFFFFF {
+            String::new()
+        } else {
+            format!("\\c{}", ass_color(item.color))
+        };
+        let size = if font == BASE_FONT_SIZE {
+            String::new()
+        } else {
+            format!("\\fs{}", font)
+        };
+
+        let (end, effect) = match item.mode {
+            DanmakuMode::Scroll => {
+                let width = text_width(&item.text, font);
+                let speed = (PLAY_RES_X + width) / SCROLL_DURATION;
+                // Time for the head to reach the left edge
+                let reach_left = item.time + PLAY_RES_X / speed;
+                let Some(lane) = scroll
+                    .iter()
+                    .position(|l| item.time >= l.free_at && reach_left >= l.exit_at)
+                else {
+                    continue;
+                };
+                let end = item.time + SCROLL_DURATION;
+                scroll[lane] = ScrollLane {
+                    free_at: item.time + width / speed,
+                    exit_at: end,
+                };
+                let y = lane as f64 * lane_height;
+                (
+                    end,
+                    format!("\\move({},{},{},{})", PLAY_RES_X, y, -width.round(), y),
+                )
+            }
+            DanmakuMode::Top => {
+                let Some(lane) = top.iter().position(|free_at| item.time >= *free_at) else {
+                    continue;
+                };
+                let end = item.time + FIXED_DURATION;
+                top[lane] = end;
+                let y = lane as f64 * lane_height;
+                (end, format!("\\an8\\pos({},{})", PLAY_RES_X / 2.0, y))
+            }
+            DanmakuMode::Bottom => {
+                let Some(lane) = bottom.iter().position(|free_at| item.time >= *free_at) else {
+                    continue;
+                };
+                let end = item.time + FIXED_DURATION;
+                bottom[lane] = end;
+                let y = PLAY_RES_Y - lane as f64 * lane_height;
+                (end, format!("\\an2\\pos({},{})", PLAY_RES_X / 2.0, y))
+            }
+        };
+
+        let _ = writeln!(
+            out,
+            "Dialogue: 0,{},{},Danmaku,,0,0,0,,{{{}{}{}}}{}",
+            format_time(item.time),
+            format_time(end),
+            effect,
+            color,
+            size,
+            text
+        );
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(time: f64, mode: DanmakuMode, text: &str) -> DanmakuItem {
+        DanmakuItem {
+            time,
+            mode,
+            font_size: 25,
+            color: 0xFFFFFF,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0.0), "0:00:00.00");
+        assert_eq!(format_time(3725.5), "1:02:05.50");
+    }
+
+    #[test]
+    fn test_ass_color() {
+        assert_eq!(ass_color(0xFF8000), "&H0080FF&");
+    }
+
+    #[test]
+    fn test_scroll_lanes_do_not_overlap() {
+        let items = vec![
+            item(1.0, DanmakuMode::Scroll, "第一条"),
+            item(1.0, DanmakuMode::Scroll, "第二条"),
+        ];
+        let ass = danmaku_to_ass(&items);
+        assert!(ass.contains(
+            "Dialogue: 0,0:00:01.00,0:00:09.00,Danmaku,,0,0,0,,{\\move(1920,0,-144,0)}第一条"
+        ));
+        assert!(ass.contains("\\move(1920,52,-144,52)}第二条"));
+    }
+
+    #[test]
+    fn test_fixed_danmaku_and_escaping() {
+        let mut red = item(2.0, DanmakuMode::Top, "{hi}");
+        red.color = 0xFF0000;
+        let ass = danmaku_to_ass(&[red, item(2.0, DanmakuMode::Bottom, "底部")]);
+        assert!(ass.contains("{\\an8\\pos(960,0)\\c&H0000FF&}｛hi｝"));
+        assert!(ass.contains("{\\an2\\pos(960,1080)}底部"));
+    }
+}
@@ -0,0 +1,131 @@
+//! Save videos to disk with yt-dlp
+//!
+//! Runs as a background task like playback: progress percentages are sent over a
+//! channel while yt-dlp runs, and the final file path is returned once it exits.
+
+use crate::storage::{Credentials, DownloadConfig};
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+
+/// File name template inside the download directory
+const OUTPUT_TEMPLATE: &str = "%(title).80s [%(id)s].%(ext)s";
+
+/// Percentage from a yt-dlp progress line like `[download]  42.3% of 12.00MiB at ...`
+fn parse_progress(line: &str) -> Option<f32> {
+    let rest = line.strip_prefix("[download]")?;
+    let percent = rest.split_whitespace().next()?.strip_suffix('%')?;
+    percent.parse().ok()
+}
+
+/// Download `bvid`, reporting progress on `progress`, and return the saved file
+///
+/// Sending on `stop` kills yt-dlp; its partial files are left for a later resume.
+pub async fn download_video(
+    bvid: &str,
+    credentials: Option<&Credentials>,
+    config: &DownloadConfig,
+    progress: mpsc::UnboundedSender<f32>,
+    mut stop: oneshot::Receiver<()>,
+) -> Result<PathBuf> {
+    let dir = config.dir();
+    tokio::fs::create_dir_all(&dir).await?;
+
+    let mut cmd = Command::new("yt-dlp");
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.kill_on_drop(true);
+
+    // Deleted when dropped, once yt-dlp has exited
+    let cookie_file = credentials
+        .map(crate::storage::export_cookies_for_ytdlp)
+        .transpose()?;
+    if let Some(cookie_file) = &cookie_file {
+        cmd.arg("--cookies").arg(cookie_file.path());
+    }
+
+    // --print makes yt-dlp quiet, --progress brings the progress lines back
+    cmd.args(["--newline", "--progress", "--no-playlist"]);
+    cmd.args(["--print", "after_move:filepath"]);
+    cmd.arg("--user-agent")
+        .arg(crate::api::client::user_agent());
+    cmd.arg("-f").arg(config.format());
+    cmd.arg("-o").arg(dir.join(OUTPUT_TEMPLATE));
+    cmd.arg(format!("https://www.bilibili.com/video/{}", bvid));
+
+    let mut child = cmd.spawn().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => anyhow!("未找到 yt-dlp，请先安装并确保它在 PATH 中"),
+        _ => anyhow!("无法启动 yt-dlp: {}", e),
+    })?;
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_task = tokio::spawn(async move {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output).await;
+        output
+    });
+
+    let mut lines = BufReader::new(stdout).lines();
+    let mut saved_path = None;
+    let mut stopped = false;
+    loop {
+        tokio::select! {
+            Ok(()) = &mut stop, if !stopped => {
+                stopped = true;
+                let _ = child.start_kill();
+            }
+            line = lines.next_line() => {
+                let Ok(Some(line)) = line else { break };
+                if let Some(percent) = parse_progress(&line) {
+                    let _ = progress.send(percent);
+                } else if !line.trim().is_empty() {
+                    saved_path = Some(PathBuf::from(line.trim()));
+                }
+            }
+        }
+    }
+
+    let status = child.wait().await?;
+    let stderr = stderr_task.await.unwrap_or_default();
+
+    drop(cookie_file);
+
+    if stopped {
+        return Err(anyhow!("下载已取消"));
+    }
+    if !status.success() {
+        // yt-dlp puts the reason on its last "ERROR:" line
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| line.starts_with("ERROR:"))
+            .or_else(|| stderr.lines().last())
+            .unwrap_or("yt-dlp 异常退出");
+        return Err(anyhow!("{}", reason.trim()));
+    }
+    saved_path.ok_or_else(|| anyhow!("yt-dlp 未报告保存位置"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            parse_progress("[download]  42.3% of   12.00MiB at    1.20MiB/s ETA 00:06"),
+            Some(42.3)
+        );
+        assert_eq!(
+            parse_progress("[download] 100% of 12.00MiB in 00:00:10"),
+            Some(100.0)
+        );
+        assert_eq!(parse_progress("[download] Destination: /tmp/a.mp4"), None);
+        assert_eq!(parse_progress("/tmp/a [BV1xx411c7mD].mp4"), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Read the playback position from mpv over its JSON IPC socket
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Give up on a query that mpv does not answer in time
+#[cfg(unix)]
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+const PLAYBACK_TIME_REQUEST: &str =
+    "{\"command\":[\"get_property\",\"playback-time\"],\"request_id\":1}\n";
+
+#[derive(Deserialize)]
+struct Reply {
+    request_id: Option<i64>,
+    error: Option<String>,
+    data: Option<f64>,
+}
+
+/// Socket path for one mpv instance, unique per process and cid
+pub fn socket_path(cid: i64) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "bilibili-tui-mpv-{}-{}.sock",
+        std::process::id(),
+        cid
+    ))
+}
+
+/// Pick the `playback-time` reply out of the lines mpv wrote, skipping event messages
+fn parse_playback_time(output: &str) -> Option<f64> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Reply>(line).ok())
+        .find(|reply| reply.request_id == Some(1))
+        .filter(|reply| reply.error.as_deref() == Some("success"))
+        .and_then(|reply| reply.data)
+}
+
+/// Current position in seconds, `None` while mpv is still loading or has gone away
+#[cfg(unix)]
+pub async fn playback_time(path: &Path) -> Option<f64> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let query = async {
+        let mut stream = tokio::net::UnixStream::connect(path).await.ok()?;
+        stream
+            .write_all(PLAYBACK_TIME_REQUEST.as_bytes())
+            .await
+            .ok()?;
+        let mut lines = BufReader::new(stream).lines();
+        while let Some(line) = lines.next_line().await.ok()? {
+            if line.contains("\"request_id\"") {
+                return parse_playback_time(&line);
+            }
+        }
+        None
+    };
+    tokio::time::timeout(QUERY_TIMEOUT, query).await.ok()?
+}
+
+/// mpv uses named pipes on Windows, which we do not talk to yet
+#[cfg(not(unix))]
+pub async fn playback_time(_path: &Path) -> Option<f64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_playback_time() {
+        let output = concat!(
+            "{\"event\":\"playback-restart\"}\n",
+            "{\"data\":83.52,\"request_id\":1,\"error\":\"success\"}\n",
+        );
+        assert_eq!(parse_playback_time(output), Some(83.52));
+        assert_eq!(
+            parse_playback_time("{\"request_id\":1,\"error\":\"property unavailable\"}"),
+            None
+        );
+        assert_eq!(parse_playback_time("not json"), None);
+    }
+}
@@ -1,13 +1,70 @@
+//! External playback through mpv
+//!
+//! `play_video` resolves once mpv exits. The app spawns it as a background task and
+//! shows a "now playing" banner meanwhile, so the TUI keeps drawing and handling input.
+//! mpv opens its own window (`--force-window`), its keys never pass through the terminal.
+//! While it plays, the position is read over mpv's IPC socket and reported as watch
+//! heartbeats for logged-in users.
+
+mod danmaku;
+pub mod download;
+mod ipc;
+
 use crate::api::client::ApiClient;
 use crate::storage::Credentials;
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
-use tokio::time::{interval, Instant};
+use tokio::sync::oneshot;
+use tokio::time::{interval, interval_at, Instant};
+
+/// How often watch progress is reported while mpv is open
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// How often the position is read from mpv, so the final heartbeat is close to where playback stopped
+const POSITION_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Player options taken from the config and the detail page
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlayOptions {
+    pub show_danmaku: bool,
+    /// Stream quality (qn) to request
+    pub quality: Option<u32>,
+    /// Position in seconds to start at, 0 plays from the beginning
+    pub start: i64,
+}
+
+/// Whether `program` is an executable on `PATH`, e.g. to check for mpv and yt-dlp
+pub fn is_installed(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    let names = if cfg!(windows) {
+        vec![format!("{}.exe", program), program.to_string()]
+    } else {
+        vec![program.to_string()]
+    };
+    std::env::split_paths(&paths).any(|dir| names.iter().any(|name| dir.join(name).is_file()))
+}
+
+/// Max video height for a Bilibili qn, used to steer yt-dlp's format choice
+fn quality_height(qn: u32) -> u32 {
+    match qn {
+        127.. => 4320,
+        120..=126 => 2160,
+        80..=116 => 1080,
+        64..=74 => 720,
+        32..=63 => 480,
+        _ => 360,
+    }
+}
 
 /// Play a video using mpv with yt-dlp and report watch progress
+///
+/// Resolves to the last position read from mpv, in whole seconds.
+#[allow(clippy::too_many_arguments)]
 pub async fn play_video(
     api_client: Arc<ApiClient>,
     bvid: &str,
@@ -15,11 +72,50 @@ pub async fn play_video(
     cid: i64,
     duration: i64,
     credentials: Option<&Credentials>,
-) -> Result<()> {
+    options: PlayOptions,
+    stop: oneshot::Receiver<()>,
+) -> Result<i64> {
     let video_url = format!("https://www.bilibili.com/video/{}", bvid);
+    play_url(
+        api_client,
+        &video_url,
+        bvid,
+        aid,
+        cid,
+        duration,
+        credentials,
+        options,
+        stop,
+    )
+    .await
+}
 
-    // Report watch start
-    let _ = crate::api::heartbeat::report_watch_start(&api_client, aid, cid, bvid, duration).await;
+/// Play an explicit page URL, e.g. a bangumi `ep` page whose `bvid` may be empty
+///
+/// Sending on `stop` kills mpv; the final heartbeat and temp file cleanup still run.
+/// Resolves to the last position read from mpv, 0 if it never answered.
+#[allow(clippy::too_many_arguments)]
+pub async fn play_url(
+    api_client: Arc<ApiClient>,
+    video_url: &str,
+    bvid: &str,
+    aid: i64,
+    cid: i64,
+    duration: i64,
+    credentials: Option<&Credentials>,
+    options: PlayOptions,
+    mut stop: oneshot::Receiver<()>,
+) -> Result<i64> {
+    // Heartbeats are tied to the account, anonymous playback is not reported,
+    // neither are episodes started without their aid (e.g. from the 追番 list)
+    let mid = credentials
+        .filter(|_| aid > 0)
+        .map(|creds| creds.dede_user_id.clone());
+    if let Some(mid) = &mid {
+        let _ =
+            crate::api::heartbeat::report_watch_start(&api_client, mid, aid, cid, bvid, duration)
+                .await;
+    }
 
     let start_ts = chrono::Utc::now().timestamp();
     let mut played_time: i64 = 0;
@@ -30,33 +126,78 @@ pub async fn play_video(
     cmd.stdout(Stdio::null());
     cmd.stderr(Stdio::null());
 
-    let mut cookie_path_to_clean = None;
-
-    if let Some(creds) = credentials {
-        let cookie_path = crate::storage::export_cookies_for_ytdlp(creds)?;
+    // Deleted when dropped, once this playback is over
+    let cookie_file = credentials
+        .map(crate::storage::export_cookies_for_ytdlp)
+        .transpose()?;
+    if let Some(cookie_file) = &cookie_file {
         cmd.arg(format!(
             "--ytdl-raw-options=cookies={}",
-            cookie_path.display()
+            cookie_file.path().display()
         ));
-        cookie_path_to_clean = Some(cookie_path);
     }
 
+    let mut danmaku_path_to_clean = None;
+
+    if let Some(qn) = options.quality {
+        let height = quality_height(qn);
+        cmd.arg(format!(
+            "--ytdl-format=bestvideo[height<=?{h}]+bestaudio/best[height<=?{h}]/best",
+            h = height
+        ));
+    }
+
+    if options.show_danmaku {
+        if let Some(path) = write_danmaku_ass(&api_client, cid).await {
+            cmd.arg(format!("--sub-file={}", path.display()));
+            danmaku_path_to_clean = Some(path);
+        }
+    }
+
+    // mpv fetches the stream itself, yt-dlp resolves it
+    let user_agent = crate::api::client::user_agent();
+    cmd.arg(format!("--user-agent={}", user_agent));
+    cmd.arg(format!(
+        "--ytdl-raw-options-append=user-agent={}",
+        user_agent
+    ));
+
+    if options.start > 0 {
+        cmd.arg(format!("--start={}", options.start));
+        played_time = options.start;
+    }
+
+    let ipc_path = ipc::socket_path(cid);
+    cmd.arg(format!("--input-ipc-server={}", ipc_path.display()));
     cmd.arg("--force-window=immediate");
-    cmd.arg(&video_url);
+    cmd.arg(video_url);
 
     let mut child = cmd.spawn()?;
     let start_time = Instant::now();
-
-    let mut heartbeat_interval = interval(Duration::from_secs(15));
+    let mut heartbeat_interval = interval_at(start_time + HEARTBEAT_INTERVAL, HEARTBEAT_INTERVAL);
+    let mut position_interval = interval(POSITION_POLL_INTERVAL);
+    let mut stopping = false;
 
     loop {
         tokio::select! {
+            // The exit is handled by the wait branch below like a normal close
+            Ok(()) = &mut stop, if !stopping => {
+                stopping = true;
+                let _ = child.start_kill();
+            }
+            // The socket is gone once mpv exits, so the final heartbeat uses the last sample
+            _ = position_interval.tick() => {
+                if let Some(position) = ipc::playback_time(&ipc_path).await {
+                    played_time = position as i64;
+                }
+            }
             _ = heartbeat_interval.tick() => {
-                played_time += 15;
+                let Some(mid) = &mid else { continue };
                 real_played_time = start_time.elapsed().as_secs() as i64;
 
                 let _ = crate::api::heartbeat::report_heartbeat(
                     &api_client,
+                    mid,
                     aid,
                     cid,
                     bvid,
@@ -70,17 +211,20 @@ pub async fn play_video(
             result = child.wait() => {
                 real_played_time = start_time.elapsed().as_secs() as i64;
 
-                let _ = crate::api::heartbeat::report_heartbeat(
-                    &api_client,
-                    aid,
-                    cid,
-                    bvid,
-                    played_time,
-                    real_played_time,
-                    real_played_time,
-                    start_ts,
-                    4, // play_type: 4 = end
-                ).await;
+                if let Some(mid) = &mid {
+                    let _ = crate::api::heartbeat::report_heartbeat(
+                        &api_client,
+                        mid,
+                        aid,
+                        cid,
+                        bvid,
+                        played_time,
+                        real_played_time,
+                        real_played_time,
+                        start_ts,
+                        4, // play_type: 4 = end
+                    ).await;
+                }
 
                 result?;
                 break;
@@ -88,9 +232,32 @@ pub async fn play_video(
         }
     }
 
-    if let Some(path) = cookie_path_to_clean {
+    let _ = tokio::fs::remove_file(&ipc_path).await;
+    drop(cookie_file);
+    if let Some(path) = danmaku_path_to_clean {
         let _ = tokio::fs::remove_file(path).await;
     }
 
-    Ok(())
+    Ok(played_time)
+}
+
+/// Position of the mpv instance playing `cid` in seconds, `None` if it does not answer
+pub async fn playback_position(cid: i64) -> Option<f64> {
+    ipc::playback_time(&ipc::socket_path(cid)).await
+}
+
+/// Fetch danmaku and write them as an ASS file in the temp dir
+/// Returns None if fetching fails or the video has danmaku disabled
+async fn write_danmaku_ass(api_client: &ApiClient, cid: i64) -> Option<PathBuf> {
+    let xml = api_client.get_danmaku_xml(cid).await.ok()?;
+    let items = crate::api::danmaku::parse_danmaku_xml(&xml);
+    if items.is_empty() {
+        return None;
+    }
+
+    let path = std::env::temp_dir().join(format!("bilibili-tui-danmaku-{}.ass", cid));
+    tokio::fs::write(&path, danmaku::danmaku_to_ass(&items))
+        .await
+        .ok()?;
+    Some(path)
 }
@@ -2,8 +2,11 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 /// User credentials from Bilibili login
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,9 +16,80 @@ pub struct Credentials {
     pub dede_user_id: String,
     pub dede_user_id_ckmd5: Option<String>,
     pub refresh_token: Option<String>,
+    /// App access token from TV-client QR login
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// App refresh token from TV-client QR login
+    #[serde(default)]
+    pub app_refresh_token: Option<String>,
+    /// Unix timestamp when the app access token expires
+    #[serde(default)]
+    pub token_expires_at: Option<i64>,
+    /// Session id cookie set alongside SESSDATA on web login
+    #[serde(default)]
+    pub sid: Option<String>,
+    /// Device id cookie, lets playurl serve the same qualities as the browser
+    #[serde(default)]
+    pub buvid3: Option<String>,
+    /// Second device id cookie checked by the anti-crawl (风控) endpoints
+    #[serde(default)]
+    pub buvid4: Option<String>,
+    /// Unix timestamp the device ids were issued at
+    #[serde(default)]
+    pub b_nut: Option<String>,
 }
 
 impl Credentials {
+    /// Refresh the app token this long before it actually expires
+    const TOKEN_REFRESH_MARGIN_SECS: i64 = 7 * 24 * 60 * 60;
+
+    /// Whether the TV-client access token expires within the refresh margin
+    pub fn app_token_expiring(&self, now: i64) -> bool {
+        self.token_expires_at
+            .is_some_and(|expires_at| expires_at - now < Self::TOKEN_REFRESH_MARGIN_SECS)
+    }
+
+    /// Whether the TV-client tokens needed to refresh the cookies are stored
+    pub fn can_refresh(&self) -> bool {
+        self.access_token.is_some() && self.app_refresh_token.is_some()
+    }
+
+    /// Name and value of every cookie we hold, in the order they are sent
+    pub fn cookies(&self) -> Vec<(&'static str, &str)> {
+        let optional = [
+            ("DedeUserID__ckMd5", &self.dede_user_id_ckmd5),
+            ("sid", &self.sid),
+            ("buvid3", &self.buvid3),
+            ("buvid4", &self.buvid4),
+            ("b_nut", &self.b_nut),
+        ];
+        let mut cookies = vec![
+            ("SESSDATA", self.sessdata.as_str()),
+            ("bili_jct", self.bili_jct.as_str()),
+            ("DedeUserID", self.dede_user_id.as_str()),
+        ];
+        cookies.extend(
+            optional
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value.as_deref()?)))
+                .filter(|(_, value)| !value.is_empty()),
+        );
+        cookies
+    }
+
+    /// Keep the device id cookies of `previous` when a refresh response did not include them
+    pub fn inherit_device_ids(&mut self, previous: &Credentials) {
+        for (field, old) in [
+            (&mut self.buvid3, &previous.buvid3),
+            (&mut self.buvid4, &previous.buvid4),
+            (&mut self.b_nut, &previous.b_nut),
+        ] {
+            if field.is_none() {
+                field.clone_from(old);
+            }
+        }
+    }
+
     pub fn from_cookies(
         cookies: &[(String, String)],
         refresh_token: Option<String>,
@@ -34,8 +108,25 @@ impl Credentials {
             dede_user_id: get_cookie("DedeUserID")?,
             dede_user_id_ckmd5: get_cookie("DedeUserID__ckMd5"),
             refresh_token,
+            access_token: None,
+            app_refresh_token: None,
+            token_expires_at: None,
+            sid: get_cookie("sid"),
+            buvid3: get_cookie("buvid3"),
+            buvid4: get_cookie("buvid4"),
+            b_nut: get_cookie("b_nut"),
         })
     }
+
+    /// Parse a pasted cookie string like "SESSDATA=...; bili_jct=...; DedeUserID=..."
+    pub fn from_cookie_string(cookie_str: &str) -> Option<Self> {
+        let cookies: Vec<(String, String)> = cookie_str
+            .split(';')
+            .filter_map(|part| part.trim().split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Self::from_cookies(&cookies, None)
+    }
 }
 
 /// Keybindings configuration
@@ -47,6 +138,14 @@ pub struct Keybindings {
     pub confirm: String,
     pub back: String,
     pub refresh: String,
+    /// Re-issue the request behind the error a page is showing
+    pub retry: String,
+    /// Copy the link of the playing (or last played) video at its current position
+    pub copy_link: String,
+    /// Pick something played recently to play it again
+    pub recent_played: String,
+    /// Run any action by name from the command palette
+    pub command_palette: String,
 
     // Navigation
     pub nav_up: String,
@@ -68,14 +167,52 @@ pub struct Keybindings {
     pub play: String,
     pub open_settings: String,
     pub search_focus: String,
+    /// Open the hot search list from the search results
+    pub hot_words: String,
+    /// Open the selected video's or dynamic's comments straight from a list
+    pub open_comments: String,
+    /// Narrow the fetched search results by title or author
+    pub filter_results: String,
+    /// Load the next search result page
+    pub next_page: String,
+    pub open_by_id: String,
+    pub open_up_space: String,
+
+    // Home page specific
+    pub cycle_fresh_type: String,
+    pub reset_feed: String,
 
     // Comments
     pub comment: String,
     pub toggle_replies: String,
+    pub reply_comment: String,
+    pub comment_page_down: String,
+    pub comment_page_up: String,
+    /// Switch comments between hot and newest first
+    pub cycle_comment_sort: String,
 
     // Dynamic page specific
     pub up_prev: String,
     pub up_next: String,
+    pub mute_up: String,
+    pub unmute_all: String,
+    pub cycle_dynamic_filter: String,
+
+    // Video grids
+    pub toggle_hide_watched: String,
+    pub toggle_view_mode: String,
+
+    // Video detail
+    pub cycle_quality: String,
+    pub download: String,
+    /// Cycle how many seconds to skip at the start of the UP's videos
+    pub skip_intro: String,
+
+    // History page specific
+    pub delete_history: String,
+    pub clear_history: String,
+    /// Mark list entries for batch actions
+    pub toggle_select: String,
 }
 
 impl Default for Keybindings {
@@ -86,6 +223,10 @@ impl Default for Keybindings {
             confirm: "Enter".to_string(),
             back: "Esc".to_string(),
             refresh: "r".to_string(),
+            retry: "e".to_string(),
+            copy_link: "y".to_string(),
+            recent_played: "`".to_string(),
+            command_palette: ":".to_string(),
 
             // Navigation
             nav_up: "k".to_string(),
@@ -107,14 +248,45 @@ impl Default for Keybindings {
             play: "p".to_string(),
             open_settings: "s".to_string(),
             search_focus: "/".to_string(),
+            hot_words: "H".to_string(),
+            open_comments: "c".to_string(),
+            filter_results: "f".to_string(),
+            next_page: "n".to_string(),
+            open_by_id: "O".to_string(),
+            open_up_space: "u".to_string(),
+
+            // Home page
+            cycle_fresh_type: "f".to_string(),
+            reset_feed: "R".to_string(),
 
             // Comments
             comment: "c".to_string(),
             toggle_replies: "r".to_string(),
+            reply_comment: "C".to_string(),
+            comment_page_down: "PageDown".to_string(),
+            comment_page_up: "PageUp".to_string(),
+            cycle_comment_sort: "o".to_string(),
 
             // Dynamic page
             up_prev: "h".to_string(),
             up_next: "l".to_string(),
+            mute_up: "m".to_string(),
+            unmute_all: "M".to_string(),
+            cycle_dynamic_filter: "F".to_string(),
+
+            // Video grids
+            toggle_hide_watched: "w".to_string(),
+            toggle_view_mode: "v".to_string(),
+
+            // Video detail
+            cycle_quality: "v".to_string(),
+            download: "D".to_string(),
+            skip_intro: "S".to_string(),
+
+            // History page
+            delete_history: "d".to_string(),
+            clear_history: "X".to_string(),
+            toggle_select: "Space".to_string(),
         }
     }
 }
@@ -218,6 +390,22 @@ impl Keybindings {
         self.matches(&self.refresh, key)
     }
 
+    pub fn matches_retry(&self, key: KeyCode) -> bool {
+        self.matches(&self.retry, key)
+    }
+
+    pub fn matches_copy_link(&self, key: KeyCode) -> bool {
+        self.matches(&self.copy_link, key)
+    }
+
+    pub fn matches_recent_played(&self, key: KeyCode) -> bool {
+        self.matches(&self.recent_played, key)
+    }
+
+    pub fn matches_command_palette(&self, key: KeyCode) -> bool {
+        self.matches(&self.command_palette, key)
+    }
+
     pub fn matches_up(&self, key: KeyCode) -> bool {
         self.matches(&self.nav_up, key) || key == KeyCode::Up
     }
@@ -294,6 +482,98 @@ impl Keybindings {
         self.matches(&self.up_next, key)
     }
 
+    pub fn matches_mute_up(&self, key: KeyCode) -> bool {
+        self.matches(&self.mute_up, key)
+    }
+
+    pub fn matches_unmute_all(&self, key: KeyCode) -> bool {
+        self.matches(&self.unmute_all, key)
+    }
+
+    pub fn matches_cycle_dynamic_filter(&self, key: KeyCode) -> bool {
+        self.matches(&self.cycle_dynamic_filter, key)
+    }
+
+    pub fn matches_open_up_space(&self, key: KeyCode) -> bool {
+        self.matches(&self.open_up_space, key)
+    }
+
+    pub fn matches_reply_comment(&self, key: KeyCode) -> bool {
+        self.matches(&self.reply_comment, key)
+    }
+
+    pub fn matches_comment_page_down(&self, key: KeyCode) -> bool {
+        self.matches(&self.comment_page_down, key)
+    }
+
+    pub fn matches_comment_page_up(&self, key: KeyCode) -> bool {
+        self.matches(&self.comment_page_up, key)
+    }
+
+    pub fn matches_cycle_comment_sort(&self, key: KeyCode) -> bool {
+        self.matches(&self.cycle_comment_sort, key)
+    }
+
+    pub fn matches_delete_history(&self, key: KeyCode) -> bool {
+        self.matches(&self.delete_history, key)
+    }
+
+    pub fn matches_clear_history(&self, key: KeyCode) -> bool {
+        self.matches(&self.clear_history, key)
+    }
+
+    pub fn matches_toggle_select(&self, key: KeyCode) -> bool {
+        self.matches(&self.toggle_select, key)
+    }
+
+    pub fn matches_cycle_fresh_type(&self, key: KeyCode) -> bool {
+        self.matches(&self.cycle_fresh_type, key)
+    }
+
+    pub fn matches_reset_feed(&self, key: KeyCode) -> bool {
+        self.matches(&self.reset_feed, key)
+    }
+
+    pub fn matches_hot_words(&self, key: KeyCode) -> bool {
+        self.matches(&self.hot_words, key)
+    }
+
+    pub fn matches_open_comments(&self, key: KeyCode) -> bool {
+        self.matches(&self.open_comments, key)
+    }
+
+    pub fn matches_filter_results(&self, key: KeyCode) -> bool {
+        self.matches(&self.filter_results, key)
+    }
+
+    pub fn matches_next_page(&self, key: KeyCode) -> bool {
+        self.matches(&self.next_page, key)
+    }
+
+    pub fn matches_open_by_id(&self, key: KeyCode) -> bool {
+        self.matches(&self.open_by_id, key)
+    }
+
+    pub fn matches_toggle_hide_watched(&self, key: KeyCode) -> bool {
+        self.matches(&self.toggle_hide_watched, key)
+    }
+
+    pub fn matches_toggle_view_mode(&self, key: KeyCode) -> bool {
+        self.matches(&self.toggle_view_mode, key)
+    }
+
+    pub fn matches_cycle_quality(&self, key: KeyCode) -> bool {
+        self.matches(&self.cycle_quality, key)
+    }
+
+    pub fn matches_download(&self, key: KeyCode) -> bool {
+        self.matches(&self.download, key)
+    }
+
+    pub fn matches_skip_intro(&self, key: KeyCode) -> bool {
+        self.matches(&self.skip_intro, key)
+    }
+
     /// Get all keybinding labels for display in settings
     pub fn get_all_labels(&self) -> Vec<(&'static str, &str)> {
         vec![
@@ -326,6 +606,43 @@ impl Keybindings {
             // Dynamic page
             ("上一UP", &self.up_prev),
             ("下一UP", &self.up_next),
+            // Home page
+            ("推荐模式", &self.cycle_fresh_type),
+            // Open by id
+            ("打开链接", &self.open_by_id),
+            // Dynamic page
+            ("屏蔽UP", &self.mute_up),
+            ("取消屏蔽", &self.unmute_all),
+            // Video grids
+            ("隐藏已看", &self.toggle_hide_watched),
+            // Video detail
+            ("切换清晰度", &self.cycle_quality),
+            // Home page
+            ("重置推荐", &self.reset_feed),
+            ("网格/列表", &self.toggle_view_mode),
+            // Video grids and detail
+            ("下载", &self.download),
+            // Dynamic page
+            ("动态类型筛选", &self.cycle_dynamic_filter),
+            ("UP主投稿", &self.open_up_space),
+            // Comments
+            ("回复评论", &self.reply_comment),
+            // History page
+            ("删除历史", &self.delete_history),
+            ("清空历史", &self.clear_history),
+            ("重试", &self.retry),
+            ("复制时间链接", &self.copy_link),
+            ("最近播放", &self.recent_played),
+            ("评论下一页", &self.comment_page_down),
+            ("评论上一页", &self.comment_page_up),
+            ("评论排序", &self.cycle_comment_sort),
+            ("多选", &self.toggle_select),
+            ("热搜", &self.hot_words),
+            ("查看评论", &self.open_comments),
+            ("筛选结果", &self.filter_results),
+            ("命令面板", &self.command_palette),
+            ("跳过片头", &self.skip_intro),
+            ("下一页", &self.next_page),
         ]
     }
 
@@ -361,24 +678,503 @@ impl Keybindings {
             // Dynamic page
             21 => self.up_prev = new_key,
             22 => self.up_next = new_key,
+            // Home page
+            23 => self.cycle_fresh_type = new_key,
+            // Open by id
+            24 => self.open_by_id = new_key,
+            // Dynamic page
+            25 => self.mute_up = new_key,
+            26 => self.unmute_all = new_key,
+            // Video grids
+            27 => self.toggle_hide_watched = new_key,
+            // Video detail
+            28 => self.cycle_quality = new_key,
+            // Home page
+            29 => self.reset_feed = new_key,
+            30 => self.toggle_view_mode = new_key,
+            // Video grids and detail
+            31 => self.download = new_key,
+            // Dynamic page
+            32 => self.cycle_dynamic_filter = new_key,
+            33 => self.open_up_space = new_key,
+            // Comments
+            34 => self.reply_comment = new_key,
+            // History page
+            35 => self.delete_history = new_key,
+            36 => self.clear_history = new_key,
+            37 => self.retry = new_key,
+            38 => self.copy_link = new_key,
+            39 => self.recent_played = new_key,
+            40 => self.comment_page_down = new_key,
+            41 => self.comment_page_up = new_key,
+            42 => self.cycle_comment_sort = new_key,
+            43 => self.toggle_select = new_key,
+            44 => self.hot_words = new_key,
+            45 => self.open_comments = new_key,
+            46 => self.filter_results = new_key,
+            47 => self.command_palette = new_key,
+            48 => self.skip_intro = new_key,
+            49 => self.next_page = new_key,
             _ => {}
         }
     }
 }
 
+/// How video covers are fitted to a 16:9 aspect ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverFit {
+    /// Crop the cover to fill the area
+    #[default]
+    Crop,
+    /// Keep the whole cover and pad it with bars
+    Letterbox,
+}
+
+/// Layout of the home page recommendations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HomeViewMode {
+    /// Cards with cover images
+    #[default]
+    Grid,
+    /// One text line per video, no covers
+    List,
+}
+
+impl HomeViewMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            HomeViewMode::Grid => HomeViewMode::List,
+            HomeViewMode::List => HomeViewMode::Grid,
+        }
+    }
+}
+
+/// Terminal graphics protocol for covers and images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageProtocol {
+    /// Ask the terminal, half blocks inside tmux
+    #[default]
+    Auto,
+    Halfblocks,
+    Sixel,
+    Kitty,
+    Iterm2,
+}
+
+impl ImageProtocol {
+    pub const ALL: [ImageProtocol; 5] = [
+        ImageProtocol::Auto,
+        ImageProtocol::Halfblocks,
+        ImageProtocol::Sixel,
+        ImageProtocol::Kitty,
+        ImageProtocol::Iterm2,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImageProtocol::Auto => "自动",
+            ImageProtocol::Halfblocks => "半块字符",
+            ImageProtocol::Sixel => "Sixel",
+            ImageProtocol::Kitty => "Kitty",
+            ImageProtocol::Iterm2 => "iTerm2",
+        }
+    }
+
+    /// The protocol `delta` places away, stopping at either end
+    pub fn step(self, delta: i64) -> Self {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as i64;
+        Self::ALL[(idx + delta).clamp(0, Self::ALL.len() as i64 - 1) as usize]
+    }
+}
+
+/// Client-side filter for the home recommendations, 0 turns a limit off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecommendFilter {
+    /// Hide videos with fewer plays
+    pub min_views: u64,
+    /// Hide videos longer than this many minutes
+    pub max_duration_minutes: u64,
+}
+
+impl RecommendFilter {
+    /// Values the settings page steps through
+    pub const MIN_VIEWS_STEPS: [u64; 5] = [0, 1_000, 10_000, 100_000, 1_000_000];
+    pub const MAX_DURATION_STEPS: [u64; 6] = [0, 5, 10, 20, 30, 60];
+
+    pub fn is_active(&self) -> bool {
+        self.min_views > 0 || self.max_duration_minutes > 0
+    }
+
+    /// Whether a video with these stats passes, unknown values fail an active limit
+    pub fn allows(&self, views: Option<i64>, duration_secs: Option<i64>) -> bool {
+        let views_ok =
+            self.min_views == 0 || views.is_some_and(|views| views >= self.min_views as i64);
+        let duration_ok = self.max_duration_minutes == 0
+            || duration_secs.is_some_and(|secs| secs <= self.max_duration_minutes as i64 * 60);
+        views_ok && duration_ok
+    }
+
+    /// Move `value` by `delta` steps along `steps`, starting from the nearest step below it
+    pub fn step(steps: &[u64], value: u64, delta: i64) -> u64 {
+        let current = steps.iter().rposition(|step| *step <= value).unwrap_or(0);
+        let next = (current as i64 + delta).clamp(0, steps.len() as i64 - 1);
+        steps[next as usize]
+    }
+
+    pub fn min_views_label(&self) -> String {
+        match self.min_views {
+            0 => "不限".to_string(),
+            views if views >= 10_000 => format!("{}万", views / 10_000),
+            views => views.to_string(),
+        }
+    }
+
+    pub fn max_duration_label(&self) -> String {
+        match self.max_duration_minutes {
+            0 => "不限".to_string(),
+            minutes => format!("{}分钟", minutes),
+        }
+    }
+}
+
+/// Network tuning, adjustable live from the settings page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Base seconds between QR login status polls, jittered and backed off by the login page
+    pub qr_poll_interval: u64,
+    /// Maximum cover downloads in flight per page
+    pub cover_download_concurrency: usize,
+    /// Seconds before an API request is abandoned
+    pub request_timeout: u64,
+    /// Rows above and below the visible ones whose covers are loaded ahead of scrolling
+    ///
+    /// Higher values make scrolling smoother on fast connections but download covers that
+    /// may never be looked at; 0 only loads what is on screen.
+    pub cover_prefetch_rows: usize,
+    /// Decoded covers a page keeps in memory, the least recently seen off-screen ones go first
+    ///
+    /// Never less than what the prefetch rows hold, dropped covers decode again from the
+    /// disk cache.
+    pub cover_cache_size: usize,
+}
+
+impl NetworkConfig {
+    pub const QR_POLL_INTERVAL_RANGE: (u64, u64) = (1, 10);
+    pub const COVER_CONCURRENCY_RANGE: (usize, usize) = (1, 16);
+    pub const REQUEST_TIMEOUT_RANGE: (u64, u64) = (3, 60);
+    pub const COVER_PREFETCH_ROWS_RANGE: (usize, usize) = (0, 10);
+    pub const COVER_CACHE_SIZE_RANGE: (usize, usize) = (12, 600);
+
+    /// Copy with every value clamped to its sane range
+    pub fn clamped(self) -> Self {
+        Self {
+            qr_poll_interval: self.qr_poll_interval.clamp(
+                Self::QR_POLL_INTERVAL_RANGE.0,
+                Self::QR_POLL_INTERVAL_RANGE.1,
+            ),
+            cover_download_concurrency: self.cover_download_concurrency.clamp(
+                Self::COVER_CONCURRENCY_RANGE.0,
+                Self::COVER_CONCURRENCY_RANGE.1,
+            ),
+            request_timeout: self
+                .request_timeout
+                .clamp(Self::REQUEST_TIMEOUT_RANGE.0, Self::REQUEST_TIMEOUT_RANGE.1),
+            cover_prefetch_rows: self.cover_prefetch_rows.clamp(
+                Self::COVER_PREFETCH_ROWS_RANGE.0,
+                Self::COVER_PREFETCH_ROWS_RANGE.1,
+            ),
+            cover_cache_size: self.cover_cache_size.clamp(
+                Self::COVER_CACHE_SIZE_RANGE.0,
+                Self::COVER_CACHE_SIZE_RANGE.1,
+            ),
+        }
+    }
+
+    pub fn qr_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.clamped().qr_poll_interval)
+    }
+
+    pub fn cover_download_concurrency(&self) -> usize {
+        self.clamped().cover_download_concurrency
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.clamped().request_timeout)
+    }
+
+    pub fn cover_prefetch_rows(&self) -> usize {
+        self.clamped().cover_prefetch_rows
+    }
+
+    pub fn cover_cache_size(&self) -> usize {
+        self.clamped().cover_cache_size
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            qr_poll_interval: 2,
+            cover_download_concurrency: 6,
+            request_timeout: 15,
+            cover_prefetch_rows: 2,
+            cover_cache_size: 60,
+        }
+    }
+}
+
+/// Video downloads through yt-dlp
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadConfig {
+    /// Target directory, empty for `<Downloads>/bilibili-tui`
+    pub dir: String,
+    /// yt-dlp format selector, see `yt-dlp -f`
+    pub format: String,
+}
+
+impl DownloadConfig {
+    pub const DEFAULT_FORMAT: &'static str = "bv*+ba/b";
+
+    /// Resolved target directory, `~` is expanded to the home directory
+    pub fn dir(&self) -> PathBuf {
+        let dir = self.dir.trim();
+        if dir.is_empty() {
+            return dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(std::env::temp_dir)
+                .join("bilibili-tui");
+        }
+        match dir.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((rest, home)) => home.join(rest),
+            None => PathBuf::from(dir),
+        }
+    }
+
+    pub fn format(&self) -> &str {
+        match self.format.trim() {
+            "" => Self::DEFAULT_FORMAT,
+            format => format,
+        }
+    }
+}
+
+impl Default for DownloadConfig {
+    fn default() -> Self {
+        Self {
+            dir: String::new(),
+            format: Self::DEFAULT_FORMAT.to_string(),
+        }
+    }
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub theme: String,
     pub keybindings: Keybindings,
+    pub cover_fit: CoverFit,
+    pub home_view: HomeViewMode,
+    /// Fixed number of recommendation columns (1-6), `None` fits them to the width
+    pub grid_columns: Option<usize>,
+    /// Minimum plays and maximum length of recommended videos
+    pub recommend_filter: RecommendFilter,
+    /// Refresh the home feed in place, keeping the selection, instead of starting from the top
+    pub keep_home_position: bool,
+    /// `l` on the last card wraps to the first and `h` on the first to the last
+    pub wrap_navigation: bool,
+    /// Overlay danmaku on mpv playback
+    pub show_danmaku: bool,
+    /// Hide locally watched videos in the home and search grids
+    pub hide_watched: bool,
+    pub network: NetworkConfig,
+    pub download: DownloadConfig,
+    /// Preferred stream quality (qn), capped to 1080P for non-VIP accounts
+    pub preferred_quality: u32,
+    /// Play the next episode of a UGC season (合集) when mpv exits
+    pub autoplay_next: bool,
+    /// Ask for the play key before list entries that play directly start mpv
+    pub confirm_before_play: bool,
+    /// Reopen the last section, feed source and home selection on launch
+    pub restore_session: bool,
+    /// Saved on quit when `restore_session` is enabled
+    pub session: SessionState,
+    /// User-Agent for API, image and stream requests, empty for a built-in desktop browser one
+    pub user_agent: String,
+    /// Keep the recently played list across sessions in `recent.json`
+    pub remember_recent: bool,
+    /// Graphics protocol for images, read once at startup
+    pub image_protocol: ImageProtocol,
+    /// Unix time the login cookies were last refreshed, automatically or from settings
+    pub last_credential_refresh: Option<i64>,
+    /// Seconds skipped at the start of every video by an UP, keyed by mid
+    pub intro_skips: BTreeMap<i64, i64>,
+}
+
+/// Where the user left off, see `AppConfig::restore_session`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Sidebar section name, e.g. "Home" or "Dynamic"
+    pub section: String,
+    /// Recommend feed source name, e.g. "Default" or "BigData"
+    pub fresh_type: String,
+    /// Selected card on the home page
+    pub home_selected: usize,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self {
+            section: "Home".to_string(),
+            fresh_type: "Default".to_string(),
+            home_selected: 0,
+        }
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
-            theme: "CatppuccinMocha".to_string(),
+            theme: "Auto".to_string(),
             keybindings: Keybindings::default(),
+            cover_fit: CoverFit::default(),
+            home_view: HomeViewMode::default(),
+            grid_columns: None,
+            recommend_filter: RecommendFilter::default(),
+            keep_home_position: true,
+            wrap_navigation: false,
+            show_danmaku: false,
+            hide_watched: false,
+            network: NetworkConfig::default(),
+            download: DownloadConfig::default(),
+            preferred_quality: 80,
+            autoplay_next: true,
+            confirm_before_play: false,
+            restore_session: false,
+            session: SessionState::default(),
+            user_agent: String::new(),
+            remember_recent: false,
+            image_protocol: ImageProtocol::default(),
+            last_credential_refresh: None,
+            intro_skips: BTreeMap::new(),
+        }
+    }
+}
+
+/// Offsets the detail page's skip-intro key cycles through, in seconds
+pub const INTRO_SKIP_STEPS: [i64; 5] = [0, 15, 30, 60, 90];
+
+impl AppConfig {
+    /// Seconds to skip at the start of videos by `mid`, 0 when none is set
+    pub fn intro_skip(&self, mid: i64) -> i64 {
+        self.intro_skips.get(&mid).copied().unwrap_or(0)
+    }
+
+    /// Move `mid`'s intro skip to the next of [`INTRO_SKIP_STEPS`], returning the new value
+    pub fn cycle_intro_skip(&mut self, mid: i64) -> i64 {
+        let current = self.intro_skip(mid);
+        let next = INTRO_SKIP_STEPS
+            .into_iter()
+            .find(|&step| step > current)
+            .unwrap_or(0);
+        if next == 0 {
+            self.intro_skips.remove(&mid);
+        } else {
+            self.intro_skips.insert(mid, next);
+        }
+        next
+    }
+}
+
+/// Bvids of videos launched from this client, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchedVideos {
+    bvids: VecDeque<String>,
+    #[serde(skip)]
+    lookup: HashSet<String>,
+}
+
+impl WatchedVideos {
+    /// Oldest entries are dropped beyond this size
+    const MAX_ENTRIES: usize = 5000;
+
+    pub fn contains(&self, bvid: &str) -> bool {
+        self.lookup.contains(bvid)
+    }
+
+    /// Record a bvid, returns false if it was already marked
+    pub fn insert(&mut self, bvid: &str) -> bool {
+        if !self.lookup.insert(bvid.to_string()) {
+            return false;
         }
+        self.bvids.push_back(bvid.to_string());
+        while self.bvids.len() > Self::MAX_ENTRIES {
+            if let Some(old) = self.bvids.pop_front() {
+                self.lookup.remove(&old);
+            }
+        }
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.bvids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bvids.is_empty()
+    }
+
+    fn rebuild_lookup(&mut self) {
+        self.lookup = self.bvids.iter().cloned().collect();
+    }
+}
+
+/// Something played from this client, enough to start it again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentItem {
+    /// Page URL handed to mpv
+    pub url: String,
+    /// Empty for bangumi episodes, which are played by URL
+    pub bvid: String,
+    /// UP of the video, 0 for episodes and for entries saved before it was kept
+    #[serde(default)]
+    pub mid: i64,
+    pub title: String,
+    pub aid: i64,
+    pub cid: i64,
+    pub duration: i64,
+}
+
+/// Recently played items, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentlyPlayed {
+    items: VecDeque<RecentItem>,
+}
+
+impl RecentlyPlayed {
+    /// Older entries are dropped beyond this size
+    const MAX_ENTRIES: usize = 20;
+
+    /// Put `item` on top, replaying an entry moves it up instead of duplicating it
+    pub fn push(&mut self, item: RecentItem) {
+        self.items.retain(|existing| existing.url != item.url);
+        self.items.push_front(item);
+        self.items.truncate(Self::MAX_ENTRIES);
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &RecentItem> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
     }
 }
 
@@ -395,9 +1191,11 @@ fn get_config_dir() -> Result<PathBuf> {
     Ok(config_dir)
 }
 
+const CREDENTIALS_FILE: &str = "credentials.json";
+
 /// Get the credentials file path
 fn get_credentials_path() -> Result<PathBuf> {
-    Ok(get_config_dir()?.join("credentials.json"))
+    Ok(get_config_dir()?.join(CREDENTIALS_FILE))
 }
 
 /// Get the config file path
@@ -405,20 +1203,65 @@ fn get_config_path() -> Result<PathBuf> {
     Ok(get_config_dir()?.join("config.json"))
 }
 
+/// Write `contents` to a temp file beside `path` and rename it over, so a crash
+/// mid-write leaves the old file rather than a truncated one
+///
+/// The file holds SESSDATA and refresh tokens, so only the owner may read it.
+fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    // The mode only applies to new files, a leftover from a crashed save keeps its own
+    let _ = fs::remove_file(&tmp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
 /// Save credentials to disk
 pub fn save_credentials(credentials: &Credentials) -> Result<()> {
-    let path = get_credentials_path()?;
+    save_credentials_in(&get_config_dir()?, credentials)
+}
+
+fn save_credentials_in(dir: &Path, credentials: &Credentials) -> Result<()> {
     let json = serde_json::to_string_pretty(credentials)?;
-    fs::write(path, json)?;
-    Ok(())
+    write_atomic(&dir.join(CREDENTIALS_FILE), &json)
 }
 
 /// Load credentials from disk
+///
+/// A file that does not parse, e.g. empty after a crash, is moved aside to
+/// `credentials.json.bak` so the next start goes straight to login.
 pub fn load_credentials() -> Result<Credentials> {
-    let path = get_credentials_path()?;
-    let json = fs::read_to_string(path)?;
-    let credentials: Credentials = serde_json::from_str(&json)?;
-    Ok(credentials)
+    load_credentials_in(&get_config_dir()?)
+}
+
+fn load_credentials_in(dir: &Path) -> Result<Credentials> {
+    let path = dir.join(CREDENTIALS_FILE);
+    let json = fs::read_to_string(&path)?;
+    let error = match serde_json::from_str::<Credentials>(&json) {
+        Ok(credentials) if !credentials.sessdata.is_empty() => return Ok(credentials),
+        Ok(_) => "SESSDATA 为空".to_string(),
+        Err(e) => e.to_string(),
+    };
+    let backup = dir.join(format!("{}.bak", CREDENTIALS_FILE));
+    fs::rename(&path, &backup)?;
+    Err(anyhow::anyhow!(
+        "登录信息已损坏 ({})，已备份到 {}",
+        error,
+        backup.display()
+    ))
 }
 
 /// Delete credentials (logout)
@@ -438,6 +1281,11 @@ pub fn save_config(config: &AppConfig) -> Result<()> {
     Ok(())
 }
 
+/// No config has been saved yet, i.e. this is the very first launch
+pub fn is_first_run() -> bool {
+    get_config_path().is_ok_and(|path| !path.exists())
+}
+
 /// Load app config from disk
 pub fn load_config() -> Result<AppConfig> {
     let path = get_config_path()?;
@@ -450,18 +1298,471 @@ pub fn load_config() -> Result<AppConfig> {
     }
 }
 
+fn get_log_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("bilibili-tui.log"))
+}
+
+/// Append a timestamped line to the log file in the config directory
+pub fn append_log(message: &str) -> Result<()> {
+    use std::io::Write;
+
+    let path = get_log_path()?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    writeln!(file, "[{}] {}", now, message)?;
+    Ok(())
+}
+
+fn get_watched_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("watched.json"))
+}
+
+/// Save the watched video set to disk
+pub fn save_watched(watched: &WatchedVideos) -> Result<()> {
+    let path = get_watched_path()?;
+    let json = serde_json::to_string(watched)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Load the watched video set from disk
+pub fn load_watched() -> Result<WatchedVideos> {
+    let path = get_watched_path()?;
+    if path.exists() {
+        let json = fs::read_to_string(path)?;
+        let mut watched: WatchedVideos = serde_json::from_str(&json)?;
+        watched.rebuild_lookup();
+        Ok(watched)
+    } else {
+        Ok(WatchedVideos::default())
+    }
+}
+
+fn get_recent_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join("recent.json"))
+}
+
+/// Save the recently played list, only done when `AppConfig::remember_recent` is set
+pub fn save_recent(recent: &RecentlyPlayed) -> Result<()> {
+    let path = get_recent_path()?;
+    let json = serde_json::to_string(recent)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Load the recently played list saved by an earlier session
+pub fn load_recent() -> Result<RecentlyPlayed> {
+    let path = get_recent_path()?;
+    if path.exists() {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    } else {
+        Ok(RecentlyPlayed::default())
+    }
+}
+
+/// Cookie file handed to yt-dlp, deleted when dropped
+///
+/// Every export gets its own file so overlapping plays and downloads never remove each
+/// other's cookies. Keep it alive until the child process has exited.
+#[derive(Debug)]
+pub struct CookieFile {
+    path: PathBuf,
+}
+
+impl CookieFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for CookieFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Lifetime written for exported cookies; yt-dlp drops session cookies (expiry 0) in some versions
+const COOKIE_EXPORT_LIFETIME_SECS: i64 = 365 * 24 * 60 * 60;
+
+/// Cookie jar text in Netscape format, with every cookie valid for a year from `now`
+fn netscape_cookies(credentials: &Credentials, now: i64) -> Result<String> {
+    let expires = now + COOKIE_EXPORT_LIFETIME_SECS;
+    let mut content = String::from("# Netscape HTTP Cookie File\n");
+    for (name, value) in credentials.cookies() {
+        // A tab or newline would shift the fields of the line
+        if value.contains(['\t', '\r', '\n']) {
+            anyhow::bail!("Cookie {} 含有非法字符", name);
+        }
+        content.push_str(&format!(
+            ".bilibili.com\tTRUE\t/\t{}\t{}\t{}\t{}\n",
+            if name == "SESSDATA" { "TRUE" } else { "FALSE" },
+            expires,
+            name,
+            value
+        ));
+    }
+    Ok(content)
+}
+
 /// Export cookies in Netscape format for yt-dlp
-pub fn export_cookies_for_ytdlp(credentials: &Credentials) -> Result<PathBuf> {
-    let path = get_config_dir()?.join("cookies.txt");
-
-    let content = format!(
-        "# Netscape HTTP Cookie File\n\
-        .bilibili.com\tTRUE\t/\tTRUE\t0\tSESSDATA\t{}\n\
-        .bilibili.com\tTRUE\t/\tFALSE\t0\tbili_jct\t{}\n\
-        .bilibili.com\tTRUE\t/\tFALSE\t0\tDedeUserID\t{}\n",
-        credentials.sessdata, credentials.bili_jct, credentials.dede_user_id
-    );
-
-    fs::write(&path, content)?;
-    Ok(path)
+pub fn export_cookies_for_ytdlp(credentials: &Credentials) -> Result<CookieFile> {
+    export_cookies_in(&std::env::temp_dir(), credentials)
+}
+
+fn export_cookies_in(dir: &Path, credentials: &Credentials) -> Result<CookieFile> {
+    use std::io::Write;
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let path = dir.join(format!(
+        "bilibili-tui-cookies-{}-{}.txt",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let content = netscape_cookies(credentials, chrono::Utc::now().timestamp())?;
+
+    // The file holds SESSDATA, so only the owner may read it
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    let cookie_file = CookieFile { path };
+    file.write_all(content.as_bytes())?;
+    Ok(cookie_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recent(url: &str) -> RecentItem {
+        RecentItem {
+            url: url.to_string(),
+            bvid: String::new(),
+            mid: 0,
+            title: url.to_string(),
+            aid: 1,
+            cid: 1,
+            duration: 60,
+        }
+    }
+
+    #[test]
+    fn test_recent_items_keep_the_up_for_intro_skips() {
+        let mut config = AppConfig::default();
+        config.cycle_intro_skip(42);
+        let item = RecentItem {
+            mid: 42,
+            ..recent("https://www.bilibili.com/video/BV1xx")
+        };
+        let saved: RecentItem =
+            serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+        assert_eq!(config.intro_skip(saved.mid), 15);
+
+        // Lists saved before the UP was kept still load, without a skip
+        let old: RecentItem = serde_json::from_str(
+            r#"{"url":"u","bvid":"BV1xx","title":"t","aid":1,"cid":2,"duration":60}"#,
+        )
+        .unwrap();
+        assert_eq!(config.intro_skip(old.mid), 0);
+    }
+
+    #[test]
+    fn test_recently_played_moves_replays_up_and_caps() {
+        let mut list = RecentlyPlayed::default();
+        list.push(recent("a"));
+        list.push(recent("b"));
+        list.push(recent("a"));
+        let urls: Vec<_> = list.items().map(|item| item.url.as_str()).collect();
+        assert_eq!(urls, ["a", "b"]);
+
+        for i in 0..30 {
+            list.push(recent(&i.to_string()));
+        }
+        assert_eq!(list.items().count(), RecentlyPlayed::MAX_ENTRIES);
+        assert_eq!(list.items().next().unwrap().url, "29");
+    }
+
+    #[test]
+    fn test_recommend_filter() {
+        let filter = RecommendFilter {
+            min_views: 10_000,
+            max_duration_minutes: 10,
+        };
+        assert!(filter.allows(Some(10_000), Some(600)));
+        assert!(!filter.allows(Some(9_999), Some(60)));
+        assert!(!filter.allows(Some(50_000), Some(601)));
+        assert!(!filter.allows(None, Some(60)));
+        assert!(RecommendFilter::default().allows(None, None));
+
+        let steps = &RecommendFilter::MIN_VIEWS_STEPS;
+        assert_eq!(RecommendFilter::step(steps, 0, 1), 1_000);
+        assert_eq!(RecommendFilter::step(steps, 1_000_000, 1), 1_000_000);
+        assert_eq!(RecommendFilter::step(steps, 0, -1), 0);
+        // Hand-edited values snap onto the step list
+        assert_eq!(RecommendFilter::step(steps, 5_000, 1), 10_000);
+        assert_eq!(filter.min_views_label(), "1万");
+        assert_eq!(filter.max_duration_label(), "10分钟");
+    }
+
+    #[test]
+    fn test_network_config_clamps() {
+        let config = NetworkConfig {
+            qr_poll_interval: 0,
+            cover_download_concurrency: 100,
+            request_timeout: 30,
+            cover_prefetch_rows: 50,
+            cover_cache_size: 0,
+        };
+        assert_eq!(config.qr_poll_interval(), Duration::from_secs(1));
+        assert_eq!(config.cover_download_concurrency(), 16);
+        assert_eq!(config.request_timeout(), Duration::from_secs(30));
+        assert_eq!(config.cover_prefetch_rows(), 10);
+        assert_eq!(config.cover_cache_size(), 12);
+    }
+
+    #[test]
+    fn test_device_id_cookies_round_trip() {
+        let mut creds = Credentials::from_cookie_string(
+            "SESSDATA=s; bili_jct=j; DedeUserID=1; buvid3=b3; buvid4=b4; b_nut=1700000000",
+        )
+        .unwrap();
+        assert_eq!(creds.buvid4.as_deref(), Some("b4"));
+        assert_eq!(
+            creds.cookies(),
+            [
+                ("SESSDATA", "s"),
+                ("bili_jct", "j"),
+                ("DedeUserID", "1"),
+                ("buvid3", "b3"),
+                ("buvid4", "b4"),
+                ("b_nut", "1700000000"),
+            ]
+        );
+
+        // A refresh response carries new login cookies but no device ids
+        let previous = creds.clone();
+        creds.buvid3 = None;
+        creds.buvid4 = None;
+        creds.b_nut = None;
+        creds.inherit_device_ids(&previous);
+        assert_eq!(creds.cookies(), previous.cookies());
+
+        // Credentials saved before the fields existed still load
+        let old: Credentials = serde_json::from_str(
+            r#"{"sessdata":"s","bili_jct":"j","dede_user_id":"1","dede_user_id_ckmd5":null,"refresh_token":null}"#,
+        )
+        .unwrap();
+        assert!(old.buvid3.is_none() && old.b_nut.is_none());
+    }
+
+    #[test]
+    fn test_netscape_cookies_parse_as_cookie_jar() {
+        let mut creds = Credentials::from_cookie_string(
+            "SESSDATA=abc%2C123; bili_jct=jct; DedeUserID=42; DedeUserID__ckMd5=md5; sid=s1",
+        )
+        .unwrap();
+        let content = netscape_cookies(&creds, 1_700_000_000).unwrap();
+
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("# Netscape HTTP Cookie File"));
+        let cookies: Vec<Vec<&str>> = lines.map(|line| line.split('\t').collect()).collect();
+        for fields in &cookies {
+            assert_eq!(fields.len(), 7);
+            assert_eq!(fields[0], ".bilibili.com");
+            assert!(fields[4].parse::<i64>().unwrap() > 1_700_000_000);
+        }
+        let names: Vec<&str> = cookies.iter().map(|fields| fields[5]).collect();
+        assert_eq!(
+            names,
+            [
+                "SESSDATA",
+                "bili_jct",
+                "DedeUserID",
+                "DedeUserID__ckMd5",
+                "sid"
+            ]
+        );
+        assert_eq!(cookies[0][3], "TRUE");
+        assert_eq!(cookies[0][6], "abc%2C123");
+
+        creds.bili_jct = "bad\tvalue".to_string();
+        assert!(netscape_cookies(&creds, 0).is_err());
+    }
+
+    #[test]
+    fn test_truncated_credentials_are_moved_aside() {
+        let dir =
+            std::env::temp_dir().join(format!("bilibili-tui-creds-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let creds = Credentials {
+            sessdata: "abc".to_string(),
+            bili_jct: "jct".to_string(),
+            dede_user_id: "1".to_string(),
+            dede_user_id_ckmd5: None,
+            refresh_token: None,
+            access_token: None,
+            app_refresh_token: None,
+            token_expires_at: None,
+            sid: None,
+            buvid3: None,
+            buvid4: None,
+            b_nut: None,
+        };
+        save_credentials_in(&dir, &creds).unwrap();
+        assert_eq!(load_credentials_in(&dir).unwrap().sessdata, "abc");
+        assert!(!dir.join("credentials.json.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join(CREDENTIALS_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // A save cut short halfway through the JSON
+        let json = fs::read_to_string(dir.join(CREDENTIALS_FILE)).unwrap();
+        fs::write(dir.join(CREDENTIALS_FILE), &json[..json.len() / 2]).unwrap();
+        let err = load_credentials_in(&dir).unwrap_err().to_string();
+        assert!(err.contains("登录信息已损坏"), "{}", err);
+        assert!(!dir.join(CREDENTIALS_FILE).exists());
+        assert!(dir.join("credentials.json.bak").exists());
+
+        // Gone now, so the next start is simply logged out
+        assert!(load_credentials_in(&dir).is_err());
+        fs::write(dir.join(CREDENTIALS_FILE), "").unwrap();
+        assert!(load_credentials_in(&dir).is_err());
+        assert!(!dir.join(CREDENTIALS_FILE).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_cookie_exports_do_not_collide() {
+        let dir = std::env::temp_dir().join(format!("bilibili-tui-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let creds = |sessdata: &str| Credentials {
+            sessdata: sessdata.to_string(),
+            bili_jct: "jct".to_string(),
+            dede_user_id: "1".to_string(),
+            dede_user_id_ckmd5: None,
+            refresh_token: None,
+            access_token: None,
+            app_refresh_token: None,
+            token_expires_at: None,
+            sid: None,
+            buvid3: None,
+            buvid4: None,
+            b_nut: None,
+        };
+
+        let (a, b) = std::thread::scope(|s| {
+            let a = s.spawn(|| export_cookies_in(&dir, &creds("first")).unwrap());
+            let b = s.spawn(|| export_cookies_in(&dir, &creds("second")).unwrap());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_ne!(a.path(), b.path());
+        assert!(fs::read_to_string(a.path())
+            .unwrap()
+            .contains("SESSDATA\tfirst"));
+        assert!(fs::read_to_string(b.path())
+            .unwrap()
+            .contains("SESSDATA\tsecond"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(a.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // Dropping one export leaves the other in place
+        let a_path = a.path().to_path_buf();
+        drop(a);
+        assert!(!a_path.exists());
+        assert!(b.path().exists());
+        drop(b);
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn test_intro_skip_cycles_per_up() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.intro_skip(42), 0);
+        let steps: Vec<i64> = (0..5).map(|_| config.cycle_intro_skip(42)).collect();
+        assert_eq!(steps, [15, 30, 60, 90, 0]);
+        assert!(config.intro_skips.is_empty());
+
+        config.cycle_intro_skip(42);
+        let json = serde_json::to_string(&config).unwrap();
+        let config: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.intro_skip(42), 15);
+        assert_eq!(config.intro_skip(7), 0);
+    }
+
+    #[test]
+    fn test_download_config_defaults() {
+        let config = DownloadConfig {
+            dir: "  ".to_string(),
+            format: String::new(),
+        };
+        assert!(config.dir().ends_with("bilibili-tui"));
+        assert_eq!(config.format(), DownloadConfig::DEFAULT_FORMAT);
+
+        let config = DownloadConfig {
+            dir: "/srv/videos".to_string(),
+            format: "bv*[height<=720]+ba".to_string(),
+        };
+        assert_eq!(config.dir(), PathBuf::from("/srv/videos"));
+        assert_eq!(config.format(), "bv*[height<=720]+ba");
+    }
+
+    #[test]
+    fn test_app_token_expiring() {
+        let mut creds = Credentials::from_cookies(
+            &[
+                ("SESSDATA".to_string(), "s".to_string()),
+                ("bili_jct".to_string(), "j".to_string()),
+                ("DedeUserID".to_string(), "1".to_string()),
+            ],
+            None,
+        )
+        .unwrap();
+        assert!(!creds.app_token_expiring(0));
+
+        let day = 24 * 60 * 60;
+        creds.token_expires_at = Some(30 * day);
+        assert!(!creds.app_token_expiring(0));
+        assert!(creds.app_token_expiring(25 * day));
+    }
+
+    #[test]
+    fn test_watched_videos_evicts_oldest() {
+        let mut watched = WatchedVideos::default();
+        for i in 0..=WatchedVideos::MAX_ENTRIES {
+            watched.insert(&format!("BV{}", i));
+        }
+        assert_eq!(watched.len(), WatchedVideos::MAX_ENTRIES);
+        assert!(!watched.contains("BV0"));
+        assert!(watched.contains("BV1"));
+        assert!(!watched.insert("BV1"));
+    }
+
+    #[test]
+    fn test_watched_videos_roundtrip_rebuilds_lookup() {
+        let mut watched = WatchedVideos::default();
+        watched.insert("BV17x411w7KC");
+        let json = serde_json::to_string(&watched).unwrap();
+        let mut loaded: WatchedVideos = serde_json::from_str(&json).unwrap();
+        loaded.rebuild_lookup();
+        assert!(loaded.contains("BV17x411w7KC"));
+    }
 }
@@ -0,0 +1,420 @@
+//! 追番/追剧 page listing the followed series in a cover grid
+
+use super::help::{self, KeyHint};
+use super::video_card::{VideoCard, VideoCardGrid};
+use super::{Component, Theme};
+use crate::api::bangumi::{BangumiFollowItem, FollowType};
+use crate::api::BiliApi;
+use crate::app::AppAction;
+use crate::storage::{CoverFit, Keybindings};
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+use std::time::Instant;
+
+/// Follow list requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BangumiRequest {
+    /// First page of the list
+    List,
+    /// Next page of the list
+    More,
+}
+
+pub struct BangumiPage {
+    /// Account whose follow list is shown
+    pub mid: i64,
+    pub follow_type: FollowType,
+    /// Series behind the cards, in the same order
+    items: Vec<BangumiFollowItem>,
+    pub grid: VideoCardGrid,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub error_message: Option<String>,
+    /// Request behind the error being shown, re-issued by the retry key
+    failed: Option<BangumiRequest>,
+    pub page: i32,
+    pub total: i32,
+    last_click_time: Option<Instant>,
+    last_click_index: Option<usize>,
+}
+
+impl BangumiPage {
+    pub fn new(mid: i64) -> Self {
+        Self {
+            mid,
+            follow_type: FollowType::default(),
+            items: Vec::new(),
+            grid: VideoCardGrid::new(),
+            loading: true,
+            loading_more: false,
+            error_message: None,
+            failed: None,
+            page: 1,
+            total: 0,
+            last_click_time: None,
+            last_click_index: None,
+        }
+    }
+
+    /// Load the first page of the current follow list
+    pub async fn load(&mut self, api_client: &impl BiliApi) {
+        self.loading = true;
+        self.error_message = None;
+        self.failed = None;
+        self.items.clear();
+        self.grid.clear();
+        match api_client
+            .get_bangumi_follow(self.mid, self.follow_type, 1)
+            .await
+        {
+            Ok(data) => {
+                self.page = 1;
+                self.total = data.total();
+                self.add_items(data.items());
+            }
+            Err(e) => {
+                self.failed = Some(BangumiRequest::List);
+                self.error_message =
+                    Some(format!("加载{}列表失败: {}", self.follow_type.label(), e));
+            }
+        }
+        self.loading = false;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if self.loading || self.loading_more || !self.has_more() {
+            return;
+        }
+        self.loading_more = true;
+        match api_client
+            .get_bangumi_follow(self.mid, self.follow_type, self.page + 1)
+            .await
+        {
+            Ok(data) => {
+                self.failed = None;
+                let items = data.items();
+                if items.is_empty() {
+                    // Trust the empty page over a stale count
+                    self.total = self.items.len() as i32;
+                } else {
+                    self.page += 1;
+                    self.add_items(items);
+                }
+            }
+            Err(_) => self.failed = Some(BangumiRequest::More),
+        }
+        self.loading_more = false;
+    }
+
+    /// Switch between the 追番 and 追剧 lists, the caller reloads
+    pub fn toggle_follow_type(&mut self) {
+        self.follow_type = self.follow_type.toggled();
+    }
+
+    fn add_items(&mut self, items: Vec<BangumiFollowItem>) {
+        for item in items {
+            let status = match item.badge.as_deref() {
+                Some(badge) if !badge.is_empty() => badge.to_string(),
+                _ if item.is_finish == Some(1) => "已完结".to_string(),
+                _ => "连载中".to_string(),
+            };
+            let card = VideoCard::new(
+                None,
+                None,
+                item.display_title().to_string(),
+                item.latest_label(),
+                item.progress_label().to_string(),
+                status,
+                item.cover_url(),
+            );
+            self.grid.add_card(card);
+            self.items.push(item);
+        }
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<BangumiRequest> {
+        self.failed.take()
+    }
+
+    pub fn has_more(&self) -> bool {
+        (self.items.len() as i32) < self.total
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+    }
+
+    /// Play the newest episode of the series at `idx`
+    fn play_selected(&self, idx: usize) -> Option<AppAction> {
+        let item = self.items.get(idx)?;
+        Some(AppAction::PlayEpisode {
+            url: item.latest_episode_url()?,
+            label: item.playing_label(),
+            aid: 0,
+            cid: 0,
+            duration: 0,
+            confirmed: false,
+        })
+    }
+
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let mut hints = vec![
+            help::navigation(keys),
+            KeyHint::new(&keys.confirm, "播放最新"),
+            KeyHint::pair(&keys.section_prev, &keys.section_next, "追番/追剧"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            help::page_switch(keys),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+
+    fn grid_area(area: Rect) -> Rect {
+        Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        }
+    }
+}
+
+impl Component for BangumiPage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(10),   // Series grid
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        let title = format!(" 🎬 我的{} ", self.follow_type.label());
+        let plain_block = || {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border_unfocused))
+                .title(Span::styled(
+                    title.clone(),
+                    Style::default().fg(theme.bilibili_pink),
+                ))
+        };
+
+        if self.loading {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(loading, chunks[0]);
+        } else if let Some(error) = &self.error_message {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(error_widget, chunks[0]);
+        } else if self.grid.cards.is_empty() {
+            let empty = Paragraph::new(format!("还没有{}", self.follow_type.label()))
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(empty, chunks[0]);
+        } else {
+            let header = Paragraph::new(Line::from(vec![
+                Span::styled(title, Style::default().fg(theme.bilibili_pink)),
+                Span::styled(
+                    format!("({}/{})", self.items.len(), self.total),
+                    Style::default().fg(theme.fg_muted),
+                ),
+                if self.loading_more {
+                    Span::styled(" 加载中...", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("")
+                },
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.border_subtle)),
+            );
+            let header_area = Rect {
+                height: 2,
+                ..chunks[0]
+            };
+            frame.render_widget(header, header_area);
+            self.grid.render(frame, Self::grid_area(chunks[0]), theme);
+        }
+
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) {
+            return Some(AppAction::Quit);
+        }
+        if keys.matches_nav_next(key) {
+            return Some(AppAction::NavNext);
+        }
+        if keys.matches_nav_prev(key) {
+            return Some(AppAction::NavPrev);
+        }
+        if keys.matches_down(key) {
+            self.grid.move_down();
+            if self.grid.is_near_bottom(3) && !self.loading_more && self.has_more() {
+                return Some(AppAction::LoadMoreBangumi);
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_up(key) {
+            self.grid.move_up();
+            return Some(AppAction::None);
+        }
+        if keys.matches_right(key) {
+            self.grid.move_right();
+            return Some(AppAction::None);
+        }
+        if keys.matches_left(key) {
+            self.grid.move_left();
+            return Some(AppAction::None);
+        }
+        if keys.matches_confirm(key) {
+            return Some(
+                self.play_selected(self.grid.selected_index)
+                    .unwrap_or(AppAction::None),
+            );
+        }
+        if keys.matches_section_prev(key) || keys.matches_section_next(key) {
+            return Some(AppAction::ToggleBangumiType);
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::RefreshBangumi);
+        }
+        Some(AppAction::None)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> Option<AppAction> {
+        match event.kind {
+            MouseEventKind::ScrollDown => {
+                if self.grid.move_down()
+                    && self.grid.is_near_bottom(3)
+                    && !self.loading_more
+                    && self.has_more()
+                {
+                    return Some(AppAction::LoadMoreBangumi);
+                }
+                None
+            }
+            MouseEventKind::ScrollUp => {
+                self.grid.move_up();
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let grid_area = Self::grid_area(area);
+                if !grid_area.contains(Position::new(event.column, event.row)) {
+                    return None;
+                }
+
+                let relative_y = event.row - grid_area.y;
+                let click_row = (relative_y / self.grid.card_height) as usize;
+                let actual_row = self.grid.scroll_row + click_row;
+                let card_width = (grid_area.width / self.grid.columns as u16).max(1);
+                let click_col = (event.column.saturating_sub(grid_area.x) / card_width) as usize;
+                let click_idx = actual_row * self.grid.columns + click_col;
+                if click_idx >= self.grid.cards.len() {
+                    return None;
+                }
+
+                let now = Instant::now();
+                let is_double_click = self.last_click_index == Some(click_idx)
+                    && self
+                        .last_click_time
+                        .is_some_and(|t| now.duration_since(t).as_millis() < 500);
+                if is_double_click {
+                    self.last_click_time = None;
+                    self.last_click_index = None;
+                    return self.play_selected(click_idx);
+                }
+                self.grid.selected_index = click_idx;
+                self.grid.update_scroll(self.grid.cached_visible_rows);
+                self.last_click_time = Some(now);
+                self.last_click_index = Some(click_idx);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_load_shows_series_with_latest_episode() {
+        let mut page = BangumiPage::new(10001);
+        page.load(&MockApi::new()).await;
+
+        assert_eq!(page.grid.cards.len(), 2);
+        assert!(!page.has_more());
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "我的追番"));
+        assert!(contains(&buffer, "(2/2)"));
+        assert!(contains(&buffer, "更新至第12话"));
+
+        let keys = Keybindings::default();
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::PlayEpisode { url, label, .. })
+                if url == "https://www.bilibili.com/bangumi/play/ep733001"
+                    && label == "示例番剧 第12话"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_error_offers_retry() {
+        let mut page = BangumiPage::new(10001);
+        page.toggle_follow_type();
+        page.load(&MockApi::failing()).await;
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 加载追剧列表失败"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &Keybindings::default()),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(BangumiRequest::List));
+    }
+}
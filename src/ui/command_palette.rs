@@ -0,0 +1,486 @@
+//! Command palette listing the app's actions by name, filtered as you type
+
+use super::help;
+use super::util::centered_fixed;
+use super::{Component, NavItem, Page, Theme};
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use crate::util::truncate_display;
+use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
+use unicode_width::UnicodeWidthStr;
+
+/// Binding a palette command presses
+type Binding = fn(&Keybindings) -> &String;
+/// Pages whose handler gives the bound key the command's meaning
+type Applies = fn(&Page) -> bool;
+
+/// Key-bound actions the palette offers: English name, label, the binding it presses and
+/// the pages it is offered on
+///
+/// Running one feeds the bound key to the app, following any rebinding. Keys mean
+/// different things on different pages (`v` is the view mode on home and the quality on a
+/// video), so a command is only listed where its key does what the label says.
+const KEY_COMMANDS: &[(&str, &str, Binding, Applies)] = &[
+    (
+        "play",
+        "播放",
+        |k| &k.play,
+        |p| matches!(p, Page::Home(_) | Page::VideoDetail(_)),
+    ),
+    (
+        "search",
+        "搜索",
+        |k| &k.search_focus,
+        |p| matches!(p, Page::Search(_) | Page::Dynamic(_)),
+    ),
+    (
+        "refresh",
+        "刷新",
+        |k| &k.refresh,
+        |p| {
+            matches!(
+                p,
+                Page::Home(_)
+                    | Page::Dynamic(_)
+                    | Page::Bangumi(_)
+                    | Page::Favorites(_)
+                    | Page::UserSpace(_)
+                    | Page::Tag(_)
+                    | Page::Comments(_)
+                    | Page::Profile(_)
+            )
+        },
+    ),
+    (
+        "retry",
+        "重试",
+        |k| &k.retry,
+        |p| {
+            matches!(
+                p,
+                Page::Home(_)
+                    | Page::Search(_)
+                    | Page::Dynamic(_)
+                    | Page::VideoDetail(_)
+                    | Page::Bangumi(_)
+                    | Page::Favorites(_)
+                    | Page::UserSpace(_)
+                    | Page::Tag(_)
+                    | Page::Comments(_)
+            )
+        },
+    ),
+    (
+        "download",
+        "下载",
+        |k| &k.download,
+        |p| {
+            matches!(
+                p,
+                Page::Home(_)
+                    | Page::Search(_)
+                    | Page::Dynamic(_)
+                    | Page::VideoDetail(_)
+                    | Page::History(_)
+                    | Page::Favorites(_)
+                    | Page::UserSpace(_)
+                    | Page::Tag(_)
+            )
+        },
+    ),
+    ("open by id", "打开链接", |k| &k.open_by_id, |_| true),
+    ("recent", "最近播放", |k| &k.recent_played, |_| true),
+    ("copy link", "复制时间链接", |k| &k.copy_link, |_| true),
+    ("theme", "切换主题", |k| &k.next_theme, |_| true),
+    (
+        "comments",
+        "查看评论",
+        |k| &k.open_comments,
+        |p| matches!(p, Page::Home(_) | Page::Search(_) | Page::Dynamic(_)),
+    ),
+    (
+        "quality",
+        "切换清晰度",
+        |k| &k.cycle_quality,
+        |p| matches!(p, Page::VideoDetail(_)),
+    ),
+    (
+        "skip intro",
+        "跳过片头",
+        |k| &k.skip_intro,
+        |p| matches!(p, Page::VideoDetail(_)),
+    ),
+    (
+        "hide watched",
+        "隐藏已看",
+        |k| &k.toggle_hide_watched,
+        |p| matches!(p, Page::Home(_) | Page::Search(_)),
+    ),
+    (
+        "view mode",
+        "网格/列表",
+        |k| &k.toggle_view_mode,
+        |p| matches!(p, Page::Home(_)),
+    ),
+    (
+        "up space",
+        "UP主投稿",
+        |k| &k.open_up_space,
+        |p| matches!(p, Page::Home(_) | Page::Dynamic(_) | Page::VideoDetail(_)),
+    ),
+    (
+        "hot words",
+        "热搜",
+        |k| &k.hot_words,
+        |p| matches!(p, Page::Search(_)),
+    ),
+    (
+        "filter",
+        "筛选结果",
+        |k| &k.filter_results,
+        |p| matches!(p, Page::Search(_)),
+    ),
+    (
+        "comment sort",
+        "评论排序",
+        |k| &k.cycle_comment_sort,
+        |p| matches!(p, Page::VideoDetail(_) | Page::Comments(_)),
+    ),
+];
+
+/// One entry of the palette
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    /// English name, matched as well as the label
+    pub name: String,
+    pub label: String,
+    /// Key that runs the same action outside the palette
+    pub key: Option<String>,
+    pub action: AppAction,
+}
+
+/// Everything the palette can run on `page`, with the keys currently bound to it
+pub fn commands(keys: &Keybindings, page: &Page) -> Vec<PaletteCommand> {
+    let mut commands: Vec<PaletteCommand> = KEY_COMMANDS
+        .iter()
+        .filter(|(.., applies)| applies(page))
+        .filter_map(|(name, label, binding, _)| {
+            let key = binding(keys);
+            Some(PaletteCommand {
+                name: name.to_string(),
+                label: label.to_string(),
+                key: Some(key.clone()),
+                action: AppAction::RunKey(Keybindings::parse_keycode(key)?),
+            })
+        })
+        .collect();
+    for &item in NavItem::all() {
+        commands.push(PaletteCommand {
+            name: item.to_string(),
+            label: format!("前往 {}", item.label()),
+            key: None,
+            action: AppAction::SwitchSection(item),
+        });
+    }
+    commands.extend([
+        PaletteCommand {
+            name: "refresh login".to_string(),
+            label: "刷新登录状态".to_string(),
+            key: None,
+            action: AppAction::RefreshCredentials,
+        },
+        PaletteCommand {
+            name: "validate login".to_string(),
+            label: "验证登录".to_string(),
+            key: None,
+            action: AppAction::ValidateLogin,
+        },
+        PaletteCommand {
+            name: "logout".to_string(),
+            label: "退出登录".to_string(),
+            key: None,
+            action: AppAction::Logout,
+        },
+        PaletteCommand {
+            name: "quit".to_string(),
+            label: "退出".to_string(),
+            key: Some(keys.quit.clone()),
+            action: AppAction::Quit,
+        },
+    ]);
+    commands
+}
+
+/// How well `query` matches `text` as an in-order subsequence, `None` when it does not
+///
+/// Case is ignored. Runs of adjacent characters and a match at the very start score
+/// higher, so "dl" ranks "download" above "cycle dynamic filter".
+pub fn fuzzy_score(text: &str, query: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = from + text[from..].iter().position(|&c| c == q)?;
+        score += 1;
+        if found == 0 {
+            score += 3;
+        }
+        if last.is_some_and(|last| last + 1 == found) {
+            score += 4;
+        }
+        last = Some(found);
+        from = found + 1;
+    }
+    Some(score)
+}
+
+pub struct CommandPalette {
+    commands: Vec<PaletteCommand>,
+    query: String,
+    /// Indices into `commands` matching the query, best first
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Palette over the commands that apply to `page`
+    pub fn new(keys: &Keybindings, page: &Page) -> Self {
+        let mut palette = Self {
+            commands: commands(keys, page),
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        };
+        palette.filter();
+        palette
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, command)| {
+                let score = fuzzy_score(&command.label, &self.query)
+                    .max(fuzzy_score(&command.name, &self.query))?;
+                Some((score, idx))
+            })
+            .collect();
+        // Stable, so equal scores keep the list order
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(_, idx)| idx).collect();
+        self.selected = 0;
+    }
+
+    fn selected_command(&self) -> Option<&PaletteCommand> {
+        self.commands.get(*self.matches.get(self.selected)?)
+    }
+}
+
+impl Component for CommandPalette {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, _keys: &Keybindings) {
+        let width = area.width.saturating_sub(4).min(56);
+        // Borders, the query line, the matches and the hint line
+        let height = (self.matches.len().max(1) as u16 + 4).min(area.height.saturating_sub(2));
+        let popup = centered_fixed(width, height, area);
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.bilibili_pink))
+            .style(Style::default().bg(theme.bg_modal))
+            .title(Span::styled(
+                " ⌘ 命令 ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+        if inner.height < 3 {
+            return;
+        }
+
+        let query = Line::from(vec![
+            Span::styled(": ", Style::default().fg(theme.bilibili_pink)),
+            Span::styled(self.query.as_str(), Style::default().fg(theme.fg_primary)),
+            Span::styled("▏", Style::default().fg(theme.fg_muted)),
+        ]);
+        frame.render_widget(Paragraph::new(query), Rect { height: 1, ..inner });
+
+        let list_area = Rect {
+            y: inner.y + 1,
+            height: inner.height - 2,
+            ..inner
+        };
+        if self.matches.is_empty() {
+            frame.render_widget(
+                Paragraph::new("没有匹配的命令").style(Style::default().fg(theme.fg_muted)),
+                list_area,
+            );
+        } else {
+            // Highlight symbol on the left, key label right-aligned
+            let cols = list_area.width.saturating_sub(2) as usize;
+            let items: Vec<ListItem> = self
+                .matches
+                .iter()
+                .map(|&idx| {
+                    let command = &self.commands[idx];
+                    let key = command
+                        .key
+                        .as_deref()
+                        .map(help::key_label)
+                        .unwrap_or_default();
+                    let label =
+                        truncate_display(&command.label, cols.saturating_sub(key.width() + 1));
+                    let pad = cols.saturating_sub(label.width() + key.width());
+                    ListItem::new(Line::from(vec![
+                        Span::styled(label, Style::default().fg(theme.fg_primary)),
+                        Span::raw(" ".repeat(pad)),
+                        Span::styled(key, Style::default().fg(theme.fg_muted)),
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).highlight_symbol("▶ ").highlight_style(
+                Style::default()
+                    .bg(theme.selection_bg)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, list_area, &mut state);
+        }
+
+        let hint_area = Rect {
+            y: inner.bottom() - 1,
+            height: 1,
+            ..inner
+        };
+        frame.render_widget(
+            Paragraph::new("↑↓ 选择  Enter 执行  Esc 关闭")
+                .style(Style::default().fg(theme.fg_muted))
+                .alignment(Alignment::Center),
+            hint_area,
+        );
+    }
+
+    fn handle_input(&mut self, key: KeyCode, _keys: &Keybindings) -> Option<AppAction> {
+        // Every printable key goes into the query, so only arrows and Tab move
+        match key {
+            KeyCode::Esc => return Some(AppAction::CloseCommandPalette),
+            KeyCode::Enter => {
+                return Some(
+                    self.selected_command()
+                        .map_or(AppAction::None, |command| command.action.clone()),
+                );
+            }
+            KeyCode::Down | KeyCode::Tab if self.selected + 1 < self.matches.len() => {
+                self.selected += 1
+            }
+            KeyCode::Up | KeyCode::BackTab => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.filter();
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.filter();
+            }
+            _ => {}
+        }
+        Some(AppAction::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+    use crate::ui::{HomePage, SearchPage};
+
+    fn type_query(palette: &mut CommandPalette, query: &str) {
+        for c in query.chars() {
+            palette.handle_input(KeyCode::Char(c), &Keybindings::default());
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("download", "dl").is_some());
+        assert!(fuzzy_score("download", "zd").is_none());
+        assert!(fuzzy_score("切换主题", "主题").is_some());
+        assert!(fuzzy_score("download", "dow") > fuzzy_score("cycle dynamic filter", "d"));
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+    }
+
+    #[test]
+    fn test_typing_filters_and_enter_runs_the_key() {
+        let keys = Keybindings::default();
+        let mut palette = CommandPalette::new(&keys, &Page::Home(HomePage::new()));
+        let buffer = render(&mut palette);
+        assert!(contains(&buffer, "命令"));
+        assert!(contains(&buffer, "播放"));
+
+        type_query(&mut palette, "downl");
+        assert_eq!(palette.selected_command().unwrap().label, "下载");
+        assert!(contains(&render(&mut palette), ": downl"));
+        assert!(matches!(
+            palette.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::RunKey(KeyCode::Char('D')))
+        ));
+    }
+
+    #[test]
+    fn test_chinese_label_and_sections_match() {
+        let keys = Keybindings::default();
+        let page = Page::Home(HomePage::new());
+        let mut palette = CommandPalette::new(&keys, &page);
+        type_query(&mut palette, "历史");
+        assert!(matches!(
+            palette.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::SwitchSection(NavItem::History))
+        ));
+
+        let mut palette = CommandPalette::new(&keys, &page);
+        type_query(&mut palette, "logout");
+        assert!(matches!(
+            palette.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::Logout)
+        ));
+        type_query(&mut palette, "zzz");
+        assert!(contains(&render(&mut palette), "没有匹配的命令"));
+        assert!(matches!(
+            palette.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+        assert!(matches!(
+            palette.handle_input(KeyCode::Esc, &keys),
+            Some(AppAction::CloseCommandPalette)
+        ));
+    }
+
+    #[test]
+    fn test_only_commands_for_the_current_page_are_listed() {
+        let keys = Keybindings::default();
+        let labels = |page: &Page| -> Vec<String> {
+            commands(&keys, page)
+                .into_iter()
+                .map(|command| command.label)
+                .collect()
+        };
+
+        // `v` and `f` mean the view mode and the fresh type on home
+        let home = labels(&Page::Home(HomePage::new()));
+        assert!(home.contains(&"网格/列表".to_string()));
+        assert!(!home.contains(&"切换清晰度".to_string()));
+        assert!(!home.contains(&"筛选结果".to_string()));
+
+        let search = labels(&Page::Search(SearchPage::new()));
+        assert!(search.contains(&"筛选结果".to_string()));
+        assert!(!search.contains(&"网格/列表".to_string()));
+        // Global keys are offered everywhere
+        assert!(home.contains(&"切换主题".to_string()));
+        assert!(search.contains(&"切换主题".to_string()));
+    }
+}
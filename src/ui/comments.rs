@@ -0,0 +1,343 @@
+//! Comment list of a video or dynamic, opened straight from a list without its detail page
+
+use super::help::{self, KeyHint};
+use super::{Component, Theme};
+use crate::api::comment::{CommentItem, CommentSort};
+use crate::api::BiliApi;
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use crate::util::truncate_display;
+use ratatui::{
+    crossterm::event::{KeyCode, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+
+/// Comment requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentsRequest {
+    /// First page in the current sort
+    List,
+    /// Next page
+    More,
+}
+
+pub struct CommentsPage {
+    /// Comment area id, the aid for videos
+    pub oid: i64,
+    /// Reply API `type`, 1 for videos
+    pub comment_type: i32,
+    /// What the comments belong to, shown in the header
+    pub title: String,
+    pub comments: Vec<CommentItem>,
+    pub selected: usize,
+    pub sort: CommentSort,
+    pub page: i32,
+    pub total: i32,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub error_message: Option<String>,
+    /// Request behind the error being shown, re-issued by the retry key
+    failed: Option<CommentsRequest>,
+}
+
+impl CommentsPage {
+    /// Load more once the cursor is this close to the end
+    const PREFETCH_DISTANCE: usize = 3;
+
+    pub fn new(oid: i64, comment_type: i32, title: String) -> Self {
+        Self {
+            oid,
+            comment_type,
+            title,
+            comments: Vec::new(),
+            selected: 0,
+            sort: CommentSort::default(),
+            page: 1,
+            total: 0,
+            loading: true,
+            loading_more: false,
+            error_message: None,
+            failed: None,
+        }
+    }
+
+    /// Load the first page in the current sort
+    pub async fn load(&mut self, api_client: &impl BiliApi) {
+        self.loading = true;
+        self.error_message = None;
+        self.failed = None;
+        match api_client
+            .get_comments_by_type(self.oid, self.comment_type, 1, self.sort)
+            .await
+        {
+            Ok(data) => {
+                let total = data.page.as_ref().and_then(|page| page.count);
+                self.comments = data.into_comments();
+                self.page = 1;
+                self.total = total.unwrap_or(self.comments.len() as i32);
+                self.selected = 0;
+            }
+            Err(e) => {
+                self.failed = Some(CommentsRequest::List);
+                self.error_message = Some(format!("加载评论失败: {}", e));
+            }
+        }
+        self.loading = false;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if self.loading || self.loading_more || !self.has_more() {
+            return;
+        }
+        self.loading_more = true;
+        match api_client
+            .get_comments_by_type(self.oid, self.comment_type, self.page + 1, self.sort)
+            .await
+        {
+            Ok(data) => {
+                self.failed = None;
+                let comments = data.replies.unwrap_or_default();
+                if comments.is_empty() {
+                    // Trust the empty page over a stale count
+                    self.total = self.comments.len() as i32;
+                } else {
+                    self.page += 1;
+                    self.comments.extend(comments);
+                }
+            }
+            Err(_) => self.failed = Some(CommentsRequest::More),
+        }
+        self.loading_more = false;
+    }
+
+    /// Switch between 热门 and 最新, the caller reloads the list
+    pub fn toggle_sort(&mut self) {
+        self.sort = self.sort.toggle();
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<CommentsRequest> {
+        self.failed.take()
+    }
+
+    pub fn has_more(&self) -> bool {
+        (self.comments.len() as i32) < self.total
+    }
+
+    fn move_down(&mut self) -> Option<AppAction> {
+        if self.selected + 1 < self.comments.len() {
+            self.selected += 1;
+        }
+        let near_end = self.selected + Self::PREFETCH_DISTANCE >= self.comments.len();
+        if near_end && !self.loading_more && self.has_more() {
+            return Some(AppAction::LoadMoreComments);
+        }
+        Some(AppAction::None)
+    }
+
+    fn comment_item<'a>(comment: &'a CommentItem, width: usize, theme: &Theme) -> ListItem<'a> {
+        let mut header = Vec::new();
+        if comment.pinned {
+            header.push(Span::styled(
+                "置顶 ",
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        header.push(Span::styled(
+            comment.author_name(),
+            Style::default().fg(theme.bilibili_pink),
+        ));
+        header.push(Span::styled(
+            format!("  {}", comment.format_time()),
+            Style::default().fg(theme.fg_secondary),
+        ));
+        ListItem::new(vec![
+            Line::from(header),
+            Line::from(Span::styled(
+                truncate_display(comment.message(), width),
+                Style::default().fg(theme.fg_primary),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "👍 {}  💬 {} 回复",
+                    comment.format_like(),
+                    comment.reply_count()
+                ),
+                Style::default().fg(theme.fg_secondary),
+            )),
+            Line::from(""),
+        ])
+    }
+
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let mut hints = vec![
+            help::scrolling(keys),
+            KeyHint::new(&keys.cycle_comment_sort, "排序"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            KeyHint::new(&keys.back, "返回"),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+}
+
+impl Component for CommentsPage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(2)])
+            .split(area);
+
+        let mut title = vec![
+            Span::styled(
+                format!(" 💬 {} ", truncate_display(&self.title, 40)),
+                Style::default().fg(theme.bilibili_pink),
+            ),
+            Span::styled(
+                format!("· {} ", self.sort.label()),
+                Style::default().fg(theme.fg_accent),
+            ),
+        ];
+        if !self.loading && self.error_message.is_none() {
+            title.push(Span::styled(
+                format!("({}/{}) ", self.comments.len(), self.total),
+                Style::default().fg(theme.fg_muted),
+            ));
+        }
+        if self.loading_more {
+            title.push(Span::styled(
+                "加载中... ",
+                Style::default().fg(theme.warning),
+            ));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_focused))
+            .title(Line::from(title));
+        let inner = block.inner(chunks[0]);
+        frame.render_widget(block, chunks[0]);
+
+        if self.loading {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center);
+            frame.render_widget(loading, inner);
+        } else if let Some(error) = &self.error_message {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center);
+            frame.render_widget(error_widget, inner);
+        } else if self.comments.is_empty() {
+            let empty = Paragraph::new("还没有评论")
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, inner);
+        } else {
+            let width = inner.width.saturating_sub(2) as usize;
+            let items: Vec<ListItem> = self
+                .comments
+                .iter()
+                .map(|comment| Self::comment_item(comment, width, theme))
+                .collect();
+            let list = List::new(items)
+                .highlight_symbol("▎")
+                .highlight_style(Style::default().bg(theme.selection_bg));
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, inner, &mut state);
+        }
+
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) || keys.matches_back(key) {
+            return Some(AppAction::BackToList);
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+        if keys.matches_down(key) {
+            return self.move_down();
+        }
+        if keys.matches_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+            return Some(AppAction::None);
+        }
+        if keys.matches_cycle_comment_sort(key) && !self.loading {
+            return Some(AppAction::CycleCommentSort);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::RefreshComments);
+        }
+        Some(AppAction::None)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, _area: Rect) -> Option<AppAction> {
+        match event.kind {
+            MouseEventKind::ScrollDown => self.move_down(),
+            MouseEventKind::ScrollUp => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_load_and_paginate_comments() {
+        let api = MockApi::new();
+        let mut page = CommentsPage::new(170001, 1, "示例视频".to_string());
+        page.load(&api).await;
+        // Two replies plus the pinned comment
+        assert_eq!(page.comments.len(), 3);
+        assert!(page.comments[0].pinned);
+        let screen = render(&mut page);
+        assert!(contains(&screen, "示例视频"));
+        assert!(contains(&screen, "置顶"));
+
+        let keys = Keybindings::default();
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('j'), &keys),
+            Some(AppAction::LoadMoreComments)
+        ));
+        page.load_more(&api).await;
+        assert_eq!(page.page, 2);
+        assert_eq!(page.comments.len(), 5);
+        assert!(!page.has_more());
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('o'), &keys),
+            Some(AppAction::CycleCommentSort)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_load_offers_retry() {
+        let mut page = CommentsPage::new(1, 17, "动态".to_string());
+        page.load(&MockApi::failing()).await;
+        assert!(contains(&render(&mut page), "按 e 重试"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &Keybindings::default()),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(CommentsRequest::List));
+    }
+}
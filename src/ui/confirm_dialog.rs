@@ -0,0 +1,191 @@
+//! Yes/no modal that runs an action only after the user agrees
+
+use super::help;
+use super::util::centered_fixed;
+use super::{Component, Theme};
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
+
+pub struct ConfirmDialog {
+    title: String,
+    lines: Vec<String>,
+    on_confirm: AppAction,
+    /// Only the play key confirms, so a stray Enter cannot start playback
+    play_key_only: bool,
+    /// Label and action of `n` when declining still does something, Esc then only cancels
+    on_decline: Option<(String, AppAction)>,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: impl Into<String>, lines: Vec<String>, on_confirm: AppAction) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+            on_confirm,
+            play_key_only: false,
+            on_decline: None,
+        }
+    }
+
+    /// Run `action` on `n` instead of just closing, e.g. "play from the start"
+    pub fn or_else(mut self, label: impl Into<String>, action: AppAction) -> Self {
+        self.on_decline = Some((label.into(), action));
+        self
+    }
+
+    /// Confirm with the play key instead of y/Enter
+    pub fn play_key_only(mut self) -> Self {
+        self.play_key_only = true;
+        self
+    }
+}
+
+impl Component for ConfirmDialog {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let width = area.width.saturating_sub(4).min(60);
+        // Borders, message lines, a spacer and the key hint
+        let height = self.lines.len() as u16 + 4;
+        let popup = centered_fixed(width, height, area);
+
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.warning))
+            .style(Style::default().bg(theme.bg_modal))
+            .title(Span::styled(
+                format!(" ⚠ {} ", self.title),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+        let mut lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| {
+                Line::from(Span::styled(
+                    line.as_str(),
+                    Style::default().fg(theme.fg_primary),
+                ))
+            })
+            .collect();
+        lines.push(Line::from(""));
+        let confirm = if self.play_key_only {
+            format!("[{}] 播放", help::key_label(&keys.play))
+        } else {
+            "[y/Enter] 确认".to_string()
+        };
+        let hint = match &self.on_decline {
+            Some((label, _)) => format!("{}  [n] {}  [Esc] 取消", confirm, label),
+            None => format!("{}  [n/Esc] 取消", confirm),
+        };
+        lines.push(Line::from(Span::styled(
+            hint,
+            Style::default().fg(theme.fg_muted),
+        )));
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, popup);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        let confirmed = if self.play_key_only {
+            keys.matches_play(key)
+        } else {
+            key == KeyCode::Char('y') || keys.matches_confirm(key)
+        };
+        if confirmed {
+            return Some(self.on_confirm.clone());
+        }
+        if let (KeyCode::Char('n'), Some((_, action))) = (key, &self.on_decline) {
+            return Some(action.clone());
+        }
+        if key == KeyCode::Char('n') || key == KeyCode::Esc || keys.matches_back(key) {
+            return Some(AppAction::CloseConfirm);
+        }
+        // Swallow everything else so keys never reach the page underneath
+        Some(AppAction::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    #[test]
+    fn test_confirm_and_cancel() {
+        let keys = Keybindings::default();
+        let mut dialog = ConfirmDialog::new(
+            "确认退出",
+            vec!["正在播放 BV1xx411c7mD".to_string()],
+            AppAction::ConfirmQuit,
+        );
+        assert!(contains(&render(&mut dialog), "正在播放 BV1xx411c7mD"));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Char('y'), &keys),
+            Some(AppAction::ConfirmQuit)
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::ConfirmQuit)
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Esc, &keys),
+            Some(AppAction::CloseConfirm)
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Char('q'), &keys),
+            Some(AppAction::None)
+        ));
+    }
+
+    #[test]
+    fn test_play_key_only_ignores_enter() {
+        let keys = Keybindings::default();
+        let mut dialog = ConfirmDialog::new(
+            "播放",
+            vec!["示例番剧 第12话".to_string()],
+            AppAction::ConfirmQuit,
+        )
+        .play_key_only();
+        assert!(contains(&render(&mut dialog), "[p] 播放"));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Char('p'), &keys),
+            Some(AppAction::ConfirmQuit)
+        ));
+    }
+
+    #[test]
+    fn test_decline_action_plays_from_start() {
+        let keys = Keybindings::default();
+        let mut dialog = ConfirmDialog::new(
+            "继续播放",
+            vec!["上次播放到 12:34".to_string()],
+            AppAction::ResumePlayback { from_start: false },
+        )
+        .or_else("从头播放", AppAction::ResumePlayback { from_start: true });
+        let buffer = render(&mut dialog);
+        assert!(contains(&buffer, "上次播放到 12:34"));
+        assert!(contains(&buffer, "[n] 从头播放  [Esc] 取消"));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::ResumePlayback { from_start: false })
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Char('n'), &keys),
+            Some(AppAction::ResumePlayback { from_start: true })
+        ));
+        assert!(matches!(
+            dialog.handle_input(KeyCode::Esc, &keys),
+            Some(AppAction::CloseConfirm)
+        ));
+    }
+}
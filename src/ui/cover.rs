@@ -0,0 +1,461 @@
+//! Cover image preprocessing before handing images to the terminal protocol, and the
+//! placeholders drawn until they arrive
+
+use super::Theme;
+use crate::storage::CoverFit;
+use crate::util::normalize_image_url;
+use image::{DynamicImage, Rgba, RgbaImage};
+use ratatui::{prelude::*, widgets::*};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+
+/// Bilibili covers are 16:9
+const COVER_ASPECT: f64 = 16.0 / 9.0;
+
+/// Width over height of a cover in cells, which are about twice as tall as wide
+const COVER_CELL_ASPECT: f64 = COVER_ASPECT * 2.0;
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Largest 16:9 box in `area`, centered horizontally and top-aligned
+///
+/// Covers and their placeholders are both drawn here so a card keeps its shape when the
+/// image arrives.
+pub fn cover_box(area: Rect) -> Rect {
+    let full_height_width = (area.height as f64 * COVER_CELL_ASPECT).round() as u16;
+    let (width, height) = if full_height_width <= area.width {
+        (full_height_width, area.height)
+    } else {
+        let height = (area.width as f64 / COVER_CELL_ASPECT).round() as u16;
+        (area.width, height.min(area.height))
+    };
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y,
+        width,
+        height,
+    }
+}
+
+/// Indices of the cards whose covers to load in a grid of `columns` scrolled to `scroll_row`
+///
+/// Covers `prefetch_rows` rows above and below the visible ones, so scrolling either way
+/// finds them ready. Every extra row costs a full row of downloads that may never be seen.
+pub fn prefetch_range(
+    scroll_row: usize,
+    visible_rows: usize,
+    prefetch_rows: usize,
+    columns: usize,
+    len: usize,
+) -> std::ops::Range<usize> {
+    let first_row = scroll_row.saturating_sub(prefetch_rows);
+    let end_row = scroll_row + visible_rows + prefetch_rows;
+    (first_row * columns).min(len)..(end_row * columns).min(len)
+}
+
+/// Spinner frame index drawn last plus one, 0 once no spinner is on screen
+static SPINNER_SHOWN: AtomicUsize = AtomicUsize::new(0);
+
+fn spinner_index() -> usize {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    (millis / 100) as usize % SPINNER.len()
+}
+
+/// Spinner frame for the current time, so all placeholders turn in step
+pub fn spinner_frame() -> &'static str {
+    let index = spinner_index();
+    SPINNER_SHOWN.store(index + 1, Ordering::Relaxed);
+    SPINNER[index]
+}
+
+/// Whether a spinner was drawn and has turned since, so the screen needs a new frame
+///
+/// Drawing it again re-arms the check, a spinner that left the screen stops asking.
+pub fn spinner_due() -> bool {
+    let shown = SPINNER_SHOWN.load(Ordering::Relaxed);
+    if shown == 0 || shown == spinner_index() + 1 {
+        return false;
+    }
+    SPINNER_SHOWN.store(0, Ordering::Relaxed);
+    true
+}
+
+/// Subtle outline of the cover box with `label` in the middle
+pub fn render_cover_placeholder(frame: &mut Frame, area: Rect, theme: &Theme, label: &str) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border_subtle));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let label_area = Rect {
+        y: inner.y + inner.height.saturating_sub(1) / 2,
+        height: inner.height.min(1),
+        ..inner
+    };
+    frame.render_widget(
+        Paragraph::new(label)
+            .style(Style::default().fg(theme.fg_muted))
+            .alignment(Alignment::Center),
+        label_area,
+    );
+}
+
+/// Image download timeout in milliseconds, the default request timeout until the app sets it
+static DOWNLOAD_TIMEOUT_MS: AtomicU64 = AtomicU64::new(15_000);
+
+/// Apply the request timeout to image downloads too
+pub fn set_download_timeout(timeout: Duration) {
+    DOWNLOAD_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Covers untouched for this long are deleted from the disk cache at startup
+const DISK_CACHE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+fn disk_cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("bilibili-tui").join("covers"))
+}
+
+/// FNV-1a, fixed across builds so cached file names stay valid after an update
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn disk_cache_path(url: &str) -> Option<PathBuf> {
+    Some(disk_cache_dir()?.join(format!("{:016x}", fnv1a(url.as_bytes()))))
+}
+
+/// Delete covers older than [`DISK_CACHE_MAX_AGE`] from the disk cache
+pub fn prune_disk_cache() {
+    let Some(entries) = disk_cache_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > DISK_CACHE_MAX_AGE);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Fetch and decode an image, giving up after the download timeout
+///
+/// Downloads are kept in the disk cache, so covers dropped from memory decode again
+/// without the network.
+pub async fn download_image(url: &str) -> Option<DynamicImage> {
+    let url = normalize_image_url(url);
+    let cache_path = disk_cache_path(&url);
+    if let Some(path) = &cache_path {
+        if let Ok(bytes) = tokio::fs::read(path).await {
+            if let Ok(img) = image::load_from_memory(&bytes) {
+                return Some(img);
+            }
+        }
+    }
+
+    let timeout = Duration::from_millis(DOWNLOAD_TIMEOUT_MS.load(Ordering::Relaxed));
+    let response = reqwest::Client::new()
+        .get(&url)
+        .header(
+            reqwest::header::USER_AGENT,
+            crate::api::client::user_agent(),
+        )
+        .timeout(timeout)
+        .send()
+        .await
+        .ok()?;
+    let bytes = response.bytes().await.ok()?;
+    let img = image::load_from_memory(&bytes).ok()?;
+    if let Some(path) = cache_path {
+        // Best effort, a failed write only costs a download next time
+        if let Some(dir) = path.parent() {
+            let _ = tokio::fs::create_dir_all(dir).await;
+        }
+        let _ = tokio::fs::write(&path, &bytes).await;
+    }
+    Some(img)
+}
+
+/// Caps how many cover downloads run at once
+pub struct DownloadLimiter {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+}
+
+impl DownloadLimiter {
+    pub fn new() -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(1)),
+            permits: 1,
+        }
+    }
+
+    /// Semaphore for the given limit, replaced when the setting changed
+    pub fn semaphore(&mut self, permits: usize) -> Arc<Semaphore> {
+        if permits != self.permits {
+            self.semaphore = Arc::new(Semaphore::new(permits));
+            self.permits = permits;
+        }
+        Arc::clone(&self.semaphore)
+    }
+}
+
+/// Cover downloads started for the current contents of a page
+///
+/// Reloading the page cancels them: running downloads are aborted and results already
+/// queued carry an older generation, so they are dropped instead of landing on new cards.
+/// Dropping the page aborts whatever is still running.
+pub struct CoverTasks {
+    generation: u64,
+    handles: Vec<AbortHandle>,
+    /// Tick at which each card index was last inside the prefetch window
+    seen: HashMap<usize, u64>,
+    tick: u64,
+    /// Prefetch window and card count at the last cover scan
+    scanned: Option<(Range<usize>, usize)>,
+}
+
+impl CoverTasks {
+    pub fn new() -> Self {
+        Self {
+            generation: 0,
+            handles: Vec::new(),
+            seen: HashMap::new(),
+            tick: 0,
+            scanned: None,
+        }
+    }
+
+    /// Whether covers in `window` of `len` cards need scanning again
+    ///
+    /// Moving the selection within the rows on screen keeps the window, so the scan only
+    /// runs again once scrolling, new cards or a cancel change what it would find.
+    pub fn needs_scan(&mut self, window: &Range<usize>, len: usize) -> bool {
+        let scan = Some((window.clone(), len));
+        if self.scanned == scan {
+            return false;
+        }
+        self.scanned = scan;
+        true
+    }
+
+    /// Mark `window` as just seen and pick decoded covers to drop, least recently seen first
+    ///
+    /// `loaded` lists the cards holding a decoded cover, of which at most `cap` are kept.
+    /// Covers inside `window` are never picked, so the cap can't throw away what the
+    /// prefetch lookahead just loaded.
+    pub fn evict(&mut self, window: Range<usize>, loaded: Vec<usize>, cap: usize) -> Vec<usize> {
+        self.tick += 1;
+        for idx in window.clone() {
+            self.seen.insert(idx, self.tick);
+        }
+        if loaded.len() <= cap {
+            return Vec::new();
+        }
+        let excess = loaded.len() - cap;
+        let mut stale: Vec<usize> = loaded
+            .into_iter()
+            .filter(|idx| !window.contains(idx))
+            .collect();
+        stale.sort_by_key(|idx| self.seen.get(idx).copied().unwrap_or(0));
+        stale.truncate(excess);
+        for idx in &stale {
+            self.seen.remove(idx);
+        }
+        stale
+    }
+
+    /// Generation to tag results of downloads started now
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn is_current(&self, generation: u64) -> bool {
+        generation == self.generation
+    }
+
+    /// Keep track of a spawned download so it can be aborted
+    pub fn track(&mut self, handle: AbortHandle) {
+        self.handles.retain(|h| !h.is_finished());
+        self.handles.push(handle);
+    }
+
+    /// Abort running downloads and invalidate their results
+    pub fn cancel(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.abort();
+        }
+        self.generation += 1;
+        // Cards may have moved, their indices no longer mean the same covers
+        self.seen.clear();
+        self.scanned = None;
+    }
+}
+
+impl Drop for CoverTasks {
+    fn drop(&mut self) {
+        for handle in &self.handles {
+            handle.abort();
+        }
+    }
+}
+
+/// Crop or letterbox a decoded cover to a consistent 16:9 aspect ratio
+pub fn fit_cover(img: DynamicImage, fit: CoverFit) -> DynamicImage {
+    let (width, height) = (img.width(), img.height());
+    if width == 0 || height == 0 {
+        return img;
+    }
+
+    let aspect = width as f64 / height as f64;
+    if (aspect - COVER_ASPECT).abs() < 0.01 {
+        return img;
+    }
+
+    match fit {
+        CoverFit::Crop => {
+            if aspect > COVER_ASPECT {
+                let new_width = ((height as f64 * COVER_ASPECT).round() as u32).min(width);
+                img.crop_imm((width - new_width) / 2, 0, new_width, height)
+            } else {
+                let new_height = ((width as f64 / COVER_ASPECT).round() as u32).min(height);
+                img.crop_imm(0, (height - new_height) / 2, width, new_height)
+            }
+        }
+        CoverFit::Letterbox => {
+            let (canvas_width, canvas_height) = if aspect > COVER_ASPECT {
+                (
+                    width,
+                    ((width as f64 / COVER_ASPECT).round() as u32).max(height),
+                )
+            } else {
+                (
+                    ((height as f64 * COVER_ASPECT).round() as u32).max(width),
+                    height,
+                )
+            };
+            let mut canvas =
+                RgbaImage::from_pixel(canvas_width, canvas_height, Rgba([0, 0, 0, 255]));
+            image::imageops::overlay(
+                &mut canvas,
+                &img.to_rgba8(),
+                ((canvas_width - width) / 2) as i64,
+                ((canvas_height - height) / 2) as i64,
+            );
+            DynamicImage::ImageRgba8(canvas)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::new(width, height))
+    }
+
+    #[test]
+    fn test_prefetch_range() {
+        assert_eq!(prefetch_range(0, 3, 2, 4, 100), 0..20);
+        assert_eq!(prefetch_range(5, 3, 2, 4, 100), 12..40);
+        assert_eq!(prefetch_range(5, 3, 0, 4, 100), 20..32);
+        assert_eq!(prefetch_range(20, 3, 2, 4, 90), 72..90);
+        assert_eq!(prefetch_range(0, 3, 2, 4, 0), 0..0);
+    }
+
+    #[test]
+    fn test_cover_box_keeps_aspect() {
+        // Wide area: full height, centered
+        assert_eq!(cover_box(Rect::new(0, 0, 40, 5)), Rect::new(11, 0, 18, 5));
+        // Narrow area: full width, shorter than the area
+        assert_eq!(cover_box(Rect::new(2, 3, 32, 20)), Rect::new(2, 3, 32, 9));
+        assert_eq!(cover_box(Rect::new(0, 0, 0, 0)), Rect::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_crop_tall_cover() {
+        let img = fit_cover(image(400, 300), CoverFit::Crop);
+        assert_eq!((img.width(), img.height()), (400, 225));
+    }
+
+    #[test]
+    fn test_crop_wide_cover() {
+        let img = fit_cover(image(900, 300), CoverFit::Crop);
+        assert_eq!((img.width(), img.height()), (533, 300));
+    }
+
+    #[test]
+    fn test_letterbox_tall_cover() {
+        let img = fit_cover(image(400, 300), CoverFit::Letterbox);
+        assert_eq!((img.width(), img.height()), (533, 300));
+    }
+
+    #[test]
+    fn test_letterbox_wide_cover() {
+        let img = fit_cover(image(900, 300), CoverFit::Letterbox);
+        assert_eq!((img.width(), img.height()), (900, 506));
+    }
+
+    #[test]
+    fn test_cancel_invalidates_generation() {
+        let mut tasks = CoverTasks::new();
+        let old = tasks.generation();
+        assert!(tasks.is_current(old));
+        tasks.cancel();
+        assert!(!tasks.is_current(old));
+        assert!(tasks.is_current(tasks.generation()));
+    }
+
+    #[test]
+    fn test_scan_repeats_only_after_window_changes_or_cancel() {
+        let mut tasks = CoverTasks::new();
+        assert!(tasks.needs_scan(&(0..8), 20));
+        assert!(!tasks.needs_scan(&(0..8), 20));
+        assert!(tasks.needs_scan(&(4..12), 20));
+        assert!(tasks.needs_scan(&(4..12), 24));
+        tasks.cancel();
+        assert!(tasks.needs_scan(&(4..12), 24));
+    }
+
+    #[test]
+    fn test_disk_cache_names_use_a_fixed_hash() {
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_evict_drops_least_recently_seen_outside_window() {
+        let mut tasks = CoverTasks::new();
+        assert!(tasks.evict(0..4, vec![0, 1, 2, 3], 4).is_empty());
+        tasks.evict(4..8, vec![0, 1, 2, 3], 4);
+        tasks.evict(0..2, vec![0, 1, 2, 3], 4);
+        // 2 and 3 went out of view first, 0 and 1 were scrolled back to, 6 and 7 are in view
+        assert_eq!(tasks.evict(6..10, (0..8).collect(), 4), vec![2, 3, 4, 5]);
+        // A window larger than the cap keeps all of its covers
+        assert!(tasks.evict(0..8, (0..8).collect(), 4).is_empty());
+    }
+
+    #[test]
+    fn test_matching_aspect_is_untouched() {
+        let img = fit_cover(image(1920, 1080), CoverFit::Crop);
+        assert_eq!((img.width(), img.height()), (1920, 1080));
+    }
+}
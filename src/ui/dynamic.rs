@@ -1,16 +1,21 @@
 //! Dynamic feed page with video card grid display
 
-use super::video_card::{VideoCard, VideoCardGrid};
+use super::help::{self, KeyHint};
+use super::toast::Toast;
+use super::video_card::{VideoCard, VideoCardGrid, MAX_TOP_UPS};
 use super::{Component, Theme};
-use crate::api::client::ApiClient;
-use crate::api::dynamic::DynamicItem;
-use crate::app::AppAction;
+use crate::api::comment::{self, CommentType};
+use crate::api::dynamic::{DynamicFeedData, DynamicItem};
+use crate::api::BiliApi;
+use crate::app::{next_request_id, AppAction};
+use crate::storage::{CoverFit, Keybindings};
+use crate::util::truncate_display;
 use ratatui::{
     crossterm::event::{KeyCode, MouseButton, MouseEvent},
     prelude::*,
     widgets::*,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 /// Dynamic feed tab types
@@ -47,6 +52,85 @@ impl DynamicTab {
     }
 }
 
+/// Client-side type filter over the items already loaded for a tab
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DynamicFilter {
+    #[default]
+    All,
+    VideoOnly,
+    /// Opus items, text posts and articles
+    ArticleOnly,
+    /// Draw items, image posts
+    ImageOnly,
+}
+
+impl DynamicFilter {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DynamicFilter::All => "全部类型",
+            DynamicFilter::VideoOnly => "仅视频",
+            DynamicFilter::ArticleOnly => "仅文章",
+            DynamicFilter::ImageOnly => "仅图片",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            DynamicFilter::All => DynamicFilter::VideoOnly,
+            DynamicFilter::VideoOnly => DynamicFilter::ArticleOnly,
+            DynamicFilter::ArticleOnly => DynamicFilter::ImageOnly,
+            DynamicFilter::ImageOnly => DynamicFilter::All,
+        }
+    }
+
+    pub fn matches(&self, item: &DynamicItem) -> bool {
+        match self {
+            DynamicFilter::All => true,
+            DynamicFilter::VideoOnly => item.is_video(),
+            DynamicFilter::ArticleOnly => item.is_opus(),
+            DynamicFilter::ImageOnly => item.is_draw(),
+        }
+    }
+}
+
+/// Feed requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicRequest {
+    /// First page of the current tab and UP
+    Feed,
+    /// Next page from the saved offset
+    More,
+}
+
+/// Feed request recorded by [`DynamicPage::begin`], copied into the task that runs it
+#[derive(Debug, Clone)]
+pub struct DynamicQuery {
+    pub kind: DynamicRequest,
+    /// Matched against the page's latest request when the answer arrives
+    id: u64,
+    tab: DynamicTab,
+    host_mid: Option<i64>,
+    /// Where the next page starts, `None` for the first page
+    offset: Option<String>,
+}
+
+/// Answer to a [`DynamicQuery`], applied by [`DynamicPage::finish`]
+pub struct DynamicAnswer {
+    query: DynamicQuery,
+    data: anyhow::Result<DynamicFeedData>,
+}
+
+pub async fn fetch_dynamic(api_client: &impl BiliApi, query: DynamicQuery) -> DynamicAnswer {
+    let data = api_client
+        .get_dynamic_feed(
+            query.offset.as_deref(),
+            query.tab.get_feed_type(),
+            query.host_mid,
+        )
+        .await;
+    DynamicAnswer { query, data }
+}
+
 pub struct DynamicPage {
     pub grid: VideoCardGrid,
     pub loading: bool,
@@ -60,7 +144,23 @@ pub struct DynamicPage {
     pub selected_up_index: usize,
     pub loading_up_list: bool,
     pub up_list_scroll_offset: usize,
+    /// Items currently shown in the grid, aligned with `grid.cards`
     pub dynamic_items: Vec<DynamicItem>,
+    /// All loaded items for the current tab, before UP filtering
+    pub loaded_items: Vec<DynamicItem>,
+    pub filter_query: String,
+    pub filter_mode: bool,
+    /// Item type shown, kept across refreshes
+    pub filter: DynamicFilter,
+    /// UPs hidden for this session
+    pub muted_mids: HashSet<i64>,
+    toast: Option<Toast>,
+    /// Request behind the error or toast being shown, re-issued by the retry key
+    failed: Option<DynamicRequest>,
+    /// Id of the latest feed request, answers to older ones are dropped
+    request_id: u64,
+    /// Pages loaded by [`top_up`](Self::top_up) since the first page
+    top_ups: usize,
     last_click_time: Option<Instant>,
     last_click_index: Option<usize>,
 }
@@ -81,6 +181,15 @@ impl DynamicPage {
             loading_up_list: false,
             up_list_scroll_offset: 0,
             dynamic_items: Vec::new(),
+            loaded_items: Vec::new(),
+            filter_query: String::new(),
+            filter_mode: false,
+            filter: DynamicFilter::default(),
+            muted_mids: HashSet::new(),
+            toast: None,
+            failed: None,
+            request_id: 0,
+            top_ups: 0,
             last_click_time: None,
             last_click_index: None,
         }
@@ -140,84 +249,12 @@ impl DynamicPage {
     }
 
     pub fn set_feed(&mut self, items: Vec<DynamicItem>, offset: Option<String>, has_more: bool) {
-        self.grid.clear();
-        self.dynamic_items.clear();
-
-        // Process items based on current tab filter
-        for item in items.into_iter() {
-            let should_include = match self.current_tab {
-                DynamicTab::All => item.is_video() || item.is_draw() || item.is_opus(),
-                DynamicTab::Videos => item.is_video(),
-                DynamicTab::Images => item.is_draw() || item.is_opus(),
-            };
-
-            if !should_include {
-                continue;
-            }
-
-            // Store the item
-            self.dynamic_items.push(item.clone());
-
-            // Handle video dynamics
-            if item.is_video() {
-                if let Some(bvid) = item.video_bvid() {
-                    let card = VideoCard::new(
-                        Some(bvid.to_string()),
-                        None,
-                        item.video_title().unwrap_or("无标题").to_string(),
-                        item.author_name().to_string(),
-                        format!("▶ {}", item.video_play()),
-                        item.video_duration().to_string(),
-                        item.video_cover().map(|s| s.to_string()),
-                    );
-                    self.grid.add_card(card);
-                }
-            }
-            // Handle image dynamics (带图动态)
-            else if item.is_draw() {
-                let images = item.draw_images();
-                let image_url = images.first().map(|s| s.to_string());
-                let desc = item.desc_text().unwrap_or("图片动态");
-                let image_count = if images.len() > 1 {
-                    format!(" [{}P]", images.len())
-                } else {
-                    String::new()
-                };
-
-                let card = VideoCard::new(
-                    None, // No bvid for images
-                    None,
-                    format!("{}{}", desc, image_count),
-                    item.author_name().to_string(),
-                    "📷 图片动态".to_string(),
-                    "".to_string(),
-                    image_url,
-                );
-                self.grid.add_card(card);
-            }
-            // Handle text/opus dynamics (图文动态)
-            else if item.is_opus() {
-                let text = item.opus_text().unwrap_or("图文动态");
-                let images = item.opus_images();
-                let image_url = images.first().map(|s| s.to_string());
-                let image_count = if !images.is_empty() {
-                    format!(" [{}P]", images.len())
-                } else {
-                    String::new()
-                };
-
-                let card = VideoCard::new(
-                    None,
-                    None,
-                    format!("{}{}", text, image_count),
-                    item.author_name().to_string(),
-                    "📝 图文".to_string(),
-                    "".to_string(),
-                    image_url,
-                );
-                self.grid.add_card(card);
-            }
-        }
+        // A fresh feed resets the UP filter
+        self.loaded_items.clear();
+        self.filter_query.clear();
+        self.filter_mode = false;
+        self.rebuild_grid();
+        self.append_items(items);
 
         // Save offset for current tab
         self.tab_offsets.insert(self.current_tab, offset.clone());
@@ -227,6 +264,16 @@ impl DynamicPage {
     }
 
     pub fn append_feed(&mut self, items: Vec<DynamicItem>, offset: Option<String>, has_more: bool) {
+        self.append_items(items);
+
+        // Save offset for current tab
+        self.tab_offsets.insert(self.current_tab, offset.clone());
+        self.offset = offset;
+        self.has_more = has_more;
+        self.loading_more = false;
+    }
+
+    fn append_items(&mut self, items: Vec<DynamicItem>) {
         // Process items based on current tab filter
         for item in items.into_iter() {
             let should_include = match self.current_tab {
@@ -239,75 +286,130 @@ impl DynamicPage {
                 continue;
             }
 
-            // Store the item
-            self.dynamic_items.push(item.clone());
-
-            // Handle video dynamics
-            if item.is_video() {
-                if let Some(bvid) = item.video_bvid() {
-                    let card = VideoCard::new(
-                        Some(bvid.to_string()),
-                        None,
-                        item.video_title().unwrap_or("无标题").to_string(),
-                        item.author_name().to_string(),
-                        format!("▶ {}", item.video_play()),
-                        item.video_duration().to_string(),
-                        item.video_cover().map(|s| s.to_string()),
-                    );
-                    self.grid.add_card(card);
-                }
+            if self.is_visible(&item) {
+                self.add_card(&item);
             }
-            // Handle image dynamics
-            else if item.is_draw() {
-                let images = item.draw_images();
-                let image_url = images.first().map(|s| s.to_string());
-                let desc = item.desc_text().unwrap_or("图片动态");
-                let image_count = if images.len() > 1 {
-                    format!(" [{}P]", images.len())
-                } else {
-                    String::new()
-                };
+            self.loaded_items.push(item);
+        }
+    }
 
-                let card = VideoCard::new(
-                    None,
-                    None,
-                    format!("{}{}", desc, image_count),
-                    item.author_name().to_string(),
-                    "📷 图片动态".to_string(),
-                    "".to_string(),
-                    image_url,
-                );
-                self.grid.add_card(card);
-            }
-            // Handle text/opus dynamics
-            else if item.is_opus() {
-                let text = item.opus_text().unwrap_or("图文动态");
-                let images = item.opus_images();
-                let image_url = images.first().map(|s| s.to_string());
-                let image_count = if !images.is_empty() {
-                    format!(" [{}P]", images.len())
-                } else {
-                    String::new()
-                };
+    /// Whether an item passes the type and UP filters and is not muted
+    fn is_visible(&self, item: &DynamicItem) -> bool {
+        if !self.filter.matches(item) {
+            return false;
+        }
+        if item
+            .author_mid()
+            .is_some_and(|mid| self.muted_mids.contains(&mid))
+        {
+            return false;
+        }
+        self.filter_query.is_empty()
+            || item
+                .author_name()
+                .to_lowercase()
+                .contains(&self.filter_query.to_lowercase())
+    }
 
-                let card = VideoCard::new(
-                    None,
-                    None,
-                    format!("{}{}", text, image_count),
-                    item.author_name().to_string(),
-                    "📝 图文".to_string(),
-                    "".to_string(),
-                    image_url,
-                );
-                self.grid.add_card(card);
+    /// Add a card for the item, keeping `dynamic_items` aligned with the grid
+    fn add_card(&mut self, item: &DynamicItem) {
+        // Handle video dynamics
+        let mut card = if item.is_video() {
+            let Some(bvid) = item.video_bvid() else {
+                return;
+            };
+            VideoCard::new(
+                Some(bvid.to_string()),
+                None,
+                item.video_title().unwrap_or("无标题").to_string(),
+                item.author_name().to_string(),
+                format!("▶ {}", item.video_play()),
+                item.video_duration().to_string(),
+                item.video_cover().map(|s| s.to_string()),
+            )
+        }
+        // Handle image dynamics (带图动态)
+        else if item.is_draw() {
+            let images = item.draw_images();
+            let image_url = images.first().map(|s| s.to_string());
+            let desc = item.desc_text().unwrap_or("图片动态");
+            let image_count = if images.len() > 1 {
+                format!(" [{}P]", images.len())
+            } else {
+                String::new()
+            };
+
+            VideoCard::new(
+                None, // No bvid for images
+                None,
+                format!("{}{}", desc, image_count),
+                item.author_name().to_string(),
+                "📷 图片动态".to_string(),
+                "".to_string(),
+                image_url,
+            )
+        }
+        // Handle text/opus dynamics (图文动态)
+        else if item.is_opus() {
+            let text = item.opus_text().unwrap_or("图文动态");
+            let images = item.opus_images();
+            let image_url = images.first().map(|s| s.to_string());
+            let image_count = if !images.is_empty() {
+                format!(" [{}P]", images.len())
+            } else {
+                String::new()
+            };
+
+            VideoCard::new(
+                None,
+                None,
+                format!("{}{}", text, image_count),
+                item.author_name().to_string(),
+                "📝 图文".to_string(),
+                "".to_string(),
+                image_url,
+            )
+        } else {
+            return;
+        };
+
+        card.badge = item.kind();
+        self.grid.add_card(card);
+        self.dynamic_items.push(item.clone());
+    }
+
+    /// Rebuild the grid from loaded items after the filter or mute list changed
+    fn rebuild_grid(&mut self) {
+        self.grid.clear();
+        self.dynamic_items.clear();
+        let items = std::mem::take(&mut self.loaded_items);
+        for item in &items {
+            if self.is_visible(item) {
+                self.add_card(item);
             }
         }
+        self.loaded_items = items;
+    }
 
-        // Save offset for current tab
-        self.tab_offsets.insert(self.current_tab, offset.clone());
-        self.offset = offset;
-        self.has_more = has_more;
-        self.loading_more = false;
+    /// Show the next item type, re-filtering what is already loaded
+    pub fn cycle_filter(&mut self) {
+        self.filter = self.filter.next();
+        self.rebuild_grid();
+    }
+
+    /// Hide the selected item's UP for this session
+    pub fn mute_selected_up(&mut self) {
+        if let Some(mid) = self.selected_dynamic_item().and_then(|i| i.author_mid()) {
+            self.muted_mids.insert(mid);
+            self.rebuild_grid();
+        }
+    }
+
+    pub fn unmute_all(&mut self) {
+        if !self.muted_mids.is_empty() {
+            self.muted_mids.clear();
+            self.rebuild_grid();
+        }
     }
 
     pub fn set_error(&mut self, msg: String) {
@@ -316,37 +418,121 @@ impl DynamicPage {
         self.loading_more = false;
     }
 
-    pub async fn load_more(&mut self, api_client: &ApiClient) {
-        if self.loading_more || !self.has_more {
-            return;
-        }
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<DynamicRequest> {
+        self.failed.take()
+    }
 
-        self.loading_more = true;
+    /// Record a request of `kind` and return what the task has to fetch
+    ///
+    /// A first-page load always starts and drops whatever was in flight, loading more
+    /// waits for it. `None` means there is nothing to run.
+    pub fn begin(&mut self, kind: DynamicRequest) -> Option<DynamicQuery> {
+        let offset = match kind {
+            DynamicRequest::Feed => {
+                self.loading = true;
+                self.loading_more = false;
+                self.error_message = None;
+                self.failed = None;
+                self.top_ups = 0;
+                None
+            }
+            DynamicRequest::More => {
+                if self.loading || self.loading_more || !self.has_more {
+                    return None;
+                }
+                self.loading_more = true;
+                self.offset.clone()
+            }
+        };
+        self.request_id = next_request_id();
+        Some(DynamicQuery {
+            kind,
+            id: self.request_id,
+            tab: self.current_tab,
+            host_mid: self.get_selected_up_mid(),
+            offset,
+        })
+    }
 
-        let feed_type = self.current_tab.get_feed_type();
-        let host_mid = self.get_selected_up_mid();
-        match api_client
-            .get_dynamic_feed(self.offset.as_deref(), feed_type, host_mid)
-            .await
-        {
-            Ok(data) => {
+    /// Record a request for the next page when the cards don't fill the screen
+    ///
+    /// Tabs and filters can leave only a few cards from a page. At most [`MAX_TOP_UPS`]
+    /// pages are added this way per first-page load, `None` once the feed runs out.
+    pub fn top_up(&mut self) -> Option<DynamicQuery> {
+        if self.top_ups >= MAX_TOP_UPS || self.failed.is_some() || self.grid.fills_screen() {
+            return None;
+        }
+        let query = self.begin(DynamicRequest::More)?;
+        self.top_ups += 1;
+        Some(query)
+    }
+
+    /// Apply the answer to the last request, answers to superseded ones are dropped
+    pub fn finish(&mut self, answer: DynamicAnswer) {
+        let DynamicAnswer { query, data } = answer;
+        if query.id != self.request_id {
+            return;
+        }
+        match (query.kind, data) {
+            (DynamicRequest::Feed, Ok(data)) => {
                 let items = data.items.unwrap_or_default();
-                let offset = data.offset;
                 let has_more = data.has_more.unwrap_or(false);
-                self.append_feed(items, offset, has_more);
+                self.set_feed(items, data.offset, has_more);
+            }
+            (DynamicRequest::Feed, Err(e)) => {
+                self.set_error(format!("加载动态失败: {}", e));
+                self.failed = Some(DynamicRequest::Feed);
             }
-            Err(_) => {
+            (DynamicRequest::More, Ok(data)) => {
+                let items = data.items.unwrap_or_default();
+                let has_more = data.has_more.unwrap_or(false);
+                self.append_feed(items, data.offset, has_more);
+                self.failed = None;
+            }
+            (DynamicRequest::More, Err(e)) => {
                 self.loading_more = false;
+                self.failed = Some(DynamicRequest::More);
+                self.toast = Some(Toast::new(format!("加载更多失败: {}", e)));
             }
         }
     }
 
-    pub fn poll_cover_results(&mut self) {
-        self.grid.poll_cover_results();
+    /// Run a request of `kind` to completion, what the app does across a spawned task
+    pub async fn run(&mut self, kind: DynamicRequest, api_client: &impl BiliApi) {
+        if let Some(query) = self.begin(kind) {
+            let answer = fetch_dynamic(api_client, query).await;
+            self.finish(answer);
+        }
     }
 
-    pub fn start_cover_downloads(&mut self) {
-        self.grid.start_cover_downloads();
+    /// Load the first page for the current tab and UP
+    pub async fn load_feed(&mut self, api_client: &impl BiliApi) {
+        self.run(DynamicRequest::Feed, api_client).await;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        self.run(DynamicRequest::More, api_client).await;
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
     }
 
     /// Get the currently selected dynamic item (if any)
@@ -354,6 +540,51 @@ impl DynamicPage {
         let selected_index = self.grid.selected_index;
         self.dynamic_items.get(selected_index)
     }
+
+    /// Open the uploads of the selected item's author
+    fn open_author_space(&mut self) -> AppAction {
+        let Some(item) = self.selected_dynamic_item() else {
+            return AppAction::None;
+        };
+        match item.author_mid() {
+            Some(mid) => AppAction::OpenUserSpace {
+                mid,
+                name: item.author_name().to_string(),
+            },
+            None => {
+                self.toast = Some(Toast::new("这条动态没有UP主信息"));
+                AppAction::None
+            }
+        }
+    }
+
+    /// Open the comments of the selected item, under the video for video dynamics
+    fn open_comments(&mut self) -> AppAction {
+        let Some(item) = self.selected_dynamic_item() else {
+            return AppAction::None;
+        };
+        let title = format!("{} 的动态", item.author_name());
+        let target = match self.grid.selected_card() {
+            Some(card) if card.bvid.is_some() => comment::video_oid(card.bvid.as_deref(), card.aid)
+                .map(|oid| (oid, CommentType::Video.as_i32())),
+            _ => item
+                .id_str
+                .as_deref()
+                .and_then(|id| item.comment_oid(id))
+                .map(|oid| (oid, item.comment_type())),
+        };
+        match target {
+            Some((oid, comment_type)) => AppAction::OpenComments {
+                oid,
+                comment_type,
+                title,
+            },
+            None => {
+                self.toast = Some(Toast::new("这条动态没有评论区"));
+                AppAction::None
+            }
+        }
+    }
 }
 
 impl Default for DynamicPage {
@@ -362,8 +593,38 @@ impl Default for DynamicPage {
     }
 }
 
+impl DynamicPage {
+    /// Footer entries for the keys handled in the current focus
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        if self.filter_mode {
+            return vec![
+                KeyHint::fixed("Enter", "确定"),
+                KeyHint::fixed("Esc", "清除筛选"),
+            ];
+        }
+        let mut hints = vec![
+            help::navigation(keys),
+            KeyHint::pair(&keys.up_prev, &keys.up_next, "切UP主"),
+            KeyHint::pair(&keys.section_prev, &keys.section_next, "切标签"),
+            KeyHint::new(&keys.confirm, "详情"),
+            KeyHint::new(&keys.open_up_space, "UP主投稿"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            KeyHint::new(&keys.search_focus, "筛选UP"),
+            KeyHint::pair(&keys.mute_up, &keys.unmute_all, "屏蔽/取消屏蔽"),
+            KeyHint::new(&keys.cycle_dynamic_filter, "类型"),
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::new(&keys.open_comments, "评论"),
+            help::page_switch(keys),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+}
+
 impl Component for DynamicPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -376,6 +637,8 @@ impl Component for DynamicPage {
 
         // UP master selection bar
         const VISIBLE_UPS: usize = 10;
+        // Long names would push the later UPs off the bar
+        const MAX_UP_NAME_COLS: usize = 12;
         let mut up_spans: Vec<Span> = Vec::new();
 
         // Show left indicator if scrolled
@@ -409,7 +672,7 @@ impl Component for DynamicPage {
         {
             let actual_index = i + 1; // +1 because index 0 is "全部"
             let is_selected = self.selected_up_index == actual_index;
-            let name = &user.uname;
+            let name = truncate_display(&user.uname, MAX_UP_NAME_COLS);
             // Add update indicator (●) for UPs with recent updates
             let text = if user.has_update {
                 format!(" ● {} ", name)
@@ -477,6 +740,34 @@ impl Component for DynamicPage {
             } else {
                 Span::raw("")
             },
+            if self.filter_mode || !self.filter_query.is_empty() {
+                Span::styled(
+                    format!(
+                        "  🔍 {}{}",
+                        self.filter_query,
+                        if self.filter_mode { "_" } else { "" }
+                    ),
+                    Style::default().fg(theme.fg_accent),
+                )
+            } else {
+                Span::raw("")
+            },
+            Span::styled(
+                format!("  [{}]", self.filter.label()),
+                Style::default().fg(if self.filter == DynamicFilter::All {
+                    theme.fg_muted
+                } else {
+                    theme.fg_accent
+                }),
+            ),
+            if self.muted_mids.is_empty() {
+                Span::raw("")
+            } else {
+                Span::styled(
+                    format!("  已屏蔽 {} 位UP", self.muted_mids.len()),
+                    Style::default().fg(theme.fg_muted),
+                )
+            },
         ]))
         .block(
             Block::default()
@@ -536,7 +827,11 @@ impl Component for DynamicPage {
                 );
             frame.render_widget(loading, chunks[2]);
         } else if let Some(ref error) = self.error_message {
-            let error_widget = Paragraph::new(format!("❌ {}", error))
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
                 .style(Style::default().fg(theme.error))
                 .alignment(Alignment::Center)
                 .block(
@@ -547,7 +842,12 @@ impl Component for DynamicPage {
                 );
             frame.render_widget(error_widget, chunks[2]);
         } else if self.grid.cards.is_empty() {
-            let empty = Paragraph::new("暂无动态，请先登录并关注UP主")
+            let message = if self.loaded_items.is_empty() {
+                "暂无动态，请先登录并关注UP主"
+            } else {
+                "没有匹配的动态"
+            };
+            let empty = Paragraph::new(message)
                 .style(Style::default().fg(theme.fg_secondary))
                 .alignment(Alignment::Center)
                 .block(
@@ -561,11 +861,12 @@ impl Component for DynamicPage {
             self.grid.render(frame, chunks[2], theme);
         }
 
-        // Help
-        let help = Paragraph::new(
-            "↑↓←→:卡片导航 | h/l:切UP主 | []:切标签 | Tab:切页面 | Enter:详情 | r:刷新",
-        )
-        .style(Style::default().fg(theme.fg_secondary))
+        // Help, replaced by a notice while one is active
+        let help = if let Some(toast) = self.toast.as_ref().and_then(Toast::active_message) {
+            Paragraph::new(format!("ℹ {}", toast)).style(Style::default().fg(theme.warning))
+        } else {
+            Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+        }
         .alignment(Alignment::Center);
         frame.render_widget(help, chunks[3]);
     }
@@ -574,10 +875,57 @@ impl Component for DynamicPage {
         &mut self,
         key: KeyCode,
         modifiers: crossterm::event::KeyModifiers,
-        keys: &crate::storage::Keybindings,
+        keys: &Keybindings,
     ) -> Option<AppAction> {
         let _ = modifiers;
 
+        // UP filter input
+        if self.filter_mode {
+            match key {
+                KeyCode::Char(c) => {
+                    self.filter_query.push(c);
+                    self.rebuild_grid();
+                }
+                KeyCode::Backspace => {
+                    self.filter_query.pop();
+                    self.rebuild_grid();
+                }
+                KeyCode::Enter => self.filter_mode = false,
+                KeyCode::Esc => {
+                    self.filter_mode = false;
+                    self.filter_query.clear();
+                    self.rebuild_grid();
+                }
+                _ => {}
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_search_focus(key) {
+            self.filter_mode = true;
+            return Some(AppAction::None);
+        }
+        if keys.matches_mute_up(key) {
+            self.mute_selected_up();
+            return Some(AppAction::None);
+        }
+        if keys.matches_unmute_all(key) {
+            self.unmute_all();
+            return Some(AppAction::None);
+        }
+        if keys.matches_cycle_dynamic_filter(key) {
+            self.cycle_filter();
+            return Some(AppAction::None);
+        }
+        if keys.matches_open_up_space(key) {
+            return Some(self.open_author_space());
+        }
+        if keys.matches_open_comments(key) {
+            return Some(self.open_comments());
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+
         // Card navigation
         if keys.matches_down(key) {
             self.grid.move_down();
@@ -669,6 +1017,15 @@ impl Component for DynamicPage {
             return Some(AppAction::None);
         }
 
+        // Only video dynamics can be downloaded
+        if keys.matches_download(key) {
+            return Some(
+                self.grid
+                    .selected_bvid()
+                    .map_or(AppAction::None, AppAction::DownloadVideo),
+            );
+        }
+
         // Refresh
         if keys.matches_refresh(key) {
             self.loading = true;
@@ -762,3 +1119,229 @@ impl Component for DynamicPage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::dynamic::DynamicKind;
+    use crate::ui::test_utils::{contains, render};
+
+    fn video_item(mid: i64, author: &str, bvid: &str) -> DynamicItem {
+        serde_json::from_value(serde_json::json!({
+            "id_str": bvid,
+            "type": "DYNAMIC_TYPE_AV",
+            "modules": {
+                "module_author": { "name": author, "mid": mid },
+                "module_dynamic": {
+                    "major": {
+                        "type": "MAJOR_TYPE_ARCHIVE",
+                        "archive": { "bvid": bvid, "title": bvid }
+                    }
+                }
+            }
+        }))
+        .expect("valid dynamic item")
+    }
+
+    fn page_with_feed() -> DynamicPage {
+        let mut page = DynamicPage::new();
+        page.set_feed(
+            vec![
+                video_item(1, "Alice", "BV1"),
+                video_item(2, "Bob", "BV2"),
+                video_item(1, "Alice", "BV3"),
+            ],
+            None,
+            false,
+        );
+        page
+    }
+
+    fn press(page: &mut DynamicPage, keys: &[KeyCode]) {
+        let bindings = Keybindings::default();
+        for key in keys {
+            page.handle_input_with_modifiers(*key, crossterm::event::KeyModifiers::NONE, &bindings);
+        }
+    }
+
+    #[test]
+    fn test_open_comments_of_video_dynamic() {
+        let mut page = DynamicPage::new();
+        page.set_feed(
+            vec![
+                video_item(1, "Alice", "BV17x411w7KC"),
+                video_item(2, "Bob", "BV2"),
+            ],
+            None,
+            false,
+        );
+        assert!(matches!(
+            page.open_comments(),
+            AppAction::OpenComments { oid: 170001, comment_type: 1, ref title } if title == "Alice 的动态"
+        ));
+
+        // Neither an aid nor a valid bvid to comment on
+        page.grid.selected_index = 1;
+        assert!(matches!(page.open_comments(), AppAction::None));
+        assert!(page.toast.is_some());
+    }
+
+    #[test]
+    fn test_open_author_space() {
+        let mut page = page_with_feed();
+        page.grid.selected_index = 1;
+        let action = page.handle_input_with_modifiers(
+            KeyCode::Char('u'),
+            crossterm::event::KeyModifiers::NONE,
+            &Keybindings::default(),
+        );
+        assert!(matches!(
+            action,
+            Some(AppAction::OpenUserSpace { mid: 2, ref name }) if name == "Bob"
+        ));
+
+        let mut item = video_item(0, "Anon", "BV4");
+        if let Some(author) = item.modules.as_mut().and_then(|m| m.module_author.as_mut()) {
+            author.mid = None;
+        }
+        page.set_feed(vec![item], None, false);
+        assert!(matches!(page.open_author_space(), AppAction::None));
+        assert!(page.toast.is_some());
+    }
+
+    #[test]
+    fn test_filter_by_author_updates_live() {
+        let mut page = page_with_feed();
+        press(&mut page, &[KeyCode::Char('/'), KeyCode::Char('b')]);
+        assert!(page.filter_mode);
+        assert_eq!(page.grid.cards.len(), 1);
+        assert_eq!(page.dynamic_items[0].author_name(), "Bob");
+
+        press(&mut page, &[KeyCode::Backspace]);
+        assert_eq!(page.grid.cards.len(), 3);
+
+        press(&mut page, &[KeyCode::Char('a'), KeyCode::Enter]);
+        assert!(!page.filter_mode);
+        assert_eq!(page.grid.cards.len(), 2);
+    }
+
+    #[test]
+    fn test_esc_clears_filter() {
+        let mut page = page_with_feed();
+        press(
+            &mut page,
+            &[KeyCode::Char('/'), KeyCode::Char('x'), KeyCode::Esc],
+        );
+        assert!(page.filter_query.is_empty());
+        assert_eq!(page.grid.cards.len(), 3);
+    }
+
+    #[test]
+    fn test_mute_and_unmute_up() {
+        let mut page = page_with_feed();
+        press(&mut page, &[KeyCode::Char('m')]);
+        assert_eq!(page.grid.cards.len(), 1);
+        assert_eq!(page.dynamic_items[0].author_name(), "Bob");
+
+        press(&mut page, &[KeyCode::Char('M')]);
+        assert_eq!(page.grid.cards.len(), 3);
+    }
+
+    #[test]
+    fn test_refresh_resets_filter() {
+        let mut page = page_with_feed();
+        press(
+            &mut page,
+            &[KeyCode::Char('/'), KeyCode::Char('b'), KeyCode::Enter],
+        );
+        page.set_feed(vec![video_item(1, "Alice", "BV4")], None, false);
+        assert!(page.filter_query.is_empty());
+        assert_eq!(page.grid.cards.len(), 1);
+    }
+
+    #[test]
+    fn test_cards_carry_type_badge() {
+        let draw: DynamicItem = serde_json::from_value(serde_json::json!({
+            "id_str": "2",
+            "type": "DYNAMIC_TYPE_DRAW",
+            "modules": {
+                "module_author": { "name": "Carol", "mid": 3 },
+                "module_dynamic": { "major": { "type": "MAJOR_TYPE_DRAW" } }
+            }
+        }))
+        .expect("valid dynamic item");
+
+        let mut page = DynamicPage::new();
+        page.set_feed(vec![video_item(1, "Alice", "BV1"), draw], None, false);
+        let badges: Vec<_> = page.grid.cards.iter().map(|card| card.badge).collect();
+        assert_eq!(badges, [Some(DynamicKind::Video), Some(DynamicKind::Draw)]);
+    }
+
+    #[test]
+    fn test_type_filter_refilters_loaded_items() {
+        let draw: DynamicItem = serde_json::from_value(serde_json::json!({
+            "id_str": "2",
+            "type": "DYNAMIC_TYPE_DRAW",
+            "modules": {
+                "module_author": { "name": "Carol", "mid": 3 },
+                "module_dynamic": { "major": { "type": "MAJOR_TYPE_DRAW" } }
+            }
+        }))
+        .expect("valid dynamic item");
+        let mut page = DynamicPage::new();
+        page.set_feed(vec![video_item(1, "Alice", "BV1"), draw], None, false);
+        assert_eq!(page.grid.cards.len(), 2);
+
+        press(&mut page, &[KeyCode::Char('F')]);
+        assert_eq!(page.filter, DynamicFilter::VideoOnly);
+        assert_eq!(page.dynamic_items[0].author_name(), "Alice");
+        assert_eq!(page.grid.cards.len(), 1);
+        assert!(contains(&render(&mut page), "[仅视频]"));
+
+        press(&mut page, &[KeyCode::Char('F'), KeyCode::Char('F')]);
+        assert_eq!(page.filter, DynamicFilter::ImageOnly);
+        assert_eq!(page.dynamic_items[0].author_name(), "Carol");
+
+        // The filter survives a refresh, the loaded items stay intact
+        page.set_feed(vec![video_item(1, "Alice", "BV3")], None, false);
+        assert!(page.grid.cards.is_empty());
+        press(&mut page, &[KeyCode::Char('F')]);
+        assert_eq!(page.grid.cards.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_feed_offers_retry() {
+        let mut page = DynamicPage::new();
+        page.load_feed(&crate::api::test_api::MockApi::failing())
+            .await;
+        assert!(contains(&render(&mut page), "按 e 重试"));
+        assert!(matches!(
+            page.handle_input_with_modifiers(
+                KeyCode::Char('e'),
+                crossterm::event::KeyModifiers::NONE,
+                &Keybindings::default()
+            ),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(DynamicRequest::Feed));
+    }
+
+    #[test]
+    fn test_render_states() {
+        let mut page = DynamicPage::new();
+        assert!(contains(&render(&mut page), "⏳ 加载动态中..."));
+
+        page.set_error("未登录".to_string());
+        assert!(contains(&render(&mut page), "❌ 未登录"));
+        assert!(!contains(&render(&mut page), "重试"));
+
+        let mut page = DynamicPage::new();
+        page.set_feed(Vec::new(), None, false);
+        assert!(contains(&render(&mut page), "暂无动态，请先登录并关注UP主"));
+
+        let buffer = render(&mut page_with_feed());
+        assert!(contains(&buffer, "BV1"));
+        assert!(contains(&buffer, "Alice"));
+        assert!(contains(&buffer, "Bob"));
+    }
+}
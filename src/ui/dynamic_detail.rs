@@ -1,11 +1,14 @@
 //! Dynamic detail page for viewing image/text dynamics
 
+use super::cover::download_image;
+use super::help::{self, KeyHint};
 use super::{Component, Theme};
 use crate::api::client::ApiClient;
 use crate::api::comment::CommentItem;
 use crate::api::dynamic::DynamicItem;
 use crate::app::AppAction;
-use image::DynamicImage;
+use crate::storage::Keybindings;
+use crate::util::wrap_display;
 use ratatui::{
     crossterm::event::{KeyCode, MouseEvent, MouseEventKind},
     prelude::*,
@@ -50,7 +53,7 @@ pub struct DynamicDetailPage {
 
 impl DynamicDetailPage {
     pub fn new(dynamic_id: String) -> Self {
-        let picker = Arc::new(Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks()));
+        let picker = super::image_protocol::picker();
         let (image_tx, image_rx) = mpsc::channel(8);
 
         Self {
@@ -151,7 +154,7 @@ impl DynamicDetailPage {
             let url = url.clone();
 
             tokio::spawn(async move {
-                if let Some(img) = Self::download_image(&url).await {
+                if let Some(img) = download_image(&url).await {
                     let protocol = picker.new_resize_protocol(img);
                     let _ = tx
                         .send(ImageResult {
@@ -174,12 +177,6 @@ impl DynamicDetailPage {
         }
     }
 
-    async fn download_image(url: &str) -> Option<DynamicImage> {
-        let response = reqwest::get(url).await.ok()?;
-        let bytes = response.bytes().await.ok()?;
-        image::load_from_memory(&bytes).ok()
-    }
-
     pub async fn load_more_comments(&mut self, api_client: &ApiClient) {
         if !self.has_more_comments || self.loading_more_comments {
             return;
@@ -240,7 +237,7 @@ impl DynamicDetailPage {
                 if !text.is_empty() {
                     lines.push("📝 动态内容:".to_string());
                     lines.push(String::new());
-                    for line in wrap_text(text, 60) {
+                    for line in wrap_display(text, 60) {
                         lines.push(format!("  {}", line));
                     }
                     lines.push(String::new());
@@ -277,7 +274,7 @@ impl DynamicDetailPage {
 
             // Comment content
             let message = comment.message();
-            for line in wrap_text(message, 80) {
+            for line in wrap_display(message, 80) {
                 block.push(format!("   {}", line));
             }
 
@@ -304,8 +301,32 @@ impl DynamicDetailPage {
     }
 }
 
+impl DynamicDetailPage {
+    /// Footer entries for the keys handled in the current focus
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        if self.input_mode {
+            return vec![
+                KeyHint::fixed("Enter", "发送评论"),
+                KeyHint::fixed("Esc", "取消"),
+            ];
+        }
+        let mut hints = Vec::new();
+        if !self.image_urls.is_empty() {
+            hints.push(KeyHint::pair(&keys.nav_left, &keys.nav_right, "图片"));
+        }
+        hints.extend([
+            help::scrolling(keys),
+            KeyHint::new(&keys.confirm, "点赞"),
+            KeyHint::new(&keys.comment, "评论"),
+            KeyHint::fixed("n", "加载更多"),
+            KeyHint::new(&keys.back, "返回"),
+        ]);
+        hints
+    }
+}
+
 impl Component for DynamicDetailPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         // Poll for completed image downloads
         self.poll_image_results();
 
@@ -417,24 +438,12 @@ impl Component for DynamicDetailPage {
         } else {
             chunks[2]
         };
-        let help_text = if self.input_mode {
-            "[Enter] 发送评论  [Esc] 取消"
-        } else if !self.image_urls.is_empty() {
-            "[h/l] 图片  [j/k] 滚动  [Enter] 点赞  [c] 评论  [n] 加载更多  [q] 返回"
-        } else {
-            "[j/k] 滚动  [Enter] 点赞  [c] 评论  [n] 加载更多  [q] 返回"
-        };
-        let help = Paragraph::new(help_text)
-            .style(Style::default().fg(theme.fg_secondary))
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
             .alignment(Alignment::Center);
         frame.render_widget(help, help_chunk);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         // Handle input mode for adding comments
         if self.input_mode {
             match key {
@@ -456,6 +465,7 @@ impl Component for DynamicDetailPage {
                                     comment_type,
                                     message,
                                     root: None,
+                                    parent: None,
                                 });
                             }
                         }
@@ -707,35 +717,3 @@ impl DynamicDetailPage {
         frame.render_widget(comments, area);
     }
 }
-
-fn wrap_text(text: &str, width: usize) -> Vec<String> {
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
-    let mut current_width = 0;
-
-    for word in text.split_whitespace() {
-        let word_width = word.chars().count();
-        if current_width + word_width + 1 > width && !current_line.is_empty() {
-            lines.push(current_line.clone());
-            current_line.clear();
-            current_width = 0;
-        }
-
-        if !current_line.is_empty() {
-            current_line.push(' ');
-            current_width += 1;
-        }
-        current_line.push_str(word);
-        current_width += word_width;
-    }
-
-    if !current_line.is_empty() {
-        lines.push(current_line);
-    }
-
-    if lines.is_empty() {
-        lines.push(text.to_string());
-    }
-
-    lines
-}
@@ -0,0 +1,561 @@
+//! 收藏 page browsing the favorite folders of the logged-in user
+
+use super::help::{self, KeyHint};
+use super::video_card::{VideoCard, VideoCardGrid};
+use super::{Component, Theme};
+use crate::api::favorite::{FavFolder, FavMedia};
+use crate::api::BiliApi;
+use crate::app::AppAction;
+use crate::storage::{CoverFit, Keybindings, WatchedVideos};
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Favorites requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavoritesRequest {
+    /// The folder list
+    Folders,
+    /// First page of the shown folder
+    Videos,
+    /// Next page of the shown folder
+    More,
+}
+
+/// Pages of one folder loaded so far, kept while other folders are shown
+struct FolderContents {
+    medias: Vec<FavMedia>,
+    page: i32,
+    /// Items in the folder as the API counts them
+    total: i32,
+    has_more: bool,
+    /// Card that was selected when the folder was left
+    selected: usize,
+}
+
+pub struct FavoritesPage {
+    /// Account whose folders are shown
+    pub mid: i64,
+    folders: Vec<FavFolder>,
+    /// Index into `folders` of the folder on screen
+    pub folder_index: usize,
+    /// Loaded pages by folder id, so going back to a folder doesn't refetch it
+    contents: HashMap<i64, FolderContents>,
+    pub grid: VideoCardGrid,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub error_message: Option<String>,
+    /// Request behind the error being shown, re-issued by the retry key
+    failed: Option<FavoritesRequest>,
+    last_click_time: Option<Instant>,
+    last_click_index: Option<usize>,
+}
+
+impl FavoritesPage {
+    pub fn new(mid: i64) -> Self {
+        Self {
+            mid,
+            folders: Vec::new(),
+            folder_index: 0,
+            contents: HashMap::new(),
+            grid: VideoCardGrid::new(),
+            loading: true,
+            loading_more: false,
+            error_message: None,
+            failed: None,
+            last_click_time: None,
+            last_click_index: None,
+        }
+    }
+
+    /// Load the folder list and the first page of the first folder, dropping anything cached
+    pub async fn load(&mut self, api_client: &impl BiliApi) {
+        self.loading = true;
+        self.error_message = None;
+        self.failed = None;
+        self.contents.clear();
+        self.grid.clear();
+        match api_client.get_fav_folders(self.mid).await {
+            Ok(data) => {
+                self.folders = data.folders();
+                self.folder_index = self.folder_index.min(self.folders.len().saturating_sub(1));
+                self.load_folder(api_client).await;
+            }
+            Err(e) => {
+                self.failed = Some(FavoritesRequest::Folders);
+                self.error_message = Some(format!("加载收藏夹失败: {}", e));
+            }
+        }
+        self.loading = false;
+    }
+
+    /// Show the current folder, fetching its first page unless it is already cached
+    pub async fn load_folder(&mut self, api_client: &impl BiliApi) {
+        let Some(folder_id) = self.current_folder().map(|folder| folder.id) else {
+            self.grid.clear();
+            return;
+        };
+        if !self.contents.contains_key(&folder_id) {
+            self.loading = true;
+            self.error_message = None;
+            self.failed = None;
+            self.grid.clear();
+            match api_client.get_fav_videos(folder_id, 1).await {
+                Ok(data) => {
+                    let total = data.total();
+                    let has_more = data.has_more;
+                    self.contents.insert(
+                        folder_id,
+                        FolderContents {
+                            medias: data.medias(),
+                            page: 1,
+                            total,
+                            has_more,
+                            selected: 0,
+                        },
+                    );
+                }
+                Err(e) => {
+                    self.failed = Some(FavoritesRequest::Videos);
+                    self.error_message = Some(format!("加载收藏内容失败: {}", e));
+                }
+            }
+            self.loading = false;
+        }
+        self.show_folder();
+    }
+
+    /// Fetch the next page of the current folder
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if self.loading || self.loading_more || !self.has_more() {
+            return;
+        }
+        let Some(folder_id) = self.current_folder().map(|folder| folder.id) else {
+            return;
+        };
+        let next_page = self.contents.get(&folder_id).map_or(1, |c| c.page + 1);
+        self.loading_more = true;
+        match api_client.get_fav_videos(folder_id, next_page).await {
+            Ok(data) => {
+                self.failed = None;
+                let has_more = data.has_more;
+                let medias = data.medias();
+                if let Some(contents) = self.contents.get_mut(&folder_id) {
+                    contents.has_more = has_more && !medias.is_empty();
+                    if !medias.is_empty() {
+                        contents.page = next_page;
+                    }
+                    for media in &medias {
+                        self.grid.add_card(Self::card(media));
+                    }
+                    contents.medias.extend(medias);
+                }
+            }
+            Err(_) => self.failed = Some(FavoritesRequest::More),
+        }
+        self.loading_more = false;
+    }
+
+    /// Move `step` folders along, wrapping around; the caller loads the folder
+    pub fn switch_folder(&mut self, step: isize) {
+        if self.folders.is_empty() {
+            return;
+        }
+        if let Some(contents) = self
+            .current_folder()
+            .map(|folder| folder.id)
+            .and_then(|id| self.contents.get_mut(&id))
+        {
+            contents.selected = self.grid.selected_index;
+        }
+        let len = self.folders.len() as isize;
+        self.folder_index = (self.folder_index as isize + step).rem_euclid(len) as usize;
+        // An error belongs to the folder it came from
+        if self.failed != Some(FavoritesRequest::Folders) {
+            self.failed = None;
+            self.error_message = None;
+        }
+    }
+
+    fn current_folder(&self) -> Option<&FavFolder> {
+        self.folders.get(self.folder_index)
+    }
+
+    fn current_contents(&self) -> Option<&FolderContents> {
+        self.contents.get(&self.current_folder()?.id)
+    }
+
+    /// Rebuild the grid from the cached pages of the current folder
+    fn show_folder(&mut self) {
+        self.grid.clear();
+        let Some(contents) = self.current_contents() else {
+            return;
+        };
+        let cards: Vec<VideoCard> = contents.medias.iter().map(Self::card).collect();
+        let selected = contents.selected;
+        for card in cards {
+            self.grid.add_card(card);
+        }
+        self.grid.selected_index = selected.min(self.grid.cards.len().saturating_sub(1));
+        self.grid.update_scroll(self.grid.cached_visible_rows);
+    }
+
+    fn card(media: &FavMedia) -> VideoCard {
+        let (bvid, aid) = if media.is_video() {
+            (media.bvid.clone(), Some(media.id))
+        } else {
+            (None, None)
+        };
+        VideoCard::new(
+            bvid,
+            aid,
+            media.display_title().to_string(),
+            media.author_name().to_string(),
+            media.format_play(),
+            media.format_duration(),
+            media.cover_url(),
+        )
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<FavoritesRequest> {
+        self.failed.take()
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.current_contents()
+            .is_some_and(|contents| contents.has_more)
+    }
+
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        self.grid.mark_watched(watched);
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+    }
+
+    fn open_selected(&self, idx: usize) -> Option<AppAction> {
+        let card = self.grid.cards.get(idx)?;
+        Some(AppAction::OpenVideoDetail(
+            card.bvid.clone()?,
+            card.aid.unwrap_or(0),
+        ))
+    }
+
+    fn wants_more(&self) -> bool {
+        self.grid.is_near_bottom(3) && !self.loading_more && self.has_more()
+    }
+
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let mut hints = vec![
+            help::navigation(keys),
+            KeyHint::new(&keys.confirm, "详情"),
+            KeyHint::pair(&keys.section_prev, &keys.section_next, "收藏夹"),
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            help::page_switch(keys),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+
+    fn grid_area(area: Rect) -> Rect {
+        Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        }
+    }
+}
+
+impl Component for FavoritesPage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(10),   // Video grid
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        let title = match self.current_folder() {
+            Some(folder) => format!(" ⭐ {} ", folder.title),
+            None => " ⭐ 我的收藏 ".to_string(),
+        };
+        let plain_block = || {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border_unfocused))
+                .title(Span::styled(
+                    title.clone(),
+                    Style::default().fg(theme.bilibili_pink),
+                ))
+        };
+
+        if self.loading {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(loading, chunks[0]);
+        } else if let Some(error) = &self.error_message {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(error_widget, chunks[0]);
+        } else if self.grid.cards.is_empty() {
+            let text = if self.folders.is_empty() {
+                "还没有收藏夹"
+            } else {
+                "这个收藏夹是空的"
+            };
+            let empty = Paragraph::new(text)
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(empty, chunks[0]);
+        } else {
+            let total = self.current_contents().map_or(0, |contents| contents.total);
+            let header = Paragraph::new(Line::from(vec![
+                Span::styled(title, Style::default().fg(theme.bilibili_pink)),
+                Span::styled(
+                    format!("({}/{})", self.grid.cards.len(), total),
+                    Style::default().fg(theme.fg_muted),
+                ),
+                Span::styled(
+                    format!("  收藏夹 {}/{}", self.folder_index + 1, self.folders.len()),
+                    Style::default().fg(theme.fg_secondary),
+                ),
+                if self.loading_more {
+                    Span::styled(" 加载中...", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("")
+                },
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.border_subtle)),
+            );
+            let header_area = Rect {
+                height: 2,
+                ..chunks[0]
+            };
+            frame.render_widget(header, header_area);
+            self.grid.render(frame, Self::grid_area(chunks[0]), theme);
+        }
+
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) {
+            return Some(AppAction::Quit);
+        }
+        if keys.matches_nav_next(key) {
+            return Some(AppAction::NavNext);
+        }
+        if keys.matches_nav_prev(key) {
+            return Some(AppAction::NavPrev);
+        }
+        if keys.matches_down(key) {
+            self.grid.move_down();
+            if self.wants_more() {
+                return Some(AppAction::LoadMoreFavorites);
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_up(key) {
+            self.grid.move_up();
+            return Some(AppAction::None);
+        }
+        if keys.matches_right(key) {
+            self.grid.move_right();
+            return Some(AppAction::None);
+        }
+        if keys.matches_left(key) {
+            self.grid.move_left();
+            return Some(AppAction::None);
+        }
+        if keys.matches_confirm(key) {
+            return Some(
+                self.open_selected(self.grid.selected_index)
+                    .unwrap_or(AppAction::None),
+            );
+        }
+        if keys.matches_download(key) {
+            return Some(
+                self.grid
+                    .selected_bvid()
+                    .map_or(AppAction::None, AppAction::DownloadVideo),
+            );
+        }
+        if keys.matches_section_prev(key) {
+            return Some(AppAction::SwitchFavFolder(-1));
+        }
+        if keys.matches_section_next(key) {
+            return Some(AppAction::SwitchFavFolder(1));
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::RefreshFavorites);
+        }
+        Some(AppAction::None)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> Option<AppAction> {
+        match event.kind {
+            MouseEventKind::ScrollDown => {
+                if self.grid.move_down() && self.wants_more() {
+                    return Some(AppAction::LoadMoreFavorites);
+                }
+                None
+            }
+            MouseEventKind::ScrollUp => {
+                self.grid.move_up();
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let grid_area = Self::grid_area(area);
+                if !grid_area.contains(Position::new(event.column, event.row)) {
+                    return None;
+                }
+
+                let relative_y = event.row - grid_area.y;
+                let click_row = (relative_y / self.grid.card_height) as usize;
+                let actual_row = self.grid.scroll_row + click_row;
+                let card_width = (grid_area.width / self.grid.columns as u16).max(1);
+                let click_col = (event.column.saturating_sub(grid_area.x) / card_width) as usize;
+                let click_idx = actual_row * self.grid.columns + click_col;
+                if click_idx >= self.grid.cards.len() {
+                    return None;
+                }
+
+                let now = Instant::now();
+                let is_double_click = self.last_click_index == Some(click_idx)
+                    && self
+                        .last_click_time
+                        .is_some_and(|t| now.duration_since(t).as_millis() < 500);
+                if is_double_click {
+                    self.last_click_time = None;
+                    self.last_click_index = None;
+                    return self.open_selected(click_idx);
+                }
+                self.grid.selected_index = click_idx;
+                self.grid.update_scroll(self.grid.cached_visible_rows);
+                self.last_click_time = Some(now);
+                self.last_click_index = Some(click_idx);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_load_shows_first_folder_with_total() {
+        let mut page = FavoritesPage::new(10001);
+        page.load(&MockApi::new()).await;
+
+        assert_eq!(page.grid.cards.len(), 2);
+        assert!(page.has_more());
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "默认收藏夹"));
+        assert!(contains(&buffer, "(2/45)"));
+        assert!(contains(&buffer, "收藏夹 1/2"));
+
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &Keybindings::default()),
+            Some(AppAction::OpenVideoDetail(bvid, 10401)) if bvid == "BV1mock0401"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_more_appends_the_next_page() {
+        let api = MockApi::new();
+        let mut page = FavoritesPage::new(10001);
+        page.load(&api).await;
+
+        page.load_more(&api).await;
+        assert_eq!(page.grid.cards.len(), 4);
+        assert_eq!(page.current_contents().unwrap().page, 2);
+        assert!(contains(&render(&mut page), "(4/45)"));
+    }
+
+    #[tokio::test]
+    async fn test_switching_back_keeps_loaded_pages() {
+        let api = MockApi::new();
+        let mut page = FavoritesPage::new(10001);
+        page.load(&api).await;
+        page.load_more(&api).await;
+        page.grid.selected_index = 3;
+        let calls = api.calls();
+
+        page.switch_folder(1);
+        page.load_folder(&api).await;
+        assert_eq!(api.calls(), calls + 1);
+        assert_eq!(page.grid.cards.len(), 2);
+        assert_eq!(page.grid.selected_index, 0);
+
+        page.switch_folder(-1);
+        page.load_folder(&api).await;
+        assert_eq!(api.calls(), calls + 1);
+        assert_eq!(page.grid.cards.len(), 4);
+        assert_eq!(page.grid.selected_index, 3);
+        assert!(contains(&render(&mut page), "(4/45)"));
+    }
+
+    #[tokio::test]
+    async fn test_load_error_offers_retry() {
+        let mut page = FavoritesPage::new(10001);
+        page.load(&MockApi::failing()).await;
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 加载收藏夹失败"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &Keybindings::default()),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(FavoritesRequest::Folders));
+    }
+}
@@ -0,0 +1,121 @@
+//! Footer key hints generated from the active keymap
+//!
+//! Pages list the actions they handle in their current focus, so the footer always
+//! shows the keys the user actually configured.
+
+use super::Theme;
+use crate::storage::Keybindings;
+use ratatui::prelude::*;
+
+/// One "[key] label" entry of a footer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHint {
+    pub keys: String,
+    pub label: &'static str,
+}
+
+impl KeyHint {
+    pub fn new(binding: &str, label: &'static str) -> Self {
+        Self {
+            keys: key_label(binding),
+            label,
+        }
+    }
+
+    /// Several bindings for one action, e.g. previous/next shown as "h/l"
+    pub fn pair(first: &str, second: &str, label: &'static str) -> Self {
+        Self {
+            keys: format!("{}/{}", key_label(first), key_label(second)),
+            label,
+        }
+    }
+
+    /// A key the page handles directly rather than through the keymap, e.g. Esc in text input
+    pub fn fixed(keys: &str, label: &'static str) -> Self {
+        Self {
+            keys: keys.to_string(),
+            label,
+        }
+    }
+}
+
+/// Short display form of a keymap entry
+pub fn key_label(binding: &str) -> String {
+    match binding {
+        "Up" => "↑".to_string(),
+        "Down" => "↓".to_string(),
+        "Left" => "←".to_string(),
+        "Right" => "→".to_string(),
+        "BackTab" => "S-Tab".to_string(),
+        "PageUp" => "PgUp".to_string(),
+        "PageDown" => "PgDn".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Grid movement with the configured keys and the arrows, which always work as well
+pub fn navigation(keys: &Keybindings) -> KeyHint {
+    KeyHint {
+        keys: format!(
+            "←↑↓→/{}{}{}{}",
+            key_label(&keys.nav_left),
+            key_label(&keys.nav_down),
+            key_label(&keys.nav_up),
+            key_label(&keys.nav_right)
+        ),
+        label: "导航",
+    }
+}
+
+/// Vertical movement only, for lists and scrolling panes
+pub fn scrolling(keys: &Keybindings) -> KeyHint {
+    KeyHint::pair(&keys.nav_down, &keys.nav_up, "滚动")
+}
+
+/// Switching sidebar pages
+pub fn page_switch(keys: &Keybindings) -> KeyHint {
+    KeyHint::new(&keys.nav_next_page, "切页面")
+}
+
+/// Render hints as one centered-ready line, keys highlighted
+pub fn hints_line(hints: &[KeyHint], theme: &Theme) -> Line<'static> {
+    let bracket = Style::default().fg(theme.fg_secondary);
+    let key = Style::default()
+        .fg(theme.fg_accent)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::with_capacity(hints.len() * 4);
+    for (idx, hint) in hints.iter().enumerate() {
+        let open = if idx == 0 { "[" } else { "  [" };
+        spans.push(Span::styled(open, bracket));
+        spans.push(Span::styled(hint.keys.clone(), key));
+        spans.push(Span::styled("] ", bracket));
+        spans.push(Span::styled(hint.label, bracket));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints_follow_keymap() {
+        let mut keys = Keybindings::default();
+        assert_eq!(navigation(&keys).keys, "←↑↓→/hjkl");
+        assert_eq!(page_switch(&keys).keys, "Tab");
+
+        keys.nav_up = "w".to_string();
+        keys.nav_down = "s".to_string();
+        keys.nav_next_page = "BackTab".to_string();
+        assert_eq!(scrolling(&keys).keys, "s/w");
+        assert_eq!(page_switch(&keys).keys, "S-Tab");
+    }
+
+    #[test]
+    fn test_hints_line_text() {
+        let hints = [KeyHint::new("Enter", "播放"), KeyHint::fixed("Esc", "取消")];
+        let line = hints_line(&hints, &Theme::default());
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "[Enter] 播放  [Esc] 取消");
+    }
+}
@@ -1,10 +1,15 @@
 //! History page with watch history display in a grid layout with cover images
 
+use super::cover::{
+    cover_box, download_image, fit_cover, prefetch_range, render_cover_placeholder, spinner_frame,
+    CoverTasks, DownloadLimiter,
+};
 use super::{Component, Theme};
-use crate::api::client::ApiClient;
 use crate::api::history::{HistoryCursor, HistoryItem};
+use crate::api::BiliApi;
 use crate::app::AppAction;
-use image::DynamicImage;
+use crate::storage::{CoverFit, Keybindings};
+use crate::util::truncate_display;
 use ratatui::{
     crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
@@ -26,19 +31,28 @@ struct HistoryCard {
 struct CoverResult {
     index: usize,
     protocol: StatefulProtocol,
+    generation: u64,
 }
 
 pub struct HistoryPage {
     items: Vec<HistoryCard>,
     selected: usize,
     scroll_offset: usize,
+    /// Rows that fit in the grid area on the last draw
+    cached_visible_rows: usize,
     loading: bool,
     error: Option<String>,
     picker: Arc<Picker>,
     cursor: Option<HistoryCursor>,
     has_more: bool,
+    /// Entry deleted optimistically, put back if the server refuses
+    removed: Option<(usize, Box<HistoryCard>)>,
+    /// `kid`s of the entries marked for batch actions
+    marked: HashSet<String>,
 
     pending_downloads: HashSet<usize>,
+    download_limiter: DownloadLimiter,
+    cover_tasks: CoverTasks,
     cover_rx: mpsc::Receiver<CoverResult>,
     cover_tx: mpsc::Sender<CoverResult>,
 
@@ -47,20 +61,28 @@ pub struct HistoryPage {
 }
 
 impl HistoryPage {
+    /// Rows assumed before the first draw
+    const DEFAULT_VISIBLE_ROWS: usize = 3;
+
     pub fn new() -> Self {
-        let picker = Arc::new(Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks()));
+        let picker = super::image_protocol::picker();
         let (tx, rx) = mpsc::channel(32);
 
         Self {
             items: Vec::new(),
             selected: 0,
             scroll_offset: 0,
+            cached_visible_rows: Self::DEFAULT_VISIBLE_ROWS,
             loading: false,
             error: None,
             picker,
             cursor: None,
             has_more: true,
+            removed: None,
+            marked: HashSet::new(),
             pending_downloads: HashSet::new(),
+            download_limiter: DownloadLimiter::new(),
+            cover_tasks: CoverTasks::new(),
             cover_rx: rx,
             cover_tx: tx,
             last_click_time: None,
@@ -68,9 +90,11 @@ impl HistoryPage {
         }
     }
 
-    pub async fn load_history(&mut self, api_client: &ApiClient) {
+    pub async fn load_history(&mut self, api_client: &impl BiliApi) {
         self.loading = true;
         self.error = None;
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
 
         match api_client.get_history(None, None, None).await {
             Ok(data) => {
@@ -93,7 +117,7 @@ impl HistoryPage {
         }
     }
 
-    pub async fn load_more(&mut self, api_client: &ApiClient) {
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
         if self.loading || !self.has_more {
             return;
         }
@@ -148,17 +172,43 @@ impl HistoryPage {
     }
 
     /// Start background downloads for visible covers (non-blocking)
-    pub fn start_cover_downloads(&mut self) {
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
         if self.items.is_empty() {
             return;
         }
+        let semaphore = self.download_limiter.semaphore(concurrency);
+        let generation = self.cover_tasks.generation();
 
         // Calculate visible range
         let cols = 4;
-        let visible_start = self.scroll_offset * cols;
-        let visible_end = (visible_start + 5 * cols).min(self.items.len());
+        let range = prefetch_range(
+            self.scroll_offset,
+            self.cached_visible_rows,
+            prefetch_rows,
+            cols,
+            self.items.len(),
+        );
+        if !self.cover_tasks.needs_scan(&range, self.items.len()) {
+            return;
+        }
+        let loaded = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.cover_protocol.is_some())
+            .map(|(idx, _)| idx)
+            .collect();
+        for idx in self.cover_tasks.evict(range.clone(), loaded, cache_size) {
+            self.items[idx].cover_protocol = None;
+        }
 
-        for idx in visible_start..visible_end {
+        for idx in range {
             if self.items[idx].cover_protocol.is_some() || self.pending_downloads.contains(&idx) {
                 continue;
             }
@@ -171,35 +221,159 @@ impl HistoryPage {
             let url = cover_url.to_string();
             let tx = self.cover_tx.clone();
             let picker = Arc::clone(&self.picker);
-
-            tokio::spawn(async move {
-                if let Some(img) = Self::download_image(&url).await {
-                    let protocol = picker.new_resize_protocol(img);
+            let semaphore = Arc::clone(&semaphore);
+
+            let task = tokio::spawn(async move {
+                let Ok(_permit) = semaphore.acquire_owned().await else {
+                    return;
+                };
+                if let Some(img) = download_image(&url).await {
+                    let protocol = picker.new_resize_protocol(fit_cover(img, cover_fit));
                     let _ = tx
                         .send(CoverResult {
                             index: idx,
                             protocol,
+                            generation,
                         })
                         .await;
                 }
             });
+            self.cover_tasks.track(task.abort_handle());
+        }
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        for card in &mut self.items {
+            card.cover_protocol = None;
         }
     }
 
     /// Poll for completed cover downloads (non-blocking)
-    pub fn poll_cover_results(&mut self) {
+    pub fn poll_cover_results(&mut self) -> bool {
+        let mut arrived = false;
         while let Ok(result) = self.cover_rx.try_recv() {
+            if !self.cover_tasks.is_current(result.generation) {
+                continue;
+            }
             self.pending_downloads.remove(&result.index);
             if result.index < self.items.len() {
                 self.items[result.index].cover_protocol = Some(result.protocol);
+                arrived = true;
             }
         }
+        arrived
+    }
+
+    /// Drop the selected entry from the list and ask the app to delete it on the server
+    fn remove_selected(&mut self) -> Option<AppAction> {
+        if self.selected >= self.items.len() || self.removed.is_some() {
+            return None;
+        }
+        let card = self.items.remove(self.selected);
+        let kid = card.item.delete_kid();
+        self.removed = Some((self.selected, Box::new(card)));
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+        // Cover results are matched by index, which just shifted
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        Some(AppAction::DeleteHistory(kid))
+    }
+
+    /// The server deleted the entry
+    pub fn confirm_removed(&mut self) {
+        self.removed = None;
+    }
+
+    /// The server refused the delete, put the entry back where it was
+    pub fn restore_removed(&mut self) {
+        if let Some((idx, card)) = self.removed.take() {
+            let idx = idx.min(self.items.len());
+            self.items.insert(idx, *card);
+            self.selected = idx;
+            self.cover_tasks.cancel();
+            self.pending_downloads.clear();
+        }
+    }
+
+    /// Mark or unmark the selected entry for batch actions
+    fn toggle_mark(&mut self) {
+        let Some(card) = self.items.get(self.selected) else {
+            return;
+        };
+        let kid = card.item.delete_kid();
+        if !self.marked.remove(&kid) {
+            self.marked.insert(kid);
+        }
+    }
+
+    /// Marked entries in list order
+    fn marked_items(&self) -> impl Iterator<Item = &HistoryItem> {
+        self.items
+            .iter()
+            .map(|card| &card.item)
+            .filter(|item| self.marked.contains(&item.delete_kid()))
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// The server deleted these entries in a batch
+    pub fn remove_entries(&mut self, kids: &[String]) {
+        if kids.is_empty() {
+            return;
+        }
+        self.items
+            .retain(|card| !kids.contains(&card.item.delete_kid()));
+        for kid in kids {
+            self.marked.remove(kid);
+        }
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
     }
 
-    async fn download_image(url: &str) -> Option<DynamicImage> {
-        let response = reqwest::get(url).await.ok()?;
-        let bytes = response.bytes().await.ok()?;
-        image::load_from_memory(&bytes).ok()
+    /// The whole history was cleared on the server
+    pub fn clear_items(&mut self) {
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        self.items.clear();
+        self.marked.clear();
+        self.removed = None;
+        self.selected = 0;
+        self.scroll_offset = 0;
+        self.cursor = None;
+        self.has_more = false;
+    }
+
+    /// Videos open their detail page, bangumi episodes without a bvid play directly
+    fn open_item(&self, idx: usize) -> Option<AppAction> {
+        let item = &self.items.get(idx)?.item;
+        if !item.is_video() {
+            return None;
+        }
+        if let Some(bvid) = item.get_bvid() {
+            return Some(AppAction::OpenVideoDetail(
+                bvid.to_string(),
+                item.history.oid,
+            ));
+        }
+        let url = item.play_url()?;
+        let label = item
+            .show_title
+            .clone()
+            .unwrap_or_else(|| item.title.clone());
+        Some(AppAction::PlayEpisode {
+            url,
+            label,
+            aid: item.history.oid,
+            cid: item.history.cid,
+            duration: item.duration,
+            confirmed: false,
+        })
     }
 
     fn visible_rows(&self, height: u16) -> usize {
@@ -228,14 +402,17 @@ impl Default for HistoryPage {
 }
 
 impl Component for HistoryPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, _keys: &Keybindings) {
         // Main block
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.border_subtle))
             .title(Span::styled(
-                " 📜 观看历史 ",
+                match self.marked.len() {
+                    0 => " 📜 观看历史 ".to_string(),
+                    n => format!(" 📜 观看历史 · 已选 {} 项 ", n),
+                },
                 Style::default()
                     .fg(theme.bilibili_pink)
                     .add_modifier(Modifier::BOLD),
@@ -276,17 +453,21 @@ impl Component for HistoryPage {
         self.render_grid(frame, inner, theme);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         let cols = 4;
         let total = self.items.len();
 
+        if keys.matches_back(key) && !self.marked.is_empty() {
+            self.marked.clear();
+            return None;
+        }
         if keys.matches_quit(key) || keys.matches_back(key) {
             return Some(AppAction::BackToList);
         }
+        if keys.matches_toggle_select(key) {
+            self.toggle_mark();
+            return None;
+        }
         if keys.matches_left(key) {
             if self.selected > 0 {
                 self.selected -= 1;
@@ -310,22 +491,38 @@ impl Component for HistoryPage {
                 self.selected += cols;
             }
             // Check if we need to load more
-            if self.is_near_bottom(4) {
+            if self.is_near_bottom(self.cached_visible_rows) {
                 return Some(AppAction::LoadMoreHistory);
             }
             return None;
         }
         if keys.matches_confirm(key) {
-            if let Some(card) = self.items.get(self.selected) {
-                // Only open video detail for video types
-                if card.item.is_video() {
-                    if let Some(bvid) = card.item.get_bvid() {
-                        let aid = card.item.history.oid;
-                        return Some(AppAction::OpenVideoDetail(bvid.to_string(), aid));
-                    }
-                }
+            return self.open_item(self.selected);
+        }
+        if keys.matches_download(key) && !self.marked.is_empty() {
+            let bvids: Vec<String> = self
+                .marked_items()
+                .filter_map(|item| item.get_bvid().map(str::to_string))
+                .collect();
+            self.marked.clear();
+            return (!bvids.is_empty()).then_some(AppAction::DownloadVideos(bvids));
+        }
+        if keys.matches_download(key) {
+            let bvid = self.items.get(self.selected)?.item.get_bvid()?;
+            return Some(AppAction::DownloadVideo(bvid.to_string()));
+        }
+        if keys.matches_delete_history(key) && !self.marked.is_empty() {
+            let kids = self.marked_items().map(|item| item.delete_kid()).collect();
+            return Some(AppAction::RequestDeleteHistoryBatch(kids));
+        }
+        if keys.matches_delete_history(key) {
+            return self.remove_selected();
+        }
+        if keys.matches_clear_history(key) {
+            if self.items.is_empty() {
+                return None;
             }
-            return None;
+            return Some(AppAction::RequestClearHistory);
         }
         if keys.matches_nav_next(key) {
             return Some(AppAction::NavNext);
@@ -333,9 +530,6 @@ impl Component for HistoryPage {
         if keys.matches_nav_prev(key) {
             return Some(AppAction::NavPrev);
         }
-        if keys.matches_next_theme(key) {
-            return Some(AppAction::NextTheme);
-        }
         None
     }
 
@@ -347,7 +541,7 @@ impl Component for HistoryPage {
             MouseEventKind::ScrollDown => {
                 if self.selected + cols < total {
                     self.selected += cols;
-                    if self.is_near_bottom(4) {
+                    if self.is_near_bottom(self.cached_visible_rows) {
                         return Some(AppAction::LoadMoreHistory);
                     }
                 }
@@ -387,14 +581,7 @@ impl Component for HistoryPage {
                     if is_double_click {
                         self.last_click_time = None;
                         self.last_click_index = None;
-                        if let Some(card) = self.items.get(click_idx) {
-                            if card.item.is_video() {
-                                if let Some(bvid) = card.item.get_bvid() {
-                                    let aid = card.item.history.oid;
-                                    return Some(AppAction::OpenVideoDetail(bvid.to_string(), aid));
-                                }
-                            }
-                        }
+                        return self.open_item(click_idx);
                     } else {
                         self.selected = click_idx;
                         let visible_rows = self.visible_rows(area.height);
@@ -414,6 +601,7 @@ impl HistoryPage {
     fn render_grid(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let cols = 4;
         let visible_rows = self.visible_rows(area.height);
+        self.cached_visible_rows = visible_rows;
         self.update_scroll(visible_rows);
 
         let card_height = 12u16;
@@ -457,11 +645,15 @@ impl HistoryPage {
         is_selected: bool,
         theme: &Theme,
     ) {
+        let pending = self.pending_downloads.contains(&idx);
+        let marked = self.marked.contains(&self.items[idx].item.delete_kid());
         let card = &mut self.items[idx];
 
-        // Card border
+        // Card border, marked entries keep their color while the cursor is elsewhere
         let border_color = if is_selected {
             theme.bilibili_pink
+        } else if marked {
+            theme.success
         } else {
             theme.border_subtle
         };
@@ -489,17 +681,18 @@ impl HistoryPage {
             .constraints([Constraint::Length(cover_height), Constraint::Min(3)])
             .split(inner);
 
-        // Render cover
+        let cover_area = cover_box(chunks[0]);
         if let Some(ref mut protocol) = card.cover_protocol {
             let image = StatefulImage::default();
-            frame.render_stateful_widget(image, chunks[0], protocol);
+            frame.render_stateful_widget(image, cover_area, protocol);
         } else {
-            // Placeholder with badge
-            let badge = card.item.badge.as_deref().unwrap_or("");
-            let placeholder = Paragraph::new(badge)
-                .alignment(Alignment::Center)
-                .style(Style::default().fg(theme.fg_muted).bg(theme.bg_secondary));
-            frame.render_widget(placeholder, chunks[0]);
+            // Entries without a cover show their badge, e.g. 直播 or 专栏
+            let label = if pending {
+                spinner_frame()
+            } else {
+                card.item.badge.as_deref().unwrap_or("")
+            };
+            render_cover_placeholder(frame, cover_area, theme, label);
         }
 
         // Info area
@@ -513,7 +706,14 @@ impl HistoryPage {
             .split(chunks[1]);
 
         // Title (2 lines)
-        let title = &card.item.title;
+        // Two wrapped lines, keep a little slack for word-boundary wrapping
+        let title_cols = (info_chunks[0].width as usize * 2).saturating_sub(4);
+        let title = if marked {
+            format!("✓ {}", card.item.title)
+        } else {
+            card.item.title.clone()
+        };
+        let title = truncate_display(&title, title_cols);
         let title_style = if is_selected {
             Style::default()
                 .fg(theme.fg_primary)
@@ -521,18 +721,33 @@ impl HistoryPage {
         } else {
             Style::default().fg(theme.fg_primary)
         };
-        let title_widget = Paragraph::new(title.as_str())
+        let title_widget = Paragraph::new(title)
             .style(title_style)
             .wrap(Wrap { trim: true });
         frame.render_widget(title_widget, info_chunks[0]);
 
-        // Author + view time
+        // Author + view time, bangumi episodes get a badge in front
         let author = &card.item.author_name;
         let view_time = card.item.format_view_time();
-        let info_text = format!("{} · {}", author, view_time);
-        let info_widget = Paragraph::new(info_text)
-            .style(Style::default().fg(theme.fg_muted))
-            .wrap(Wrap { trim: true });
+        let badge = if card.item.is_bangumi() {
+            "番剧 "
+        } else {
+            ""
+        };
+        let info_text = truncate_display(
+            &format!("{} · {}", author, view_time),
+            (info_chunks[1].width as usize).saturating_sub(Line::from(badge).width()),
+        );
+        let info_widget = Paragraph::new(Line::from(vec![
+            Span::styled(
+                badge,
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(info_text, Style::default().fg(theme.fg_muted)),
+        ]))
+        .wrap(Wrap { trim: true });
         frame.render_widget(info_widget, info_chunks[1]);
 
         // Progress / Duration
@@ -548,3 +763,63 @@ impl HistoryPage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_delete_is_reverted_on_failure() {
+        let keys = Keybindings::default();
+        let mut page = HistoryPage::new();
+        page.load_history(&MockApi::new()).await;
+        let kid = page.items[0].item.delete_kid();
+
+        let action = page.handle_input(KeyCode::Char('d'), &keys);
+        assert!(matches!(action, Some(AppAction::DeleteHistory(ref k)) if *k == kid));
+        assert!(page.items.is_empty());
+
+        page.restore_removed();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].item.delete_kid(), kid);
+
+        page.handle_input(KeyCode::Char('d'), &keys);
+        page.confirm_removed();
+        page.restore_removed();
+        assert!(page.items.is_empty());
+        assert!(page.handle_input(KeyCode::Char('X'), &keys).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_batch_actions_on_marked_entries() {
+        let keys = Keybindings::default();
+        let mut page = HistoryPage::new();
+        page.load_history(&MockApi::new()).await;
+        let kid = page.items[0].item.delete_kid();
+
+        page.handle_input(KeyCode::Char(' '), &keys);
+        assert!(contains(&render(&mut page), "已选 1 项"));
+
+        // Esc drops the marks before it leaves the page
+        assert!(page.handle_input(KeyCode::Esc, &keys).is_none());
+        assert!(page.marked.is_empty());
+
+        page.handle_input(KeyCode::Char(' '), &keys);
+        let action = page.handle_input(KeyCode::Char('D'), &keys);
+        assert!(matches!(action, Some(AppAction::DownloadVideos(ref b)) if b == &["BV1mock0001"]));
+        assert!(page.marked.is_empty());
+
+        page.handle_input(KeyCode::Char(' '), &keys);
+        let action = page.handle_input(KeyCode::Char('d'), &keys);
+        assert!(
+            matches!(action, Some(AppAction::RequestDeleteHistoryBatch(ref k)) if *k == vec![kid.clone()])
+        );
+        assert_eq!(page.items.len(), 1);
+
+        page.remove_entries(&[kid]);
+        assert!(page.items.is_empty());
+        assert!(page.marked.is_empty());
+    }
+}
@@ -1,10 +1,19 @@
-//! Homepage with video recommendations in a grid layout with cover images
+//! Homepage with video recommendations in a cover grid or a compact text list
 
+use super::cover::{
+    cover_box, download_image, fit_cover, prefetch_range, render_cover_placeholder, spinner_frame,
+    CoverTasks, DownloadLimiter,
+};
+use super::help::{self, KeyHint};
+use super::toast::Toast;
+use super::video_card::MAX_TOP_UPS;
 use super::{Component, Theme};
-use crate::api::client::ApiClient;
-use crate::api::recommend::VideoItem;
-use crate::app::AppAction;
-use image::DynamicImage;
+use crate::api::comment::{self, CommentType};
+use crate::api::recommend::{FeedTarget, FreshType, VideoItem};
+use crate::api::BiliApi;
+use crate::app::{next_request_id, AppAction};
+use crate::storage::{CoverFit, HomeViewMode, Keybindings, RecommendFilter, WatchedVideos};
+use crate::util::truncate_display;
 use ratatui::{
     crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
@@ -15,34 +24,157 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::mpsc;
+use unicode_width::UnicodeWidthStr;
 
 /// Video card with cached cover image
 pub struct VideoCard {
     pub video: VideoItem,
     pub cover: Option<StatefulProtocol>,
+    /// Already launched from this client
+    pub watched: bool,
+}
+
+impl VideoCard {
+    fn new(video: VideoItem) -> Self {
+        Self {
+            video,
+            cover: None,
+            watched: false,
+        }
+    }
+}
+
+/// Home requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomeRequest {
+    /// Replacing the grid with the next feed page
+    Recommendations,
+    /// Appending the next feed page
+    More,
+    /// Swapping in the next feed page in place, see [`HomePage::refresh`]
+    Refresh,
+}
+
+/// Feed query recorded by [`HomePage::begin`], copied into the task that runs it
+#[derive(Debug, Clone, Copy)]
+pub struct FeedQuery {
+    pub kind: HomeRequest,
+    /// Matched against the page's latest query when the answer arrives
+    id: u64,
+    /// Last feed page fetched before this query
+    fresh_idx: i32,
+    fresh_type: FreshType,
+    filter: RecommendFilter,
+}
+
+/// Answer to a [`FeedQuery`], applied by [`HomePage::finish`]
+pub struct FeedAnswer {
+    query: FeedQuery,
+    /// Last feed page fetched, unchanged when the first one failed
+    fresh_idx: i32,
+    /// Answered from the public 热门 list because no one is logged in
+    popular: bool,
+    videos: anyhow::Result<Vec<VideoItem>>,
+}
+
+/// Next feed page with filtered-out videos backfilled from up to [`HomePage::BACKFILL_PAGES`] more
+///
+/// Stops once as many videos passed as one unfiltered page holds. A failure only counts
+/// when nothing was fetched yet, otherwise the videos so far are returned.
+pub async fn fetch_feed(api_client: &impl BiliApi, query: FeedQuery) -> FeedAnswer {
+    let mut fresh_idx = query.fresh_idx;
+    let mut kept = Vec::new();
+    let mut target = None;
+    let mut error = None;
+    for _ in 0..=HomePage::BACKFILL_PAGES {
+        let page = match api_client
+            .get_recommendations_paged(fresh_idx + 1, query.fresh_type)
+            .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                if kept.is_empty() {
+                    error = Some(e);
+                }
+                break;
+            }
+        };
+        fresh_idx += 1;
+        // Ads never make it into the grid, so they don't count towards a full batch
+        let page: Vec<VideoItem> = page.into_iter().filter(|video| !video.is_ad()).collect();
+        let target = *target.get_or_insert(page.len());
+        if page.is_empty() {
+            break;
+        }
+        kept.extend(page.into_iter().filter(|video| {
+            query
+                .filter
+                .allows(video.stat.as_ref().and_then(|s| s.view), video.duration)
+        }));
+        if kept.len() >= target {
+            break;
+        }
+    }
+    FeedAnswer {
+        query,
+        fresh_idx,
+        popular: !api_client.is_logged_in(),
+        videos: error.map_or(Ok(kept), Err),
+    }
 }
 
 /// Message for completed cover download
 pub struct CoverResult {
     pub index: usize,
     pub protocol: StatefulProtocol,
+    /// `CoverTasks` generation the download was started in
+    pub generation: u64,
 }
 
 pub struct HomePage {
     videos: Vec<VideoCard>,
+    /// Watched cards kept out of `videos` while hiding is on, with their index in the full list
+    hidden: Vec<(usize, VideoCard)>,
     selected_index: usize,
     loading: bool,
     error_message: Option<String>,
+    /// Request behind the error or toast being shown, re-issued by the retry key
+    failed: Option<HomeRequest>,
     scroll_row: usize,
     picker: Arc<Picker>,
+    /// Columns of the last grid draw
     columns: usize,
+    /// Fixed column count from the settings, `None` fits columns to the width
+    column_setting: Option<usize>,
     card_height: u16,
+    view_mode: HomeViewMode,
+    /// Rows that fit in the grid area on the last draw
+    cached_visible_rows: usize,
     // Async cover loading
     cover_tx: mpsc::Sender<CoverResult>,
     cover_rx: mpsc::Receiver<CoverResult>,
     pending_downloads: HashSet<usize>,
+    download_limiter: DownloadLimiter,
+    cover_tasks: CoverTasks,
+    /// Last feed page requested, kept across refreshes so each one rotates to new items
     fresh_idx: i32,
+    fresh_type: FreshType,
+    /// Showing the public 热门 list because no one is logged in
+    popular: bool,
+    /// Selection restored from the last session, applied once recommendations load
+    restore_index: Option<usize>,
     loading_more: bool,
+    /// Views and duration limits applied to every loaded page
+    filter: RecommendFilter,
+    /// The refresh key swaps batches in place instead of starting over from the top
+    keep_position: bool,
+    /// `l` on the last video wraps to the first and `h` on the first to the last
+    wrap_navigation: bool,
+    /// Id of the latest feed request, answers to older ones are dropped
+    request_id: u64,
+    /// Pages loaded by [`top_up`](Self::top_up) since the last reload or refresh
+    top_ups: usize,
+    toast: Option<Toast>,
     // Double-click detection
     last_click_time: Option<Instant>,
     last_click_index: Option<usize>,
@@ -51,85 +183,409 @@ pub struct HomePage {
 impl HomePage {
     /// 默认列数
     const DEFAULT_COLUMNS: usize = 3;
+    /// 列数上限
+    pub const MAX_COLUMNS: usize = 6;
+    /// 自动列数时每张卡片的最小宽度
+    const MIN_CARD_WIDTH: u16 = 36;
     /// 卡片高度
     const CARD_HEIGHT: u16 = 10;
-    /// 预加载行数（用于提前下载封面）
-    const PREFETCH_ROWS: usize = 4;
-    /// 默认可见行数（用于滚动计算）
+    /// 首次绘制前使用的可见行数
     const DEFAULT_VISIBLE_ROWS: usize = 3;
+    /// 列表模式下 UP 主名称的最大显示宽度
+    const LIST_AUTHOR_COLS: usize = 12;
+    /// 筛选掉的视频最多再请求几页来补足
+    const BACKFILL_PAGES: usize = 3;
 
     pub fn new() -> Self {
         // Try to detect terminal graphics protocol (Kitty/Sixel/iTerm2)
         // Fall back to halfblocks if detection fails
-        let picker = Arc::new(Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks()));
+        let picker = super::image_protocol::picker();
 
         // Create channel for background image downloads
         let (cover_tx, cover_rx) = mpsc::channel(32);
 
         Self {
             videos: Vec::new(),
+            hidden: Vec::new(),
             selected_index: 0,
             loading: true,
             error_message: None,
+            failed: None,
             scroll_row: 0,
             picker,
             columns: Self::DEFAULT_COLUMNS,
+            column_setting: None,
             card_height: Self::CARD_HEIGHT,
+            view_mode: HomeViewMode::default(),
+            cached_visible_rows: Self::DEFAULT_VISIBLE_ROWS,
             cover_tx,
             cover_rx,
             pending_downloads: HashSet::new(),
-            fresh_idx: 1,
+            download_limiter: DownloadLimiter::new(),
+            cover_tasks: CoverTasks::new(),
+            fresh_idx: 0,
+            fresh_type: FreshType::default(),
+            popular: false,
+            restore_index: None,
             loading_more: false,
+            filter: RecommendFilter::default(),
+            keep_position: true,
+            wrap_navigation: false,
+            request_id: 0,
+            top_ups: 0,
+            toast: None,
             last_click_time: None,
             last_click_index: None,
         }
     }
 
-    pub async fn load_recommendations(&mut self, api_client: &ApiClient) {
-        self.loading = true;
-        self.error_message = None;
-        self.pending_downloads.clear();
-        self.fresh_idx = 1;
+    /// Pick up a previous session: the feed source applies now, the selection after loading
+    pub fn restore(&mut self, fresh_type: FreshType, selected_index: usize) {
+        self.fresh_type = fresh_type;
+        self.restore_index = Some(selected_index);
+    }
+
+    pub fn fresh_type(&self) -> FreshType {
+        self.fresh_type
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Switch layout, the selected video stays selected and is scrolled to the top
+    pub fn set_view_mode(&mut self, mode: HomeViewMode) {
+        if self.view_mode == mode {
+            return;
+        }
+        self.view_mode = mode;
+        self.scroll_row = self.selected_row();
+    }
+
+    /// Use a fixed number of grid columns, or fit them to the width with `None`
+    pub fn set_grid_columns(&mut self, columns: Option<usize>) {
+        self.column_setting = columns.map(|n| n.clamp(1, Self::MAX_COLUMNS));
+    }
+
+    /// Columns for a grid `width` cells wide
+    fn grid_columns(&self, width: u16) -> usize {
+        self.column_setting.unwrap_or_else(|| {
+            ((width / Self::MIN_CARD_WIDTH) as usize).clamp(1, Self::MAX_COLUMNS)
+        })
+    }
+
+    /// Videos per row: the grid columns, or one in list mode
+    fn row_len(&self) -> usize {
+        match self.view_mode {
+            HomeViewMode::Grid => self.columns,
+            HomeViewMode::List => 1,
+        }
+    }
+
+    fn row_height(&self) -> u16 {
+        match self.view_mode {
+            HomeViewMode::Grid => self.card_height,
+            HomeViewMode::List => 1,
+        }
+    }
 
-        match api_client.get_recommendations().await {
+    /// Limit the feed by views and duration, applied from the next loaded page
+    pub fn set_filter(&mut self, filter: RecommendFilter) {
+        self.filter = filter;
+    }
+
+    pub fn set_keep_position(&mut self, keep: bool) {
+        self.keep_position = keep;
+    }
+
+    pub fn set_wrap_navigation(&mut self, wrap: bool) {
+        self.wrap_navigation = wrap;
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<HomeRequest> {
+        self.failed.take()
+    }
+
+    /// Start the next refresh from the feed's first page again
+    pub fn reset_feed(&mut self) {
+        self.fresh_idx = 0;
+    }
+
+    /// Header label of the feed, the 热门 fallback has no feed modes
+    fn feed_label(&self) -> &'static str {
+        if self.popular {
+            "热门"
+        } else {
+            "推荐"
+        }
+    }
+
+    /// Record a feed request of `kind` and return what the task has to fetch
+    ///
+    /// A reload always starts and drops whatever was in flight. Loading more and refreshing
+    /// wait for the request in flight, `None` means there is nothing to run.
+    pub fn begin(&mut self, kind: HomeRequest) -> Option<FeedQuery> {
+        match kind {
+            HomeRequest::Recommendations => {
+                self.loading = true;
+                self.loading_more = false;
+                self.error_message = None;
+                self.failed = None;
+                self.top_ups = 0;
+                self.cancel_cover_downloads();
+            }
+            HomeRequest::More | HomeRequest::Refresh => {
+                if self.loading || self.loading_more {
+                    return None;
+                }
+                self.loading_more = true;
+                if kind == HomeRequest::Refresh {
+                    self.error_message = None;
+                    self.failed = None;
+                    self.top_ups = 0;
+                }
+            }
+        }
+        self.request_id = next_request_id();
+        Some(FeedQuery {
+            kind,
+            id: self.request_id,
+            fresh_idx: self.fresh_idx,
+            fresh_type: self.fresh_type,
+            filter: self.filter,
+        })
+    }
+
+    /// Apply the answer to the last request, returning the index the new videos start at
+    ///
+    /// Answers to superseded requests and failures return `None`.
+    pub fn finish(&mut self, feed: FeedAnswer) -> Option<usize> {
+        if feed.query.id != self.request_id {
+            return None;
+        }
+        self.popular = feed.popular;
+        self.fresh_idx = feed.fresh_idx;
+        match feed.query.kind {
+            HomeRequest::Recommendations => self.finish_reload(feed.videos),
+            HomeRequest::More => self.finish_more(feed.videos),
+            HomeRequest::Refresh => self.finish_refresh(feed.videos),
+        }
+    }
+
+    /// Replace the grid with the next page of the feed
+    fn finish_reload(&mut self, videos: anyhow::Result<Vec<VideoItem>>) -> Option<usize> {
+        self.loading = false;
+        match videos {
             Ok(videos) => {
-                self.videos = videos
-                    .into_iter()
-                    .map(|video| VideoCard { video, cover: None })
-                    .collect();
-                self.loading = false;
-                self.selected_index = 0;
+                // Covers started for the old cards while loading must not land on these
+                self.cancel_cover_downloads();
+                self.videos = videos.into_iter().map(VideoCard::new).collect();
+                self.hidden.clear();
+                // Restored index may point past a shorter fresh feed
+                self.selected_index = self
+                    .restore_index
+                    .take()
+                    .map_or(0, |idx| idx.min(self.videos.len().saturating_sub(1)));
                 self.scroll_row = 0;
+                self.update_scroll(self.cached_visible_rows);
+                Some(0)
             }
             Err(e) => {
-                self.error_message = Some(format!("加载推荐视频失败: {}", e));
-                self.loading = false;
+                self.failed = Some(HomeRequest::Recommendations);
+                self.error_message = Some(format!("加载{}视频失败: {}", self.feed_label(), e));
+                None
             }
         }
     }
 
-    pub async fn load_more(&mut self, api_client: &ApiClient) {
-        if self.loading_more {
-            return;
+    /// Swap in the next batch without clearing the page first
+    ///
+    /// The selected video stays selected if the new batch still has it, otherwise the cursor
+    /// only moves when the list shrank past it. On failure the current batch stays on screen.
+    fn finish_refresh(&mut self, videos: anyhow::Result<Vec<VideoItem>>) -> Option<usize> {
+        self.loading_more = false;
+        match videos {
+            Ok(videos) => {
+                let selected_bvid = self
+                    .videos
+                    .get(self.selected_index)
+                    .and_then(|card| card.video.bvid.clone());
+                self.cancel_cover_downloads();
+                self.videos = videos.into_iter().map(VideoCard::new).collect();
+                self.hidden.clear();
+                let kept = selected_bvid.and_then(|bvid| {
+                    self.videos
+                        .iter()
+                        .position(|card| card.video.bvid.as_deref() == Some(bvid.as_str()))
+                });
+                self.selected_index = kept.unwrap_or_else(|| {
+                    self.selected_index.min(self.videos.len().saturating_sub(1))
+                });
+                self.update_scroll(self.cached_visible_rows);
+                Some(0)
+            }
+            Err(e) => {
+                self.failed = Some(HomeRequest::Refresh);
+                self.toast = Some(Toast::new(format!("刷新推荐失败: {}", e)));
+                None
+            }
         }
+    }
 
-        self.loading_more = true;
-        self.fresh_idx += 1;
-
-        match api_client.get_recommendations_paged(self.fresh_idx).await {
+    fn finish_more(&mut self, videos: anyhow::Result<Vec<VideoItem>>) -> Option<usize> {
+        self.loading_more = false;
+        match videos {
             Ok(videos) => {
-                for video in videos {
-                    self.videos.push(VideoCard { video, cover: None });
-                }
-                self.loading_more = false;
+                let loaded = self.videos.len();
+                self.videos.extend(videos.into_iter().map(VideoCard::new));
+                self.failed = None;
+                Some(loaded)
             }
-            Err(_) => {
-                self.fresh_idx -= 1;
-                self.loading_more = false;
+            Err(e) => {
+                self.failed = Some(HomeRequest::More);
+                self.toast = Some(Toast::new(format!("加载更多失败: {}", e)));
+                None
+            }
+        }
+    }
+
+    /// Record a request for the next page when the videos don't fill the screen yet
+    ///
+    /// Called after each answer, so a small batch is followed by up to [`MAX_TOP_UPS`]
+    /// more pages instead of leaving the grid half empty.
+    pub fn top_up(&mut self) -> Option<FeedQuery> {
+        let screenful = match self.view_mode {
+            HomeViewMode::Grid => self.columns * self.cached_visible_rows,
+            HomeViewMode::List => self.cached_visible_rows,
+        };
+        if self.top_ups >= MAX_TOP_UPS || self.videos.len() >= screenful {
+            return None;
+        }
+        let query = self.begin(HomeRequest::More)?;
+        self.top_ups += 1;
+        Some(query)
+    }
+
+    /// Run a request of `kind` to completion, what the app does across a spawned task
+    pub async fn run(&mut self, kind: HomeRequest, api_client: &impl BiliApi) -> Option<usize> {
+        let query = self.begin(kind)?;
+        let feed = fetch_feed(api_client, query).await;
+        self.finish(feed)
+    }
+
+    pub async fn load_recommendations(&mut self, api_client: &impl BiliApi) {
+        self.run(HomeRequest::Recommendations, api_client).await;
+    }
+
+    pub async fn refresh(&mut self, api_client: &impl BiliApi) {
+        self.run(HomeRequest::Refresh, api_client).await;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        self.run(HomeRequest::More, api_client).await;
+    }
+
+    /// Open the card's video, or explain why it cannot be played
+    fn open_card(&mut self, idx: usize) -> AppAction {
+        let Some(card) = self.videos.get(idx) else {
+            return AppAction::None;
+        };
+        match card.video.target() {
+            Some(FeedTarget::Video { bvid, aid }) => AppAction::OpenVideoDetail(bvid, aid),
+            // No detail page for these, mpv plays the page URL directly
+            Some(FeedTarget::Bangumi(url) | FeedTarget::Live(url)) => AppAction::PlayEpisode {
+                url,
+                label: card.video.title.clone().unwrap_or_default(),
+                aid: 0,
+                cid: 0,
+                duration: 0,
+                confirmed: false,
+            },
+            None => {
+                self.toast = Some(Toast::new("该内容暂不支持播放"));
+                AppAction::None
             }
         }
     }
 
+    /// Open the card's comments, or explain why it has none
+    fn open_comments(&mut self, idx: usize) -> AppAction {
+        let Some(card) = self.videos.get(idx) else {
+            return AppAction::None;
+        };
+        let oid = card
+            .video
+            .playable_target()
+            .and_then(|(bvid, aid)| comment::video_oid(Some(&bvid), Some(aid)));
+        match oid {
+            Some(oid) => AppAction::OpenComments {
+                oid,
+                comment_type: CommentType::Video.as_i32(),
+                title: card.video.title.clone().unwrap_or_default(),
+            },
+            None => {
+                self.toast = Some(Toast::new("该内容没有评论区"));
+                AppAction::None
+            }
+        }
+    }
+
+    pub fn video_count(&self) -> usize {
+        self.videos.len()
+    }
+
+    /// Flag cards whose bvid is in the local watched set
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        for card in &mut self.videos {
+            card.watched = card
+                .video
+                .bvid
+                .as_deref()
+                .is_some_and(|bvid| watched.contains(bvid));
+        }
+    }
+
+    /// Hide watched cards, or bring the hidden ones back in their place
+    ///
+    /// Nothing loaded is dropped, so turning hiding off restores the full list. Called again
+    /// after each load to hide the new watched cards too; the selected card stays selected
+    /// while it is shown.
+    pub fn set_hide_watched(&mut self, hide: bool, watched: &WatchedVideos) {
+        let selected = self
+            .videos
+            .get(self.selected_index)
+            .and_then(|card| card.video.bvid.clone());
+        let was_hiding = !self.hidden.is_empty();
+        for (idx, card) in std::mem::take(&mut self.hidden) {
+            self.videos.insert(idx.min(self.videos.len()), card);
+        }
+        self.mark_watched(watched);
+        if hide {
+            for (idx, card) in std::mem::take(&mut self.videos).into_iter().enumerate() {
+                if card.watched {
+                    self.hidden.push((idx, card));
+                } else {
+                    self.videos.push(card);
+                }
+            }
+        }
+        if !was_hiding && self.hidden.is_empty() {
+            return;
+        }
+        // Indices shifted, covers in flight would land on the wrong card
+        self.cancel_cover_downloads();
+        let kept = selected.and_then(|bvid| {
+            self.videos
+                .iter()
+                .position(|card| card.video.bvid.as_deref() == Some(bvid.as_str()))
+        });
+        self.selected_index = kept
+            .unwrap_or(self.selected_index)
+            .min(self.videos.len().saturating_sub(1));
+        self.update_scroll(self.cached_visible_rows);
+    }
+
     pub fn is_near_bottom(&self, visible_rows: usize) -> bool {
         if self.videos.is_empty() {
             return false;
@@ -139,17 +595,60 @@ impl HomePage {
         current_row + 2 >= total.saturating_sub(1) && total > visible_rows
     }
 
+    /// Card indices whose covers should be loaded: the visible rows plus a prefetch margin
+    fn cover_range(&self, prefetch_rows: usize) -> std::ops::Range<usize> {
+        prefetch_range(
+            self.scroll_row,
+            self.cached_visible_rows,
+            prefetch_rows,
+            self.row_len(),
+            self.videos.len(),
+        )
+    }
+
+    /// Drop pending covers so results for the old cards can't land on new ones
+    fn cancel_cover_downloads(&mut self) {
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.cancel_cover_downloads();
+        for card in &mut self.videos {
+            card.cover = None;
+        }
+    }
+
     /// Start background downloads for visible covers (non-blocking)
-    pub fn start_cover_downloads(&mut self) {
-        if self.videos.is_empty() {
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        if self.videos.is_empty() || self.view_mode == HomeViewMode::List {
             return;
         }
+        let semaphore = self.download_limiter.semaphore(concurrency);
+        let generation = self.cover_tasks.generation();
+        let range = self.cover_range(prefetch_rows);
+        if !self.cover_tasks.needs_scan(&range, self.videos.len()) {
+            return;
+        }
+        let loaded = self
+            .videos
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.cover.is_some())
+            .map(|(idx, _)| idx)
+            .collect();
+        for idx in self.cover_tasks.evict(range.clone(), loaded, cache_size) {
+            self.videos[idx].cover = None;
+        }
 
-        // Calculate visible range
-        let start = self.scroll_row * self.columns;
-        let end = (start + self.columns * Self::PREFETCH_ROWS).min(self.videos.len()); // Prefetch extra rows
-
-        for idx in start..end {
+        for idx in range {
             // Skip if already has cover or is pending
             if self.videos[idx].cover.is_some() || self.pending_downloads.contains(&idx) {
                 continue;
@@ -159,47 +658,53 @@ impl HomePage {
                 self.pending_downloads.insert(idx);
                 let tx = self.cover_tx.clone();
                 let picker = Arc::clone(&self.picker);
+                let semaphore = Arc::clone(&semaphore);
 
                 // Spawn background task
-                tokio::spawn(async move {
-                    if let Some(img) = Self::download_image(&pic_url).await {
-                        let protocol = picker.new_resize_protocol(img);
+                let task = tokio::spawn(async move {
+                    let Ok(_permit) = semaphore.acquire_owned().await else {
+                        return;
+                    };
+                    if let Some(img) = download_image(&pic_url).await {
+                        let protocol = picker.new_resize_protocol(fit_cover(img, cover_fit));
                         let _ = tx
                             .send(CoverResult {
                                 index: idx,
                                 protocol,
+                                generation,
                             })
                             .await;
                     }
                 });
+                self.cover_tasks.track(task.abort_handle());
             }
         }
     }
 
     /// Poll for completed cover downloads (non-blocking)
-    pub fn poll_cover_results(&mut self) {
+    pub fn poll_cover_results(&mut self) -> bool {
         // Try to receive all available results without blocking
+        let mut arrived = false;
         while let Ok(result) = self.cover_rx.try_recv() {
+            if !self.cover_tasks.is_current(result.generation) {
+                continue;
+            }
             if result.index < self.videos.len() {
                 self.videos[result.index].cover = Some(result.protocol);
                 self.pending_downloads.remove(&result.index);
+                arrived = true;
             }
         }
-    }
-
-    async fn download_image(url: &str) -> Option<DynamicImage> {
-        let response = reqwest::get(url).await.ok()?;
-        let bytes = response.bytes().await.ok()?;
-        image::load_from_memory(&bytes).ok()
+        arrived
     }
 
     fn visible_rows(&self, height: u16) -> usize {
         let available_height = height.saturating_sub(1);
-        (available_height / self.card_height).max(1) as usize
+        (available_height / self.row_height()).max(1) as usize
     }
 
     fn selected_row(&self) -> usize {
-        self.selected_index / self.columns
+        self.selected_index / self.row_len()
     }
 
     fn update_scroll(&mut self, visible_rows: usize) {
@@ -212,7 +717,7 @@ impl HomePage {
     }
 
     fn total_rows(&self) -> usize {
-        self.videos.len().div_ceil(self.columns)
+        self.videos.len().div_ceil(self.row_len())
     }
 }
 
@@ -222,8 +727,46 @@ impl Default for HomePage {
     }
 }
 
+impl HomePage {
+    /// Active limits for the header, e.g. "≥1万播放 ≤10分钟"
+    fn filter_summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.filter.min_views > 0 {
+            parts.push(format!("≥{}播放", self.filter.min_views_label()));
+        }
+        if self.filter.max_duration_minutes > 0 {
+            parts.push(format!("≤{}", self.filter.max_duration_label()));
+        }
+        parts.join(" ")
+    }
+
+    /// Footer entries for the keys this page handles
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let mut hints = vec![
+            help::navigation(keys),
+            KeyHint::new(&keys.confirm, "播放"),
+            KeyHint::new(&keys.refresh, "换一批"),
+            KeyHint::new(&keys.reset_feed, "重置"),
+            KeyHint::new(&keys.toggle_view_mode, "视图"),
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::new(&keys.open_comments, "评论"),
+            KeyHint::new(&keys.open_up_space, "UP主"),
+            KeyHint::new(&keys.open_by_id, "打开链接"),
+            KeyHint::new(&keys.next_theme, "切换主题"),
+            KeyHint::new(&keys.quit, "退出"),
+        ];
+        if !self.popular {
+            hints.insert(4, KeyHint::new(&keys.cycle_fresh_type, "推荐模式"));
+        }
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+}
+
 impl Component for HomePage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -234,7 +777,7 @@ impl Component for HomePage {
             .split(area);
 
         // Header with enhanced styling
-        let title = Line::from(vec![
+        let mut title = Line::from(vec![
             Span::styled(" ", Style::default()),
             Span::styled(
                 "B",
@@ -248,8 +791,24 @@ impl Component for HomePage {
                     .fg(theme.fg_primary)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("推荐", Style::default().fg(theme.fg_accent)),
+            Span::styled(self.feed_label(), Style::default().fg(theme.fg_accent)),
         ]);
+        if !self.popular {
+            title.push_span(Span::styled(
+                format!(" · {}", self.fresh_type.label()),
+                Style::default().fg(theme.fg_muted),
+            ));
+        }
+        let title = if self.filter.is_active() {
+            let mut spans = title.spans;
+            spans.push(Span::styled(
+                format!(" · 筛选: {}", self.filter_summary()),
+                Style::default().fg(theme.warning),
+            ));
+            Line::from(spans)
+        } else {
+            title
+        };
 
         let header = Paragraph::new(title)
             .block(
@@ -278,133 +837,143 @@ impl Component for HomePage {
                 .alignment(Alignment::Center);
             frame.render_widget(loading, chunks[1]);
         } else if let Some(error) = &self.error_message {
-            let error_widget = Paragraph::new(format!("❌ {}", error))
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
                 .style(Style::default().fg(theme.error))
                 .alignment(Alignment::Center);
             frame.render_widget(error_widget, chunks[1]);
         } else if self.videos.is_empty() {
-            let empty = Paragraph::new("📭 暂无推荐视频")
+            let message = if self.filter.is_active() {
+                format!("📭 没有符合筛选条件的{}视频", self.feed_label())
+            } else {
+                format!("📭 暂无{}视频", self.feed_label())
+            };
+            let empty = Paragraph::new(message)
                 .style(Style::default().fg(theme.fg_secondary))
                 .alignment(Alignment::Center);
             frame.render_widget(empty, chunks[1]);
+        } else if self.view_mode == HomeViewMode::List {
+            self.render_list(frame, chunks[1], theme);
         } else {
             self.render_grid(frame, chunks[1], theme);
         }
 
-        // Help with styled shortcuts
-        let help_line = Line::from(vec![
-            Span::styled(" [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "←↑↓→",
-                Style::default()
-                    .fg(theme.fg_accent)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("/", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "hjkl",
-                Style::default()
-                    .fg(theme.fg_accent)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("导航", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "Enter",
-                Style::default()
-                    .fg(theme.success)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("播放", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "r",
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("刷新", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "q",
-                Style::default()
-                    .fg(theme.error)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("退出", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "t",
-                Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("切换主题", Style::default().fg(theme.fg_secondary)),
-        ]);
-        let help = Paragraph::new(help_line).alignment(Alignment::Center);
+        let help_line = help::hints_line(&self.footer_hints(keys), theme);
+        let help = match self.toast.as_ref().and_then(Toast::active_message) {
+            Some(toast) => {
+                Paragraph::new(format!("ℹ {}", toast)).style(Style::default().fg(theme.warning))
+            }
+            None => Paragraph::new(help_line),
+        }
+        .alignment(Alignment::Center);
         frame.render_widget(help, chunks[2]);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         if keys.matches_quit(key) {
             return Some(AppAction::Quit);
         }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
         if keys.matches_down(key) {
             if !self.videos.is_empty() {
-                let new_idx = self.selected_index + self.columns;
+                let new_idx = self.selected_index + self.row_len();
                 if new_idx < self.videos.len() {
                     self.selected_index = new_idx;
                 }
-                self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+                self.update_scroll(self.cached_visible_rows);
                 // Check for pagination
-                if self.is_near_bottom(Self::DEFAULT_VISIBLE_ROWS) && !self.loading_more {
+                if self.is_near_bottom(self.cached_visible_rows) && !self.loading_more {
                     return Some(AppAction::LoadMoreRecommendations);
                 }
             }
             return Some(AppAction::None);
         }
         if keys.matches_up(key) {
-            if !self.videos.is_empty() && self.selected_index >= self.columns {
-                self.selected_index -= self.columns;
-                self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+            if !self.videos.is_empty() && self.selected_index >= self.row_len() {
+                self.selected_index -= self.row_len();
+                self.update_scroll(self.cached_visible_rows);
             }
             return Some(AppAction::None);
         }
         if keys.matches_right(key) {
-            if !self.videos.is_empty() && self.selected_index + 1 < self.videos.len() {
+            if self.selected_index + 1 < self.videos.len() {
                 self.selected_index += 1;
-                self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+            } else if self.wrap_navigation && self.videos.len() > 1 {
+                self.selected_index = 0;
             }
+            self.update_scroll(self.cached_visible_rows);
             return Some(AppAction::None);
         }
         if keys.matches_left(key) {
-            if !self.videos.is_empty() && self.selected_index > 0 {
+            if self.selected_index > 0 {
                 self.selected_index -= 1;
-                self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+            } else if self.wrap_navigation && self.videos.len() > 1 {
+                self.selected_index = self.videos.len() - 1;
             }
+            self.update_scroll(self.cached_visible_rows);
             return Some(AppAction::None);
         }
         if keys.matches_confirm(key) || keys.matches_play(key) {
-            if let Some(card) = self.videos.get(self.selected_index) {
-                if let Some(bvid) = &card.video.bvid {
-                    let aid = card.video.id;
-                    return Some(AppAction::OpenVideoDetail(bvid.clone(), aid));
-                }
-            }
-            return Some(AppAction::None);
+            return Some(self.open_card(self.selected_index));
         }
         if keys.matches_refresh(key) {
+            if self.keep_position && !self.videos.is_empty() {
+                return Some(AppAction::RefreshRecommendations);
+            }
             self.loading = true;
             self.videos.clear();
-            self.pending_downloads.clear();
-            return Some(AppAction::RefreshHome);
+            self.hidden.clear();
+            self.cancel_cover_downloads();
+            return Some(AppAction::ReloadRecommendations);
+        }
+        if keys.matches_reset_feed(key) {
+            self.reset_feed();
+            self.loading = true;
+            self.videos.clear();
+            self.hidden.clear();
+            self.cancel_cover_downloads();
+            return Some(AppAction::ReloadRecommendations);
+        }
+        if keys.matches_cycle_fresh_type(key) && !self.popular {
+            self.fresh_type = self.fresh_type.next();
+            self.reset_feed();
+            self.loading = true;
+            self.videos.clear();
+            self.hidden.clear();
+            self.cancel_cover_downloads();
+            return Some(AppAction::ReloadRecommendations);
+        }
+        if keys.matches_toggle_hide_watched(key) {
+            return Some(AppAction::ToggleHideWatched);
+        }
+        if keys.matches_download(key) {
+            let target = self
+                .videos
+                .get(self.selected_index)
+                .and_then(|card| card.video.playable_target());
+            return Some(
+                target.map_or(AppAction::None, |(bvid, _)| AppAction::DownloadVideo(bvid)),
+            );
+        }
+        if keys.matches_toggle_view_mode(key) {
+            return Some(AppAction::ToggleHomeView);
+        }
+        if keys.matches_open_comments(key) {
+            return Some(self.open_comments(self.selected_index));
+        }
+        if keys.matches_open_up_space(key) {
+            let video = &self.videos.get(self.selected_index)?.video;
+            return Some(video.owner_mid().map_or(AppAction::None, |mid| {
+                AppAction::OpenUserSpace {
+                    mid,
+                    name: video.author_name().to_string(),
+                }
+            }));
         }
         if keys.matches_nav_next(key) {
             return Some(AppAction::NavNext);
@@ -412,9 +981,6 @@ impl Component for HomePage {
         if keys.matches_nav_prev(key) {
             return Some(AppAction::NavPrev);
         }
-        if keys.matches_next_theme(key) {
-            return Some(AppAction::NextTheme);
-        }
         if keys.matches_open_settings(key) {
             return Some(AppAction::SwitchToSettings);
         }
@@ -426,12 +992,12 @@ impl Component for HomePage {
             MouseEventKind::ScrollDown => {
                 // Scroll down by one row
                 if !self.videos.is_empty() {
-                    let new_idx = self.selected_index + self.columns;
+                    let new_idx = self.selected_index + self.row_len();
                     if new_idx < self.videos.len() {
                         self.selected_index = new_idx;
-                        self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+                        self.update_scroll(self.cached_visible_rows);
                         // Check for pagination only when actually moved
-                        if self.is_near_bottom(Self::DEFAULT_VISIBLE_ROWS) && !self.loading_more {
+                        if self.is_near_bottom(self.cached_visible_rows) && !self.loading_more {
                             return Some(AppAction::LoadMoreRecommendations);
                         }
                     }
@@ -440,9 +1006,9 @@ impl Component for HomePage {
             }
             MouseEventKind::ScrollUp => {
                 // Scroll up by one row
-                if !self.videos.is_empty() && self.selected_index >= self.columns {
-                    self.selected_index -= self.columns;
-                    self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+                if !self.videos.is_empty() && self.selected_index >= self.row_len() {
+                    self.selected_index -= self.row_len();
+                    self.update_scroll(self.cached_visible_rows);
                 }
                 None
             }
@@ -454,13 +1020,14 @@ impl Component for HomePage {
                 if event.row >= content_top && event.row < content_bottom {
                     // Calculate which card was clicked
                     let relative_y = event.row - content_top;
-                    let click_row = (relative_y / self.card_height) as usize;
+                    let click_row = (relative_y / self.row_height()) as usize;
                     let actual_row = self.scroll_row + click_row;
 
-                    let card_width = area.width / self.columns as u16;
+                    let row_len = self.row_len();
+                    let card_width = area.width / row_len as u16;
                     let click_col = (event.column.saturating_sub(area.x) / card_width) as usize;
 
-                    let click_idx = actual_row * self.columns + click_col.min(self.columns - 1);
+                    let click_idx = actual_row * row_len + click_col.min(row_len - 1);
 
                     if click_idx < self.videos.len() {
                         // Check for double-click (same card within 500ms)
@@ -474,16 +1041,11 @@ impl Component for HomePage {
                             // Double-click: open video detail
                             self.last_click_time = None;
                             self.last_click_index = None;
-                            if let Some(card) = self.videos.get(click_idx) {
-                                if let Some(bvid) = &card.video.bvid {
-                                    let aid = card.video.id;
-                                    return Some(AppAction::OpenVideoDetail(bvid.clone(), aid));
-                                }
-                            }
+                            return Some(self.open_card(click_idx));
                         } else {
                             // Single click: select card and record for potential double-click
                             self.selected_index = click_idx;
-                            self.update_scroll(Self::DEFAULT_VISIBLE_ROWS);
+                            self.update_scroll(self.cached_visible_rows);
                             self.last_click_time = Some(now);
                             self.last_click_index = Some(click_idx);
                         }
@@ -493,13 +1055,10 @@ impl Component for HomePage {
             }
             MouseEventKind::Down(MouseButton::Middle) => {
                 // Middle click opens video detail
-                if let Some(card) = self.videos.get(self.selected_index) {
-                    if let Some(bvid) = &card.video.bvid {
-                        let aid = card.video.id;
-                        return Some(AppAction::OpenVideoDetail(bvid.clone(), aid));
-                    }
+                if self.videos.is_empty() {
+                    return None;
                 }
-                None
+                Some(self.open_card(self.selected_index))
             }
             _ => None,
         }
@@ -508,7 +1067,16 @@ impl Component for HomePage {
 
 impl HomePage {
     fn render_grid(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let columns = self.grid_columns(area.width);
+        if columns != self.columns {
+            // Card widths changed, covers have to be fitted again
+            self.columns = columns;
+            self.invalidate_covers();
+        }
         let visible_rows = self.visible_rows(area.height);
+        self.cached_visible_rows = visible_rows;
+        // A resize may have pushed the selection out of view
+        self.update_scroll(visible_rows);
 
         let row_constraints: Vec<Constraint> = (0..visible_rows)
             .map(|_| Constraint::Min(self.card_height))
@@ -555,6 +1123,67 @@ impl HomePage {
         }
     }
 
+    /// One line per video: title on the left, author, views and duration on the right
+    fn render_list(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let visible_rows = self.visible_rows(area.height);
+        self.cached_visible_rows = visible_rows;
+        self.update_scroll(visible_rows);
+
+        let start = self.scroll_row.min(self.videos.len());
+        let end = (start + visible_rows).min(self.videos.len());
+        let width = area.width as usize;
+
+        let lines: Vec<Line> = (start..end)
+            .map(|idx| {
+                let card = &self.videos[idx];
+                let is_selected = idx == self.selected_index;
+                let meta = format!(
+                    "{}  {}  {} ",
+                    truncate_display(card.video.author_name(), Self::LIST_AUTHOR_COLS),
+                    card.video.format_views(),
+                    card.video.format_duration()
+                );
+                let meta_width = Line::from(meta.as_str()).width();
+
+                let marker = if is_selected { " ▶ " } else { "   " };
+                let title = card.video.title.as_deref().unwrap_or("无标题");
+                let title = match card.video.kind_badge() {
+                    Some(badge) => format!("[{}] {}", badge, title),
+                    None if card.watched => format!("✓ {}", title),
+                    None => title.to_string(),
+                };
+                // The marker is three columns wide either way
+                let title_cols = width.saturating_sub(meta_width + 3 + 2);
+                let title = truncate_display(&title, title_cols);
+                let padding =
+                    width.saturating_sub(3 + Line::from(title.as_str()).width() + meta_width);
+
+                let (title_style, row_style) = if is_selected {
+                    (
+                        Style::default()
+                            .fg(theme.fg_primary)
+                            .add_modifier(Modifier::BOLD),
+                        Style::default().bg(theme.bg_highlight),
+                    )
+                } else if card.watched {
+                    (Style::default().fg(theme.fg_muted), Style::default())
+                } else {
+                    (Style::default().fg(theme.fg_secondary), Style::default())
+                };
+
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(theme.fg_accent)),
+                    Span::styled(title, title_style),
+                    Span::raw(" ".repeat(padding)),
+                    Span::styled(meta, Style::default().fg(theme.fg_muted)),
+                ])
+                .style(row_style)
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
     fn render_video_card(
         &mut self,
         frame: &mut Frame,
@@ -589,12 +1218,24 @@ impl HomePage {
             Span::raw("")
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_type(border_type)
             .border_style(border_style)
             .title(title_span);
 
+        if let Some(badge) = self.videos[video_idx].video.kind_badge() {
+            block = block.title(
+                Line::from(Span::styled(
+                    format!(" {} ", badge),
+                    Style::default()
+                        .fg(theme.bilibili_cyan)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .right_aligned(),
+            );
+        }
+
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -603,24 +1244,17 @@ impl HomePage {
             .constraints([Constraint::Min(4), Constraint::Length(4)])
             .split(inner);
 
-        // Cover area - render with StatefulImage
-        let cover_area = card_chunks[0];
+        let cover_area = cover_box(card_chunks[0]);
         if let Some(cover) = &mut self.videos[video_idx].cover {
-            // Render actual image using StatefulImage
             let image_widget = StatefulImage::new();
             frame.render_stateful_widget(image_widget, cover_area, cover);
         } else {
-            // Loading placeholder with spinner animation hint
-            let is_pending = self.pending_downloads.contains(&video_idx);
-            let placeholder_text = if is_pending {
-                "📺 加载中..."
+            let label = if self.pending_downloads.contains(&video_idx) {
+                spinner_frame()
             } else {
                 "📺"
             };
-            let placeholder = Paragraph::new(placeholder_text)
-                .style(Style::default().fg(theme.fg_secondary))
-                .alignment(Alignment::Center);
-            frame.render_widget(placeholder, cover_area);
+            render_cover_placeholder(frame, cover_area, theme, label);
         }
 
         // Video info with enhanced styling
@@ -629,30 +1263,34 @@ impl HomePage {
 
         let title = card.video.title.as_deref().unwrap_or("无标题");
         let author = card.video.author_name();
-        let views = card.video.format_views();
         let duration = card.video.format_duration();
 
-        let max_title_len = (info_area.width as usize).saturating_sub(2);
-        let display_title: String = if title.chars().count() > max_title_len {
-            title
-                .chars()
-                .take(max_title_len.saturating_sub(3))
-                .collect::<String>()
-                + "..."
+        let title = if card.watched {
+            format!("✓ {}", title)
         } else {
             title.to_string()
         };
 
-        // Multi-styled info text
+        let max_cols = (info_area.width as usize).saturating_sub(2);
+        let display_title = truncate_display(&title, max_cols);
+        let author = truncate_display(author, max_cols);
+
+        // Multi-styled info text, watched videos are dimmed
         let title_style = if is_selected {
             Style::default()
                 .fg(theme.fg_primary)
                 .add_modifier(Modifier::BOLD)
+        } else if card.watched {
+            Style::default().fg(theme.fg_muted)
         } else {
             Style::default().fg(theme.fg_secondary)
         };
 
-        let meta_style = Style::default().fg(theme.fg_secondary);
+        let stats = [
+            ("▶", card.video.format_views(), theme.bilibili_pink),
+            ("👍", card.video.format_likes(), theme.fg_accent),
+            ("💬", card.video.format_danmaku(), theme.bilibili_cyan),
+        ];
 
         let info_text = Text::from(vec![
             Line::from(Span::styled(&display_title, title_style)),
@@ -660,14 +1298,644 @@ impl HomePage {
                 author,
                 Style::default().fg(theme.fg_secondary),
             )),
-            Line::from(vec![
-                Span::styled(&views, meta_style),
-                Span::styled(" · ", meta_style),
-                Span::styled(&duration, Style::default().fg(theme.success)),
-            ]),
+            stats_line(&stats, max_cols, theme),
+            Line::from(Span::styled(&duration, Style::default().fg(theme.success))),
         ]);
 
         let info = Paragraph::new(info_text).wrap(Wrap { trim: true });
         frame.render_widget(info, info_area);
     }
 }
+
+/// "▶ views  👍 likes  💬 danmaku", dropping trailing entries that don't fit in `max_cols`
+fn stats_line(
+    stats: &[(&'static str, String, Color)],
+    max_cols: usize,
+    theme: &Theme,
+) -> Line<'static> {
+    let value_style = Style::default().fg(theme.fg_secondary);
+    let mut spans = Vec::new();
+    let mut width = 0;
+    for (icon, value, color) in stats {
+        let sep = if spans.is_empty() { "" } else { "  " };
+        let entry = sep.width() + icon.width() + 1 + value.width();
+        if width + entry > max_cols {
+            break;
+        }
+        width += entry;
+        spans.push(Span::raw(sep));
+        spans.push(Span::styled(*icon, Style::default().fg(*color)));
+        spans.push(Span::styled(format!(" {}", value), value_style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::storage::Keybindings;
+    use crate::ui::test_utils::{contains, find, render, render_with};
+
+    fn video(id: i64) -> VideoItem {
+        VideoItem {
+            id,
+            bvid: Some(format!("BV{}", id)),
+            cid: None,
+            goto: "av".to_string(),
+            uri: None,
+            pic: None,
+            title: Some(format!("video {}", id)),
+            duration: Some(60),
+            pubdate: None,
+            owner: None,
+            stat: None,
+        }
+    }
+
+    #[test]
+    fn test_stats_line_drops_entries_that_do_not_fit() {
+        let theme = Theme::default();
+        let stats = [
+            ("▶", "12.3万".to_string(), theme.bilibili_pink),
+            ("👍", "4567".to_string(), theme.fg_accent),
+            ("💬", "890".to_string(), theme.bilibili_cyan),
+        ];
+        let text = |max: usize| stats_line(&stats, max, &theme).to_string();
+        assert_eq!(text(40), "▶ 12.3万  👍 4567  💬 890");
+        assert_eq!(text(20), "▶ 12.3万  👍 4567");
+        assert_eq!(text(4), "");
+        assert!(stats_line(&stats, 20, &theme).width() <= 20);
+    }
+
+    fn page_with(count: usize) -> HomePage {
+        let mut page = HomePage::new();
+        page.videos = (0..count)
+            .map(|i| VideoCard::new(video(i as i64)))
+            .collect();
+        page.loading = false;
+        page
+    }
+
+    fn press(page: &mut HomePage, keys: &[KeyCode]) -> Option<AppAction> {
+        let bindings = Keybindings::default();
+        let mut last = None;
+        for key in keys {
+            last = page.handle_input(*key, &bindings);
+        }
+        last
+    }
+
+    #[test]
+    fn test_open_owner_space() {
+        let keys = Keybindings::default();
+        let mut page = page_with(2);
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('u'), &keys),
+            Some(AppAction::None)
+        ));
+
+        page.videos[0].video.owner = Some(crate::api::recommend::VideoOwner {
+            mid: 42,
+            name: "UP".to_string(),
+            face: None,
+        });
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('u'), &keys),
+            Some(AppAction::OpenUserSpace { mid: 42, ref name }) if name == "UP"
+        ));
+    }
+
+    #[test]
+    fn test_open_comments_of_selected_card() {
+        let mut page = page_with(2);
+        page.selected_index = 1;
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Char('c')]),
+            Some(AppAction::OpenComments { oid: 1, comment_type: 1, ref title }) if title == "video 1"
+        ));
+
+        // "BV0" has no aid behind it
+        page.selected_index = 0;
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Char('c')]),
+            Some(AppAction::None)
+        ));
+        assert!(page.toast.is_some());
+    }
+
+    #[test]
+    fn test_right_at_last_item_stays() {
+        let mut page = page_with(5);
+        page.selected_index = 4;
+        let action = press(&mut page, &[KeyCode::Char('l')]);
+        assert_eq!(page.selected_index, 4);
+        assert!(matches!(action, Some(AppAction::None)));
+    }
+
+    #[test]
+    fn test_right_moves_across_row_boundary() {
+        let mut page = page_with(6);
+        page.selected_index = 2;
+        press(&mut page, &[KeyCode::Right]);
+        assert_eq!(page.selected_index, 3);
+    }
+
+    #[test]
+    fn test_left_and_up_at_origin_stay() {
+        let mut page = page_with(6);
+        press(&mut page, &[KeyCode::Char('h'), KeyCode::Char('k')]);
+        assert_eq!(page.selected_index, 0);
+        assert_eq!(page.scroll_row, 0);
+    }
+
+    #[test]
+    fn test_wrap_navigation_crosses_list_ends() {
+        let mut page = page_with(30);
+        page.set_wrap_navigation(true);
+        press(&mut page, &[KeyCode::Char('h')]);
+        assert_eq!(page.selected_index, 29);
+        assert_eq!(page.scroll_row, 7);
+
+        press(&mut page, &[KeyCode::Char('l')]);
+        assert_eq!(page.selected_index, 0);
+        assert_eq!(page.scroll_row, 0);
+
+        // A single video has nowhere to wrap to
+        let mut page = page_with(1);
+        page.set_wrap_navigation(true);
+        press(&mut page, &[KeyCode::Char('l'), KeyCode::Char('h')]);
+        assert_eq!(page.selected_index, 0);
+    }
+
+    #[test]
+    fn test_down_into_partial_last_row_is_blocked() {
+        let mut page = page_with(5);
+        page.selected_index = 2;
+        press(&mut page, &[KeyCode::Char('j')]);
+        // Index 5 does not exist, so the selection stays put
+        assert_eq!(page.selected_index, 2);
+    }
+
+    #[test]
+    fn test_down_scrolls_past_visible_rows() {
+        let mut page = page_with(30);
+        let action = press(&mut page, &[KeyCode::Down, KeyCode::Down, KeyCode::Down]);
+        assert_eq!(page.selected_index, 9);
+        assert_eq!(page.scroll_row, 1);
+        assert!(matches!(action, Some(AppAction::None)));
+
+        press(&mut page, &[KeyCode::Up, KeyCode::Up, KeyCode::Up]);
+        assert_eq!(page.selected_index, 0);
+        assert_eq!(page.scroll_row, 0);
+    }
+
+    #[test]
+    fn test_down_near_bottom_requests_more() {
+        let mut page = page_with(15);
+        let action = press(&mut page, &[KeyCode::Down]);
+        assert!(matches!(action, Some(AppAction::None)));
+        let action = press(&mut page, &[KeyCode::Down]);
+        assert!(matches!(action, Some(AppAction::LoadMoreRecommendations)));
+    }
+
+    #[test]
+    fn test_confirm_opens_selected_video() {
+        let mut page = page_with(3);
+        page.selected_index = 1;
+        let action = press(&mut page, &[KeyCode::Enter]);
+        match action {
+            Some(AppAction::OpenVideoDetail(bvid, aid)) => {
+                assert_eq!(bvid, "BV1");
+                assert_eq!(aid, 1);
+            }
+            other => panic!("unexpected action: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_navigation_on_empty_page_is_noop() {
+        let mut page = page_with(0);
+        let action = press(&mut page, &[KeyCode::Down, KeyCode::Right, KeyCode::Enter]);
+        assert_eq!(page.selected_index, 0);
+        assert!(matches!(action, Some(AppAction::None)));
+    }
+
+    #[test]
+    fn test_hide_watched_keeps_cards_to_bring_back() {
+        let mut page = page_with(6);
+        let mut watched = WatchedVideos::default();
+        watched.insert("BV1");
+        watched.insert("BV4");
+        page.selected_index = 5;
+        let bvids = |page: &HomePage| -> Vec<String> {
+            page.videos
+                .iter()
+                .filter_map(|card| card.video.bvid.clone())
+                .collect()
+        };
+
+        page.set_hide_watched(true, &watched);
+        assert_eq!(bvids(&page), ["BV0", "BV2", "BV3", "BV5"]);
+        assert_eq!(page.selected_index, 3);
+
+        // A later load hides its own watched cards without losing the earlier ones
+        watched.insert("BV3");
+        page.set_hide_watched(true, &watched);
+        assert_eq!(bvids(&page), ["BV0", "BV2", "BV5"]);
+
+        page.set_hide_watched(false, &watched);
+        assert_eq!(bvids(&page), ["BV0", "BV1", "BV2", "BV3", "BV4", "BV5"]);
+        assert!(page.videos[1].watched);
+        assert_eq!(page.selected_index, 5);
+    }
+
+    #[tokio::test]
+    async fn test_mixed_feed_drops_ads_and_plays_other_kinds() {
+        let mut page = HomePage::new();
+        page.load_recommendations(&MockApi::new()).await;
+        assert_eq!(page.videos.len(), 5);
+        assert!(page.videos.iter().all(|card| !card.video.is_ad()));
+        let screen = render(&mut page);
+        assert!(contains(&screen, " 直播 "));
+        assert!(contains(&screen, " 番剧 "));
+
+        page.selected_index = 4;
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Enter]),
+            Some(AppAction::PlayEpisode { url, cid: 0, confirmed: false, .. })
+                if url == "https://www.bilibili.com/bangumi/play/ss28001"
+        ));
+        page.selected_index = 3;
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Enter]),
+            Some(AppAction::PlayEpisode { url, .. }) if url == "https://live.bilibili.com/22001"
+        ));
+    }
+
+    #[test]
+    fn test_enter_on_unplayable_item_shows_toast() {
+        let mut page = page_with(2);
+        page.videos[1].video.goto = "article".to_string();
+        page.selected_index = 1;
+        let action = press(&mut page, &[KeyCode::Enter]);
+        assert!(matches!(action, Some(AppAction::None)));
+        assert_eq!(
+            page.toast.as_ref().and_then(Toast::active_message),
+            Some("该内容暂不支持播放")
+        );
+
+        page.selected_index = 0;
+        let action = press(&mut page, &[KeyCode::Enter]);
+        assert!(matches!(action, Some(AppAction::OpenVideoDetail(_, 0))));
+    }
+
+    #[test]
+    fn test_refresh_rotates_and_reset_rewinds() {
+        let mut page = page_with(6);
+        // Refreshing in place is covered below, this is the reload that rotates the feed
+        page.set_keep_position(false);
+        page.fresh_idx = 3;
+        let action = press(&mut page, &[KeyCode::Char('r')]);
+        assert!(matches!(action, Some(AppAction::ReloadRecommendations)));
+        // The next load continues from page 4 instead of starting over
+        assert_eq!(page.fresh_idx, 3);
+
+        let action = press(&mut page, &[KeyCode::Char('R')]);
+        assert!(matches!(action, Some(AppAction::ReloadRecommendations)));
+        assert_eq!(page.fresh_idx, 0);
+
+        page.fresh_idx = 2;
+        press(&mut page, &[KeyCode::Char('f')]);
+        assert_eq!(page.fresh_idx, 0);
+    }
+
+    #[test]
+    fn test_list_view_keeps_selection() {
+        let mut page = page_with(30);
+        page.selected_index = 7;
+        let action = press(&mut page, &[KeyCode::Char('v')]);
+        assert!(matches!(action, Some(AppAction::ToggleHomeView)));
+
+        page.set_view_mode(HomeViewMode::List);
+        assert_eq!(page.selected_index, 7);
+        assert_eq!(page.scroll_row, 7);
+        press(&mut page, &[KeyCode::Down]);
+        assert_eq!(page.selected_index, 8);
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "▶ video 8"));
+        assert!(!contains(&buffer, "video 6"));
+
+        page.set_view_mode(HomeViewMode::Grid);
+        assert_eq!(page.selected_index, 8);
+        assert_eq!(page.scroll_row, 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_recommendations_from_api() {
+        let api = MockApi::new();
+        let mut page = HomePage::new();
+        page.load_recommendations(&api).await;
+        assert!(!page.loading);
+        assert!(page.error_message.is_none());
+        assert!(!page.videos.is_empty());
+        assert_eq!(page.fresh_idx, 1);
+
+        let before = page.videos.len();
+        page.load_more(&api).await;
+        assert_eq!(page.videos.len(), before * 2);
+        assert_eq!(page.fresh_idx, 2);
+        assert_eq!(api.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_feed_shows_popular() {
+        let mut page = HomePage::new();
+        page.load_recommendations(&MockApi::anonymous()).await;
+        assert_eq!(page.videos.len(), 2);
+        let screen = render(&mut page);
+        assert!(contains(&screen, "热门"));
+        assert!(!contains(&screen, "推荐模式"));
+
+        // Feed modes only exist for the personalized feed
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Char('f')]),
+            Some(AppAction::None)
+        ));
+        assert_eq!(page.fresh_type, FreshType::Default);
+        assert!(!page.loading);
+
+        page.load_recommendations(&MockApi::new()).await;
+        assert!(contains(&render(&mut page), "推荐"));
+    }
+
+    #[tokio::test]
+    async fn test_filter_backfills_from_more_pages() {
+        let api = MockApi::new();
+        let mut page = HomePage::new();
+        // Three of the five fixture items besides the ad have at least 1000 plays
+        page.set_filter(RecommendFilter {
+            min_views: 1_000,
+            max_duration_minutes: 0,
+        });
+        page.load_recommendations(&api).await;
+        assert_eq!(page.videos.len(), 6);
+        assert_eq!(page.fresh_idx, 2);
+        assert!(contains(&render(&mut page), "筛选: ≥1000播放"));
+
+        // Nothing passes: gives up after the backfill pages
+        page.set_filter(RecommendFilter {
+            min_views: 1_000,
+            max_duration_minutes: 1,
+        });
+        page.load_recommendations(&api).await;
+        assert!(page.videos.is_empty());
+        assert_eq!(api.calls(), 2 + 1 + HomePage::BACKFILL_PAGES);
+        assert!(contains(&render(&mut page), "没有符合筛选条件"));
+    }
+
+    #[tokio::test]
+    async fn test_small_batch_is_topped_up_to_a_screenful() {
+        let api = MockApi::anonymous();
+        let mut page = HomePage::new();
+        page.load_recommendations(&api).await;
+        assert_eq!(page.videos.len(), 2);
+
+        while let Some(query) = page.top_up() {
+            let feed = fetch_feed(&api, query).await;
+            page.finish(feed);
+        }
+        // Two per page never fill the 3x3 grid, the cap ends it
+        assert_eq!(page.videos.len(), 2 * (1 + MAX_TOP_UPS));
+        assert_eq!(api.calls(), 1 + MAX_TOP_UPS);
+
+        // A grid that is already full asks for nothing
+        page.load_recommendations(&api).await;
+        page.cached_visible_rows = 1;
+        page.columns = 2;
+        assert!(page.top_up().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_recommendations_error_keeps_feed_position() {
+        let mut page = HomePage::new();
+        page.load_recommendations(&MockApi::failing()).await;
+        assert!(!page.loading);
+        assert_eq!(page.fresh_idx, 0);
+        assert!(page
+            .error_message
+            .as_deref()
+            .is_some_and(|e| e.contains("mock failure")));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_keeps_selection() {
+        let api = MockApi::new();
+        let mut page = HomePage::new();
+        page.load_recommendations(&api).await;
+        page.videos.reverse();
+        page.selected_index = page
+            .videos
+            .iter()
+            .position(|card| card.video.bvid.as_deref() == Some("BV1mock0002"))
+            .unwrap();
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Char('r')]),
+            Some(AppAction::RefreshRecommendations)
+        ));
+
+        page.refresh(&api).await;
+        assert!(!page.loading);
+        assert_eq!(
+            page.videos[page.selected_index].video.bvid.as_deref(),
+            Some("BV1mock0002")
+        );
+
+        // A shorter batch without the selected video only clamps the cursor
+        let mut page = page_with(12);
+        page.selected_index = 10;
+        page.refresh(&api).await;
+        assert_eq!(page.selected_index, page.videos.len() - 1);
+
+        page.refresh(&MockApi::failing()).await;
+        assert!(!page.videos.is_empty());
+        assert_eq!(page.take_failed(), Some(HomeRequest::Refresh));
+    }
+
+    #[test]
+    fn test_refresh_in_place_follows_the_selected_video() {
+        let mut page = page_with(6);
+        page.selected_index = 2;
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Char('r')]),
+            Some(AppAction::RefreshRecommendations)
+        ));
+
+        let answer = |page: &mut HomePage, ids: &[i64]| {
+            let query = page.begin(HomeRequest::Refresh).unwrap();
+            page.finish(FeedAnswer {
+                query,
+                fresh_idx: 1,
+                popular: false,
+                videos: Ok(ids.iter().map(|&id| video(id)).collect()),
+            })
+        };
+
+        // BV2 moved to the end of the new batch, the cursor goes with it
+        assert_eq!(answer(&mut page, &[7, 8, 9, 10, 2]), Some(0));
+        assert_eq!(page.selected_index, 4);
+        assert_eq!(page.videos[4].video.bvid.as_deref(), Some("BV2"));
+
+        // Gone from a shorter batch: the cursor stays put unless it would fall off the end
+        answer(&mut page, &[11, 12, 13, 14, 15]);
+        assert_eq!(page.selected_index, 4);
+        answer(&mut page, &[16, 17]);
+        assert_eq!(page.selected_index, 1);
+    }
+
+    #[tokio::test]
+    async fn test_superseded_answer_is_dropped() {
+        let api = MockApi::new();
+        let mut page = HomePage::new();
+        let stale = page.begin(HomeRequest::Recommendations).unwrap();
+        let current = page.begin(HomeRequest::Recommendations).unwrap();
+        // A load in flight blocks paging until it lands
+        assert!(page.begin(HomeRequest::More).is_none());
+
+        let stale = fetch_feed(&api, stale).await;
+        assert_eq!(page.finish(stale), None);
+        assert!(page.loading);
+        assert!(page.videos.is_empty());
+
+        let current = fetch_feed(&api, current).await;
+        assert_eq!(page.finish(current), Some(0));
+        assert!(!page.loading);
+        assert!(!page.videos.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_load_more_offers_retry() {
+        let keys = Keybindings::default();
+        let mut page = page_with(3);
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &keys),
+            Some(AppAction::None)
+        ));
+
+        page.load_more(&MockApi::failing()).await;
+        assert_eq!(page.videos.len(), 3);
+        assert!(contains(&render(&mut page), "加载更多失败"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &keys),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(HomeRequest::More));
+    }
+
+    #[test]
+    fn test_moving_within_a_row_skips_the_cover_scan() {
+        let mut page = page_with(12);
+        page.columns = 4;
+        page.cached_visible_rows = 1;
+        page.start_cover_downloads(CoverFit::Crop, 4, 0, 60);
+
+        press(&mut page, &[KeyCode::Char('l'), KeyCode::Char('l')]);
+        let window = page.cover_range(0);
+        assert!(!page.cover_tasks.needs_scan(&window, page.videos.len()));
+
+        press(&mut page, &[KeyCode::Char('j')]);
+        let window = page.cover_range(0);
+        assert_eq!(window, 4..8);
+        assert!(page.cover_tasks.needs_scan(&window, page.videos.len()));
+    }
+
+    #[test]
+    fn test_invalidate_covers_keeps_selection() {
+        let mut page = page_with(12);
+        page.selected_index = 10;
+        page.pending_downloads.insert(3);
+        let generation = page.cover_tasks.generation();
+        page.invalidate_covers();
+        assert_eq!(page.selected_index, 10);
+        assert!(page.pending_downloads.is_empty());
+        assert!(!page.cover_tasks.is_current(generation));
+    }
+
+    #[test]
+    fn test_render_states() {
+        let mut page = HomePage::new();
+        assert!(contains(&render(&mut page), "⏳ 加载中..."));
+
+        page.loading = false;
+        page.error_message = Some("网络错误".to_string());
+        assert!(contains(&render(&mut page), "❌ 网络错误"));
+        assert!(!contains(&render(&mut page), "重试"));
+
+        page.error_message = Some("加载推荐视频失败: 请求超时".to_string());
+        page.failed = Some(HomeRequest::Recommendations);
+        assert!(contains(&render(&mut page), "按 e 重试"));
+        page.failed = None;
+
+        page.error_message = None;
+        assert!(contains(&render(&mut page), "📭 暂无推荐视频"));
+
+        let buffer = render(&mut page_with(3));
+        assert!(contains(&buffer, "video 0"));
+        assert!(contains(&buffer, "video 2"));
+        assert!(!contains(&buffer, "加载中"));
+    }
+
+    #[test]
+    fn test_render_applies_theme() {
+        let theme = Theme::from_variant(crate::ui::ThemeVariant::CatppuccinLatte);
+        let buffer = render_with(&mut page_with(1), &theme);
+        let pos = find(&buffer, "推荐").expect("header is rendered");
+        assert_eq!(buffer[pos].fg, theme.fg_accent);
+    }
+
+    #[test]
+    fn test_cover_range_follows_visible_rows() {
+        let mut page = page_with(60);
+        assert_eq!(page.cover_range(2), 0..15);
+
+        page.cached_visible_rows = 8;
+        assert_eq!(page.cover_range(2), 0..30);
+        assert_eq!(page.cover_range(0), 0..24);
+
+        // Rows above the visible ones are prefetched too
+        page.scroll_row = 10;
+        assert_eq!(page.cover_range(2), 24..60);
+        assert_eq!(page.cover_range(0), 30..54);
+    }
+
+    #[test]
+    fn test_grid_columns_setting_drives_navigation() {
+        let mut page = page_with(30);
+        assert_eq!(page.grid_columns(160), 4);
+        assert_eq!(page.grid_columns(20), 1);
+
+        page.set_grid_columns(Some(2));
+        render(&mut page);
+        assert_eq!(page.columns, 2);
+        press(&mut page, &[KeyCode::Down]);
+        assert_eq!(page.selected_index, 2);
+
+        // Out of range counts are clamped
+        page.set_grid_columns(Some(20));
+        render(&mut page);
+        assert_eq!(page.columns, HomePage::MAX_COLUMNS);
+        press(&mut page, &[KeyCode::Down]);
+        assert_eq!(page.selected_index, 8);
+    }
+
+    #[test]
+    fn test_draw_caches_visible_rows() {
+        let mut page = page_with(60);
+        page.card_height = 5;
+        render(&mut page);
+        let rows = page.visible_rows(crate::ui::test_utils::HEIGHT - 3 - 2);
+        assert!(rows > HomePage::DEFAULT_VISIBLE_ROWS);
+        assert_eq!(page.cached_visible_rows, rows);
+    }
+}
@@ -0,0 +1,98 @@
+//! Pick the terminal graphics protocol once at startup, shared by every page that shows images
+
+use crate::storage::ImageProtocol;
+use ratatui_image::picker::{cap_parser::QueryStdioOptions, Picker, ProtocolType};
+use std::io::IsTerminal;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// How long to wait for the terminal to answer the capability query
+///
+/// Terminals that support it answer within a few milliseconds; tmux passthrough
+/// and some SSH setups never answer and would otherwise freeze the first paint.
+const QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+
+static PICKER: OnceLock<Picker> = OnceLock::new();
+
+/// How the picker is built for a setting
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Detection {
+    /// Skip the query and draw with half blocks
+    Halfblocks,
+    /// Query the terminal, then use the forced protocol if there is one
+    Query(Option<ProtocolType>),
+}
+
+/// Detect the protocol, call once after entering the alternate screen and before reading events
+pub fn init(setting: ImageProtocol) {
+    PICKER.get_or_init(|| {
+        let in_tmux = std::env::var_os("TMUX").is_some_and(|v| !v.is_empty());
+        let picker = build(detection(setting, in_tmux));
+        let _ = crate::storage::append_log(&format!(
+            "image protocol: {:?} (setting {:?}, tmux {})",
+            picker.protocol_type(),
+            setting,
+            in_tmux
+        ));
+        picker
+    });
+}
+
+/// The detected picker, half blocks if `init` has not run (e.g. in tests)
+pub fn picker() -> Arc<Picker> {
+    Arc::new(PICKER.get().cloned().unwrap_or_else(Picker::halfblocks))
+}
+
+/// tmux only forwards graphics with passthrough, so auto mode plays it safe there
+fn detection(setting: ImageProtocol, in_tmux: bool) -> Detection {
+    match setting {
+        ImageProtocol::Auto if in_tmux => Detection::Halfblocks,
+        ImageProtocol::Auto => Detection::Query(None),
+        ImageProtocol::Halfblocks => Detection::Halfblocks,
+        ImageProtocol::Sixel => Detection::Query(Some(ProtocolType::Sixel)),
+        ImageProtocol::Kitty => Detection::Query(Some(ProtocolType::Kitty)),
+        ImageProtocol::Iterm2 => Detection::Query(Some(ProtocolType::Iterm2)),
+    }
+}
+
+fn build(detection: Detection) -> Picker {
+    let Detection::Query(forced) = detection else {
+        return Picker::halfblocks();
+    };
+    if !std::io::stdout().is_terminal() {
+        return Picker::halfblocks();
+    }
+    // The query still runs for a forced protocol, pixel protocols need the font size
+    let options = QueryStdioOptions {
+        timeout: QUERY_TIMEOUT,
+        ..QueryStdioOptions::default()
+    };
+    let mut picker =
+        Picker::from_query_stdio_with_options(options).unwrap_or_else(|_| Picker::halfblocks());
+    if let Some(protocol) = forced {
+        picker.set_protocol_type(protocol);
+    }
+    picker
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_skips_the_query_under_tmux() {
+        assert_eq!(detection(ImageProtocol::Auto, true), Detection::Halfblocks);
+        assert_eq!(
+            detection(ImageProtocol::Auto, false),
+            Detection::Query(None)
+        );
+        assert_eq!(
+            detection(ImageProtocol::Kitty, true),
+            Detection::Query(Some(ProtocolType::Kitty))
+        );
+        assert_eq!(
+            detection(ImageProtocol::Halfblocks, false),
+            Detection::Halfblocks
+        );
+    }
+}
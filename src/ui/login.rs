@@ -1,39 +1,305 @@
-//! Login page with QR code display
+//! Login page with QR code display and manual cookie entry
 
+use super::util::centered_fixed;
 use super::{Component, Theme};
 use crate::api::auth::{QrcodeData, QrcodePollStatus};
 use crate::api::client::ApiClient;
 use crate::app::AppAction;
-use crate::storage::Credentials;
+use crate::storage::{Credentials, Keybindings};
 use qrcode::QrCode;
 use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
 use std::time::{Duration, Instant};
 use tui_qrcode::{Colors, QrCodeWidget, QuietZone};
 
+/// Login method shown on the login page
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginMode {
+    /// Scan a QR code with the mobile app
+    Qrcode,
+    /// Paste cookie values manually
+    Cookie,
+}
+
+/// Which client the login QR code is generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrcodeSource {
+    /// Web login, yields cookies only
+    Web,
+    /// TV-client login, also yields long-lived app tokens
+    Tv,
+}
+
+const COOKIE_FIELDS: [&str; 3] = ["SESSDATA", "bili_jct", "DedeUserID"];
+
+/// Random offset added to every poll so many clients do not poll in lockstep
+const POLL_JITTER_MS: i64 = 200;
+/// Unscanned polls at the configured interval before slowing down
+const WAITING_BEFORE_BACKOFF: u32 = 5;
+/// Polls over which the interval grows to twice the configured one
+const BACKOFF_STEPS: u32 = 5;
+/// Fastest poll while waiting for the confirmation on the phone
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Uniform value in `-POLL_JITTER_MS..=POLL_JITTER_MS`, from the std hasher's random keys
+fn poll_jitter_ms() -> i64 {
+    use std::hash::{BuildHasher, Hasher};
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (random % (2 * POLL_JITTER_MS as u64 + 1)) as i64 - POLL_JITTER_MS
+}
+
+/// Delay before the next QR poll
+///
+/// Backs off gently the longer the code sits unscanned, and polls faster once it is
+/// scanned so the confirmation is picked up quickly.
+fn next_poll_delay(
+    base: Duration,
+    status: QrcodePollStatus,
+    waiting_polls: u32,
+    jitter_ms: i64,
+) -> Duration {
+    let delay = match status {
+        QrcodePollStatus::Scanned => (base / 2).max(MIN_POLL_INTERVAL),
+        _ => {
+            let steps = waiting_polls
+                .saturating_sub(WAITING_BEFORE_BACKOFF)
+                .min(BACKOFF_STEPS);
+            base + base * steps / BACKOFF_STEPS
+        }
+    };
+    let millis = delay.as_millis() as i64 + jitter_ms;
+    Duration::from_millis(millis.max(MIN_POLL_INTERVAL.as_millis() as i64) as u64)
+}
+
+/// QR poll recorded by [`LoginPage::begin_poll`], run on a spawned task
+pub struct QrPoll {
+    source: QrcodeSource,
+    key: String,
+}
+
+/// Answer to a [`QrPoll`], applied by [`LoginPage::finish_poll`]
+pub struct QrPollAnswer {
+    poll: QrPoll,
+    /// New login state with the session once confirmed, `None` when the answer had no state
+    result: anyhow::Result<Option<(QrcodePollStatus, Option<Credentials>)>>,
+}
+
+/// Ask whether the QR code was scanned and confirmed
+pub async fn fetch_qr_poll(api_client: &ApiClient, poll: QrPoll) -> QrPollAnswer {
+    let confirmed = |status: QrcodePollStatus| status == QrcodePollStatus::Success;
+    let result = match poll.source {
+        QrcodeSource::Tv => api_client.poll_tv_qrcode(&poll.key).await.map(|result| {
+            let status = QrcodePollStatus::from(result.code);
+            let creds = result
+                .data
+                .filter(|_| confirmed(status))
+                .and_then(|data| data.into_credentials());
+            Some((status, creds))
+        }),
+        QrcodeSource::Web => api_client.poll_qrcode(&poll.key).await.map(|result| {
+            result.data.map(|data| {
+                let status = QrcodePollStatus::from(data.code);
+                let creds = confirmed(status)
+                    .then(|| Credentials::from_cookies(&result.cookies, Some(data.refresh_token)))
+                    .flatten();
+                (status, creds)
+            })
+        }),
+    };
+    QrPollAnswer { poll, result }
+}
+
 pub struct LoginPage {
+    pub mode: LoginMode,
+    pub qrcode_source: QrcodeSource,
     qrcode_data: Option<QrcodeData>,
     error_message: Option<String>,
     poll_status: QrcodePollStatus,
     last_poll: Option<Instant>,
+    /// Wait after `last_poll`, recomputed with jitter and backoff after every poll
+    poll_delay: Duration,
+    /// Consecutive polls answered with "not scanned yet"
+    waiting_polls: u32,
+    /// A poll is running, the next one waits for its answer
+    polling: bool,
+    /// Session from a confirmed scan, taken by the app to finish logging in
+    credentials: Option<Credentials>,
+    cookie_fields: [String; 3],
+    cookie_focus: usize,
+    cookie_error: Option<String>,
+    pub validating: bool,
 }
 
 impl LoginPage {
     pub fn new() -> Self {
         Self {
+            mode: LoginMode::Qrcode,
+            qrcode_source: QrcodeSource::Web,
             qrcode_data: None,
             error_message: None,
             poll_status: QrcodePollStatus::Waiting,
             last_poll: None,
+            poll_delay: Duration::ZERO,
+            waiting_polls: 0,
+            polling: false,
+            credentials: None,
+            cookie_fields: Default::default(),
+            cookie_focus: 0,
+            cookie_error: None,
+            validating: false,
+        }
+    }
+
+    pub fn set_cookie_error(&mut self, msg: String) {
+        self.cookie_error = Some(msg);
+        self.validating = false;
+    }
+
+    /// Build credentials from the entered fields, or from a full cookie string
+    /// pasted into any of them
+    fn cookie_credentials(&self) -> Option<Credentials> {
+        if let Some(creds) = self
+            .cookie_fields
+            .iter()
+            .find(|field| field.contains("SESSDATA="))
+            .and_then(|field| Credentials::from_cookie_string(field))
+        {
+            return Some(creds);
+        }
+
+        let [sessdata, bili_jct, dede_user_id] = self.cookie_fields.clone().map(|f| {
+            // Accept "name=value" in individual fields too
+            match f.trim().split_once('=') {
+                Some((_, value)) => value.trim().to_string(),
+                None => f.trim().to_string(),
+            }
+        });
+        if sessdata.is_empty() || bili_jct.is_empty() || dede_user_id.is_empty() {
+            return None;
+        }
+        Some(Credentials {
+            sessdata,
+            bili_jct,
+            dede_user_id,
+            dede_user_id_ckmd5: None,
+            refresh_token: None,
+            access_token: None,
+            app_refresh_token: None,
+            token_expires_at: None,
+            sid: None,
+            buvid3: None,
+            buvid4: None,
+            b_nut: None,
+        })
+    }
+
+    fn handle_cookie_input(&mut self, key: KeyCode) -> Option<AppAction> {
+        if self.validating {
+            return Some(AppAction::None);
+        }
+        match key {
+            KeyCode::Esc => {
+                self.mode = LoginMode::Qrcode;
+                self.cookie_error = None;
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                self.cookie_focus = (self.cookie_focus + 1) % COOKIE_FIELDS.len();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                self.cookie_focus =
+                    (self.cookie_focus + COOKIE_FIELDS.len() - 1) % COOKIE_FIELDS.len();
+            }
+            KeyCode::Char(c) => {
+                self.cookie_fields[self.cookie_focus].push(c);
+                self.cookie_error = None;
+            }
+            KeyCode::Backspace => {
+                self.cookie_fields[self.cookie_focus].pop();
+                self.cookie_error = None;
+            }
+            KeyCode::Enter => match self.cookie_credentials() {
+                Some(creds) => {
+                    self.validating = true;
+                    self.cookie_error = None;
+                    return Some(AppAction::CookieLogin(creds));
+                }
+                None => {
+                    self.cookie_error = Some(
+                        "请填写 SESSDATA、bili_jct 和 DedeUserID，或粘贴完整 Cookie".to_string(),
+                    );
+                }
+            },
+            _ => {}
+        }
+        Some(AppAction::None)
+    }
+
+    fn draw_cookie_form(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_unfocused))
+            .title(Span::styled(
+                " Cookie 登录 ",
+                Style::default().fg(theme.fg_secondary),
+            ));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut constraints = vec![Constraint::Length(2)];
+        constraints.extend(COOKIE_FIELDS.iter().map(|_| Constraint::Length(3)));
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(inner);
+
+        let hint = Paragraph::new("填写以下字段，或在任意一栏粘贴完整的 Cookie 字符串")
+            .style(Style::default().fg(theme.fg_muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hint, rows[0]);
+
+        for (idx, label) in COOKIE_FIELDS.iter().enumerate() {
+            let focused = idx == self.cookie_focus;
+            let border_color = if focused {
+                theme.bilibili_pink
+            } else {
+                theme.border_subtle
+            };
+            let text = if focused {
+                format!("{}_", self.cookie_fields[idx])
+            } else {
+                self.cookie_fields[idx].clone()
+            };
+            let field = Paragraph::new(text)
+                .style(Style::default().fg(theme.fg_primary))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(border_color))
+                        .title(Span::styled(
+                            format!(" {} ", label),
+                            Style::default().fg(border_color),
+                        )),
+                );
+            frame.render_widget(field, rows[idx + 1]);
         }
     }
 
     pub async fn load_qrcode(&mut self, api_client: &ApiClient) {
-        match api_client.get_qrcode_data().await {
+        let result = match self.qrcode_source {
+            QrcodeSource::Web => api_client.get_qrcode_data().await,
+            QrcodeSource::Tv => api_client.get_tv_qrcode_data().await,
+        };
+        match result {
             Ok(data) => {
                 self.qrcode_data = Some(data);
                 self.error_message = None;
                 self.poll_status = QrcodePollStatus::Waiting;
                 self.last_poll = None;
+                self.waiting_polls = 0;
             }
             Err(e) => {
                 self.error_message = Some(format!("获取二维码失败: {}", e));
@@ -41,14 +307,17 @@ impl LoginPage {
         }
     }
 
-    pub async fn tick(&mut self, api_client: &ApiClient) -> Option<AppAction> {
-        // Only poll if we have a QR code and haven't succeeded/expired
-        let qrcode_key = match &self.qrcode_data {
-            Some(data) => data.qrcode_key.clone(),
-            None => return None,
-        };
+    /// Record a poll of the shown QR code once the poll delay has passed
+    ///
+    /// `None` while in cookie mode, without a code, after the code succeeded or expired,
+    /// and while the previous poll is still running.
+    pub fn begin_poll(&mut self) -> Option<QrPoll> {
+        if self.mode != LoginMode::Qrcode || self.polling {
+            return None;
+        }
 
-        // Don't poll if already successful or expired
+        // Only poll if we have a QR code and haven't succeeded/expired
+        let key = self.qrcode_data.as_ref()?.qrcode_key.clone();
         if matches!(
             self.poll_status,
             QrcodePollStatus::Success | QrcodePollStatus::Expired
@@ -56,42 +325,69 @@ impl LoginPage {
             return None;
         }
 
-        // Poll every 2 seconds
         let should_poll = self
             .last_poll
-            .map(|t| t.elapsed() > Duration::from_secs(2))
+            .map(|t| t.elapsed() > self.poll_delay)
             .unwrap_or(true);
-
         if !should_poll {
             return None;
         }
 
-        self.last_poll = Some(Instant::now());
+        self.polling = true;
+        Some(QrPoll {
+            source: self.qrcode_source,
+            key,
+        })
+    }
 
-        match api_client.poll_qrcode(&qrcode_key).await {
-            Ok(result) => {
-                if let Some(data) = result.data {
-                    self.poll_status = QrcodePollStatus::from(data.code);
-
-                    if self.poll_status == QrcodePollStatus::Success {
-                        // Extract credentials from cookies
-                        if let Some(creds) =
-                            Credentials::from_cookies(&result.cookies, Some(data.refresh_token))
-                        {
-                            return Some(AppAction::LoginSuccess(creds));
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                self.error_message = Some(format!("轮询失败: {}", e));
+    /// Apply a poll answer and schedule the next poll
+    ///
+    /// Answers for a code that was replaced in the meantime are dropped.
+    pub fn finish_poll(&mut self, answer: QrPollAnswer, poll_interval: Duration) {
+        self.polling = false;
+        let current = self
+            .qrcode_data
+            .as_ref()
+            .map(|data| data.qrcode_key.as_str());
+        if answer.poll.source != self.qrcode_source || current != Some(answer.poll.key.as_str()) {
+            return;
+        }
+
+        match answer.result {
+            Ok(Some((status, credentials))) => {
+                self.poll_status = status;
+                self.credentials = credentials;
             }
+            Ok(None) => {}
+            Err(e) => self.error_message = Some(format!("轮询失败: {}", e)),
+        }
+        self.last_poll = Some(Instant::now());
+        if self.poll_status == QrcodePollStatus::Waiting {
+            self.waiting_polls += 1;
         }
+        self.poll_delay = next_poll_delay(
+            poll_interval,
+            self.poll_status,
+            self.waiting_polls,
+            poll_jitter_ms(),
+        );
+    }
 
-        None
+    /// Session from a confirmed scan, once
+    pub fn take_credentials(&mut self) -> Option<Credentials> {
+        self.credentials.take()
     }
 
     fn status_text(&self, theme: &Theme) -> (&str, Color) {
+        if self.mode == LoginMode::Cookie {
+            return if self.validating {
+                ("⏳ 正在验证 Cookie...", theme.warning)
+            } else if let Some(err) = &self.cookie_error {
+                (err.as_str(), theme.error)
+            } else {
+                ("✏️ 输入完成后按 Enter 验证登录", theme.info)
+            };
+        }
         match self.poll_status {
             QrcodePollStatus::Waiting => ("⏳ 等待扫描二维码...", theme.warning),
             QrcodePollStatus::Scanned => ("📱 已扫描，请在手机上确认登录", theme.info),
@@ -109,7 +405,7 @@ impl Default for LoginPage {
 }
 
 impl Component for LoginPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, _keys: &Keybindings) {
         // Layout: title, QR code, status, help
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -167,11 +463,16 @@ impl Component for LoginPage {
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.border_unfocused))
             .title(Span::styled(
-                " 扫码登录 ",
+                match self.qrcode_source {
+                    QrcodeSource::Web => " 扫码登录 ",
+                    QrcodeSource::Tv => " TV 扫码登录 (长效令牌) ",
+                },
                 Style::default().fg(theme.fg_secondary),
             ));
 
-        if let Some(error) = &self.error_message {
+        if self.mode == LoginMode::Cookie {
+            self.draw_cookie_form(frame, chunks[1], theme);
+        } else if let Some(error) = &self.error_message {
             let error_widget = Paragraph::new(format!("❌ {}", error))
                 .style(Style::default().fg(theme.error))
                 .alignment(Alignment::Center)
@@ -194,15 +495,7 @@ impl Component for LoginPage {
                 let qr_size = qr_widget.size(inner_area);
 
                 // Center the QR code based on its actual size
-                let x_offset = (inner_area.width.saturating_sub(qr_size.width)) / 2;
-                let y_offset = (inner_area.height.saturating_sub(qr_size.height)) / 2;
-
-                let qr_area = Rect::new(
-                    inner_area.x + x_offset,
-                    inner_area.y + y_offset,
-                    qr_size.width.min(inner_area.width),
-                    qr_size.height.min(inner_area.height),
-                );
+                let qr_area = centered_fixed(qr_size.width, qr_size.height, inner_area);
 
                 frame.render_widget(qr_widget, qr_area);
             }
@@ -240,44 +533,214 @@ impl Component for LoginPage {
         frame.render_widget(status, chunks[2]);
 
         // Help with styled shortcuts
-        let help_line = Line::from(vec![
-            Span::styled(" [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "r",
-                Style::default()
-                    .fg(theme.warning)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("刷新二维码", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "q",
-                Style::default()
-                    .fg(theme.error)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("退出", Style::default().fg(theme.fg_secondary)),
-        ]);
+        let help_line = if self.mode == LoginMode::Cookie {
+            Line::from(vec![
+                Span::styled(" [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "Tab",
+                    Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("切换输入框", Style::default().fg(theme.fg_secondary)),
+                Span::styled("  [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "Enter",
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("验证登录", Style::default().fg(theme.fg_secondary)),
+                Span::styled("  [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "Esc",
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("返回扫码", Style::default().fg(theme.fg_secondary)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled(" [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "r",
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("刷新二维码", Style::default().fg(theme.fg_secondary)),
+                Span::styled("  [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "t",
+                    Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    match self.qrcode_source {
+                        QrcodeSource::Web => "TV 扫码",
+                        QrcodeSource::Tv => "网页扫码",
+                    },
+                    Style::default().fg(theme.fg_secondary),
+                ),
+                Span::styled("  [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "c",
+                    Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("Cookie 登录", Style::default().fg(theme.fg_secondary)),
+                Span::styled("  [", Style::default().fg(theme.fg_secondary)),
+                Span::styled(
+                    "q",
+                    Style::default()
+                        .fg(theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("] ", Style::default().fg(theme.fg_secondary)),
+                Span::styled("退出", Style::default().fg(theme.fg_secondary)),
+            ])
+        };
         let help = Paragraph::new(help_line).alignment(Alignment::Center);
         frame.render_widget(help, chunks[3]);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if self.mode == LoginMode::Cookie {
+            return self.handle_cookie_input(key);
+        }
         if keys.matches_quit(key) {
             return Some(AppAction::Quit);
         }
+        if key == KeyCode::Char('c') {
+            self.mode = LoginMode::Cookie;
+            self.cookie_error = None;
+            return Some(AppAction::None);
+        }
+        if key == KeyCode::Char('t') {
+            self.qrcode_source = match self.qrcode_source {
+                QrcodeSource::Web => QrcodeSource::Tv,
+                QrcodeSource::Tv => QrcodeSource::Web,
+            };
+            self.qrcode_data = None;
+            self.error_message = None;
+            self.poll_status = QrcodePollStatus::Waiting;
+            return Some(AppAction::ReloadLoginQrcode);
+        }
         if keys.matches_refresh(key) {
             // Request refresh - will be handled by App
             self.qrcode_data = None;
+            self.error_message = None;
             self.poll_status = QrcodePollStatus::Waiting;
-            return Some(AppAction::SwitchToLogin);
+            return Some(AppAction::ReloadLoginQrcode);
         }
         Some(AppAction::None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    #[test]
+    fn test_render_qrcode_states() {
+        let mut page = LoginPage::new();
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "⏳ 加载中..."));
+        assert!(contains(&buffer, "扫码登录"));
+
+        page.error_message = Some("获取二维码失败: timeout".to_string());
+        assert!(contains(&render(&mut page), "❌ 获取二维码失败: timeout"));
+
+        page.error_message = None;
+        page.qrcode_data = Some(QrcodeData {
+            url: "https://passport.bilibili.com/h5-app/passport/login/scan".to_string(),
+            qrcode_key: "key".to_string(),
+        });
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "⏳ 等待扫描二维码..."));
+        assert!(!contains(&buffer, "加载中"));
+    }
+
+    #[test]
+    fn test_next_poll_delay() {
+        let base = Duration::from_secs(2);
+        let waiting = QrcodePollStatus::Waiting;
+        assert_eq!(next_poll_delay(base, waiting, 1, 0), Duration::from_secs(2));
+        assert_eq!(
+            next_poll_delay(base, waiting, 1, -200),
+            Duration::from_millis(1800)
+        );
+        assert_eq!(
+            next_poll_delay(base, waiting, 8, 0),
+            Duration::from_millis(3200)
+        );
+        assert_eq!(
+            next_poll_delay(base, waiting, 100, 0),
+            Duration::from_secs(4)
+        );
+        assert_eq!(
+            next_poll_delay(base, QrcodePollStatus::Scanned, 100, 0),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            next_poll_delay(Duration::from_secs(1), QrcodePollStatus::Scanned, 0, -200),
+            MIN_POLL_INTERVAL
+        );
+    }
+
+    #[test]
+    fn test_poll_jitter_in_range() {
+        for _ in 0..100 {
+            assert!(poll_jitter_ms().abs() <= POLL_JITTER_MS);
+        }
+    }
+
+    #[test]
+    fn test_poll_answer_for_replaced_code_is_dropped() {
+        let mut page = LoginPage::new();
+        page.qrcode_data = Some(QrcodeData {
+            url: String::new(),
+            qrcode_key: "old".to_string(),
+        });
+        let poll = page.begin_poll().unwrap();
+        // One poll at a time
+        assert!(page.begin_poll().is_none());
+
+        page.qrcode_data = Some(QrcodeData {
+            url: String::new(),
+            qrcode_key: "new".to_string(),
+        });
+        let answer = QrPollAnswer {
+            poll,
+            result: Ok(Some((QrcodePollStatus::Scanned, None))),
+        };
+        page.finish_poll(answer, Duration::from_secs(2));
+        assert_eq!(page.poll_status, QrcodePollStatus::Waiting);
+        assert!(page.last_poll.is_none());
+
+        let poll = page.begin_poll().unwrap();
+        assert_eq!(poll.key, "new");
+        let answer = QrPollAnswer {
+            poll,
+            result: Ok(Some((QrcodePollStatus::Scanned, None))),
+        };
+        page.finish_poll(answer, Duration::from_secs(2));
+        assert_eq!(page.poll_status, QrcodePollStatus::Scanned);
+        assert!(page.begin_poll().is_none());
+        assert!(page.take_credentials().is_none());
+    }
+
+    #[test]
+    fn test_render_cookie_form() {
+        let mut page = LoginPage::new();
+        page.mode = LoginMode::Cookie;
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "Cookie 登录"));
+        assert!(contains(&buffer, "SESSDATA"));
+        assert!(contains(&buffer, "✏️ 输入完成后按 Enter 验证登录"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Placeholder shown instead of a page that needs an account when nobody is logged in
+
+use super::help::{self, KeyHint};
+use super::{Component, NavItem, Theme};
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+
+pub struct LoginRequiredPage {
+    /// Section the user tried to open
+    pub section: NavItem,
+}
+
+impl LoginRequiredPage {
+    pub fn new(section: NavItem) -> Self {
+        Self { section }
+    }
+}
+
+impl Component for LoginRequiredPage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_subtle))
+            .title(Span::styled(
+                format!(" {} ", self.section.label()),
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left);
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let text = vec![
+            Line::from(Span::styled(
+                "🔒 请先登录",
+                Style::default()
+                    .fg(theme.fg_primary)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "这个页面需要登录账号后才能查看",
+                Style::default().fg(theme.fg_secondary),
+            )),
+            Line::from(""),
+            help::hints_line(
+                &[
+                    KeyHint::new(&keys.confirm, "前往登录"),
+                    help::page_switch(keys),
+                ],
+                theme,
+            ),
+        ];
+        let height = (text.len() as u16).min(inner.height);
+        let panel = Rect {
+            y: inner.y + inner.height.saturating_sub(height) / 2,
+            height,
+            ..inner
+        };
+        frame.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            panel,
+        );
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_confirm(key) {
+            return Some(AppAction::SwitchToLogin);
+        }
+        if keys.matches_quit(key) {
+            return Some(AppAction::Quit);
+        }
+        if keys.matches_back(key) {
+            return Some(AppAction::BackToList);
+        }
+        if keys.matches_nav_next(key) {
+            return Some(AppAction::NavNext);
+        }
+        if keys.matches_nav_prev(key) {
+            return Some(AppAction::NavPrev);
+        }
+        None
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> Option<AppAction> {
+        let clicked = matches!(event.kind, MouseEventKind::Down(MouseButton::Left))
+            && area.contains(Position::new(event.column, event.row));
+        clicked.then_some(AppAction::SwitchToLogin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    #[test]
+    fn test_login_required_panel() {
+        let mut page = LoginRequiredPage::new(NavItem::History);
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "请先登录"));
+        assert!(contains(&buffer, "前往登录"));
+
+        let keys = Keybindings::default();
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::SwitchToLogin)
+        ));
+    }
+}
@@ -1,26 +1,60 @@
+mod bangumi;
+mod command_palette;
+mod comments;
+mod confirm_dialog;
+mod cover;
 mod dynamic;
 mod dynamic_detail;
+mod favorites;
+mod help;
 mod history;
 mod home;
+pub mod image_protocol;
 mod login;
+mod login_required;
+mod open_prompt;
+mod profile;
+mod recent_picker;
 mod search;
 mod settings;
 mod sidebar;
+mod tag;
+pub mod terminal_bg;
+#[cfg(test)]
+mod test_utils;
 pub mod theme;
+mod toast;
+mod user_space;
+pub mod util;
 mod video_card;
 mod video_detail;
+mod welcome;
 
-pub use dynamic::{DynamicPage, DynamicTab};
+pub use bangumi::{BangumiPage, BangumiRequest};
+pub use command_palette::CommandPalette;
+pub use comments::{CommentsPage, CommentsRequest};
+pub use confirm_dialog::ConfirmDialog;
+pub use cover::{prune_disk_cache, set_download_timeout, spinner_due};
+pub use dynamic::{fetch_dynamic, DynamicPage, DynamicQuery, DynamicRequest, DynamicTab};
 pub use dynamic_detail::DynamicDetailPage;
+pub use favorites::{FavoritesPage, FavoritesRequest};
 pub use history::HistoryPage;
-pub use home::HomePage;
-pub use login::LoginPage;
-pub use search::SearchPage;
-pub use settings::SettingsPage;
+pub use home::{fetch_feed, FeedQuery, HomePage, HomeRequest};
+pub use login::{fetch_qr_poll, LoginPage};
+pub use login_required::LoginRequiredPage;
+pub use open_prompt::OpenPrompt;
+pub use profile::ProfilePage;
+pub use recent_picker::RecentPicker;
+pub use search::{fetch_search, SearchPage, SearchQuery, SearchRequest};
+pub use settings::{LoginCheck, SettingsPage, TokenStatus};
 pub use sidebar::{NavItem, Sidebar};
+pub use tag::{TagPage, TagRequest};
 pub use theme::{Theme, ThemeVariant};
+pub use toast::{toast_expired, Toast};
+pub use user_space::{SpaceRequest, UserSpacePage};
 pub use video_card::{VideoCard, VideoCardGrid};
-pub use video_detail::VideoDetailPage;
+pub use video_detail::{DetailRequest, VideoDetailPage};
+pub use welcome::WelcomePage;
 
 use crate::app::AppAction;
 use crate::storage::Keybindings;
@@ -32,7 +66,7 @@ use ratatui::{
 
 /// UI Component trait
 pub trait Component {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme);
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings);
     fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         let _ = (key, keys);
         None
@@ -55,11 +89,96 @@ pub trait Component {
 /// Application pages
 pub enum Page {
     Login(LoginPage),
+    /// Stands in for a section that needs an account while logged out
+    LoginRequired(LoginRequiredPage),
     Home(HomePage),
     Search(SearchPage),
     Dynamic(DynamicPage),
     DynamicDetail(Box<DynamicDetailPage>),
     VideoDetail(Box<VideoDetailPage>),
     History(HistoryPage),
+    /// 追番/追剧 list of the logged-in user
+    Bangumi(Box<BangumiPage>),
+    /// Favorite folders of the logged-in user
+    Favorites(Box<FavoritesPage>),
+    UserSpace(Box<UserSpacePage>),
+    /// Newest videos under a video tag
+    Tag(Box<TagPage>),
+    /// Comments of a list item, opened without its detail page
+    Comments(Box<CommentsPage>),
+    Profile(ProfilePage),
     Settings(Box<SettingsPage>),
+    /// First-run introduction shown before the login page
+    Welcome(WelcomePage),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::render_sized;
+    use super::*;
+    use crate::api::test_api::MockApi;
+
+    /// Sizes below the app's minimum, down to a single cell
+    const TINY_SIZES: [(u16, u16); 4] = [(1, 1), (2, 1), (12, 4), (39, 9)];
+
+    fn render_tiny(component: &mut impl Component) {
+        let theme = Theme::default();
+        for (width, height) in TINY_SIZES {
+            render_sized(component, &theme, width, height);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pages_render_in_tiny_areas() {
+        let api = MockApi::new();
+
+        let mut home = HomePage::new();
+        render_tiny(&mut home);
+        home.load_recommendations(&api).await;
+        render_tiny(&mut home);
+
+        let mut history = HistoryPage::new();
+        history.load_history(&api).await;
+        render_tiny(&mut history);
+
+        let mut bangumi = BangumiPage::new(10001);
+        bangumi.load(&api).await;
+        render_tiny(&mut bangumi);
+
+        let mut favorites = FavoritesPage::new(10001);
+        favorites.load(&api).await;
+        render_tiny(&mut favorites);
+
+        let mut space = UserSpacePage::new(10001, String::new());
+        space.load(&api).await;
+        render_tiny(&mut space);
+
+        let mut tag = TagPage::new(1628, "Rust".to_string());
+        tag.load(&api).await;
+        render_tiny(&mut tag);
+
+        let mut comments = CommentsPage::new(1, 1, "示例视频".to_string());
+        comments.load(&api).await;
+        render_tiny(&mut comments);
+
+        let mut dynamic = DynamicPage::new();
+        dynamic.load_more(&api).await;
+        render_tiny(&mut dynamic);
+
+        render_tiny(&mut LoginPage::new());
+        render_tiny(&mut LoginRequiredPage::new(NavItem::Dynamic));
+        render_tiny(&mut SearchPage::new());
+        render_tiny(&mut DynamicDetailPage::new("1".to_string()));
+        render_tiny(&mut VideoDetailPage::new("BV1mock0001".to_string(), 1));
+        render_tiny(&mut ProfilePage::new());
+        render_tiny(&mut SettingsPage::default());
+        render_tiny(&mut WelcomePage::new(vec![true, false]));
+        render_tiny(&mut OpenPrompt::new());
+        render_tiny(&mut RecentPicker::new(Vec::new()));
+        render_tiny(&mut ConfirmDialog::new(
+            "确认",
+            vec!["一行说明".to_string()],
+            AppAction::None,
+        ));
+    }
 }
@@ -0,0 +1,112 @@
+//! "Open by id" prompt for jumping to a video from a bvid, av number or URL
+
+use super::util::centered_fixed;
+use super::{Component, Theme};
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
+
+pub struct OpenPrompt {
+    pub input: String,
+    pub error_message: Option<String>,
+    pub resolving: bool,
+}
+
+impl OpenPrompt {
+    pub fn new() -> Self {
+        Self {
+            input: String::new(),
+            error_message: None,
+            resolving: false,
+        }
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.error_message = Some(msg);
+        self.resolving = false;
+    }
+}
+
+impl Default for OpenPrompt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for OpenPrompt {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, _keys: &Keybindings) {
+        let width = area.width.saturating_sub(4).min(72);
+        let popup = centered_fixed(width, 5, area);
+
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.bilibili_pink))
+            .style(Style::default().bg(theme.bg_modal))
+            .title(Span::styled(
+                " 🔗 打开视频 (BV号 / av号 / 链接) ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+        let status = if self.resolving {
+            Line::from(Span::styled(
+                "⏳ 正在解析...",
+                Style::default().fg(theme.fg_secondary),
+            ))
+        } else if let Some(err) = &self.error_message {
+            Line::from(Span::styled(
+                format!("❌ {}", err),
+                Style::default().fg(theme.error),
+            ))
+        } else {
+            Line::from(Span::styled(
+                "[Enter] 打开  [Esc] 取消",
+                Style::default().fg(theme.fg_muted),
+            ))
+        };
+
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("{}_", self.input),
+                Style::default().fg(theme.fg_primary),
+            )),
+            Line::from(""),
+            status,
+        ];
+
+        let paragraph = Paragraph::new(lines).block(block);
+        frame.render_widget(paragraph, popup);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, _keys: &Keybindings) -> Option<AppAction> {
+        if self.resolving {
+            return Some(AppAction::None);
+        }
+        match key {
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error_message = None;
+                Some(AppAction::None)
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.error_message = None;
+                Some(AppAction::None)
+            }
+            KeyCode::Enter => {
+                if self.input.trim().is_empty() {
+                    return Some(AppAction::None);
+                }
+                self.resolving = true;
+                self.error_message = None;
+                Some(AppAction::OpenByInput(self.input.trim().to_string()))
+            }
+            KeyCode::Esc => Some(AppAction::CloseOpenPrompt),
+            _ => Some(AppAction::None),
+        }
+    }
+}
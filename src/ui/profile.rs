@@ -0,0 +1,291 @@
+//! Profile page with level, experience, coins and 大会员 status of the logged-in user
+
+use super::help::{self, KeyHint};
+use super::{Component, Theme};
+use crate::api::auth::{NavInfo, NavStat};
+use crate::api::message::UnreadCounts;
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
+
+pub struct ProfilePage {
+    nav: Option<NavInfo>,
+    stat: Option<NavStat>,
+    unread: Option<UnreadCounts>,
+    loading: bool,
+    error: Option<String>,
+}
+
+impl ProfilePage {
+    pub fn new() -> Self {
+        Self {
+            nav: None,
+            stat: None,
+            unread: None,
+            loading: true,
+            error: None,
+        }
+    }
+
+    pub fn start_loading(&mut self) {
+        self.loading = true;
+        self.error = None;
+    }
+
+    /// Show fresh account info, the follow counts are optional since their endpoint can fail alone
+    pub fn set_data(&mut self, nav: NavInfo, stat: Option<NavStat>) {
+        self.nav = Some(nav);
+        self.stat = stat;
+        self.loading = false;
+        self.error = None;
+    }
+
+    pub fn set_unread(&mut self, unread: Option<UnreadCounts>) {
+        self.unread = unread;
+    }
+
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+        self.loading = false;
+    }
+
+    fn vip_text(nav: &NavInfo) -> String {
+        if !nav.is_vip() {
+            return "未开通".to_string();
+        }
+        nav.vip_due_date
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|due| {
+                let due = due.with_timezone(&chrono::Local);
+                format!("有效期至 {}", due.format("%Y-%m-%d"))
+            })
+            .unwrap_or_else(|| "已开通".to_string())
+    }
+
+    fn render_info(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        nav: &NavInfo,
+        theme: &Theme,
+        keys: &Keybindings,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Name + uid
+                Constraint::Length(1), // Level
+                Constraint::Length(1), // Exp gauge
+                Constraint::Length(1), // Spacer
+                Constraint::Length(4), // Coins, vip, counts, unread
+                Constraint::Min(0),
+                Constraint::Length(1), // Help
+            ])
+            .split(area);
+
+        let name = Line::from(vec![
+            Span::styled(
+                nav.uname.as_deref().unwrap_or("未知用户"),
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                nav.mid
+                    .map(|mid| format!("  UID {}", mid))
+                    .unwrap_or_default(),
+                Style::default().fg(theme.fg_muted),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(name), chunks[0]);
+
+        let Some(level) = &nav.level_info else {
+            let missing =
+                Paragraph::new("等级信息不可用").style(Style::default().fg(theme.fg_muted));
+            frame.render_widget(missing, chunks[1]);
+            return;
+        };
+
+        let level_line = Line::from(vec![
+            Span::styled("等级 ", Style::default().fg(theme.fg_secondary)),
+            Span::styled(
+                format!("Lv{}", level.current_level),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(level_line), chunks[1]);
+
+        let exp_label = match level.next_exp {
+            Some(next) => format!("{} / {}", level.current_exp, next),
+            None => format!("{} (已满级)", level.current_exp),
+        };
+        let gauge = Gauge::default()
+            .gauge_style(
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .bg(theme.bg_secondary),
+            )
+            .ratio(level.progress())
+            .label(Span::styled(
+                exp_label,
+                Style::default().fg(theme.fg_primary),
+            ));
+        frame.render_widget(gauge, chunks[2]);
+
+        let label = Style::default().fg(theme.fg_secondary);
+        let value = Style::default().fg(theme.fg_primary);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("硬币    ", label),
+                Span::styled(format!("{}", nav.money.unwrap_or(0.0)), value),
+            ]),
+            Line::from(vec![
+                Span::styled("大会员  ", label),
+                Span::styled(Self::vip_text(nav), value),
+            ]),
+        ];
+        if let Some(stat) = &self.stat {
+            lines.push(Line::from(vec![
+                Span::styled("关注 ", label),
+                Span::styled(stat.following.to_string(), value),
+                Span::styled(" · 粉丝 ", label),
+                Span::styled(stat.follower.to_string(), value),
+                Span::styled(" · 动态 ", label),
+                Span::styled(stat.dynamic_count.to_string(), value),
+            ]));
+        }
+        if let Some(unread) = &self.unread {
+            lines.push(Line::from(vec![
+                Span::styled("未读消息 回复 ", label),
+                Span::styled(unread.reply.to_string(), value),
+                Span::styled(" · @ ", label),
+                Span::styled(unread.at.to_string(), value),
+                Span::styled(" · 赞 ", label),
+                Span::styled(unread.like.to_string(), value),
+                Span::styled(" · 私信 ", label),
+                Span::styled(unread.chat.to_string(), value),
+            ]));
+        }
+        frame.render_widget(Paragraph::new(lines), chunks[4]);
+
+        let hints = [KeyHint::new(&keys.refresh, "刷新"), help::page_switch(keys)];
+        let help = Paragraph::new(help::hints_line(&hints, theme));
+        frame.render_widget(help, chunks[6]);
+    }
+}
+
+impl Default for ProfilePage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for ProfilePage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_subtle))
+            .title(Span::styled(
+                " 👤 我的账号 ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Left);
+
+        let inner = block.inner(area).inner(Margin::new(2, 1));
+        frame.render_widget(block, area);
+
+        if let Some(ref err) = self.error {
+            let error = Paragraph::new(err.as_str())
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme.error));
+            frame.render_widget(error, inner);
+            return;
+        }
+
+        match &self.nav {
+            Some(nav) if !self.loading => self.render_info(frame, inner, nav, theme, keys),
+            _ => {
+                let loading = Paragraph::new("加载中...")
+                    .alignment(Alignment::Center)
+                    .style(Style::default().fg(theme.fg_muted));
+                frame.render_widget(loading, inner);
+            }
+        }
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) || keys.matches_back(key) {
+            return Some(AppAction::BackToList);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::RefreshProfile);
+        }
+        if keys.matches_nav_next(key) {
+            return Some(AppAction::NavNext);
+        }
+        if keys.matches_nav_prev(key) {
+            return Some(AppAction::NavPrev);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::LevelInfo;
+    use crate::ui::test_utils::{contains, render};
+
+    fn nav() -> NavInfo {
+        NavInfo {
+            is_login: true,
+            mid: Some(10000),
+            uname: Some("测试用户".to_string()),
+            face: None,
+            money: Some(42.5),
+            vip_status: Some(0),
+            vip_type: Some(0),
+            vip_due_date: None,
+            level_info: Some(LevelInfo {
+                current_level: 4,
+                current_min: 4500,
+                current_exp: 7200,
+                next_exp: Some(10800),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_render_profile() {
+        let mut page = ProfilePage::new();
+        assert!(contains(&render(&mut page), "加载中..."));
+
+        page.set_data(
+            nav(),
+            Some(NavStat {
+                following: 128,
+                follower: 16,
+                dynamic_count: 3,
+            }),
+        );
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "测试用户  UID 10000"));
+        assert!(contains(&buffer, "Lv4"));
+        assert!(contains(&buffer, "7200 / 10800"));
+        assert!(contains(&buffer, "42.5"));
+        assert!(contains(&buffer, "未开通"));
+        assert!(contains(&buffer, "关注 128 · 粉丝 16 · 动态 3"));
+    }
+
+    #[test]
+    fn test_render_profile_error() {
+        let mut page = ProfilePage::new();
+        page.set_error("获取账号信息失败");
+        assert!(contains(&render(&mut page), "获取账号信息失败"));
+    }
+}
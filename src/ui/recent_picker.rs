@@ -0,0 +1,141 @@
+//! Quick-pick list of recently played videos, drawn over the current page
+
+use super::help::{self, KeyHint};
+use super::util::centered_fixed;
+use super::{Component, Theme};
+use crate::app::AppAction;
+use crate::storage::{Keybindings, RecentItem};
+use crate::util::truncate_display;
+use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
+
+pub struct RecentPicker {
+    items: Vec<RecentItem>,
+    selected: usize,
+}
+
+impl RecentPicker {
+    pub fn new(items: Vec<RecentItem>) -> Self {
+        Self { items, selected: 0 }
+    }
+}
+
+impl Component for RecentPicker {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let width = area.width.saturating_sub(4).min(64);
+        // Border, items and the hint line
+        let height = (self.items.len() as u16 + 3).min(area.height.saturating_sub(2));
+        let popup = centered_fixed(width, height, area);
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.bilibili_pink))
+            .style(Style::default().bg(theme.bg_modal))
+            .title(Span::styled(
+                " 🕘 最近播放 ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+        if inner.height == 0 {
+            return;
+        }
+
+        let list_area = Rect {
+            height: inner.height.saturating_sub(1),
+            ..inner
+        };
+        let title_cols = list_area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                ListItem::new(truncate_display(&item.title, title_cols))
+                    .style(Style::default().fg(theme.fg_primary))
+            })
+            .collect();
+        let list = List::new(items).highlight_symbol("▶ ").highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        let hints = [
+            help::scrolling(keys),
+            KeyHint::new(&keys.confirm, "播放"),
+            KeyHint::new(&keys.back, "关闭"),
+        ];
+        let hint_area = Rect {
+            y: inner.bottom() - 1,
+            height: 1,
+            ..inner
+        };
+        frame.render_widget(
+            Paragraph::new(help::hints_line(&hints, theme)).alignment(Alignment::Center),
+            hint_area,
+        );
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_down(key) {
+            if self.selected + 1 < self.items.len() {
+                self.selected += 1;
+            }
+        } else if keys.matches_up(key) {
+            self.selected = self.selected.saturating_sub(1);
+        } else if keys.matches_confirm(key) {
+            if let Some(item) = self.items.get(self.selected) {
+                return Some(AppAction::ReplayRecent(Box::new(item.clone())));
+            }
+        } else if keys.matches_back(key)
+            || keys.matches_quit(key)
+            || keys.matches_recent_played(key)
+        {
+            return Some(AppAction::CloseRecentPicker);
+        }
+        Some(AppAction::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    fn item(title: &str) -> RecentItem {
+        RecentItem {
+            url: format!("https://www.bilibili.com/video/{}", title),
+            bvid: title.to_string(),
+            mid: 3,
+            title: title.to_string(),
+            aid: 1,
+            cid: 2,
+            duration: 60,
+        }
+    }
+
+    #[test]
+    fn test_pick_recent_item() {
+        let keys = Keybindings::default();
+        let mut picker = RecentPicker::new(vec![item("BV1first"), item("BV1second")]);
+        let buffer = render(&mut picker);
+        assert!(contains(&buffer, "最近播放"));
+        assert!(contains(&buffer, "BV1second"));
+
+        picker.handle_input(KeyCode::Char('j'), &keys);
+        picker.handle_input(KeyCode::Char('j'), &keys);
+        match picker.handle_input(KeyCode::Enter, &keys) {
+            Some(AppAction::ReplayRecent(picked)) => assert_eq!(picked.bvid, "BV1second"),
+            other => panic!("unexpected action {:?}", other),
+        }
+        assert!(matches!(
+            picker.handle_input(KeyCode::Esc, &keys),
+            Some(AppAction::CloseRecentPicker)
+        ));
+    }
+}
@@ -1,10 +1,15 @@
 //! Search page with video card grid display
 
-use super::video_card::{VideoCard, VideoCardGrid};
+use super::help::{self, KeyHint};
+use super::toast::Toast;
+use super::video_card::{VideoCard, VideoCardGrid, MAX_TOP_UPS};
 use super::{Component, Theme};
-use crate::api::client::ApiClient;
-use crate::api::search::{HotwordItem, SearchVideoItem};
-use crate::app::AppAction;
+use crate::api::bvid::{parse_exact_video_ref, VideoRef};
+use crate::api::comment::{self, CommentType};
+use crate::api::search::{HotwordItem, SearchData, SearchVideoItem, MAX_SEARCH_PAGES};
+use crate::api::BiliApi;
+use crate::app::{next_request_id, AppAction};
+use crate::storage::{CoverFit, Keybindings, WatchedVideos};
 use ratatui::{
     crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
@@ -12,6 +17,36 @@ use ratatui::{
 };
 use std::time::Instant;
 
+/// Search requests that can fail and be retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchRequest {
+    /// First result page for the keyword
+    Search(String),
+    /// Next result page of the current query
+    More,
+}
+
+/// Search request recorded by the page, copied into the task that runs it
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub kind: SearchRequest,
+    /// Matched against the page's latest request when the answer arrives
+    id: u64,
+    keyword: String,
+    page: i32,
+}
+
+/// Answer to a [`SearchQuery`], applied by [`SearchPage::finish`]
+pub struct SearchAnswer {
+    query: SearchQuery,
+    data: anyhow::Result<SearchData>,
+}
+
+pub async fn fetch_search(api_client: &impl BiliApi, query: SearchQuery) -> SearchAnswer {
+    let data = api_client.search_videos(&query.keyword, query.page).await;
+    SearchAnswer { query, data }
+}
+
 pub struct SearchPage {
     pub query: String,
     pub grid: VideoCardGrid,
@@ -25,7 +60,25 @@ pub struct SearchPage {
     hot_selected: Option<usize>,
     pub page: i32,
     pub total_results: i32,
+    pub total_pages: i32,
+    /// Results go beyond the search page cap
+    pub capped: bool,
     pub loading_more: bool,
+    toast: Option<Toast>,
+    /// Request behind the error or toast being shown, re-issued by the retry key
+    failed: Option<SearchRequest>,
+    /// Query of the results on screen while a hot word search replaces them
+    pivot_from: Option<String>,
+    /// Substring narrowing the fetched results by title or author, empty for none
+    pub result_filter: String,
+    /// Watched results are kept out of the grid until hiding is turned off
+    hide_watched: bool,
+    /// Typing into the result filter
+    pub filter_input: bool,
+    /// Id of the latest search request, answers to older ones are dropped
+    request_id: u64,
+    /// Pages loaded by [`top_up`](Self::top_up) since the last search
+    top_ups: usize,
     last_click_time: Option<Instant>,
     last_click_index: Option<usize>,
 }
@@ -45,15 +98,32 @@ impl SearchPage {
             hot_selected: None,
             page: 1,
             total_results: 0,
+            total_pages: 0,
+            capped: false,
             loading_more: false,
+            toast: None,
+            failed: None,
+            pivot_from: None,
+            result_filter: String::new(),
+            hide_watched: false,
+            filter_input: false,
+            request_id: 0,
+            top_ups: 0,
             last_click_time: None,
             last_click_index: None,
         }
     }
 
-    pub fn set_results(&mut self, results: Vec<SearchVideoItem>, total: i32) {
+    pub fn set_results(&mut self, data: SearchData) {
         self.grid.clear();
-        for item in results {
+        self.page = 1;
+        self.pivot_from = None;
+        self.result_filter.clear();
+        self.filter_input = false;
+        self.total_pages = data.total_pages();
+        self.capped = data.is_capped();
+        self.toast = None;
+        for item in data.result.unwrap_or_default() {
             let card = VideoCard::new(
                 item.bvid.clone(),
                 item.mid,
@@ -65,11 +135,12 @@ impl SearchPage {
             );
             self.grid.add_card(card);
         }
-        self.total_results = total;
+        self.total_results = data.num_results.unwrap_or(0);
         self.loading = false;
         self.input_mode = false;
         self.show_hot_list = false;
         self.error_message = None;
+        self.failed = None;
     }
 
     pub fn append_results(&mut self, results: Vec<SearchVideoItem>) {
@@ -85,16 +156,94 @@ impl SearchPage {
             );
             self.grid.add_card(card);
         }
+        if !self.result_filter.is_empty() || self.hide_watched {
+            self.apply_filter();
+        }
         self.loading_more = false;
     }
 
-    pub fn set_error(&mut self, msg: String) {
-        self.error_message = Some(msg);
+    /// Show only the fetched results whose title or author contains the filter, leaving out
+    /// watched ones while they are hidden
+    fn apply_filter(&mut self) {
+        if self.result_filter.is_empty() && !self.hide_watched {
+            self.grid.unfilter();
+            return;
+        }
+        let needle = self.result_filter.to_lowercase();
+        let hide_watched = self.hide_watched;
+        self.grid.filter(|card| {
+            !(hide_watched && card.watched)
+                && (card.title.to_lowercase().contains(&needle)
+                    || card.author.to_lowercase().contains(&needle))
+        });
+    }
+
+    /// Drop the text filter and bring back every fetched result it hid
+    fn clear_filter(&mut self) {
+        self.result_filter.clear();
+        self.filter_input = false;
+        self.apply_filter();
+    }
+
+    /// Hide watched results, or bring them back, keeping everything fetched so far
+    ///
+    /// Called again after each load so the new results are checked too.
+    pub fn set_hide_watched(&mut self, hide: bool, watched: &WatchedVideos) {
+        self.hide_watched = hide;
+        // Hidden cards need their watched flag refreshed as well
+        self.grid.unfilter();
+        self.grid.mark_watched(watched);
+        self.apply_filter();
+    }
+
+    /// What Enter opens when the query is a video id or link rather than a keyword
+    fn detected_target(&self) -> Option<String> {
+        match parse_exact_video_ref(&self.query)? {
+            VideoRef::Video { bvid, aid } => Some(format!("↵ 打开视频 av{} · {}", aid, bvid)),
+            VideoRef::ShortLink(_) => Some("↵ 打开短链接".to_string()),
+        }
+    }
+
+    fn handle_filter_input(&mut self, key: KeyCode) -> Option<AppAction> {
+        match key {
+            KeyCode::Char(c) => {
+                self.result_filter.push(c);
+                self.apply_filter();
+            }
+            KeyCode::Backspace => {
+                self.result_filter.pop();
+                self.apply_filter();
+            }
+            KeyCode::Enter => self.filter_input = false,
+            KeyCode::Esc => self.clear_filter(),
+            _ => {}
+        }
+        Some(AppAction::None)
+    }
+
+    /// Show a failed search, leaving input so the retry key reaches the page
+    ///
+    /// A failed hot word search keeps the previous results and reports the error as a toast.
+    pub fn set_error(&mut self, msg: String, failed: SearchRequest) {
+        self.failed = Some(failed);
+        match self.pivot_from.take() {
+            Some(query) if self.grid.total_cards() > 0 => {
+                self.query = query;
+                self.toast = Some(Toast::new(msg));
+            }
+            _ => self.error_message = Some(msg),
+        }
         self.loading = false;
         self.loading_more = false;
+        self.input_mode = false;
         self.show_hot_list = false;
     }
 
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<SearchRequest> {
+        self.failed.take()
+    }
+
     pub fn start_hotword_loading(&mut self) {
         self.hotword_loading = true;
         self.hotword_error = None;
@@ -117,40 +266,161 @@ impl SearchPage {
         self.hotword_loading = false;
     }
 
-    pub async fn load_more(&mut self, api_client: &ApiClient) {
-        if self.loading_more || self.query.is_empty() || self.show_hot_list {
-            return;
+    /// Record a search for `keyword`, dropping a next-page request still in flight
+    pub fn begin_search(&mut self, keyword: String) -> SearchQuery {
+        self.loading_more = false;
+        self.top_ups = 0;
+        self.request_id = next_request_id();
+        SearchQuery {
+            kind: SearchRequest::Search(keyword.clone()),
+            id: self.request_id,
+            keyword,
+            page: 1,
         }
+    }
 
-        // Check if we have more results
-        if self.grid.cards.len() >= self.total_results as usize {
-            return;
+    /// Record a request for the next result page, `None` when there is none to load
+    pub fn begin_more(&mut self) -> Option<SearchQuery> {
+        if self.loading || self.loading_more || self.query.is_empty() || self.show_hot_list {
+            return None;
+        }
+        if !self.has_more_pages() {
+            return None;
         }
-
         self.loading_more = true;
-        self.page += 1;
+        self.request_id = next_request_id();
+        Some(SearchQuery {
+            kind: SearchRequest::More,
+            id: self.request_id,
+            keyword: self.query.clone(),
+            page: self.page + 1,
+        })
+    }
 
-        match api_client.search_videos(&self.query, self.page).await {
-            Ok(data) => {
+    /// Record a request for the next result page when the results don't fill the screen
+    ///
+    /// At most [`MAX_TOP_UPS`] pages per search, `None` once the results run out.
+    pub fn top_up(&mut self) -> Option<SearchQuery> {
+        if self.top_ups >= MAX_TOP_UPS || self.grid.fills_screen() {
+            return None;
+        }
+        let query = self.begin_more()?;
+        self.top_ups += 1;
+        Some(query)
+    }
+
+    /// Apply the answer to the last request, returning the index the new results start at
+    ///
+    /// Answers to superseded requests and failures return `None`.
+    pub fn finish(&mut self, answer: SearchAnswer) -> Option<usize> {
+        let SearchAnswer { query, data } = answer;
+        if query.id != self.request_id {
+            return None;
+        }
+        match (query.kind, data) {
+            (SearchRequest::Search(_), Ok(data)) => {
+                self.set_results(data);
+                Some(0)
+            }
+            (SearchRequest::Search(keyword), Err(e)) => {
+                self.set_error(format!("搜索失败: {}", e), SearchRequest::Search(keyword));
+                None
+            }
+            (SearchRequest::More, Ok(data)) => {
                 let results = data.result.unwrap_or_default();
-                if results.is_empty() {
-                    self.page -= 1;
+                if !results.is_empty() {
+                    self.page = query.page;
                 }
+                let loaded = self.grid.cards.len();
                 self.append_results(results);
+                self.failed = None;
+                Some(loaded)
             }
-            Err(_) => {
-                self.page -= 1;
+            (SearchRequest::More, Err(e)) => {
                 self.loading_more = false;
+                self.failed = Some(SearchRequest::More);
+                self.toast = Some(Toast::new(format!("加载更多失败: {}", e)));
+                None
             }
         }
     }
 
-    pub fn poll_cover_results(&mut self) {
-        self.grid.poll_cover_results();
+    /// Load the next result page in place, what the app does across a spawned task
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if let Some(query) = self.begin_more() {
+            let answer = fetch_search(api_client, query).await;
+            self.finish(answer);
+        }
     }
 
-    pub fn start_cover_downloads(&mut self) {
-        self.grid.start_cover_downloads();
+    pub fn has_more_pages(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// Open the selected result's comments, or explain why it has none
+    fn open_comments(&mut self) -> AppAction {
+        let Some(card) = self.grid.selected_card() else {
+            return AppAction::None;
+        };
+        match comment::video_oid(card.bvid.as_deref(), card.aid) {
+            Some(oid) => AppAction::OpenComments {
+                oid,
+                comment_type: CommentType::Video.as_i32(),
+                title: card.title.clone(),
+            },
+            None => {
+                self.toast = Some(Toast::new("该视频没有评论区"));
+                AppAction::None
+            }
+        }
+    }
+
+    fn active_toast(&self) -> Option<&str> {
+        self.toast.as_ref().and_then(Toast::active_message)
+    }
+
+    /// Request the next result page, or explain why there is none
+    fn request_next_page(&mut self) -> Option<AppAction> {
+        if self.loading_more {
+            return Some(AppAction::None);
+        }
+        if self.has_more_pages() {
+            return Some(AppAction::LoadMoreSearch);
+        }
+        let msg = if self.capped {
+            format!(
+                "已到达第 {} 页，B站搜索最多只返回 {} 页",
+                self.page, MAX_SEARCH_PAGES
+            )
+        } else {
+            "已经是最后一页".to_string()
+        };
+        self.toast = Some(Toast::new(msg));
+        Some(AppAction::None)
+    }
+
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        self.grid.mark_watched(watched);
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
     }
 
     fn select_hotword(&mut self, idx: usize) {
@@ -163,9 +433,14 @@ impl SearchPage {
         if let Some(idx) = self.hot_selected {
             if let Some(item) = self.hotwords.get(idx) {
                 if let Some(keyword) = item.keyword_text() {
+                    if self.grid.total_cards() == 0 {
+                        self.loading = true;
+                        self.page = 1;
+                    } else {
+                        // Results stay up until the new search answers
+                        self.pivot_from = Some(std::mem::take(&mut self.query));
+                    }
                     self.query = keyword.clone();
-                    self.loading = true;
-                    self.page = 1;
                     self.show_hot_list = false;
                     return Some(AppAction::Search(keyword));
                 }
@@ -174,6 +449,27 @@ impl SearchPage {
         None
     }
 
+    /// Show the hot search list over the results, fetching it if it never arrived
+    fn open_hot_list(&mut self) -> Option<AppAction> {
+        self.show_hot_list = true;
+        if self.hotwords.is_empty() {
+            if self.hotword_loading {
+                return Some(AppAction::None);
+            }
+            self.start_hotword_loading();
+            return Some(AppAction::LoadHotwords);
+        }
+        if self.hot_selected.is_none() {
+            self.hot_selected = Some(0);
+        }
+        Some(AppAction::None)
+    }
+
+    /// Whether the hot list was opened over results it can go back to
+    fn has_results_behind(&self) -> bool {
+        self.grid.total_cards() > 0 || self.error_message.is_some()
+    }
+
     fn draw_hot_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -251,8 +547,77 @@ impl Default for SearchPage {
     }
 }
 
+impl SearchPage {
+    /// Footer entries for the keys handled in the current focus
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        if self.filter_input {
+            vec![
+                KeyHint::fixed("Enter", "确定"),
+                KeyHint::fixed("Esc", "清除筛选"),
+            ]
+        } else if self.input_mode {
+            let mut hints = vec![
+                KeyHint::fixed("Enter", "搜索"),
+                KeyHint::fixed("Esc", "取消"),
+            ];
+            if self.show_hot_list && !self.hotwords.is_empty() {
+                hints.push(KeyHint::fixed("↑↓", "热搜"));
+            }
+            hints.push(help::page_switch(keys));
+            hints
+        } else if self.show_hot_list {
+            let mut hints = vec![
+                KeyHint::pair(&keys.nav_down, &keys.nav_up, "选择"),
+                KeyHint::new(&keys.confirm, "搜索热词"),
+                KeyHint::new(&keys.search_focus, "输入"),
+                help::page_switch(keys),
+                KeyHint::new(&keys.quit, "退出"),
+            ];
+            if self.has_results_behind() {
+                hints.insert(3, KeyHint::new(&keys.back, "返回结果"));
+            }
+            hints
+        } else {
+            let mut hints = vec![
+                help::navigation(keys),
+                KeyHint::new(&keys.confirm, "详情"),
+                KeyHint::new(&keys.next_page, "下一页"),
+                KeyHint::new(&keys.search_focus, "搜索"),
+                KeyHint::new(&keys.hot_words, "热搜"),
+                KeyHint::new(&keys.filter_results, "筛选"),
+                KeyHint::new(&keys.download, "下载"),
+                KeyHint::new(&keys.open_comments, "评论"),
+                KeyHint::new(&keys.toggle_hide_watched, "隐藏已看"),
+                help::page_switch(keys),
+            ];
+            if self.failed.is_some() {
+                hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+            }
+            if !self.result_filter.is_empty() {
+                hints.insert(1, KeyHint::new(&keys.back, "清除筛选"));
+            }
+            hints
+        }
+    }
+
+    /// Header after the result count while a filter is typed or applied
+    fn filter_label(&self) -> Option<String> {
+        if !self.filter_input && self.result_filter.is_empty() {
+            return None;
+        }
+        let cursor = if self.filter_input { "▌" } else { "" };
+        Some(format!(
+            " · 筛选: {}{} ({}/{})",
+            self.result_filter,
+            cursor,
+            self.grid.cards.len(),
+            self.grid.total_cards()
+        ))
+    }
+}
+
 impl Component for SearchPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -269,7 +634,7 @@ impl Component for SearchPage {
             Style::default().fg(theme.fg_primary)
         };
 
-        let input_block = Block::default()
+        let mut input_block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(if self.input_mode {
@@ -281,6 +646,15 @@ impl Component for SearchPage {
                 " 🔍 搜索视频 ",
                 Style::default().fg(theme.bilibili_pink),
             ));
+        if let Some(target) = self.input_mode.then(|| self.detected_target()).flatten() {
+            input_block = input_block.title(
+                Line::from(Span::styled(
+                    format!(" {} ", target),
+                    Style::default().fg(theme.success),
+                ))
+                .right_aligned(),
+            );
+        }
 
         let cursor_char = if self.input_mode { "▌" } else { "" };
         let input = Paragraph::new(format!("{}{}", self.query, cursor_char))
@@ -307,7 +681,11 @@ impl Component for SearchPage {
                 );
             frame.render_widget(loading, chunks[1]);
         } else if let Some(error) = &self.error_message {
-            let error_widget = Paragraph::new(format!("❌ {}", error))
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
                 .style(Style::default().fg(theme.error))
                 .alignment(Alignment::Center)
                 .block(
@@ -318,28 +696,53 @@ impl Component for SearchPage {
                 );
             frame.render_widget(error_widget, chunks[1]);
         } else if self.grid.cards.is_empty() {
-            let empty = Paragraph::new(if self.query.is_empty() {
-                "输入关键词开始搜索"
+            let text = if self.grid.total_cards() > 0
+                && self.result_filter.is_empty()
+                && !self.filter_input
+            {
+                format!(
+                    "这些结果都看过了，按 {} 显示已看",
+                    help::key_label(&keys.toggle_hide_watched)
+                )
+            } else if self.grid.total_cards() > 0 {
+                format!(
+                    "没有匹配「{}{}」的结果，按 Esc 清除筛选",
+                    self.result_filter,
+                    if self.filter_input { "▌" } else { "" }
+                )
+            } else if self.query.is_empty() {
+                "输入关键词开始搜索".to_string()
             } else {
-                "没有找到相关视频"
-            })
-            .style(Style::default().fg(theme.fg_secondary))
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(theme.border_unfocused)),
-            );
+                "没有找到相关视频".to_string()
+            };
+            let empty = Paragraph::new(text)
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(theme.border_unfocused)),
+                );
             frame.render_widget(empty, chunks[1]);
         } else {
             // Render with header
             let header = Paragraph::new(Line::from(vec![
                 Span::styled(" 搜索结果 ", Style::default().fg(theme.bilibili_pink)),
                 Span::styled(
-                    format!("({}/{})", self.grid.cards.len(), self.total_results),
+                    format!(
+                        "({}/{}) · 第 {}/{} 页",
+                        self.grid.cards.len(),
+                        self.total_results,
+                        self.page,
+                        self.total_pages.max(1)
+                    ),
                     Style::default().fg(theme.fg_muted),
                 ),
+                Span::styled(
+                    self.filter_label().unwrap_or_default(),
+                    Style::default().fg(theme.fg_accent),
+                ),
                 if self.loading_more {
                     Span::styled(" 加载中...", Style::default().fg(theme.warning))
                 } else {
@@ -367,24 +770,20 @@ impl Component for SearchPage {
             self.grid.render(frame, grid_area, theme);
         }
 
-        // Help
-        let help_text = if self.input_mode {
-            "[Enter] 搜索  [Esc] 取消  [Tab] 导航"
+        // Help, replaced by a pagination notice while one is active
+        let help = if let Some(toast) = self.active_toast() {
+            Paragraph::new(format!("ℹ {}", toast)).style(Style::default().fg(theme.warning))
         } else {
-            "[←↑↓→/hjkl] 导航  [Enter] 详情  [/] 搜索  [Tab] 切换"
-        };
-        let help = Paragraph::new(help_text)
-            .style(Style::default().fg(theme.fg_secondary))
-            .alignment(Alignment::Center);
+            Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+        }
+        .alignment(Alignment::Center);
         frame.render_widget(help, chunks[2]);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
-        if self.input_mode {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if self.filter_input {
+            self.handle_filter_input(key)
+        } else if self.input_mode {
             match key {
                 KeyCode::Char(c) => {
                     self.query.push(c);
@@ -421,7 +820,11 @@ impl Component for SearchPage {
                     Some(AppAction::None)
                 }
                 KeyCode::Enter => {
-                    if !self.query.trim().is_empty() {
+                    if parse_exact_video_ref(&self.query).is_some() {
+                        // Ids and links go straight to the video instead of searching for them
+                        self.show_hot_list = false;
+                        Some(AppAction::OpenByInput(self.query.trim().to_string()))
+                    } else if !self.query.trim().is_empty() {
                         self.loading = true;
                         self.page = 1;
                         self.show_hot_list = false;
@@ -467,6 +870,11 @@ impl Component for SearchPage {
                 self.show_hot_list = true;
                 return Some(AppAction::None);
             }
+            if (keys.matches_back(key) || keys.matches_hot_words(key)) && self.has_results_behind()
+            {
+                self.show_hot_list = false;
+                return Some(AppAction::None);
+            }
             if keys.matches_nav_next(key) {
                 return Some(AppAction::NavNext);
             }
@@ -478,14 +886,23 @@ impl Component for SearchPage {
             }
             Some(AppAction::None)
         } else {
+            if keys.matches_retry(key) && self.failed.is_some() {
+                return Some(AppAction::RetryFailed);
+            }
             if keys.matches_down(key) {
-                self.grid.move_down();
+                let moved = self.grid.move_down();
                 // Check for pagination
-                if self.grid.is_near_bottom(3) && !self.loading_more {
+                if self.grid.is_near_bottom(3) && !self.loading_more && self.has_more_pages() {
                     return Some(AppAction::LoadMoreSearch);
                 }
+                if !moved && self.grid.selected_row() + 1 >= self.grid.total_rows() {
+                    return self.request_next_page();
+                }
                 return Some(AppAction::None);
             }
+            if keys.matches_next_page(key) {
+                return self.request_next_page();
+            }
             if keys.matches_up(key) {
                 self.grid.move_up();
                 return Some(AppAction::None);
@@ -506,6 +923,30 @@ impl Component for SearchPage {
                 }
                 return Some(AppAction::None);
             }
+            if keys.matches_toggle_hide_watched(key) {
+                return Some(AppAction::ToggleHideWatched);
+            }
+            if keys.matches_open_comments(key) {
+                return Some(self.open_comments());
+            }
+            if keys.matches_hot_words(key) {
+                return self.open_hot_list();
+            }
+            if keys.matches_filter_results(key) && self.grid.total_cards() > 0 {
+                self.filter_input = true;
+                return Some(AppAction::None);
+            }
+            if keys.matches_back(key) && !self.result_filter.is_empty() {
+                self.clear_filter();
+                return Some(AppAction::None);
+            }
+            if keys.matches_download(key) {
+                return Some(
+                    self.grid
+                        .selected_bvid()
+                        .map_or(AppAction::None, AppAction::DownloadVideo),
+                );
+            }
             if keys.matches_search_focus(key) {
                 self.input_mode = true;
                 self.show_hot_list = true;
@@ -660,3 +1101,417 @@ impl Component for SearchPage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    fn press(page: &mut SearchPage, keys: &[KeyCode]) -> Option<AppAction> {
+        let bindings = Keybindings::default();
+        let mut last = None;
+        for key in keys {
+            last = page.handle_input(*key, &bindings);
+        }
+        last
+    }
+
+    fn hotword(keyword: &str) -> HotwordItem {
+        HotwordItem {
+            keyword: Some(keyword.to_string()),
+            show_name: None,
+            icon: None,
+            pos: None,
+            word_type: None,
+        }
+    }
+
+    #[test]
+    fn test_video_id_opens_instead_of_searching() {
+        let mut page = SearchPage::new();
+        let keys: Vec<_> = "av170001".chars().map(KeyCode::Char).collect();
+        press(&mut page, &keys);
+        assert!(contains(
+            &render(&mut page),
+            "打开视频 av170001 · BV17x411w7KC"
+        ));
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Enter]),
+            Some(AppAction::OpenByInput(input)) if input == "av170001"
+        ));
+        assert!(!page.loading);
+
+        // A keyword that only starts like an id is still searched
+        let mut page = SearchPage::new();
+        let keys: Vec<_> = "avatar".chars().map(KeyCode::Char).collect();
+        press(&mut page, &keys);
+        assert!(!contains(&render(&mut page), "打开视频"));
+        assert!(matches!(
+            press(&mut page, &[KeyCode::Enter]),
+            Some(AppAction::Search(keyword)) if keyword == "avatar"
+        ));
+    }
+
+    #[test]
+    fn test_typing_appends_to_query() {
+        let mut page = SearchPage::new();
+        let action = press(&mut page, &[KeyCode::Char('r'), KeyCode::Char('s')]);
+        assert_eq!(page.query, "rs");
+        assert!(page.input_mode);
+        assert!(page.show_hot_list);
+        assert!(matches!(action, Some(AppAction::None)));
+    }
+
+    #[test]
+    fn test_quit_key_is_typed_in_input_mode() {
+        let mut page = SearchPage::new();
+        let action = press(&mut page, &[KeyCode::Char('q')]);
+        assert_eq!(page.query, "q");
+        assert!(matches!(action, Some(AppAction::None)));
+    }
+
+    #[test]
+    fn test_backspace_on_empty_query() {
+        let mut page = SearchPage::new();
+        let action = press(&mut page, &[KeyCode::Backspace]);
+        assert!(page.query.is_empty());
+        assert!(page.input_mode);
+        assert!(matches!(action, Some(AppAction::None)));
+    }
+
+    #[test]
+    fn test_enter_submits_query() {
+        let mut page = SearchPage::new();
+        let action = press(
+            &mut page,
+            &[KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Enter],
+        );
+        match action {
+            Some(AppAction::Search(keyword)) => assert_eq!(keyword, "ab"),
+            other => panic!("unexpected action: {:?}", other),
+        }
+        assert!(page.loading);
+        assert_eq!(page.page, 1);
+        assert!(!page.show_hot_list);
+    }
+
+    #[test]
+    fn test_enter_with_blank_query_searches_hotword() {
+        let mut page = SearchPage::new();
+        page.set_hotwords(vec![hotword("first"), hotword("second")]);
+        let action = press(&mut page, &[KeyCode::Down, KeyCode::Enter]);
+        match action {
+            Some(AppAction::Search(keyword)) => assert_eq!(keyword, "second"),
+            other => panic!("unexpected action: {:?}", other),
+        }
+        assert_eq!(page.query, "second");
+    }
+
+    #[test]
+    fn test_enter_with_blank_query_and_no_hotwords() {
+        let mut page = SearchPage::new();
+        page.show_hot_list = false;
+        let action = press(&mut page, &[KeyCode::Char(' '), KeyCode::Enter]);
+        assert!(!matches!(action, Some(AppAction::Search(_))));
+        assert!(!page.loading);
+    }
+
+    #[test]
+    fn test_esc_leaves_input_mode_and_slash_returns() {
+        let mut page = SearchPage::new();
+        press(&mut page, &[KeyCode::Esc]);
+        assert!(!page.input_mode);
+
+        let action = press(&mut page, &[KeyCode::Char('q')]);
+        assert!(matches!(action, Some(AppAction::Quit)));
+
+        press(&mut page, &[KeyCode::Char('/')]);
+        assert!(page.input_mode);
+        assert!(page.show_hot_list);
+    }
+
+    #[test]
+    fn test_hotword_selection_wraps() {
+        let mut page = SearchPage::new();
+        page.set_hotwords(vec![hotword("a"), hotword("b"), hotword("c")]);
+        press(&mut page, &[KeyCode::Up]);
+        assert_eq!(page.hot_selected, Some(2));
+        press(&mut page, &[KeyCode::Down]);
+        assert_eq!(page.hot_selected, Some(0));
+    }
+
+    fn results_page(total: i32, pages: i32, capped: bool) -> SearchPage {
+        let mut page = SearchPage::new();
+        page.set_results(SearchData {
+            result: None,
+            num_results: Some(total),
+            page: Some(1),
+            pagesize: Some(20),
+            num_pages: Some(pages),
+        });
+        page.capped = capped;
+        page
+    }
+
+    #[test]
+    fn test_next_page_requests_more() {
+        let mut page = results_page(60, 3, false);
+        let action = press(&mut page, &[KeyCode::Char('n')]);
+        assert!(matches!(action, Some(AppAction::LoadMoreSearch)));
+        assert!(page.active_toast().is_none());
+    }
+
+    #[test]
+    fn test_next_page_follows_the_keybinding() {
+        let mut page = results_page(60, 3, false);
+        let keys = Keybindings {
+            next_page: "N".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('N'), &keys),
+            Some(AppAction::LoadMoreSearch)
+        ));
+        let labels: Vec<_> = page
+            .footer_hints(&keys)
+            .into_iter()
+            .map(|hint| hint.keys)
+            .collect();
+        assert!(labels.contains(&"N".to_string()));
+    }
+
+    #[test]
+    fn test_next_page_on_last_page_shows_toast() {
+        let mut page = results_page(15, 1, false);
+        let action = press(&mut page, &[KeyCode::Char('n')]);
+        assert!(matches!(action, Some(AppAction::None)));
+        assert_eq!(page.active_toast(), Some("已经是最后一页"));
+    }
+
+    #[test]
+    fn test_short_results_are_topped_up_until_the_last_page() {
+        let mut page = results_page(60, 3, false);
+        page.query = "rust".to_string();
+        // Nothing on screen yet, so the next page is fetched right away
+        assert_eq!(page.top_up().map(|query| query.page), Some(2));
+        // Waits for the page in flight
+        assert!(page.top_up().is_none());
+
+        page.loading_more = false;
+        page.page = 3;
+        assert!(page.top_up().is_none());
+    }
+
+    #[test]
+    fn test_next_page_beyond_cap_explains_limit() {
+        let mut page = results_page(1000, 50, true);
+        page.page = MAX_SEARCH_PAGES;
+        press(&mut page, &[KeyCode::Char('n')]);
+        assert!(page.active_toast().is_some_and(|msg| msg.contains("50 页")));
+    }
+
+    fn search_data(titles: &[&str]) -> SearchData {
+        let result: Vec<_> = titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                serde_json::json!({
+                    "bvid": format!("BV{}", i),
+                    "title": title,
+                    "author": "UP",
+                    "play": 100,
+                    "duration": "1:00",
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "result": result,
+            "numResults": titles.len(),
+            "page": 1,
+            "pagesize": 20,
+        }))
+        .expect("valid search data")
+    }
+
+    #[test]
+    fn test_hide_watched_filters_fetched_results() {
+        let mut page = SearchPage::new();
+        page.set_results(search_data(&["Rust 入门", "Go 入门", "rust 进阶"]));
+        let mut watched = WatchedVideos::default();
+        watched.insert("BV0");
+
+        page.set_hide_watched(true, &watched);
+        assert_eq!(page.grid.cards.len(), 2);
+        // Clearing the text filter keeps watched results hidden
+        press(&mut page, &[KeyCode::Char('f'), KeyCode::Char('R')]);
+        assert_eq!(page.grid.cards.len(), 1);
+        press(&mut page, &[KeyCode::Esc]);
+        assert_eq!(page.grid.cards.len(), 2);
+
+        page.set_hide_watched(false, &watched);
+        assert_eq!(page.grid.cards.len(), 3);
+        assert_eq!(page.grid.cards[0].title, "Rust 入门");
+    }
+
+    #[test]
+    fn test_filter_narrows_and_restores_results() {
+        let mut page = SearchPage::new();
+        page.set_results(search_data(&["Rust 入门", "Go 入门", "rust 进阶"]));
+        page.grid.selected_index = 2;
+
+        press(
+            &mut page,
+            &[KeyCode::Char('f'), KeyCode::Char('R'), KeyCode::Char('u')],
+        );
+        assert!(page.filter_input);
+        assert_eq!(page.grid.cards.len(), 2);
+        assert_eq!(page.grid.selected_card().unwrap().title, "rust 进阶");
+        assert!(contains(&render(&mut page), "筛选: Ru▌ (2/3)"));
+
+        press(&mut page, &[KeyCode::Enter]);
+        assert!(!page.filter_input);
+        assert!(contains(&render(&mut page), "筛选: Ru (2/3)"));
+
+        press(&mut page, &[KeyCode::Esc]);
+        assert!(page.result_filter.is_empty());
+        assert_eq!(page.grid.cards.len(), 3);
+        assert_eq!(page.grid.cards[1].title, "Go 入门");
+        assert_eq!(page.grid.selected_card().unwrap().title, "rust 进阶");
+
+        press(&mut page, &[KeyCode::Char('f'), KeyCode::Char('x')]);
+        assert!(page.grid.cards.is_empty());
+        assert!(contains(&render(&mut page), "没有匹配"));
+        press(&mut page, &[KeyCode::Esc]);
+        assert_eq!(page.grid.cards.len(), 3);
+    }
+
+    #[test]
+    fn test_render_hot_list_states() {
+        let mut page = SearchPage::new();
+        page.hotword_loading = true;
+        assert!(contains(&render(&mut page), "⏳ 正在获取热搜..."));
+
+        page.set_hotword_error("超时".to_string());
+        assert!(contains(&render(&mut page), "❌ 超时"));
+
+        page.set_hotwords(vec![hotword("rust")]);
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "热搜榜"));
+        assert!(contains(&buffer, "rust"));
+    }
+
+    #[test]
+    fn test_render_result_states() {
+        let mut page = SearchPage::new();
+        page.query = "rust".to_string();
+        page.show_hot_list = false;
+        page.loading = true;
+        assert!(contains(&render(&mut page), "⏳ 搜索中..."));
+
+        page.loading = false;
+        page.error_message = Some("请求失败".to_string());
+        assert!(contains(&render(&mut page), "❌ 请求失败"));
+
+        page.set_results(search_data(&[]));
+        assert!(contains(&render(&mut page), "没有找到相关视频"));
+
+        page.set_results(search_data(&["first video", "second video"]));
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "(2/2) · 第 1/1 页"));
+        assert!(contains(&buffer, "first video"));
+        assert!(contains(&buffer, "second video"));
+    }
+
+    #[test]
+    fn test_footer_follows_focus() {
+        let mut page = SearchPage::new();
+        assert!(contains(&render(&mut page), "[Enter] 搜索  [Esc] 取消"));
+
+        let keys = Keybindings {
+            search_focus: "s".to_string(),
+            ..Default::default()
+        };
+        page.input_mode = false;
+        page.show_hot_list = false;
+        let labels: Vec<_> = page
+            .footer_hints(&keys)
+            .into_iter()
+            .map(|hint| format!("[{}] {}", hint.keys, hint.label))
+            .collect();
+        assert!(labels.contains(&"[Enter] 详情".to_string()));
+        assert!(labels.contains(&"[s] 搜索".to_string()));
+        assert!(!labels.iter().any(|label| label.contains("取消")));
+    }
+
+    #[test]
+    fn test_failed_search_keeps_query_for_retry() {
+        let keys = Keybindings::default();
+        let mut page = SearchPage::new();
+        for c in "rust".chars() {
+            page.handle_input(KeyCode::Char(c), &keys);
+        }
+        page.handle_input(KeyCode::Enter, &keys);
+        page.set_error(
+            "搜索失败: 请求超时".to_string(),
+            SearchRequest::Search("rust".to_string()),
+        );
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 搜索失败: 请求超时"));
+        assert!(contains(&buffer, "按 e 重试"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &keys),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.query, "rust");
+        assert_eq!(
+            page.take_failed(),
+            Some(SearchRequest::Search("rust".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_hot_word_pivot_keeps_results_on_failure() {
+        let mut page = SearchPage::new();
+        page.query = "rust".to_string();
+        page.set_results(search_data(&["first video"]));
+        page.set_hotwords(vec![hotword("tokio")]);
+
+        press(&mut page, &[KeyCode::Char('H')]);
+        assert!(page.show_hot_list);
+        let action = press(&mut page, &[KeyCode::Enter]);
+        assert!(matches!(action, Some(AppAction::Search(keyword)) if keyword == "tokio"));
+        assert!(!page.loading);
+
+        page.set_error(
+            "搜索失败: 请求超时".to_string(),
+            SearchRequest::Search("tokio".to_string()),
+        );
+        assert_eq!(page.query, "rust");
+        assert_eq!(page.grid.cards.len(), 1);
+        assert_eq!(page.active_toast(), Some("搜索失败: 请求超时"));
+
+        press(&mut page, &[KeyCode::Char('H')]);
+        press(&mut page, &[KeyCode::Esc]);
+        assert!(!page.show_hot_list);
+    }
+
+    #[test]
+    fn test_hot_word_key_fetches_missing_list() {
+        let mut page = SearchPage::new();
+        page.set_results(search_data(&["first video"]));
+        page.set_hotword_error("超时".to_string());
+
+        let action = press(&mut page, &[KeyCode::Char('H')]);
+        assert!(matches!(action, Some(AppAction::LoadHotwords)));
+        assert!(page.hotword_loading);
+        assert!(contains(&render(&mut page), "⏳ 正在获取热搜..."));
+
+        // Still loading: the list stays open without fetching twice
+        press(&mut page, &[KeyCode::Esc]);
+        assert!(!page.show_hot_list);
+        let action = press(&mut page, &[KeyCode::Char('H')]);
+        assert!(matches!(action, Some(AppAction::None)));
+        assert!(page.show_hot_list);
+    }
+}
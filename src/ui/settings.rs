@@ -1,15 +1,20 @@
 //! Settings page with theme selection, keybinding display, and account management
 
-use super::{Component, Theme, ThemeVariant};
+use super::help::{self, KeyHint};
+use super::{Component, HomePage, Theme, ThemeVariant};
+use crate::api::doctor::{self, CheckResult};
 use crate::app::AppAction;
-use crate::storage::Keybindings;
+use crate::storage::{DownloadConfig, ImageProtocol, Keybindings, NetworkConfig, RecommendFilter};
 use ratatui::{crossterm::event::KeyCode, prelude::*, widgets::*};
 
 /// Settings sections
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingsSection {
     Theme,
+    Display,
     Keybindings,
+    Network,
+    Download,
     Account,
 }
 
@@ -17,7 +22,10 @@ impl SettingsSection {
     pub fn all() -> &'static [SettingsSection] {
         &[
             SettingsSection::Theme,
+            SettingsSection::Display,
             SettingsSection::Keybindings,
+            SettingsSection::Network,
+            SettingsSection::Download,
             SettingsSection::Account,
         ]
     }
@@ -25,24 +33,110 @@ impl SettingsSection {
     pub fn label(&self) -> &'static str {
         match self {
             SettingsSection::Theme => "🎨 主题",
+            SettingsSection::Display => "🖥 显示",
             SettingsSection::Keybindings => "⌨️ 快捷键",
+            SettingsSection::Network => "🌐 网络",
+            SettingsSection::Download => "📥 下载",
             SettingsSection::Account => "👤 账户",
         }
     }
 }
 
+/// What the server last said about the saved cookies
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoginCheck {
+    /// Not asked yet, or the server could not be reached
+    #[default]
+    Unknown,
+    /// A check started from this page is still running
+    Checking,
+    Valid,
+    Expired,
+}
+
+/// Login refresh state shown in the account section
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenStatus {
+    /// The TV-login tokens needed to refresh the cookies are stored
+    pub has_refresh_token: bool,
+    /// Unix time of the last successful refresh
+    pub last_refresh: Option<i64>,
+    /// A refresh started from this page is still running
+    pub refreshing: bool,
+    /// Whether the cookies still log in
+    pub login: LoginCheck,
+    /// Unix time the server last answered a login check
+    pub last_validated: Option<i64>,
+}
+
+/// Local date and minute of a unix time, `never` when there is none
+fn time_text(at: Option<i64>, never: &str) -> String {
+    at.and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+        .map(|at| {
+            let at = at.with_timezone(&chrono::Local);
+            at.format("%Y-%m-%d %H:%M").to_string()
+        })
+        .unwrap_or_else(|| never.to_string())
+}
+
 pub struct SettingsPage {
     pub current_section: SettingsSection,
     pub selected_theme_index: usize,
     pub selected_keybind_index: usize,
     pub keybindings: Keybindings,
     pub current_theme_variant: ThemeVariant,
+    pub network: NetworkConfig,
+    pub selected_network_index: usize,
+    /// Fixed recommendation columns, `None` for automatic
+    pub grid_columns: Option<usize>,
+    /// Minimum plays and maximum length of recommended videos
+    pub recommend_filter: RecommendFilter,
+    /// Applied on the next launch, the picker is built once at startup
+    pub image_protocol: ImageProtocol,
+    /// `h`/`l` wrap around the ends of video lists
+    pub wrap_navigation: bool,
+    selected_display_index: usize,
+    pub download: DownloadConfig,
+    selected_download_index: usize,
+    /// Text being typed for the selected download row, `None` when not editing
+    pub editing_download: Option<String>,
     section_index: usize,
     pub editing_keybind: bool,
+    /// Logout, self-test, refresh or validate button
+    selected_account_index: usize,
+    pub token_status: TokenStatus,
+    /// Last login self-test report, `None` until one is run
+    pub diagnostics: Option<Vec<CheckResult>>,
 }
 
 impl SettingsPage {
-    pub fn new(keybindings: Keybindings, theme_variant: ThemeVariant) -> Self {
+    /// Recommendation columns, minimum plays, maximum duration, image protocol and wrap
+    /// navigation
+    const DISPLAY_ROWS: usize = 5;
+    /// Number of adjustable rows in the network section
+    const NETWORK_ROWS: usize = 5;
+    /// Download directory and format
+    const DOWNLOAD_ROWS: usize = 2;
+    /// Logout, login self-test, refresh now and validate login
+    const ACCOUNT_ROWS: usize = 4;
+
+    /// Follow a theme change made outside the settings page
+    pub fn select_theme(&mut self, variant: ThemeVariant) {
+        self.current_theme_variant = variant;
+        if let Some(idx) = ThemeVariant::all().iter().position(|v| *v == variant) {
+            self.selected_theme_index = idx;
+        }
+    }
+
+    pub fn new(
+        keybindings: Keybindings,
+        theme_variant: ThemeVariant,
+        network: NetworkConfig,
+        download: DownloadConfig,
+        grid_columns: Option<usize>,
+        recommend_filter: RecommendFilter,
+        image_protocol: ImageProtocol,
+    ) -> Self {
         let theme_index = ThemeVariant::all()
             .iter()
             .position(|v| *v == theme_variant)
@@ -54,54 +148,78 @@ impl SettingsPage {
             selected_keybind_index: 0,
             keybindings,
             current_theme_variant: theme_variant,
+            network,
+            selected_network_index: 0,
+            grid_columns,
+            recommend_filter,
+            image_protocol,
+            wrap_navigation: false,
+            selected_display_index: 0,
+            download,
+            selected_download_index: 0,
+            editing_download: None,
             section_index: 0,
             editing_keybind: false,
+            selected_account_index: 0,
+            token_status: TokenStatus::default(),
+            diagnostics: None,
         }
     }
 
+    pub fn set_diagnostics(&mut self, results: Vec<CheckResult>) {
+        self.diagnostics = Some(results);
+    }
+
     fn keybind_labels(&self) -> Vec<(&'static str, &str)> {
-        vec![
-            // Global actions
-            ("退出", &self.keybindings.quit),
-            ("确认", &self.keybindings.confirm),
-            ("返回", &self.keybindings.back),
-            ("刷新", &self.keybindings.refresh),
-            // Navigation
-            ("向上", &self.keybindings.nav_up),
-            ("向下", &self.keybindings.nav_down),
-            ("向左", &self.keybindings.nav_left),
-            ("向右", &self.keybindings.nav_right),
-            ("下一页面", &self.keybindings.nav_next_page),
-            ("上一页面", &self.keybindings.nav_prev_page),
-            // Section/Tab
-            ("上一分区", &self.keybindings.section_prev),
-            ("下一分区", &self.keybindings.section_next),
-            ("标签1", &self.keybindings.tab_1),
-            ("标签2", &self.keybindings.tab_2),
-            ("标签3", &self.keybindings.tab_3),
-            // Actions
-            ("切换主题", &self.keybindings.next_theme),
-            ("播放", &self.keybindings.play),
-            ("设置", &self.keybindings.open_settings),
-            ("搜索", &self.keybindings.search_focus),
-            // Comments
-            ("评论", &self.keybindings.comment),
-            ("展开回复", &self.keybindings.toggle_replies),
-            // Dynamic page
-            ("上一UP", &self.keybindings.up_prev),
-            ("下一UP", &self.keybindings.up_next),
-        ]
+        self.keybindings.get_all_labels()
     }
 }
 
 impl Default for SettingsPage {
     fn default() -> Self {
-        Self::new(Keybindings::default(), ThemeVariant::CatppuccinMocha)
+        Self::new(
+            Keybindings::default(),
+            ThemeVariant::CatppuccinMocha,
+            NetworkConfig::default(),
+            DownloadConfig::default(),
+            None,
+            RecommendFilter::default(),
+            ImageProtocol::default(),
+        )
+    }
+}
+
+impl SettingsPage {
+    /// Footer entries for the keys handled in the current section and edit state
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        if self.editing_keybind {
+            return vec![KeyHint::fixed("任意键", "设为新按键")];
+        }
+        if self.editing_download.is_some() {
+            return vec![
+                KeyHint::fixed("Enter", "保存"),
+                KeyHint::fixed("Esc", "取消"),
+            ];
+        }
+        let mut hints = vec![
+            KeyHint::pair(&keys.section_prev, &keys.section_next, "切换分类"),
+            KeyHint::pair(&keys.nav_up, &keys.nav_down, "选择"),
+        ];
+        match self.current_section {
+            SettingsSection::Display | SettingsSection::Network => {
+                hints.push(KeyHint::pair(&keys.nav_left, &keys.nav_right, "调整"))
+            }
+            SettingsSection::Keybindings => hints.push(KeyHint::new(&keys.confirm, "修改按键")),
+            SettingsSection::Download => hints.push(KeyHint::new(&keys.confirm, "编辑")),
+            _ => hints.push(KeyHint::new(&keys.confirm, "确认")),
+        }
+        hints.push(help::page_switch(keys));
+        hints
     }
 }
 
 impl Component for SettingsPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         // Main layout: header + content
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -147,61 +265,27 @@ impl Component for SettingsPage {
         // Section content
         match self.current_section {
             SettingsSection::Theme => self.draw_theme_section(frame, content_chunks[1], theme),
+            SettingsSection::Display => self.draw_display_section(frame, content_chunks[1], theme),
             SettingsSection::Keybindings => {
                 self.draw_keybindings_section(frame, content_chunks[1], theme)
             }
+            SettingsSection::Network => self.draw_network_section(frame, content_chunks[1], theme),
+            SettingsSection::Download => {
+                self.draw_download_section(frame, content_chunks[1], theme)
+            }
             SettingsSection::Account => self.draw_account_section(frame, content_chunks[1], theme),
         }
 
         // Help bar
-        let help_line = Line::from(vec![
-            Span::styled(" [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "[]",
-                Style::default()
-                    .fg(theme.fg_accent)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("切换分类", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "↑↓",
-                Style::default()
-                    .fg(theme.fg_accent)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("选择", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "Enter",
-                Style::default()
-                    .fg(theme.success)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("确认", Style::default().fg(theme.fg_secondary)),
-            Span::styled("  [", Style::default().fg(theme.fg_secondary)),
-            Span::styled(
-                "Tab",
-                Style::default().fg(theme.info).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("] ", Style::default().fg(theme.fg_secondary)),
-            Span::styled("切页面", Style::default().fg(theme.fg_secondary)),
-        ]);
+        let help_line = help::hints_line(&self.footer_hints(keys), theme);
         let help = Paragraph::new(help_line).alignment(Alignment::Center);
         frame.render_widget(help, main_chunks[2]);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         // Handle keybind editing mode - any key pressed becomes the new binding
         if self.editing_keybind {
-            let new_key = crate::storage::Keybindings::keycode_to_string(key);
+            let new_key = Keybindings::keycode_to_string(key);
             self.keybindings
                 .update_by_index(self.selected_keybind_index, new_key);
             self.editing_keybind = false;
@@ -211,6 +295,27 @@ impl Component for SettingsPage {
             )));
         }
 
+        if let Some(input) = &mut self.editing_download {
+            match key {
+                KeyCode::Enter => {
+                    let value = input.trim().to_string();
+                    match self.selected_download_index {
+                        0 => self.download.dir = value,
+                        _ => self.download.format = value,
+                    }
+                    self.editing_download = None;
+                    return Some(AppAction::SaveDownloadConfig(self.download.clone()));
+                }
+                KeyCode::Esc => self.editing_download = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Some(AppAction::None);
+        }
+
         if keys.matches_back(key) {
             return Some(AppAction::BackToList);
         }
@@ -250,7 +355,18 @@ impl Component for SettingsPage {
                         self.selected_keybind_index -= 1;
                     }
                 }
-                SettingsSection::Account => {}
+                SettingsSection::Display => {
+                    self.selected_display_index = self.selected_display_index.saturating_sub(1);
+                }
+                SettingsSection::Network => {
+                    self.selected_network_index = self.selected_network_index.saturating_sub(1);
+                }
+                SettingsSection::Download => {
+                    self.selected_download_index = self.selected_download_index.saturating_sub(1);
+                }
+                SettingsSection::Account => {
+                    self.selected_account_index = self.selected_account_index.saturating_sub(1);
+                }
             }
             return Some(AppAction::None);
         }
@@ -268,10 +384,54 @@ impl Component for SettingsPage {
                         self.selected_keybind_index += 1;
                     }
                 }
-                SettingsSection::Account => {}
+                SettingsSection::Display => {
+                    if self.selected_display_index + 1 < Self::DISPLAY_ROWS {
+                        self.selected_display_index += 1;
+                    }
+                }
+                SettingsSection::Network => {
+                    if self.selected_network_index + 1 < Self::NETWORK_ROWS {
+                        self.selected_network_index += 1;
+                    }
+                }
+                SettingsSection::Download => {
+                    if self.selected_download_index + 1 < Self::DOWNLOAD_ROWS {
+                        self.selected_download_index += 1;
+                    }
+                }
+                SettingsSection::Account => {
+                    if self.selected_account_index + 1 < Self::ACCOUNT_ROWS {
+                        self.selected_account_index += 1;
+                    }
+                }
             }
             return Some(AppAction::None);
         }
+        let delta = if keys.matches_left(key) {
+            -1
+        } else if keys.matches_right(key) {
+            1
+        } else {
+            0
+        };
+        if delta != 0 {
+            match self.current_section {
+                SettingsSection::Display => {
+                    self.adjust_display(delta);
+                    return Some(match self.selected_display_index {
+                        0 => AppAction::SaveGridColumns(self.grid_columns),
+                        3 => AppAction::SaveImageProtocol(self.image_protocol),
+                        4 => AppAction::SaveWrapNavigation(self.wrap_navigation),
+                        _ => AppAction::SaveRecommendFilter(self.recommend_filter),
+                    });
+                }
+                SettingsSection::Network => {
+                    self.adjust_network(delta);
+                    return Some(AppAction::SaveNetworkConfig(self.network));
+                }
+                _ => {}
+            }
+        }
         if keys.matches_confirm(key) {
             match self.current_section {
                 SettingsSection::Theme => {
@@ -283,13 +443,34 @@ impl Component for SettingsPage {
                     }
                 }
                 SettingsSection::Account => {
-                    // Logout
-                    return Some(AppAction::Logout);
+                    return Some(match self.selected_account_index {
+                        0 => AppAction::Logout,
+                        1 => AppAction::RunDiagnostics,
+                        3 if self.token_status.login != LoginCheck::Checking => {
+                            self.token_status.login = LoginCheck::Checking;
+                            AppAction::ValidateLogin
+                        }
+                        2 if self.token_status.has_refresh_token
+                            && !self.token_status.refreshing =>
+                        {
+                            self.token_status.refreshing = true;
+                            AppAction::RefreshCredentials
+                        }
+                        _ => AppAction::None,
+                    });
                 }
                 SettingsSection::Keybindings => {
                     // Enter keybind editing mode
                     self.editing_keybind = true;
                 }
+                SettingsSection::Download => {
+                    // Start from the stored value so small edits stay small
+                    self.editing_download = Some(match self.selected_download_index {
+                        0 => self.download.dir.clone(),
+                        _ => self.download.format.clone(),
+                    });
+                }
+                SettingsSection::Display | SettingsSection::Network => {}
             }
             return Some(AppAction::None);
         }
@@ -301,6 +482,59 @@ impl Component for SettingsPage {
 }
 
 impl SettingsPage {
+    /// Step the selected network value, keeping it in its allowed range
+    fn adjust_network(&mut self, delta: i64) {
+        let step = |value: u64| value.saturating_add_signed(delta);
+        match self.selected_network_index {
+            0 => self.network.qr_poll_interval = step(self.network.qr_poll_interval),
+            1 => {
+                self.network.cover_download_concurrency =
+                    step(self.network.cover_download_concurrency as u64) as usize
+            }
+            2 => self.network.request_timeout = step(self.network.request_timeout),
+            3 => {
+                self.network.cover_prefetch_rows =
+                    step(self.network.cover_prefetch_rows as u64) as usize
+            }
+            // A dozen covers is about a screen, single steps would take forever
+            _ => {
+                self.network.cover_cache_size = (self.network.cover_cache_size as u64)
+                    .saturating_add_signed(delta * 12)
+                    as usize
+            }
+        }
+        self.network = self.network.clamped();
+    }
+
+    /// Step the selected display value: columns through 自动, 1, ..., the maximum, the
+    /// recommendation limits through their preset steps
+    fn adjust_display(&mut self, delta: i64) {
+        let filter = &mut self.recommend_filter;
+        match self.selected_display_index {
+            0 => {
+                let current = self.grid_columns.unwrap_or(0) as i64;
+                let next = (current + delta).clamp(0, HomePage::MAX_COLUMNS as i64) as usize;
+                self.grid_columns = (next > 0).then_some(next);
+            }
+            1 => {
+                filter.min_views = RecommendFilter::step(
+                    &RecommendFilter::MIN_VIEWS_STEPS,
+                    filter.min_views,
+                    delta,
+                )
+            }
+            2 => {
+                filter.max_duration_minutes = RecommendFilter::step(
+                    &RecommendFilter::MAX_DURATION_STEPS,
+                    filter.max_duration_minutes,
+                    delta,
+                )
+            }
+            3 => self.image_protocol = self.image_protocol.step(delta),
+            _ => self.wrap_navigation = !self.wrap_navigation,
+        }
+    }
+
     fn draw_section_list(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let block = Block::default()
             .borders(Borders::RIGHT)
@@ -432,6 +666,195 @@ impl SettingsPage {
         frame.render_widget(list, inner);
     }
 
+    fn draw_network_section(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_subtle))
+            .title(Span::styled(
+                " 🌐 网络 (←→ 调整) ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = [
+            (
+                "二维码轮询间隔",
+                format!("{}s", self.network.qr_poll_interval),
+            ),
+            (
+                "封面并发下载数",
+                self.network.cover_download_concurrency.to_string(),
+            ),
+            ("请求超时", format!("{}s", self.network.request_timeout)),
+            (
+                "封面预加载行数",
+                self.network.cover_prefetch_rows.to_string(),
+            ),
+            ("封面内存缓存数", self.network.cover_cache_size.to_string()),
+        ];
+
+        let items: Vec<ListItem> = rows
+            .iter()
+            .enumerate()
+            .map(|(idx, (label, value))| {
+                let is_selected = idx == self.selected_network_index;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.fg_primary)
+                        .add_modifier(Modifier::BOLD)
+                        .bg(theme.selection_bg)
+                } else {
+                    Style::default().fg(theme.fg_secondary)
+                };
+
+                let prefix = if is_selected { "▶ " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(format!("{:<16}", label), style),
+                    Span::styled(
+                        format!("◀ {} ▶", value),
+                        Style::default()
+                            .fg(theme.fg_accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items);
+        frame.render_widget(list, inner);
+    }
+
+    fn draw_display_section(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_subtle))
+            .title(Span::styled(
+                " 🖥 显示 (←→ 调整) ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let columns = match self.grid_columns {
+            Some(n) => n.to_string(),
+            None => "自动".to_string(),
+        };
+        let rows = [
+            ("推荐列数", columns),
+            ("最低播放量", self.recommend_filter.min_views_label()),
+            ("最长时长", self.recommend_filter.max_duration_label()),
+            (
+                "图片协议 (重启生效)",
+                self.image_protocol.label().to_string(),
+            ),
+            (
+                "循环导航",
+                if self.wrap_navigation { "开" } else { "关" }.to_string(),
+            ),
+        ];
+
+        let items: Vec<ListItem> = rows
+            .iter()
+            .enumerate()
+            .map(|(idx, (label, value))| {
+                let is_selected = idx == self.selected_display_index;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.fg_primary)
+                        .add_modifier(Modifier::BOLD)
+                        .bg(theme.selection_bg)
+                } else {
+                    Style::default().fg(theme.fg_secondary)
+                };
+
+                let prefix = if is_selected { "▶ " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(format!("{:<16}", label), style),
+                    Span::styled(
+                        format!("◀ {} ▶", value),
+                        Style::default()
+                            .fg(theme.fg_accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]))
+            })
+            .collect();
+
+        frame.render_widget(List::new(items), inner);
+    }
+
+    fn draw_download_section(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_subtle))
+            .title(Span::styled(
+                " 📥 下载 (Enter 编辑) ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = [
+            ("下载目录", self.download.dir().display().to_string()),
+            ("视频格式", self.download.format().to_string()),
+        ];
+
+        let mut items: Vec<ListItem> = rows
+            .iter()
+            .enumerate()
+            .map(|(idx, (label, value))| {
+                let is_selected = idx == self.selected_download_index;
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.fg_primary)
+                        .add_modifier(Modifier::BOLD)
+                        .bg(theme.selection_bg)
+                } else {
+                    Style::default().fg(theme.fg_secondary)
+                };
+
+                let value = match &self.editing_download {
+                    Some(input) if is_selected => format!("{}_", input),
+                    _ => value.clone(),
+                };
+                let prefix = if is_selected { "▶ " } else { "  " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(format!("{:<12}", label), style),
+                    Span::styled(
+                        value,
+                        Style::default()
+                            .fg(theme.fg_accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ]))
+            })
+            .collect();
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            "留空使用默认值，目录支持 ~/ 开头；需要安装 yt-dlp",
+            Style::default().fg(theme.fg_muted),
+        )));
+
+        let list = List::new(items);
+        frame.render_widget(list, inner);
+    }
+
     fn draw_account_section(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let block = Block::default()
             .borders(Borders::ALL)
@@ -447,35 +870,345 @@ impl SettingsPage {
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        // Layout for account info + logout button
+        // Layout for account info, the four buttons and the self-test report
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([
                 Constraint::Length(3), // Info
                 Constraint::Length(3), // Logout button
-                Constraint::Min(0),    // Spacer
+                Constraint::Length(3), // Self-test button
+                Constraint::Length(3), // Refresh button
+                Constraint::Length(3), // Validate button
+                Constraint::Min(0),    // Self-test report
             ])
             .split(inner);
 
-        let info = Paragraph::new("已登录")
-            .style(Style::default().fg(theme.success))
-            .alignment(Alignment::Left);
+        let status = &self.token_status;
+        let label_style = Style::default().fg(theme.fg_muted);
+        let (login, login_color) = match status.login {
+            LoginCheck::Unknown => ("未知", theme.fg_secondary),
+            LoginCheck::Checking => ("验证中...", theme.warning),
+            LoginCheck::Valid => ("有效", theme.success),
+            LoginCheck::Expired => ("已失效", theme.error),
+        };
+        let info = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled("登录状态: ", label_style),
+                Span::styled(login, Style::default().fg(login_color)),
+                Span::styled(
+                    format!(
+                        " · 上次验证 {}",
+                        time_text(status.last_validated, "从未验证")
+                    ),
+                    label_style,
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("刷新令牌: ", label_style),
+                if status.has_refresh_token {
+                    Span::styled("已保存", Style::default().fg(theme.success))
+                } else {
+                    Span::styled(
+                        "未保存（扫码登录后才有）",
+                        Style::default().fg(theme.warning),
+                    )
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("上次刷新: ", label_style),
+                Span::styled(
+                    time_text(status.last_refresh, "从未刷新"),
+                    Style::default().fg(theme.fg_secondary),
+                ),
+            ]),
+        ])
+        .alignment(Alignment::Left);
         frame.render_widget(info, chunks[0]);
 
-        let logout_btn = Paragraph::new("▶ 退出登录")
-            .style(
-                Style::default()
-                    .fg(theme.error)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(theme.error)),
-            )
-            .alignment(Alignment::Center);
-        frame.render_widget(logout_btn, chunks[1]);
+        let refresh = if !status.has_refresh_token {
+            ("立即刷新（无刷新令牌）", theme.fg_muted)
+        } else if status.refreshing {
+            ("刷新中...", theme.warning)
+        } else {
+            ("立即刷新", theme.bilibili_cyan)
+        };
+        let buttons = [
+            ("退出登录", theme.error),
+            ("登录自检", theme.fg_accent),
+            refresh,
+            if status.login == LoginCheck::Checking {
+                ("验证中...", theme.warning)
+            } else {
+                ("验证登录", theme.bilibili_cyan)
+            },
+        ];
+        for (idx, (label, color)) in buttons.into_iter().enumerate() {
+            let is_selected = idx == self.selected_account_index;
+            let prefix = if is_selected { "▶ " } else { "  " };
+            let border = if is_selected {
+                color
+            } else {
+                theme.border_subtle
+            };
+            let button = Paragraph::new(format!("{}{}", prefix, label))
+                .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(border)),
+                )
+                .alignment(Alignment::Center);
+            frame.render_widget(button, chunks[idx + 1]);
+        }
+
+        let Some(results) = &self.diagnostics else {
+            return;
+        };
+        let mut lines: Vec<Line> = results
+            .iter()
+            .map(|check| {
+                let (mark, color) = if check.ok {
+                    ("✔", theme.success)
+                } else {
+                    ("✘", theme.error)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} {}  ", mark, check.name),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(
+                        check.detail.clone(),
+                        Style::default().fg(theme.fg_secondary),
+                    ),
+                ])
+            })
+            .collect();
+        if let Some(hint) = doctor::diagnosis(results) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                hint,
+                Style::default().fg(theme.warning),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[5]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, render};
+
+    #[test]
+    fn test_account_self_test_report() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Account,
+            ..Default::default()
+        };
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::Logout)
+        ));
+        page.handle_input(KeyCode::Char('j'), &keys);
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::RunDiagnostics)
+        ));
+
+        page.set_diagnostics(vec![
+            CheckResult {
+                name: "登录状态 (nav)",
+                ok: false,
+                detail: "未登录或 Cookie 已失效".to_string(),
+            },
+            CheckResult {
+                name: "推荐接口",
+                ok: true,
+                detail: "返回 10 条视频".to_string(),
+            },
+        ]);
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "✘ 登录状态"));
+        assert!(contains(&buffer, "✔ 推荐接口"));
+        assert!(contains(&buffer, "重新扫码登录"));
+    }
+
+    #[test]
+    fn test_refresh_button_needs_a_refresh_token() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Account,
+            ..Default::default()
+        };
+        page.handle_input(KeyCode::Char('j'), &keys);
+        page.handle_input(KeyCode::Char('j'), &keys);
+        assert!(contains(&render(&mut page), "未保存"));
+        assert!(contains(&render(&mut page), "立即刷新（无刷新令牌）"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+
+        page.token_status = TokenStatus {
+            has_refresh_token: true,
+            ..Default::default()
+        };
+        assert!(contains(&render(&mut page), "从未刷新"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::RefreshCredentials)
+        ));
+        // A second press while it runs does nothing
+        assert!(contains(&render(&mut page), "刷新中..."));
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+    }
+
+    #[test]
+    fn test_validate_button_shows_login_state() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Account,
+            ..Default::default()
+        };
+        assert!(contains(
+            &render(&mut page),
+            "登录状态: 未知 · 上次验证 从未验证"
+        ));
+        for _ in 0..3 {
+            page.handle_input(KeyCode::Char('j'), &keys);
+        }
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::ValidateLogin)
+        ));
+        assert!(contains(&render(&mut page), "验证中..."));
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::None)
+        ));
+
+        page.token_status.login = LoginCheck::Expired;
+        page.token_status.last_validated = Some(0);
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "已失效"));
+        assert!(!contains(&buffer, "从未验证"));
+    }
+
+    #[test]
+    fn test_edit_download_dir() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Download,
+            ..Default::default()
+        };
+
+        page.handle_input(KeyCode::Enter, &keys);
+        assert_eq!(page.editing_download.as_deref(), Some(""));
+        for c in "~/Videos".chars() {
+            page.handle_input(KeyCode::Char(c), &keys);
+        }
+        let action = page.handle_input(KeyCode::Enter, &keys);
+        assert!(matches!(
+            action,
+            Some(AppAction::SaveDownloadConfig(ref config)) if config.dir == "~/Videos"
+        ));
+        assert!(page.editing_download.is_none());
+
+        // Esc drops the edit
+        page.handle_input(KeyCode::Enter, &keys);
+        page.handle_input(KeyCode::Backspace, &keys);
+        page.handle_input(KeyCode::Esc, &keys);
+        assert_eq!(page.download.dir, "~/Videos");
+    }
+
+    #[test]
+    fn test_adjust_grid_columns() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Display,
+            ..Default::default()
+        };
+
+        let action = page.handle_input(KeyCode::Right, &keys);
+        assert!(matches!(action, Some(AppAction::SaveGridColumns(Some(1)))));
+        for _ in 0..10 {
+            page.handle_input(KeyCode::Char('l'), &keys);
+        }
+        assert_eq!(page.grid_columns, Some(HomePage::MAX_COLUMNS));
+
+        for _ in 0..10 {
+            page.handle_input(KeyCode::Left, &keys);
+        }
+        assert_eq!(page.grid_columns, None);
+    }
+
+    #[test]
+    fn test_toggle_wrap_navigation() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Display,
+            ..Default::default()
+        };
+        for _ in 0..SettingsPage::DISPLAY_ROWS {
+            page.handle_input(KeyCode::Char('j'), &keys);
+        }
+        let action = page.handle_input(KeyCode::Right, &keys);
+        assert!(matches!(action, Some(AppAction::SaveWrapNavigation(true))));
+        assert!(contains(&render(&mut page), "循环导航"));
+        let action = page.handle_input(KeyCode::Left, &keys);
+        assert!(matches!(action, Some(AppAction::SaveWrapNavigation(false))));
+    }
+
+    #[test]
+    fn test_adjust_recommend_filter() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Display,
+            ..Default::default()
+        };
+
+        page.handle_input(KeyCode::Down, &keys);
+        let action = page.handle_input(KeyCode::Right, &keys);
+        assert!(matches!(
+            action,
+            Some(AppAction::SaveRecommendFilter(RecommendFilter {
+                min_views: 1_000,
+                ..
+            }))
+        ));
+
+        page.handle_input(KeyCode::Down, &keys);
+        page.handle_input(KeyCode::Right, &keys);
+        page.handle_input(KeyCode::Right, &keys);
+        assert_eq!(page.recommend_filter.max_duration_minutes, 10);
+        assert!(contains(&render(&mut page), "10分钟"));
+    }
+
+    #[test]
+    fn test_step_image_protocol() {
+        let keys = Keybindings::default();
+        let mut page = SettingsPage {
+            current_section: SettingsSection::Display,
+            ..Default::default()
+        };
+
+        for _ in 0..3 {
+            page.handle_input(KeyCode::Down, &keys);
+        }
+        page.handle_input(KeyCode::Left, &keys);
+        assert_eq!(page.image_protocol, ImageProtocol::Auto);
+        let action = page.handle_input(KeyCode::Right, &keys);
+        assert!(matches!(
+            action,
+            Some(AppAction::SaveImageProtocol(ImageProtocol::Halfblocks))
+        ));
+        assert!(contains(&render(&mut page), "半块字符"));
     }
 }
@@ -2,6 +2,8 @@
 
 use super::Theme;
 use ratatui::{prelude::*, widgets::*};
+use std::fmt;
+use std::str::FromStr;
 
 /// Navigation menu items
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,7 +11,10 @@ pub enum NavItem {
     Home,
     Search,
     Dynamic,
+    Bangumi,
+    Favorites,
     History,
+    Profile,
     Settings,
 }
 
@@ -19,30 +24,85 @@ impl NavItem {
             NavItem::Home => "🏠 首页",
             NavItem::Search => "🔍 搜索",
             NavItem::Dynamic => "📺 动态",
+            NavItem::Bangumi => "🎬 追番",
+            NavItem::Favorites => "⭐ 收藏",
             NavItem::History => "📜 历史",
+            NavItem::Profile => "👤 我的",
             NavItem::Settings => "⚙️ 设置",
         }
     }
 
+    /// Sections backed by the logged-in user's own data, meaningless without an account
+    pub fn requires_auth(&self) -> bool {
+        matches!(
+            self,
+            NavItem::Dynamic
+                | NavItem::Bangumi
+                | NavItem::Favorites
+                | NavItem::History
+                | NavItem::Profile
+        )
+    }
+
     pub fn all() -> &'static [NavItem] {
         &[
             NavItem::Home,
             NavItem::Search,
             NavItem::Dynamic,
+            NavItem::Bangumi,
+            NavItem::Favorites,
             NavItem::History,
+            NavItem::Profile,
             NavItem::Settings,
         ]
     }
 }
 
+impl fmt::Display for NavItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            NavItem::Home => "Home",
+            NavItem::Search => "Search",
+            NavItem::Dynamic => "Dynamic",
+            NavItem::Bangumi => "Bangumi",
+            NavItem::Favorites => "Favorites",
+            NavItem::History => "History",
+            NavItem::Profile => "Profile",
+            NavItem::Settings => "Settings",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for NavItem {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Home" => Ok(NavItem::Home),
+            "Search" => Ok(NavItem::Search),
+            "Dynamic" => Ok(NavItem::Dynamic),
+            "Bangumi" => Ok(NavItem::Bangumi),
+            "Favorites" => Ok(NavItem::Favorites),
+            "History" => Ok(NavItem::History),
+            "Profile" => Ok(NavItem::Profile),
+            "Settings" => Ok(NavItem::Settings),
+            _ => Err(()),
+        }
+    }
+}
+
 pub struct Sidebar {
     pub selected: NavItem,
+    /// Unread message count shown as a badge next to the profile item
+    pub unread: u64,
 }
 
 impl Sidebar {
     pub fn new() -> Self {
         Self {
             selected: NavItem::Home,
+            unread: 0,
         }
     }
 
@@ -122,7 +182,21 @@ impl Sidebar {
                 // Use block indicator for selection instead of arrow
                 let prefix = if is_selected { " ▌" } else { "  " };
                 let suffix = if is_selected { " " } else { "" };
-                ListItem::new(format!("{}{}{}", prefix, item.label(), suffix)).style(style)
+                let mut spans = vec![Span::raw(format!("{}{}{}", prefix, item.label(), suffix))];
+                if *item == NavItem::Profile && self.unread > 0 {
+                    let count = if self.unread > 99 {
+                        "99+".to_string()
+                    } else {
+                        self.unread.to_string()
+                    };
+                    spans.push(Span::styled(
+                        format!(" ●{}", count),
+                        Style::default()
+                            .fg(theme.bilibili_pink)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+                ListItem::new(Line::from(spans)).style(style)
             })
             .collect();
 
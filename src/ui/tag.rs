@@ -0,0 +1,385 @@
+//! Tag page listing the newest videos filed under a video tag in a card grid
+
+use super::help::{self, KeyHint};
+use super::video_card::{VideoCard, VideoCardGrid};
+use super::{Component, Theme};
+use crate::api::video::RelatedVideoItem;
+use crate::api::BiliApi;
+use crate::app::AppAction;
+use crate::storage::{CoverFit, Keybindings, WatchedVideos};
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+use std::time::Instant;
+
+/// Tag feed requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagRequest {
+    /// First page of the tag's videos
+    Videos,
+    /// Next page
+    More,
+}
+
+pub struct TagPage {
+    pub tag_id: i64,
+    /// Tag name from the detail page, replaced by the API's spelling once loaded
+    pub name: String,
+    pub grid: VideoCardGrid,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub error_message: Option<String>,
+    /// Request behind the error being shown, re-issued by the retry key
+    failed: Option<TagRequest>,
+    pub page: i32,
+    pub total: i32,
+    last_click_time: Option<Instant>,
+    last_click_index: Option<usize>,
+}
+
+impl TagPage {
+    pub fn new(tag_id: i64, name: String) -> Self {
+        Self {
+            tag_id,
+            name,
+            grid: VideoCardGrid::new(),
+            loading: true,
+            loading_more: false,
+            error_message: None,
+            failed: None,
+            page: 1,
+            total: 0,
+            last_click_time: None,
+            last_click_index: None,
+        }
+    }
+
+    /// Load the newest videos under the tag
+    pub async fn load(&mut self, api_client: &impl BiliApi) {
+        self.loading = true;
+        self.error_message = None;
+        self.failed = None;
+        self.grid.clear();
+        match api_client.get_tag_videos(self.tag_id, 1).await {
+            Ok(data) => {
+                if let Some(name) = data.tag_name().filter(|name| !name.is_empty()) {
+                    self.name = name.to_string();
+                }
+                self.page = 1;
+                self.total = data.total();
+                self.add_videos(data.videos());
+            }
+            Err(e) => {
+                self.failed = Some(TagRequest::Videos);
+                self.error_message = Some(format!("加载标签视频失败: {}", e));
+            }
+        }
+        self.loading = false;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if self.loading || self.loading_more || !self.has_more() {
+            return;
+        }
+        self.loading_more = true;
+        match api_client.get_tag_videos(self.tag_id, self.page + 1).await {
+            Ok(data) => {
+                self.failed = None;
+                let videos = data.videos();
+                if videos.is_empty() {
+                    // Trust the empty page over a stale count
+                    self.total = self.grid.cards.len() as i32;
+                } else {
+                    self.page += 1;
+                    self.add_videos(videos);
+                }
+            }
+            Err(_) => self.failed = Some(TagRequest::More),
+        }
+        self.loading_more = false;
+    }
+
+    fn add_videos(&mut self, videos: Vec<RelatedVideoItem>) {
+        for video in &videos {
+            self.grid.add_card(VideoCard::from_related(video));
+        }
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<TagRequest> {
+        self.failed.take()
+    }
+
+    pub fn has_more(&self) -> bool {
+        (self.grid.cards.len() as i32) < self.total
+    }
+
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        self.grid.mark_watched(watched);
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+    }
+
+    fn open_selected(&self, idx: usize) -> Option<AppAction> {
+        let card = self.grid.cards.get(idx)?;
+        Some(AppAction::OpenVideoDetail(
+            card.bvid.clone()?,
+            card.aid.unwrap_or(0),
+        ))
+    }
+
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let mut hints = vec![
+            help::navigation(keys),
+            KeyHint::new(&keys.confirm, "详情"),
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            KeyHint::new(&keys.back, "返回"),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+
+    fn grid_area(area: Rect) -> Rect {
+        Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        }
+    }
+}
+
+impl Component for TagPage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(10),   // Videos grid
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        let title = format!(" 🏷 {} ", self.name);
+        let plain_block = || {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border_unfocused))
+                .title(Span::styled(
+                    title.clone(),
+                    Style::default().fg(theme.bilibili_pink),
+                ))
+        };
+
+        if self.loading {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(loading, chunks[0]);
+        } else if let Some(error) = &self.error_message {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(error_widget, chunks[0]);
+        } else if self.grid.cards.is_empty() {
+            let empty = Paragraph::new("这个标签下还没有视频")
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(empty, chunks[0]);
+        } else {
+            let header = Paragraph::new(Line::from(vec![
+                Span::styled(title, Style::default().fg(theme.bilibili_pink)),
+                Span::styled(
+                    format!("({}/{})", self.grid.cards.len(), self.total),
+                    Style::default().fg(theme.fg_muted),
+                ),
+                if self.loading_more {
+                    Span::styled(" 加载中...", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("")
+                },
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.border_subtle)),
+            );
+            let header_area = Rect {
+                height: 2,
+                ..chunks[0]
+            };
+            frame.render_widget(header, header_area);
+            self.grid.render(frame, Self::grid_area(chunks[0]), theme);
+        }
+
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) || keys.matches_back(key) {
+            return Some(AppAction::BackToList);
+        }
+        if keys.matches_down(key) {
+            self.grid.move_down();
+            if self.grid.is_near_bottom(3) && !self.loading_more && self.has_more() {
+                return Some(AppAction::LoadMoreTagVideos);
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_up(key) {
+            self.grid.move_up();
+            return Some(AppAction::None);
+        }
+        if keys.matches_right(key) {
+            self.grid.move_right();
+            return Some(AppAction::None);
+        }
+        if keys.matches_left(key) {
+            self.grid.move_left();
+            return Some(AppAction::None);
+        }
+        if keys.matches_confirm(key) {
+            return Some(
+                self.open_selected(self.grid.selected_index)
+                    .unwrap_or(AppAction::None),
+            );
+        }
+        if keys.matches_download(key) {
+            return Some(
+                self.grid
+                    .selected_bvid()
+                    .map_or(AppAction::None, AppAction::DownloadVideo),
+            );
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::OpenTag {
+                tag_id: self.tag_id,
+                name: self.name.clone(),
+            });
+        }
+        Some(AppAction::None)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> Option<AppAction> {
+        match event.kind {
+            MouseEventKind::ScrollDown => {
+                if self.grid.move_down()
+                    && self.grid.is_near_bottom(3)
+                    && !self.loading_more
+                    && self.has_more()
+                {
+                    return Some(AppAction::LoadMoreTagVideos);
+                }
+                None
+            }
+            MouseEventKind::ScrollUp => {
+                self.grid.move_up();
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let grid_area = Self::grid_area(area);
+                if !grid_area.contains(Position::new(event.column, event.row)) {
+                    return None;
+                }
+
+                let relative_y = event.row - grid_area.y;
+                let click_row = (relative_y / self.grid.card_height) as usize;
+                let actual_row = self.grid.scroll_row + click_row;
+                let card_width = (grid_area.width / self.grid.columns as u16).max(1);
+                let click_col = (event.column.saturating_sub(grid_area.x) / card_width) as usize;
+                let click_idx = actual_row * self.grid.columns + click_col;
+                if click_idx >= self.grid.cards.len() {
+                    return None;
+                }
+
+                let now = Instant::now();
+                let is_double_click = self.last_click_index == Some(click_idx)
+                    && self
+                        .last_click_time
+                        .is_some_and(|t| now.duration_since(t).as_millis() < 500);
+                if is_double_click {
+                    self.last_click_time = None;
+                    self.last_click_index = None;
+                    return self.open_selected(click_idx);
+                }
+                self.grid.selected_index = click_idx;
+                self.grid.update_scroll(self.grid.cached_visible_rows);
+                self.last_click_time = Some(now);
+                self.last_click_index = Some(click_idx);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_load_shows_tag_videos() {
+        let mut page = TagPage::new(1628, "rust".to_string());
+        page.load(&MockApi::new()).await;
+
+        assert_eq!(page.name, "Rust");
+        assert_eq!(page.grid.cards.len(), 2);
+        assert!(!page.has_more());
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "🏷 Rust"));
+        assert!(contains(&buffer, "(2/2)"));
+
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &Keybindings::default()),
+            Some(AppAction::OpenVideoDetail(bvid, 10301)) if bvid == "BV1mock0301"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_error_offers_retry() {
+        let mut page = TagPage::new(1628, "Rust".to_string());
+        page.load(&MockApi::failing()).await;
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 加载标签视频失败"));
+        assert!(contains(&buffer, "按 e 重试"));
+        assert_eq!(page.take_failed(), Some(TagRequest::Videos));
+    }
+}
@@ -0,0 +1,131 @@
+//! Detect whether the terminal has a light background, for the "Auto" theme
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// How long to wait for the terminal to answer the background color query
+#[cfg(unix)]
+const QUERY_TIMEOUT_MS: i32 = 100;
+
+/// Whether the terminal background is light, detected once and cached
+///
+/// Falls back to dark when the terminal does not tell us.
+pub fn is_light() -> bool {
+    static LIGHT: OnceLock<bool> = OnceLock::new();
+    *LIGHT.get_or_init(|| detect().unwrap_or(false))
+}
+
+fn detect() -> Option<bool> {
+    if let Some(light) = std::env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| parse_colorfgbg(&value))
+    {
+        return Some(light);
+    }
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    query_osc11()
+}
+
+/// `COLORFGBG` is "fg;bg" (rxvt also puts "default" in the middle), using ANSI color indices
+fn parse_colorfgbg(value: &str) -> Option<bool> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    Some(matches!(bg, 7 | 9..=15))
+}
+
+/// Parse an OSC 11 reply such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`
+fn parse_osc11(reply: &[u8]) -> Option<bool> {
+    let reply = std::str::from_utf8(reply).ok()?;
+    let start = reply.find("rgb:")? + "rgb:".len();
+    let rest = &reply[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_hexdigit() && c != '/')
+        .unwrap_or(rest.len());
+
+    let mut channels = rest[..end].split('/').map(|hex| {
+        if hex.is_empty() || hex.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = (1u32 << (4 * hex.len())) - 1;
+        Some(value as f64 / max as f64)
+    });
+    let (r, g, b) = (channels.next()??, channels.next()??, channels.next()??);
+
+    // Relative luminance (Rec. 709)
+    Some(0.2126 * r + 0.7152 * g + 0.0722 * b > 0.5)
+}
+
+/// Ask the terminal for its background color
+///
+/// A DA1 query follows OSC 11: every terminal answers DA1, so a terminal that ignores
+/// OSC 11 ends the wait early instead of running into the timeout. Expects raw mode.
+#[cfg(unix)]
+fn query_osc11() -> Option<bool> {
+    use std::io::{Read, Write};
+    use std::os::fd::AsRawFd;
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    tty.write_all(b"\x1b]11;?\x07\x1b[c").ok()?;
+    tty.flush().ok()?;
+
+    let mut reply = Vec::new();
+    let mut buf = [0u8; 64];
+    loop {
+        let mut fd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `fd` is a valid pollfd for an open descriptor and nfds is 1
+        let ready = unsafe { libc::poll(&mut fd, 1, QUERY_TIMEOUT_MS) };
+        if ready <= 0 {
+            break;
+        }
+        let n = tty.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..n]);
+        // The DA1 reply (`ESC [ ? ... c`) is always last
+        if reply.ends_with(b"c") && reply.windows(3).any(|w| w == b"\x1b[?") {
+            break;
+        }
+    }
+    parse_osc11(&reply)
+}
+
+#[cfg(not(unix))]
+fn query_osc11() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(false));
+        assert_eq!(parse_colorfgbg("0;15"), Some(true));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(true));
+        assert_eq!(parse_colorfgbg("7;8"), Some(false));
+        assert_eq!(parse_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_parse_osc11() {
+        assert_eq!(
+            parse_osc11(b"\x1b]11;rgb:ffff/ffff/ffff\x07\x1b[?62;c"),
+            Some(true)
+        );
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:1e/1e/2e\x1b\\"), Some(false));
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:eff1/f5f5/f9f9\x07"), Some(true));
+        assert_eq!(parse_osc11(b"\x1b[?62;c"), None);
+    }
+}
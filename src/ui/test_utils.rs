@@ -0,0 +1,68 @@
+//! Helpers for rendering pages on a fixed-size `TestBackend`
+
+use super::{Component, Theme};
+use crate::storage::Keybindings;
+use ratatui::{backend::TestBackend, buffer::Buffer, prelude::*, Terminal};
+
+/// Default backend size, wide enough for the help lines of every page
+pub const WIDTH: u16 = 160;
+pub const HEIGHT: u16 = 40;
+
+/// Render a component once on a backend of the given size
+pub fn render_sized(
+    component: &mut impl Component,
+    theme: &Theme,
+    width: u16,
+    height: u16,
+) -> Buffer {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+    terminal
+        .draw(|frame| component.draw(frame, frame.area(), theme, &Keybindings::default()))
+        .expect("test backend never fails");
+    terminal.backend().buffer().clone()
+}
+
+/// Render a component once with the given theme and return the resulting buffer
+pub fn render_with(component: &mut impl Component, theme: &Theme) -> Buffer {
+    render_sized(component, theme, WIDTH, HEIGHT)
+}
+
+/// Render a component once with the default theme
+pub fn render(component: &mut impl Component) -> Buffer {
+    render_with(component, &Theme::default())
+}
+
+/// Text of row `y` starting at column `x`, skipping the filler cells after wide characters
+fn row_text(buffer: &Buffer, x: u16, y: u16) -> String {
+    let mut line = String::new();
+    let mut x = x;
+    while x < buffer.area.right() {
+        let symbol = buffer[(x, y)].symbol();
+        line.push_str(symbol);
+        x += Line::from(symbol).width().max(1) as u16;
+    }
+    line
+}
+
+/// Buffer rows as plain text with trailing spaces removed
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| row_text(buffer, area.left(), y).trim_end().to_string())
+        .collect()
+}
+
+/// Whether any row of the buffer contains `text`
+pub fn contains(buffer: &Buffer, text: &str) -> bool {
+    buffer_lines(buffer).iter().any(|line| line.contains(text))
+}
+
+/// Position of the first cell where `text` starts
+pub fn find(buffer: &Buffer, text: &str) -> Option<Position> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .flat_map(|y| (area.left()..area.right()).map(move |x| (x, y)))
+        .find(|&(x, y)| row_text(buffer, x, y).starts_with(text))
+        .map(|(x, y)| Position { x, y })
+}
@@ -4,45 +4,55 @@ use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThemeVariant {
+    /// Follow the terminal: CatppuccinLatte on light backgrounds, Default otherwise
+    Auto,
     Default,
     CatppuccinLatte,
     CatppuccinFrappe,
     CatppuccinMacchiato,
     CatppuccinMocha,
     Nord,
+    /// Pure black and white with saturated accents for low-vision users
+    HighContrast,
 }
 
 impl ThemeVariant {
     pub fn next(&self) -> Self {
         match self {
+            ThemeVariant::Auto => ThemeVariant::Default,
             ThemeVariant::Default => ThemeVariant::CatppuccinLatte,
             ThemeVariant::CatppuccinLatte => ThemeVariant::CatppuccinFrappe,
             ThemeVariant::CatppuccinFrappe => ThemeVariant::CatppuccinMacchiato,
             ThemeVariant::CatppuccinMacchiato => ThemeVariant::CatppuccinMocha,
             ThemeVariant::CatppuccinMocha => ThemeVariant::Nord,
-            ThemeVariant::Nord => ThemeVariant::Default,
+            ThemeVariant::Nord => ThemeVariant::HighContrast,
+            ThemeVariant::HighContrast => ThemeVariant::Auto,
         }
     }
 
     pub fn all() -> &'static [ThemeVariant] {
         &[
+            ThemeVariant::Auto,
             ThemeVariant::Default,
             ThemeVariant::CatppuccinLatte,
             ThemeVariant::CatppuccinFrappe,
             ThemeVariant::CatppuccinMacchiato,
             ThemeVariant::CatppuccinMocha,
             ThemeVariant::Nord,
+            ThemeVariant::HighContrast,
         ]
     }
 
     pub fn label(&self) -> &'static str {
         match self {
+            ThemeVariant::Auto => "自动 (跟随终端)",
             ThemeVariant::Default => "默认暗色",
             ThemeVariant::CatppuccinLatte => "Catppuccin Latte",
             ThemeVariant::CatppuccinFrappe => "Catppuccin Frappé",
             ThemeVariant::CatppuccinMacchiato => "Catppuccin Macchiato",
             ThemeVariant::CatppuccinMocha => "Catppuccin Mocha",
             ThemeVariant::Nord => "Nord",
+            ThemeVariant::HighContrast => "高对比度",
         }
     }
 }
@@ -50,12 +60,14 @@ impl ThemeVariant {
 impl fmt::Display for ThemeVariant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
+            ThemeVariant::Auto => "Auto",
             ThemeVariant::Default => "Default",
             ThemeVariant::CatppuccinLatte => "CatppuccinLatte",
             ThemeVariant::CatppuccinFrappe => "CatppuccinFrappe",
             ThemeVariant::CatppuccinMacchiato => "CatppuccinMacchiato",
             ThemeVariant::CatppuccinMocha => "CatppuccinMocha",
             ThemeVariant::Nord => "Nord",
+            ThemeVariant::HighContrast => "HighContrast",
         };
         write!(f, "{}", s)
     }
@@ -66,12 +78,14 @@ impl FromStr for ThemeVariant {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "Auto" => Ok(ThemeVariant::Auto),
             "Default" => Ok(ThemeVariant::Default),
             "CatppuccinLatte" => Ok(ThemeVariant::CatppuccinLatte),
             "CatppuccinFrappe" => Ok(ThemeVariant::CatppuccinFrappe),
             "CatppuccinMacchiato" => Ok(ThemeVariant::CatppuccinMacchiato),
             "CatppuccinMocha" => Ok(ThemeVariant::CatppuccinMocha),
             "Nord" => Ok(ThemeVariant::Nord),
+            "HighContrast" => Ok(ThemeVariant::HighContrast),
             _ => Err(()),
         }
     }
@@ -117,12 +131,15 @@ impl Default for Theme {
 impl Theme {
     pub fn from_variant(variant: ThemeVariant) -> Self {
         match variant {
+            ThemeVariant::Auto if super::terminal_bg::is_light() => Self::catppuccin_latte(),
+            ThemeVariant::Auto => Self::default_theme(),
             ThemeVariant::Default => Self::default_theme(),
             ThemeVariant::CatppuccinLatte => Self::catppuccin_latte(),
             ThemeVariant::CatppuccinFrappe => Self::catppuccin_frappe(),
             ThemeVariant::CatppuccinMacchiato => Self::catppuccin_macchiato(),
             ThemeVariant::CatppuccinMocha => Self::catppuccin_mocha(),
             ThemeVariant::Nord => Self::nord(),
+            ThemeVariant::HighContrast => Self::high_contrast(),
         }
     }
 
@@ -345,4 +362,56 @@ impl Theme {
             bilibili_cyan: nord7,
         }
     }
+
+    fn high_contrast() -> Self {
+        // Only black, white and fully saturated colors, no grays
+        Self {
+            bg_primary: Color::Black,
+            bg_secondary: Color::Black,
+            bg_modal: Color::Black,
+            bg_card: Color::Black,
+            bg_highlight: Color::Rgb(0, 0, 160),
+            bg_overlay: Color::Black,
+
+            fg_primary: Color::White,
+            fg_secondary: Color::White,
+            fg_accent: Color::Rgb(255, 255, 0),
+            fg_muted: Color::Rgb(220, 220, 220),
+
+            border_focused: Color::Rgb(255, 255, 0),
+            border_unfocused: Color::White,
+            border_subtle: Color::White,
+
+            // Selected rows keep white text, so the highlight is a dark blue
+            selection_bg: Color::Rgb(0, 0, 160),
+            selection_fg: Color::White,
+
+            success: Color::Rgb(0, 255, 0),
+            warning: Color::Rgb(255, 255, 0),
+            error: Color::Rgb(255, 80, 80),
+            info: Color::Rgb(0, 255, 255),
+
+            bilibili_pink: Color::Rgb(255, 120, 200),
+            bilibili_blue: Color::Rgb(0, 255, 255),
+            bilibili_cyan: Color::Rgb(0, 255, 255),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_roundtrip_and_cycle() {
+        let all = ThemeVariant::all();
+        for variant in all {
+            assert_eq!(variant.to_string().parse(), Ok(*variant));
+        }
+        let mut variant = all[0];
+        for expected in all.iter().skip(1).chain(all.iter().take(1)) {
+            variant = variant.next();
+            assert_eq!(variant, *expected);
+        }
+    }
 }
@@ -0,0 +1,63 @@
+//! Short-lived notice shown in a page footer
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// When each live toast runs out, drawing only happens on change so an expiry has to ask
+static EXPIRIES: Mutex<Vec<Instant>> = Mutex::new(Vec::new());
+
+/// Whether a toast ran out since the last call and the screen needs redrawing to hide it
+pub fn toast_expired() -> bool {
+    let Ok(mut expiries) = EXPIRIES.lock() else {
+        return false;
+    };
+    let now = Instant::now();
+    let live = expiries.len();
+    expiries.retain(|at| *at > now);
+    expiries.len() < live
+}
+
+pub struct Toast {
+    message: String,
+    shown_at: Instant,
+    duration: Duration,
+}
+
+impl Toast {
+    /// How long a toast stays visible
+    const DURATION: Duration = Duration::from_secs(2);
+
+    pub fn new(message: impl Into<String>) -> Self {
+        Self::with_duration(message, Self::DURATION)
+    }
+
+    /// Toast that stays up longer, for notices the user may miss at a glance
+    pub fn with_duration(message: impl Into<String>, duration: Duration) -> Self {
+        let shown_at = Instant::now();
+        if let Ok(mut expiries) = EXPIRIES.lock() {
+            expiries.push(shown_at + duration);
+        }
+        Self {
+            message: message.into(),
+            shown_at,
+            duration,
+        }
+    }
+
+    /// Message while the toast has not expired yet
+    pub fn active_message(&self) -> Option<&str> {
+        (self.shown_at.elapsed() < self.duration).then_some(self.message.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_toast_asks_for_a_redraw() {
+        let toast = Toast::with_duration("已复制链接", Duration::ZERO);
+        assert!(toast.active_message().is_none());
+        assert!(toast_expired());
+    }
+}
@@ -0,0 +1,793 @@
+//! UP主 space page listing a user's uploads in a video card grid
+
+use super::help::{self, KeyHint};
+use super::video_card::{VideoCard, VideoCardGrid};
+use super::{Component, Theme};
+use crate::api::space::{Collection, CollectionKind, SpaceVideoItem};
+use crate::api::BiliApi;
+use crate::app::AppAction;
+use crate::storage::{CoverFit, Keybindings, WatchedVideos};
+use crate::util::truncate_display;
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+use std::collections::HashSet;
+use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
+
+/// Space page requests that can fail and be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceRequest {
+    /// First page of uploads
+    Uploads,
+    /// Next page of uploads
+    More,
+    /// 合集 and 系列 list
+    Collections,
+    /// Full episode list of the collection at this index
+    Episodes(usize),
+}
+
+/// What the space page lists, switched with the section keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpaceView {
+    /// Flat upload grid, newest first
+    #[default]
+    Uploads,
+    /// 合集 and 系列 as a collapsible tree
+    Collections,
+}
+
+/// One visible line of the collection tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TreeRow {
+    Collection(usize),
+    /// Collection index, episode index
+    Episode(usize, usize),
+}
+
+pub struct UserSpacePage {
+    pub mid: i64,
+    /// Name shown until the uploads tell us otherwise
+    pub name: String,
+    pub grid: VideoCardGrid,
+    pub loading: bool,
+    pub loading_more: bool,
+    pub error_message: Option<String>,
+    /// Request behind the error being shown, re-issued by the retry key
+    failed: Option<SpaceRequest>,
+    pub page: i32,
+    pub total: i32,
+    pub view: SpaceView,
+    pub collections: Vec<Collection>,
+    /// Set once the collections have been fetched, they load on the first switch
+    collections_loaded: bool,
+    pub loading_collections: bool,
+    pub collections_error: Option<String>,
+    /// Collections shown with their episodes, by index
+    expanded: HashSet<usize>,
+    /// Collections whose full episode list has arrived, the rest show a preview
+    episodes_loaded: HashSet<usize>,
+    /// Cursor over the visible tree rows
+    pub tree_cursor: usize,
+    last_click_time: Option<Instant>,
+    last_click_index: Option<usize>,
+}
+
+impl UserSpacePage {
+    pub fn new(mid: i64, name: String) -> Self {
+        Self {
+            mid,
+            name,
+            grid: VideoCardGrid::new(),
+            loading: true,
+            loading_more: false,
+            error_message: None,
+            failed: None,
+            page: 1,
+            total: 0,
+            view: SpaceView::default(),
+            collections: Vec::new(),
+            collections_loaded: false,
+            loading_collections: false,
+            collections_error: None,
+            expanded: HashSet::new(),
+            episodes_loaded: HashSet::new(),
+            tree_cursor: 0,
+            last_click_time: None,
+            last_click_index: None,
+        }
+    }
+
+    /// Load the newest uploads
+    pub async fn load(&mut self, api_client: &impl BiliApi) {
+        self.loading = true;
+        self.error_message = None;
+        self.failed = None;
+        self.grid.clear();
+        match api_client.get_space_videos(self.mid, 1).await {
+            Ok(data) => {
+                self.page = 1;
+                self.total = data.total();
+                self.add_videos(data.videos());
+            }
+            Err(e) => {
+                self.failed = Some(SpaceRequest::Uploads);
+                self.error_message = Some(format!("加载投稿失败: {}", e));
+            }
+        }
+        self.loading = false;
+    }
+
+    pub async fn load_more(&mut self, api_client: &impl BiliApi) {
+        if self.loading || self.loading_more || !self.has_more() {
+            return;
+        }
+        self.loading_more = true;
+        match api_client.get_space_videos(self.mid, self.page + 1).await {
+            Ok(data) => {
+                self.failed = None;
+                let videos = data.videos();
+                if videos.is_empty() {
+                    // Trust the empty page over a stale count
+                    self.total = self.grid.cards.len() as i32;
+                } else {
+                    self.page += 1;
+                    self.add_videos(videos);
+                }
+            }
+            Err(_) => self.failed = Some(SpaceRequest::More),
+        }
+        self.loading_more = false;
+    }
+
+    fn add_videos(&mut self, videos: Vec<SpaceVideoItem>) {
+        if let Some(author) = videos.first().and_then(|v| v.author.as_deref()) {
+            self.name = author.to_string();
+        }
+        for video in videos {
+            let card = VideoCard::new(
+                Some(video.bvid.clone()),
+                Some(video.aid),
+                video.display_title().to_string(),
+                video.author_name().to_string(),
+                video.format_play(),
+                video.length.clone().unwrap_or_default(),
+                video.cover_url(),
+            );
+            self.grid.add_card(card);
+        }
+    }
+
+    /// Load the 合集 and 系列 list, all collapsed
+    pub async fn load_collections(&mut self, api_client: &impl BiliApi) {
+        self.loading_collections = true;
+        self.collections_error = None;
+        match api_client.get_user_collections(self.mid).await {
+            Ok(data) => {
+                self.collections = data.collections();
+                self.collections_loaded = true;
+                self.expanded.clear();
+                self.episodes_loaded.clear();
+                self.tree_cursor = 0;
+            }
+            Err(e) => {
+                self.failed = Some(SpaceRequest::Collections);
+                self.collections_error = Some(format!("加载合集失败: {}", e));
+            }
+        }
+        self.loading_collections = false;
+    }
+
+    /// Replace the preview of collection `index` with its full episode list
+    pub async fn load_episodes(&mut self, api_client: &impl BiliApi, index: usize) {
+        let Some(collection) = self.collections.get(index) else {
+            return;
+        };
+        let (kind, id) = (collection.kind, collection.id);
+        match api_client.get_collection_videos(self.mid, kind, id).await {
+            Ok(data) => {
+                if !data.archives.is_empty() {
+                    self.collections[index].episodes = data.archives;
+                }
+                self.episodes_loaded.insert(index);
+            }
+            Err(_) => self.failed = Some(SpaceRequest::Episodes(index)),
+        }
+    }
+
+    /// Switch between uploads and collections, the collections load on first use
+    fn switch_view(&mut self) -> AppAction {
+        self.view = match self.view {
+            SpaceView::Uploads => SpaceView::Collections,
+            SpaceView::Collections => SpaceView::Uploads,
+        };
+        if self.view == SpaceView::Collections
+            && !self.collections_loaded
+            && !self.loading_collections
+        {
+            return AppAction::LoadSpaceCollections;
+        }
+        AppAction::None
+    }
+
+    fn tree_rows(&self) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        for (i, collection) in self.collections.iter().enumerate() {
+            rows.push(TreeRow::Collection(i));
+            if self.expanded.contains(&i) {
+                rows.extend((0..collection.episodes.len()).map(|ep| TreeRow::Episode(i, ep)));
+            }
+        }
+        rows
+    }
+
+    fn selected_row(&self) -> Option<TreeRow> {
+        self.tree_rows().get(self.tree_cursor).copied()
+    }
+
+    /// Expand collection `index`, fetching its episodes when only a preview is known
+    fn expand(&mut self, index: usize) -> AppAction {
+        self.expanded.insert(index);
+        let Some(collection) = self.collections.get(index) else {
+            return AppAction::None;
+        };
+        if self.episodes_loaded.contains(&index)
+            || collection.episodes.len() as i32 >= collection.total
+        {
+            return AppAction::None;
+        }
+        AppAction::LoadCollectionEpisodes(index)
+    }
+
+    /// Fold collection `index` and put the cursor on its header
+    fn collapse(&mut self, index: usize) {
+        self.expanded.remove(&index);
+        if let Some(pos) = self
+            .tree_rows()
+            .iter()
+            .position(|row| *row == TreeRow::Collection(index))
+        {
+            self.tree_cursor = pos;
+        }
+    }
+
+    fn move_tree_cursor(&mut self, down: bool) {
+        let len = self.tree_rows().len();
+        self.tree_cursor = if down {
+            (self.tree_cursor + 1).min(len.saturating_sub(1))
+        } else {
+            self.tree_cursor.saturating_sub(1)
+        };
+    }
+
+    fn play_episode(&self, index: usize, episode: usize) -> Option<AppAction> {
+        let archive = self.collections.get(index)?.episodes.get(episode)?;
+        Some(AppAction::PlayFromList {
+            bvid: archive.bvid.clone(),
+            mid: self.mid,
+            aid: archive.aid,
+            title: archive.display_title().to_string(),
+            duration: archive.duration.unwrap_or(0),
+        })
+    }
+
+    fn handle_tree_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_down(key) {
+            self.move_tree_cursor(true);
+            return Some(AppAction::None);
+        }
+        if keys.matches_up(key) {
+            self.move_tree_cursor(false);
+            return Some(AppAction::None);
+        }
+        let row = self.selected_row();
+        if keys.matches_right(key) {
+            if let Some(TreeRow::Collection(i)) = row {
+                if !self.expanded.contains(&i) {
+                    return Some(self.expand(i));
+                }
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_left(key) {
+            if let Some(TreeRow::Collection(i) | TreeRow::Episode(i, _)) = row {
+                self.collapse(i);
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_confirm(key) {
+            return Some(match row {
+                Some(TreeRow::Collection(i)) if self.expanded.contains(&i) => {
+                    self.collapse(i);
+                    AppAction::None
+                }
+                Some(TreeRow::Collection(i)) => self.expand(i),
+                Some(TreeRow::Episode(i, ep)) => {
+                    self.play_episode(i, ep).unwrap_or(AppAction::None)
+                }
+                None => AppAction::None,
+            });
+        }
+        if keys.matches_download(key) {
+            if let Some(TreeRow::Episode(i, ep)) = row {
+                let bvid = self.collections[i].episodes[ep].bvid.clone();
+                return Some(AppAction::DownloadVideo(bvid));
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::LoadSpaceCollections);
+        }
+        Some(AppAction::None)
+    }
+
+    fn tree_item(&self, row: TreeRow, width: usize, theme: &Theme) -> ListItem<'static> {
+        match row {
+            TreeRow::Collection(i) => {
+                let collection = &self.collections[i];
+                let arrow = if self.expanded.contains(&i) {
+                    "▾"
+                } else {
+                    "▸"
+                };
+                let icon = match collection.kind {
+                    CollectionKind::Season => "📚",
+                    CollectionKind::Series => "🗂",
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} {} {}", arrow, icon, collection.name),
+                        Style::default()
+                            .fg(theme.fg_primary)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  共 {} 个视频", collection.total),
+                        Style::default().fg(theme.fg_muted),
+                    ),
+                ]))
+            }
+            TreeRow::Episode(i, ep) => {
+                let archive = &self.collections[i].episodes[ep];
+                let meta = format!(
+                    "  {}  ▶ {}",
+                    archive.format_duration(),
+                    archive.format_views()
+                );
+                let prefix = format!("    {:>2}. ", ep + 1);
+                let title_width = width.saturating_sub(prefix.width() + meta.width());
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, Style::default().fg(theme.fg_muted)),
+                    Span::styled(
+                        truncate_display(archive.display_title(), title_width),
+                        Style::default().fg(theme.fg_primary),
+                    ),
+                    Span::styled(meta, Style::default().fg(theme.fg_secondary)),
+                ]))
+            }
+        }
+    }
+
+    fn draw_uploads(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let title = format!(" 👤 {} 的投稿 ", self.name);
+        let plain_block = || {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.border_unfocused))
+                .title(Span::styled(
+                    title.clone(),
+                    Style::default().fg(theme.bilibili_pink),
+                ))
+        };
+
+        if self.loading {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(loading, area);
+        } else if let Some(error) = &self.error_message {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(error_widget, area);
+        } else if self.grid.cards.is_empty() {
+            let empty = Paragraph::new("还没有投稿")
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center)
+                .block(plain_block());
+            frame.render_widget(empty, area);
+        } else {
+            let header = Paragraph::new(Line::from(vec![
+                Span::styled(title, Style::default().fg(theme.bilibili_pink)),
+                Span::styled(
+                    format!("({}/{})", self.grid.cards.len(), self.total),
+                    Style::default().fg(theme.fg_muted),
+                ),
+                if self.loading_more {
+                    Span::styled(" 加载中...", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("")
+                },
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.border_subtle)),
+            );
+            let header_area = Rect { height: 2, ..area };
+            frame.render_widget(header, header_area);
+            self.grid.render(frame, Self::grid_area(area), theme);
+        }
+    }
+
+    fn draw_collections(&self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_unfocused))
+            .title(Span::styled(
+                format!(" 👤 {} 的合集 ", self.name),
+                Style::default().fg(theme.bilibili_pink),
+            ));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.loading_collections {
+            let loading = Paragraph::new("⏳ 加载中...")
+                .style(Style::default().fg(theme.warning))
+                .alignment(Alignment::Center);
+            frame.render_widget(loading, inner);
+        } else if let Some(error) = &self.collections_error {
+            let mut text = format!("❌ {}", error);
+            if self.failed.is_some() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
+                .style(Style::default().fg(theme.error))
+                .alignment(Alignment::Center);
+            frame.render_widget(error_widget, inner);
+        } else if self.collections.is_empty() {
+            let empty = Paragraph::new("还没有合集")
+                .style(Style::default().fg(theme.fg_secondary))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, inner);
+        } else {
+            let width = inner.width.saturating_sub(2) as usize;
+            let items: Vec<ListItem> = self
+                .tree_rows()
+                .into_iter()
+                .map(|row| self.tree_item(row, width, theme))
+                .collect();
+            let list = List::new(items)
+                .highlight_symbol("▎")
+                .highlight_style(Style::default().bg(theme.selection_bg));
+            let mut state = ListState::default().with_selected(Some(self.tree_cursor));
+            frame.render_stateful_widget(list, inner, &mut state);
+        }
+    }
+
+    /// Take the failed request so the app can re-issue it
+    pub fn take_failed(&mut self) -> Option<SpaceRequest> {
+        self.failed.take()
+    }
+
+    pub fn has_more(&self) -> bool {
+        (self.grid.cards.len() as i32) < self.total
+    }
+
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        self.grid.mark_watched(watched);
+    }
+
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.grid.invalidate_covers();
+    }
+
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.grid
+            .start_cover_downloads(cover_fit, concurrency, prefetch_rows, cache_size);
+    }
+
+    fn open_selected(&self, idx: usize) -> Option<AppAction> {
+        let card = self.grid.cards.get(idx)?;
+        Some(AppAction::OpenVideoDetail(
+            card.bvid.clone()?,
+            card.aid.unwrap_or(0),
+        ))
+    }
+
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        let confirm = match self.view {
+            SpaceView::Uploads => KeyHint::new(&keys.confirm, "详情"),
+            SpaceView::Collections => KeyHint::new(&keys.confirm, "展开/播放"),
+        };
+        let mut hints = vec![
+            help::navigation(keys),
+            confirm,
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::pair(&keys.section_prev, &keys.section_next, "投稿/合集"),
+            KeyHint::new(&keys.refresh, "刷新"),
+            KeyHint::new(&keys.back, "返回"),
+        ];
+        if self.failed.is_some() {
+            hints.insert(1, KeyHint::new(&keys.retry, "重试"));
+        }
+        hints
+    }
+
+    fn grid_area(area: Rect) -> Rect {
+        Rect {
+            y: area.y + 2,
+            height: area.height.saturating_sub(2),
+            ..area
+        }
+    }
+}
+
+impl Component for UserSpacePage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(10),   // Uploads grid or collection tree
+                Constraint::Length(2), // Help
+            ])
+            .split(area);
+
+        match self.view {
+            SpaceView::Uploads => self.draw_uploads(frame, chunks[0], theme, keys),
+            SpaceView::Collections => self.draw_collections(frame, chunks[0], theme, keys),
+        }
+
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
+        if keys.matches_quit(key) || keys.matches_back(key) {
+            return Some(AppAction::BackToList);
+        }
+        if keys.matches_section_prev(key) || keys.matches_section_next(key) {
+            return Some(self.switch_view());
+        }
+        if keys.matches_retry(key) && self.failed.is_some() {
+            return Some(AppAction::RetryFailed);
+        }
+        if self.view == SpaceView::Collections {
+            return self.handle_tree_input(key, keys);
+        }
+        if keys.matches_down(key) {
+            self.grid.move_down();
+            if self.grid.is_near_bottom(3) && !self.loading_more && self.has_more() {
+                return Some(AppAction::LoadMoreSpaceVideos);
+            }
+            return Some(AppAction::None);
+        }
+        if keys.matches_up(key) {
+            self.grid.move_up();
+            return Some(AppAction::None);
+        }
+        if keys.matches_right(key) {
+            self.grid.move_right();
+            return Some(AppAction::None);
+        }
+        if keys.matches_left(key) {
+            self.grid.move_left();
+            return Some(AppAction::None);
+        }
+        if keys.matches_confirm(key) {
+            return Some(
+                self.open_selected(self.grid.selected_index)
+                    .unwrap_or(AppAction::None),
+            );
+        }
+        if keys.matches_download(key) {
+            return Some(
+                self.grid
+                    .selected_bvid()
+                    .map_or(AppAction::None, AppAction::DownloadVideo),
+            );
+        }
+        if keys.matches_refresh(key) {
+            return Some(AppAction::OpenUserSpace {
+                mid: self.mid,
+                name: self.name.clone(),
+            });
+        }
+        Some(AppAction::None)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, area: Rect) -> Option<AppAction> {
+        if self.view == SpaceView::Collections {
+            match event.kind {
+                MouseEventKind::ScrollDown => self.move_tree_cursor(true),
+                MouseEventKind::ScrollUp => self.move_tree_cursor(false),
+                _ => {}
+            }
+            return None;
+        }
+        match event.kind {
+            MouseEventKind::ScrollDown => {
+                if self.grid.move_down()
+                    && self.grid.is_near_bottom(3)
+                    && !self.loading_more
+                    && self.has_more()
+                {
+                    return Some(AppAction::LoadMoreSpaceVideos);
+                }
+                None
+            }
+            MouseEventKind::ScrollUp => {
+                self.grid.move_up();
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let grid_area = Self::grid_area(area);
+                if !grid_area.contains(Position::new(event.column, event.row)) {
+                    return None;
+                }
+
+                let relative_y = event.row - grid_area.y;
+                let click_row = (relative_y / self.grid.card_height) as usize;
+                let actual_row = self.grid.scroll_row + click_row;
+                let card_width = (grid_area.width / self.grid.columns as u16).max(1);
+                let click_col = (event.column.saturating_sub(grid_area.x) / card_width) as usize;
+                let click_idx = actual_row * self.grid.columns + click_col;
+                if click_idx >= self.grid.cards.len() {
+                    return None;
+                }
+
+                let now = Instant::now();
+                let is_double_click = self.last_click_index == Some(click_idx)
+                    && self
+                        .last_click_time
+                        .is_some_and(|t| now.duration_since(t).as_millis() < 500);
+                if is_double_click {
+                    self.last_click_time = None;
+                    self.last_click_index = None;
+                    return self.open_selected(click_idx);
+                }
+                self.grid.selected_index = click_idx;
+                self.grid.update_scroll(self.grid.cached_visible_rows);
+                self.last_click_time = Some(now);
+                self.last_click_index = Some(click_idx);
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_api::MockApi;
+    use crate::ui::test_utils::{contains, render};
+
+    #[tokio::test]
+    async fn test_load_shows_uploads() {
+        let mut page = UserSpacePage::new(10001, String::new());
+        page.load(&MockApi::new()).await;
+
+        assert_eq!(page.name, "示例UP主");
+        assert_eq!(page.grid.cards.len(), 2);
+        assert!(!page.has_more());
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "示例UP主 的投稿"));
+        assert!(contains(&buffer, "(2/2)"));
+
+        let keys = Keybindings::default();
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::OpenVideoDetail(bvid, 10101)) if bvid == "BV1mock0201"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_load_error_keeps_name() {
+        let mut page = UserSpacePage::new(10001, "某UP".to_string());
+        page.load(&MockApi::failing()).await;
+
+        assert_eq!(page.name, "某UP");
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 加载投稿失败"));
+        assert!(contains(&buffer, "按 e 重试"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('e'), &Keybindings::default()),
+            Some(AppAction::RetryFailed)
+        ));
+        assert_eq!(page.take_failed(), Some(SpaceRequest::Uploads));
+    }
+
+    #[tokio::test]
+    async fn test_collections_tree_expands_and_plays() {
+        let api = MockApi::new();
+        let keys = Keybindings::default();
+        let mut page = UserSpacePage::new(10001, "示例UP主".to_string());
+        page.load(&api).await;
+
+        assert!(matches!(
+            page.handle_input(KeyCode::Char(']'), &keys),
+            Some(AppAction::LoadSpaceCollections)
+        ));
+        page.load_collections(&api).await;
+        assert_eq!(page.collections.len(), 2);
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "示例UP主 的合集"));
+        assert!(contains(&buffer, "示例合集"));
+
+        // Only a preview came with the list, expanding fetches the rest
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::LoadCollectionEpisodes(0))
+        ));
+        page.load_episodes(&api, 0).await;
+        assert_eq!(page.tree_rows().len(), 5);
+        assert!(contains(&render(&mut page), "示例合集 第三集"));
+
+        page.handle_input(KeyCode::Char('j'), &keys);
+        page.handle_input(KeyCode::Char('j'), &keys);
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            // Played straight from the list, so the UP's intro skip needs the mid with it
+            Some(AppAction::PlayFromList { bvid, mid: 10001, aid: 10202, duration: 612, .. })
+                if bvid == "BV1mock0402"
+        ));
+
+        // Folding from an episode lands on its collection
+        page.handle_input(KeyCode::Char('h'), &keys);
+        assert_eq!(page.tree_cursor, 0);
+        assert_eq!(page.tree_rows().len(), 2);
+
+        // The series preview is complete, expanding needs no request
+        page.handle_input(KeyCode::Char('j'), &keys);
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('l'), &keys),
+            Some(AppAction::None)
+        ));
+        assert_eq!(page.tree_rows().len(), 3);
+
+        // Switching back shows the uploads again without another request
+        page.handle_input(KeyCode::Char('['), &keys);
+        assert!(contains(&render(&mut page), "示例UP主 的投稿"));
+        assert!(matches!(
+            page.handle_input(KeyCode::Char(']'), &keys),
+            Some(AppAction::None)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_collections_offer_retry() {
+        let mut page = UserSpacePage::new(10001, "某UP".to_string());
+        page.view = SpaceView::Collections;
+        page.load_collections(&MockApi::failing()).await;
+
+        let buffer = render(&mut page);
+        assert!(contains(&buffer, "❌ 加载合集失败"));
+        assert!(contains(&buffer, "按 e 重试"));
+        assert_eq!(page.take_failed(), Some(SpaceRequest::Collections));
+    }
+}
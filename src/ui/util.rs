@@ -0,0 +1,42 @@
+//! Layout helpers for popups and other centered areas
+
+use ratatui::layout::Rect;
+
+/// Center a rect sized as a percentage of `area` in each direction
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let width = (area.width as u32 * percent_x.min(100) as u32 / 100) as u16;
+    let height = (area.height as u32 * percent_y.min(100) as u32 / 100) as u16;
+    centered_fixed(width, height, area)
+}
+
+/// Center a rect of a fixed size in `area`, shrinking it to fit when the area is smaller
+pub fn centered_fixed(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_centered_fixed() {
+        let area = Rect::new(10, 5, 80, 24);
+        assert_eq!(centered_fixed(40, 10, area), Rect::new(30, 12, 40, 10));
+        // Larger than the area: clamp and fill it
+        assert_eq!(centered_fixed(200, 50, area), area);
+    }
+
+    #[test]
+    fn test_centered_rect() {
+        let area = Rect::new(0, 0, 100, 40);
+        assert_eq!(centered_rect(50, 50, area), Rect::new(25, 10, 50, 20));
+        assert_eq!(centered_rect(150, 100, area), area);
+    }
+}
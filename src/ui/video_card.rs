@@ -1,7 +1,14 @@
 //! Shared video card components for grid display across pages
 
+use super::cover::{
+    cover_box, download_image, fit_cover, prefetch_range, render_cover_placeholder, spinner_frame,
+    CoverTasks, DownloadLimiter,
+};
 use super::Theme;
-use image::DynamicImage;
+use crate::api::dynamic::DynamicKind;
+use crate::api::video::RelatedVideoItem;
+use crate::storage::{CoverFit, WatchedVideos};
+use crate::util::truncate_display;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use ratatui_image::{picker::Picker, protocol::StatefulProtocol, StatefulImage};
@@ -9,10 +16,17 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+/// Pages fetched on their own after a load that leaves the screen part empty
+///
+/// Keeps a feed that keeps answering with a few items from loading forever.
+pub const MAX_TOP_UPS: usize = 3;
+
 /// Message for completed cover download
 pub struct CoverResult {
     pub index: usize,
     pub protocol: StatefulProtocol,
+    /// `CoverTasks` generation the download was started in
+    pub generation: u64,
 }
 
 /// A video card with cover image
@@ -25,6 +39,10 @@ pub struct VideoCard {
     pub duration: String,
     pub pic_url: Option<String>,
     pub cover: Option<StatefulProtocol>,
+    /// Already launched from this client
+    pub watched: bool,
+    /// Content type badge for feed items
+    pub badge: Option<DynamicKind>,
 }
 
 impl VideoCard {
@@ -46,9 +64,24 @@ impl VideoCard {
             duration,
             pic_url,
             cover: None,
+            watched: false,
+            badge: None,
         }
     }
 
+    /// Card for an archive in the related video shape, also used by tag feeds
+    pub fn from_related(video: &RelatedVideoItem) -> Self {
+        Self::new(
+            video.bvid.clone(),
+            video.aid,
+            video.title.clone().unwrap_or_else(|| "无标题".to_string()),
+            video.author_name().to_string(),
+            video.format_views(),
+            video.format_duration(),
+            video.cover_url(),
+        )
+    }
+
     /// Render a single video card
     pub fn render(&mut self, frame: &mut Frame, area: Rect, is_selected: bool, theme: &Theme) {
         // Enhanced border styling - use Bilibili pink for selection
@@ -78,12 +111,27 @@ impl VideoCard {
             Span::raw("")
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_type(border_type)
             .border_style(border_style)
             .title(title_span);
 
+        if let Some(kind) = self.badge {
+            let color = match kind {
+                DynamicKind::Video => theme.bilibili_pink,
+                DynamicKind::Draw => theme.bilibili_cyan,
+                DynamicKind::Opus => theme.fg_accent,
+            };
+            block = block.title(
+                Line::from(Span::styled(
+                    format!(" {} ", kind.badge()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ))
+                .right_aligned(),
+            );
+        }
+
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -95,47 +143,38 @@ impl VideoCard {
             ])
             .split(inner);
 
-        // Cover area - center the image horizontally
-        let cover_area = card_chunks[0];
-
-        // Calculate centered cover area (assuming 16:9 aspect ratio for video covers)
-        let target_width = cover_area.width.saturating_sub(2);
-        let centered_cover = Rect {
-            x: cover_area.x + (cover_area.width.saturating_sub(target_width)) / 2,
-            y: cover_area.y,
-            width: target_width,
-            height: cover_area.height,
-        };
-
+        let cover_area = cover_box(card_chunks[0]);
         if let Some(ref mut cover) = self.cover {
             let image_widget = StatefulImage::new();
-            frame.render_stateful_widget(image_widget, centered_cover, cover);
+            frame.render_stateful_widget(image_widget, cover_area, cover);
         } else {
-            // Modern placeholder with subtle styling
-            let placeholder = Paragraph::new("📺")
-                .style(Style::default().fg(theme.fg_muted))
-                .alignment(Alignment::Center);
-            frame.render_widget(placeholder, cover_area);
+            // Cards without a cover URL never get one
+            let label = if self.pic_url.is_some() {
+                spinner_frame()
+            } else {
+                "📺"
+            };
+            render_cover_placeholder(frame, cover_area, theme, label);
         }
 
         // Video info with improved hierarchy
         let info_area = card_chunks[1];
-        let max_title_len = (info_area.width as usize).saturating_sub(2);
-        let display_title: String = if self.title.chars().count() > max_title_len {
-            self.title
-                .chars()
-                .take(max_title_len.saturating_sub(2))
-                .collect::<String>()
-                + "…"
+        let title = if self.watched {
+            format!("✓ {}", self.title)
         } else {
             self.title.clone()
         };
+        let max_cols = (info_area.width as usize).saturating_sub(2);
+        let display_title = truncate_display(&title, max_cols);
+        let author = truncate_display(&self.author, max_cols);
 
-        // Title styling - selected items get primary color and bold
+        // Title styling - selected items get primary color and bold, watched ones are dimmed
         let title_style = if is_selected {
             Style::default()
                 .fg(theme.fg_primary)
                 .add_modifier(Modifier::BOLD)
+        } else if self.watched {
+            Style::default().fg(theme.fg_muted)
         } else {
             Style::default().fg(theme.fg_secondary)
         };
@@ -143,7 +182,7 @@ impl VideoCard {
         let info_text = Text::from(vec![
             Line::from(Span::styled(&display_title, title_style)),
             Line::from(Span::styled(
-                &self.author,
+                author,
                 Style::default().fg(theme.bilibili_cyan),
             )),
             Line::from(vec![
@@ -171,12 +210,18 @@ pub struct VideoCardGrid {
     pub cover_tx: mpsc::Sender<CoverResult>,
     pub cover_rx: mpsc::Receiver<CoverResult>,
     pub pending_downloads: HashSet<usize>,
+    download_limiter: DownloadLimiter,
+    cover_tasks: CoverTasks,
     pub cached_visible_rows: usize,
+    /// Cards hidden by [`filter`](Self::filter), with their index in the full list
+    hidden: Vec<(usize, VideoCard)>,
+    /// Moving right past the last card selects the first one and left past the first the last
+    pub wrap: bool,
 }
 
 impl VideoCardGrid {
     pub fn new() -> Self {
-        let picker = Arc::new(Picker::from_query_stdio().unwrap_or_else(|_| Picker::halfblocks()));
+        let picker = super::image_protocol::picker();
         let (cover_tx, cover_rx) = mpsc::channel(32);
 
         Self {
@@ -189,14 +234,20 @@ impl VideoCardGrid {
             cover_tx,
             cover_rx,
             pending_downloads: HashSet::new(),
+            download_limiter: DownloadLimiter::new(),
+            cover_tasks: CoverTasks::new(),
             cached_visible_rows: 3,
+            hidden: Vec::new(),
+            wrap: false,
         }
     }
 
     pub fn clear(&mut self) {
         self.cards.clear();
+        self.hidden.clear();
         self.selected_index = 0;
         self.scroll_row = 0;
+        self.cover_tasks.cancel();
         self.pending_downloads.clear();
     }
 
@@ -204,6 +255,67 @@ impl VideoCardGrid {
         self.cards.push(card);
     }
 
+    /// Flag cards whose bvid is in the local watched set
+    pub fn mark_watched(&mut self, watched: &WatchedVideos) {
+        for card in &mut self.cards {
+            card.watched = card
+                .bvid
+                .as_deref()
+                .is_some_and(|bvid| watched.contains(bvid));
+        }
+    }
+
+    /// Show only the cards matching `keep`, replacing any earlier filter
+    ///
+    /// The selection moves to where the selected card sits among the kept ones.
+    pub fn filter(&mut self, keep: impl Fn(&VideoCard) -> bool) {
+        self.unfilter();
+        // Indices shift, covers in flight would land on the wrong card
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        let selected = self.selected_index;
+        let mut kept = Vec::new();
+        let mut selected_index = None;
+        for (idx, card) in std::mem::take(&mut self.cards).into_iter().enumerate() {
+            if idx >= selected && selected_index.is_none() {
+                selected_index = Some(kept.len());
+            }
+            if keep(&card) {
+                kept.push(card);
+            } else {
+                self.hidden.push((idx, card));
+            }
+        }
+        self.cards = kept;
+        self.selected_index = selected_index
+            .unwrap_or(0)
+            .min(self.cards.len().saturating_sub(1));
+        self.scroll_row = 0;
+        self.update_scroll(self.cached_visible_rows);
+    }
+
+    /// Bring back the cards hidden by [`filter`](Self::filter) in their original order
+    pub fn unfilter(&mut self) {
+        if self.hidden.is_empty() {
+            return;
+        }
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        let selected = self.cards.get(self.selected_index).is_some();
+        for (idx, card) in std::mem::take(&mut self.hidden) {
+            if selected && idx <= self.selected_index {
+                self.selected_index += 1;
+            }
+            self.cards.insert(idx.min(self.cards.len()), card);
+        }
+        self.update_scroll(self.cached_visible_rows);
+    }
+
+    /// Number of cards including the ones hidden by the filter
+    pub fn total_cards(&self) -> usize {
+        self.cards.len() + self.hidden.len()
+    }
+
     pub fn visible_rows(&self, height: u16) -> usize {
         let available_height = height.saturating_sub(1);
         (available_height / self.card_height).max(1) as usize
@@ -248,21 +360,32 @@ impl VideoCardGrid {
     }
 
     pub fn move_right(&mut self) -> bool {
-        if !self.cards.is_empty() && self.selected_index + 1 < self.cards.len() {
+        if self.selected_index + 1 < self.cards.len() {
             self.selected_index += 1;
-            self.update_scroll(self.cached_visible_rows);
-            return true;
+        } else if self.wrap && self.cards.len() > 1 {
+            self.selected_index = 0;
+        } else {
+            return false;
         }
-        false
+        self.update_scroll(self.cached_visible_rows);
+        true
     }
 
     pub fn move_left(&mut self) -> bool {
         if !self.cards.is_empty() && self.selected_index > 0 {
             self.selected_index -= 1;
-            self.update_scroll(self.cached_visible_rows);
-            return true;
+        } else if self.wrap && self.cards.len() > 1 {
+            self.selected_index = self.cards.len() - 1;
+        } else {
+            return false;
         }
-        false
+        self.update_scroll(self.cached_visible_rows);
+        true
+    }
+
+    /// Whether the cards fill every row that fit on the last draw
+    pub fn fills_screen(&self) -> bool {
+        self.cards.len() >= self.columns * self.cached_visible_rows
     }
 
     /// Check if near bottom for pagination
@@ -276,17 +399,41 @@ impl VideoCardGrid {
     }
 
     /// Start background downloads for visible covers
-    pub fn start_cover_downloads(&mut self) {
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
         if self.cards.is_empty() {
             return;
         }
+        let semaphore = self.download_limiter.semaphore(concurrency);
+        let generation = self.cover_tasks.generation();
+
+        let range = prefetch_range(
+            self.scroll_row,
+            self.cached_visible_rows,
+            prefetch_rows,
+            self.columns,
+            self.cards.len(),
+        );
+        if !self.cover_tasks.needs_scan(&range, self.cards.len()) {
+            return;
+        }
+        let loaded = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.cover.is_some())
+            .map(|(idx, _)| idx)
+            .collect();
+        for idx in self.cover_tasks.evict(range.clone(), loaded, cache_size) {
+            self.cards[idx].cover = None;
+        }
 
-        let start = self.scroll_row * self.columns;
-        // Prefetch all visible rows plus 2 extra rows for smooth scrolling
-        let prefetch_rows = self.cached_visible_rows + 2;
-        let end = (start + self.columns * prefetch_rows).min(self.cards.len());
-
-        for idx in start..end {
+        for idx in range {
             if self.cards[idx].cover.is_some() || self.pending_downloads.contains(&idx) {
                 continue;
             }
@@ -295,36 +442,59 @@ impl VideoCardGrid {
                 self.pending_downloads.insert(idx);
                 let tx = self.cover_tx.clone();
                 let picker = Arc::clone(&self.picker);
+                let semaphore = Arc::clone(&semaphore);
 
-                tokio::spawn(async move {
+                let task = tokio::spawn(async move {
+                    let Ok(_permit) = semaphore.acquire_owned().await else {
+                        return;
+                    };
                     if let Some(img) = download_image(&pic_url).await {
-                        let protocol = picker.new_resize_protocol(img);
+                        let protocol = picker.new_resize_protocol(fit_cover(img, cover_fit));
                         let _ = tx
                             .send(CoverResult {
                                 index: idx,
                                 protocol,
+                                generation,
                             })
                             .await;
                     }
                 });
+                self.cover_tasks.track(task.abort_handle());
             }
         }
     }
 
-    /// Poll for completed cover downloads
-    pub fn poll_cover_results(&mut self) {
+    /// Poll for completed cover downloads, returning whether any cover arrived
+    pub fn poll_cover_results(&mut self) -> bool {
+        let mut arrived = false;
         while let Ok(result) = self.cover_rx.try_recv() {
+            if !self.cover_tasks.is_current(result.generation) {
+                continue;
+            }
             if result.index < self.cards.len() {
                 self.cards[result.index].cover = Some(result.protocol);
                 self.pending_downloads.remove(&result.index);
+                arrived = true;
             }
         }
+        arrived
+    }
+
+    /// Drop covers sized for the old card dimensions so they download again
+    pub fn invalidate_covers(&mut self) {
+        self.cover_tasks.cancel();
+        self.pending_downloads.clear();
+        for card in &mut self.cards {
+            card.cover = None;
+        }
     }
 
     /// Render the grid
     pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let visible_rows = self.visible_rows(area.height);
         self.cached_visible_rows = visible_rows;
+        // A resize may have pushed the selection out of view
+        self.update_scroll(visible_rows);
 
         let row_constraints: Vec<Constraint> = (0..visible_rows)
             .map(|_| Constraint::Min(self.card_height))
@@ -372,6 +542,11 @@ impl VideoCardGrid {
     pub fn selected_card(&self) -> Option<&VideoCard> {
         self.cards.get(self.selected_index)
     }
+
+    /// Bvid of the selected card, `None` for non-video cards
+    pub fn selected_bvid(&self) -> Option<String> {
+        self.selected_card()?.bvid.clone()
+    }
 }
 
 impl Default for VideoCardGrid {
@@ -379,9 +554,3 @@ impl Default for VideoCardGrid {
         Self::new()
     }
 }
-
-async fn download_image(url: &str) -> Option<DynamicImage> {
-    let response = reqwest::get(url).await.ok()?;
-    let bytes = response.bytes().await.ok()?;
-    image::load_from_memory(&bytes).ok()
-}
@@ -1,11 +1,15 @@
 //! Video detail page showing video info, comments, and related videos
 
+use super::help::{self, KeyHint};
 use super::video_card::{VideoCard, VideoCardGrid};
 use super::{Component, Theme};
 use crate::api::client::ApiClient;
-use crate::api::comment::CommentItem;
-use crate::api::video::{RelatedVideoItem, VideoInfo};
+use crate::api::comment::{self, CommentData, CommentItem, CommentSort, CommentType};
+use crate::api::tag::VideoTag;
+use crate::api::video::{default_quality, QualityOption, RelatedVideoItem, VideoInfo};
 use crate::app::AppAction;
+use crate::storage::{CoverFit, Keybindings};
+use crate::util::truncate_display;
 use ratatui::{
     crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
     prelude::*,
@@ -18,6 +22,24 @@ use std::time::Instant;
 pub enum DetailFocus {
     Comments,
     Related,
+    /// Tag row in the info block, only reachable when the video has tags
+    Tags,
+}
+
+/// Comment the input box replies to
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplyTarget {
+    pub root: i64,
+    pub parent: i64,
+    pub name: String,
+}
+
+/// Parts of the page loaded by separate requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailRequest {
+    Info,
+    Comments,
+    Related,
 }
 
 pub struct VideoDetailPage {
@@ -27,10 +49,18 @@ pub struct VideoDetailPage {
     pub comments: Vec<CommentItem>,
     pub related_videos: Vec<RelatedVideoItem>,
     pub related_card_grid: VideoCardGrid,
+    /// Tags of the video, each opens the newest videos under it
+    pub tags: Vec<VideoTag>,
+    pub selected_tag: usize,
     pub loading: bool,
     pub error_message: Option<String>,
     pub comment_page: i32,
     pub comment_scroll: usize,
+    pub comment_sort: CommentSort,
+    /// Top-level comment count reported by the reply API
+    pub comment_total: i32,
+    /// Where a page jump past the loaded comments lands once the next page arrives
+    comment_jump: Option<usize>,
     pub related_scroll: usize,
     pub focus: DetailFocus,
     pub has_more_comments: bool,
@@ -38,9 +68,24 @@ pub struct VideoDetailPage {
     pub expanded_comment: Option<i64>,
     pub comment_replies: Vec<CommentItem>,
     pub loading_replies: bool,
+    /// Cursor inside the expanded thread, `None` while on the root comment
+    pub reply_selected: Option<usize>,
+    pub reply_page: i32,
+    pub reply_total: i32,
+    pub has_more_replies: bool,
     pub liked_comments: HashSet<i64>,
     pub input_mode: bool,
     pub input_buffer: String,
+    /// Set while writing a reply, `None` for a top-level comment
+    pub reply_target: Option<ReplyTarget>,
+    /// Stream qualities from highest to lowest
+    pub qualities: Vec<QualityOption>,
+    pub selected_quality: Option<u32>,
+    pub is_vip: bool,
+    /// Seconds skipped at the start of this UP's videos, from the app config
+    pub intro_skip: i64,
+    /// Requests that failed on the last load, re-issued by the retry key
+    failed: Vec<DetailRequest>,
     last_click_time: Option<Instant>,
     last_click_index: Option<usize>,
 }
@@ -58,10 +103,15 @@ impl VideoDetailPage {
             comments: Vec::new(),
             related_videos: Vec::new(),
             related_card_grid,
+            tags: Vec::new(),
+            selected_tag: 0,
             loading: true,
             error_message: None,
             comment_page: 1,
             comment_scroll: 0,
+            comment_sort: CommentSort::default(),
+            comment_total: 0,
+            comment_jump: None,
             related_scroll: 0,
             focus: DetailFocus::Comments,
             has_more_comments: true,
@@ -69,9 +119,19 @@ impl VideoDetailPage {
             expanded_comment: None,
             comment_replies: Vec::new(),
             loading_replies: false,
+            reply_selected: None,
+            reply_page: 1,
+            reply_total: 0,
+            has_more_replies: false,
             liked_comments: HashSet::new(),
             input_mode: false,
             input_buffer: String::new(),
+            reply_target: None,
+            qualities: Vec::new(),
+            selected_quality: None,
+            is_vip: false,
+            intro_skip: 0,
+            failed: Vec::new(),
             last_click_time: None,
             last_click_index: None,
         }
@@ -80,60 +140,117 @@ impl VideoDetailPage {
     pub async fn load_data(&mut self, api_client: &ApiClient) {
         self.loading = true;
         self.error_message = None;
+        self.failed.clear();
+        for part in [
+            DetailRequest::Info,
+            DetailRequest::Comments,
+            DetailRequest::Related,
+        ] {
+            self.load_part(api_client, part).await;
+        }
+        // Tags are extra, a video without them still shows everything else
+        self.tags = api_client
+            .get_video_tags(&self.bvid)
+            .await
+            .unwrap_or_default();
+        self.selected_tag = 0;
+        self.loading = false;
+    }
 
-        // Load video info
-        match api_client.get_video_info(&self.bvid).await {
-            Ok(info) => {
-                self.video_info = Some(info);
-            }
-            Err(e) => {
-                self.error_message = Some(format!("加载视频信息失败: {}", e));
-            }
+    /// Re-issue only the requests that failed, keeping what already loaded
+    pub async fn retry_failed(&mut self, api_client: &ApiClient) {
+        self.loading = true;
+        self.error_message = None;
+        for part in std::mem::take(&mut self.failed) {
+            self.load_part(api_client, part).await;
         }
+        self.loading = false;
+    }
 
-        // Load comments
-        match api_client.get_comments(self.aid, 1).await {
-            Ok(data) => {
-                self.comments = data.replies.unwrap_or_default();
-                self.comment_page = 1;
-                if let Some(page) = data.page {
-                    self.has_more_comments = page.count.unwrap_or(0) > self.comments.len() as i32;
+    async fn load_part(&mut self, api_client: &ApiClient, part: DetailRequest) {
+        let error = match part {
+            DetailRequest::Info => match api_client.get_video_info(&self.bvid).await {
+                Ok(info) => {
+                    self.video_info = Some(info);
+                    return;
                 }
-            }
-            Err(e) => {
-                if self.error_message.is_none() {
-                    self.error_message = Some(format!("加载评论失败: {}", e));
+                Err(e) => format!("加载视频信息失败: {}", e),
+            },
+            DetailRequest::Comments => match api_client
+                .get_comments(self.aid, 1, self.comment_sort)
+                .await
+            {
+                Ok(data) => {
+                    self.set_comments(data);
+                    return;
                 }
-            }
+                Err(e) => format!("加载评论失败: {}", e),
+            },
+            DetailRequest::Related => match api_client.get_related_videos(&self.bvid).await {
+                Ok(videos) => {
+                    self.set_related(videos);
+                    return;
+                }
+                Err(e) => format!("加载相关视频失败: {}", e),
+            },
+        };
+        if self.error_message.is_none() {
+            self.error_message = Some(error);
         }
+        self.failed.push(part);
+    }
 
-        // Load related videos
-        match api_client.get_related_videos(&self.bvid).await {
-            Ok(videos) => {
-                self.related_videos = videos.clone();
-                // Populate video card grid
-                self.related_card_grid.clear();
-                for video in &videos {
-                    let card = VideoCard::new(
-                        video.bvid.clone(),
-                        video.aid,
-                        video.title.clone().unwrap_or_else(|| "无标题".to_string()),
-                        video.author_name().to_string(),
-                        video.format_views(),
-                        video.format_duration(),
-                        video.cover_url(),
-                    );
-                    self.related_card_grid.add_card(card);
-                }
-            }
-            Err(e) => {
-                if self.error_message.is_none() {
-                    self.error_message = Some(format!("加载相关视频失败: {}", e));
-                }
-            }
+    /// Replace the list with a freshly fetched first page, keeping the cursor inside it
+    fn set_comments(&mut self, data: CommentData) {
+        let total = data.page.as_ref().and_then(|page| page.count);
+        self.comments = data.into_comments();
+        self.comment_page = 1;
+        self.comment_total = total.unwrap_or(self.comments.len() as i32);
+        self.has_more_comments = self.comment_total > self.comments.len() as i32;
+        self.comment_scroll = self
+            .comment_scroll
+            .min(self.comments.len().saturating_sub(1));
+    }
+
+    fn set_related(&mut self, videos: Vec<RelatedVideoItem>) {
+        // Populate video card grid
+        self.related_card_grid.clear();
+        for video in &videos {
+            self.related_card_grid
+                .add_card(VideoCard::from_related(video));
         }
+        self.related_videos = videos;
+    }
 
-        self.loading = false;
+    /// Load the quality list and pick a default the account can play
+    pub async fn load_qualities(&mut self, api_client: &ApiClient, preferred: u32, is_vip: bool) {
+        let Some(cid) = self.video_info.as_ref().map(|info| info.cid) else {
+            return;
+        };
+        self.is_vip = is_vip;
+        if let Ok(data) = api_client.get_play_url(&self.bvid, cid).await {
+            self.qualities = data.quality_options();
+            self.selected_quality = default_quality(&self.qualities, preferred, is_vip);
+        }
+    }
+
+    /// Select the next lower quality, wrapping around and skipping VIP-only ones for non-VIP accounts
+    fn cycle_quality(&mut self) {
+        let playable: Vec<u32> = self
+            .qualities
+            .iter()
+            .filter(|q| self.is_vip || !q.need_vip)
+            .map(|q| q.qn)
+            .collect();
+        if playable.is_empty() {
+            return;
+        }
+        let next = self
+            .selected_quality
+            .and_then(|qn| playable.iter().position(|&q| q == qn))
+            .map(|idx| (idx + 1) % playable.len())
+            .unwrap_or(0);
+        self.selected_quality = Some(playable[next]);
     }
 
     pub async fn load_more_comments(&mut self, api_client: &ApiClient) {
@@ -143,8 +260,14 @@ impl VideoDetailPage {
 
         self.loading_more_comments = true;
         self.comment_page += 1;
-        match api_client.get_comments(self.aid, self.comment_page).await {
+        match api_client
+            .get_comments(self.aid, self.comment_page, self.comment_sort)
+            .await
+        {
             Ok(data) => {
+                if let Some(count) = data.page.as_ref().and_then(|p| p.count) {
+                    self.comment_total = count;
+                }
                 if let Some(replies) = data.replies {
                     if replies.is_empty() {
                         self.has_more_comments = false;
@@ -160,6 +283,20 @@ impl VideoDetailPage {
             }
         }
         self.loading_more_comments = false;
+        if let Some(target) = self.comment_jump.take() {
+            self.comment_scroll = target.min(self.comments.len().saturating_sub(1));
+        }
+    }
+
+    /// Switch between hot and newest-first comments and reload the first page
+    pub async fn cycle_comment_sort(&mut self, api_client: &ApiClient) {
+        let sort = self.comment_sort.toggle();
+        if let Ok(data) = api_client.get_comments(self.aid, 1, sort).await {
+            self.comment_sort = sort;
+            self.collapse_replies();
+            self.comment_jump = None;
+            self.set_comments(data);
+        }
     }
 
     pub async fn toggle_comment_replies(&mut self, api_client: &ApiClient) {
@@ -167,48 +304,259 @@ impl VideoDetailPage {
             return;
         }
 
-        let comment = &self.comments[self.comment_scroll];
-        let comment_rpid = comment.rpid;
+        let comment_rpid = self.comments[self.comment_scroll].rpid;
+        let reply_count = self.comments[self.comment_scroll].reply_count();
 
         // If already expanded, collapse it
         if self.expanded_comment == Some(comment_rpid) {
-            self.expanded_comment = None;
-            self.comment_replies.clear();
+            self.collapse_replies();
             return;
         }
 
         // Check if comment has replies
-        if comment.reply_count() == 0 {
+        if reply_count == 0 {
             return;
         }
 
         // Expand and load replies
-        self.expanded_comment = Some(comment_rpid);
+        self.collapse_replies();
+        self.reply_total = reply_count;
+        self.load_thread(api_client, comment_rpid).await;
+    }
+
+    /// Load the first page of `root`'s replies and keep its thread open
+    async fn load_thread(&mut self, api_client: &ApiClient, root: i64) {
+        self.expanded_comment = Some(root);
+        self.reply_page = 1;
         self.loading_replies = true;
 
         match api_client
-            .get_comment_replies(self.aid, comment_rpid, 1)
+            .get_comment_replies(self.aid, CommentType::Video.as_i32(), root, 1)
             .await
         {
             Ok(data) => {
+                if let Some(count) = data.page.as_ref().and_then(|p| p.count) {
+                    self.reply_total = count;
+                }
                 self.comment_replies = data.replies.unwrap_or_default();
+                self.has_more_replies = self.reply_total > self.comment_replies.len() as i32
+                    && !self.comment_replies.is_empty();
             }
             Err(_) => {
                 self.comment_replies.clear();
             }
         }
+        if let Some(selected) = self.reply_selected {
+            self.reply_selected = match self.comment_replies.len() {
+                0 => None,
+                len => Some(selected.min(len - 1)),
+            };
+        }
+
+        self.loading_replies = false;
+    }
+
+    /// Reload the thread of `root` after posting a reply to it
+    pub async fn refresh_thread(&mut self, api_client: &ApiClient, root: i64) {
+        if self.expanded_comment != Some(root) {
+            let Some(idx) = self.comments.iter().position(|c| c.rpid == root) else {
+                return;
+            };
+            self.collapse_replies();
+            self.comment_scroll = idx;
+        }
+        self.load_thread(api_client, root).await;
+    }
+
+    /// Reload the first page of comments after posting a top-level comment
+    pub async fn reload_comments(&mut self, api_client: &ApiClient) {
+        if let Ok(data) = api_client
+            .get_comments(self.aid, 1, self.comment_sort)
+            .await
+        {
+            self.collapse_replies();
+            self.comment_scroll = 0;
+            self.set_comments(data);
+        }
+    }
 
+    /// Reply target for the selected comment, or the selected reply inside an open thread
+    fn selected_reply_target(&self) -> Option<ReplyTarget> {
+        let comment = self.comments.get(self.comment_scroll)?;
+        let reply = self
+            .reply_selected
+            .filter(|_| self.thread_open())
+            .and_then(|i| self.comment_replies.get(i));
+        let (parent, name) = match reply {
+            Some(reply) => (reply.rpid, reply.author_name()),
+            None => (comment.rpid, comment.author_name()),
+        };
+        Some(ReplyTarget {
+            root: comment.rpid,
+            parent,
+            name: name.to_string(),
+        })
+    }
+
+    /// Fetch the next page of the expanded reply thread
+    pub async fn load_more_replies(&mut self, api_client: &ApiClient) {
+        let Some(root) = self.expanded_comment else {
+            return;
+        };
+        if !self.has_more_replies || self.loading_replies {
+            return;
+        }
+
+        self.loading_replies = true;
+        if let Ok(data) = api_client
+            .get_comment_replies(
+                self.aid,
+                CommentType::Video.as_i32(),
+                root,
+                self.reply_page + 1,
+            )
+            .await
+        {
+            let replies = data.replies.unwrap_or_default();
+            if replies.is_empty() {
+                self.has_more_replies = false;
+            } else {
+                self.reply_page += 1;
+                self.comment_replies.extend(replies);
+                self.has_more_replies = self.reply_total > self.comment_replies.len() as i32;
+            }
+        }
         self.loading_replies = false;
     }
 
+    fn collapse_replies(&mut self) {
+        self.expanded_comment = None;
+        self.comment_replies.clear();
+        self.reply_selected = None;
+        self.reply_page = 1;
+        self.reply_total = 0;
+        self.has_more_replies = false;
+    }
+
+    /// Whether the selected top-level comment has its reply thread open
+    fn thread_open(&self) -> bool {
+        self.expanded_comment.is_some()
+            && self.comments.get(self.comment_scroll).map(|c| c.rpid) == self.expanded_comment
+    }
+
+    /// Move the comment cursor down, stepping through an open reply thread first
+    fn comment_down(&mut self) -> Option<AppAction> {
+        if self.thread_open() {
+            let next = self.reply_selected.map_or(0, |i| i + 1);
+            if next < self.comment_replies.len() {
+                self.reply_selected = Some(next);
+                if next + 3 >= self.comment_replies.len()
+                    && self.has_more_replies
+                    && !self.loading_replies
+                {
+                    return Some(AppAction::LoadMoreReplies);
+                }
+                return None;
+            }
+            if self.has_more_replies || self.loading_replies {
+                return None;
+            }
+            if self.comment_scroll + 1 >= self.comments.len() {
+                return None;
+            }
+            self.collapse_replies();
+        }
+        if self.comment_scroll + 1 < self.comments.len() {
+            self.comment_scroll += 1;
+        }
+        // Check if near bottom to load more comments
+        if self.is_near_comments_bottom(10) && self.has_more_comments && !self.loading_more_comments
+        {
+            return Some(AppAction::LoadMoreComments);
+        }
+        None
+    }
+
+    /// Move the comment cursor up, leaving the thread once past its root
+    fn comment_up(&mut self) {
+        if self.thread_open() {
+            match self.reply_selected {
+                Some(0) => {
+                    self.reply_selected = None;
+                    return;
+                }
+                Some(i) => {
+                    self.reply_selected = Some(i - 1);
+                    return;
+                }
+                None => self.collapse_replies(),
+            }
+        }
+        if self.comment_scroll > 0 {
+            self.comment_scroll -= 1;
+        }
+    }
+
+    /// Jump a page of comments down, fetching the next page when it isn't loaded yet
+    fn comment_page_down(&mut self) -> Option<AppAction> {
+        if self.comments.is_empty() {
+            return None;
+        }
+        self.collapse_replies();
+        let target = self.comment_scroll + comment::PAGE_SIZE;
+        if target < self.comments.len() {
+            self.comment_scroll = target;
+        } else {
+            self.comment_scroll = self.comments.len() - 1;
+            if self.has_more_comments {
+                self.comment_jump = Some(target);
+            }
+        }
+        if self.is_near_comments_bottom(10) && self.has_more_comments && !self.loading_more_comments
+        {
+            return Some(AppAction::LoadMoreComments);
+        }
+        None
+    }
+
+    fn comment_page_up(&mut self) {
+        self.collapse_replies();
+        self.comment_scroll = self.comment_scroll.saturating_sub(comment::PAGE_SIZE);
+    }
+
+    /// "第 X 页 / 共 N 条评论" for the page holding the selected comment
+    fn comment_page_label(&self) -> String {
+        format!(
+            "第 {} 页 / 共 {} 条评论",
+            self.comment_scroll / comment::PAGE_SIZE + 1,
+            self.comment_total
+        )
+    }
+
     /// Poll for completed related video cover downloads
-    pub fn poll_cover_results(&mut self) {
-        self.related_card_grid.poll_cover_results();
+    pub fn poll_cover_results(&mut self) -> bool {
+        self.related_card_grid.poll_cover_results()
+    }
+
+    /// Rebuild covers at the current card size, e.g. after a terminal resize
+    pub fn invalidate_covers(&mut self) {
+        self.related_card_grid.invalidate_covers();
     }
 
     /// Start background downloads for visible related video covers
-    pub fn start_cover_downloads(&mut self) {
-        self.related_card_grid.start_cover_downloads();
+    pub fn start_cover_downloads(
+        &mut self,
+        cover_fit: CoverFit,
+        concurrency: usize,
+        prefetch_rows: usize,
+        cache_size: usize,
+    ) {
+        self.related_card_grid.start_cover_downloads(
+            cover_fit,
+            concurrency,
+            prefetch_rows,
+            cache_size,
+        );
     }
 
     /// Check if scrolling near bottom of comments
@@ -239,6 +587,8 @@ impl VideoDetailPage {
                     Constraint::Length(1), // Title
                     Constraint::Length(1), // Author
                     Constraint::Length(1), // Stats
+                    Constraint::Length(1), // Quality
+                    Constraint::Length(1), // Tags
                     Constraint::Min(1),    // Description
                 ])
                 .split(inner);
@@ -252,8 +602,22 @@ impl VideoDetailPage {
             frame.render_widget(title, chunks[0]);
 
             // Author
-            let author = Paragraph::new(format!("UP: {}", info.owner.name))
-                .style(Style::default().fg(theme.bilibili_pink));
+            let mut author_spans = vec![Span::styled(
+                format!("UP: {}", info.owner.name),
+                Style::default().fg(theme.bilibili_pink),
+            )];
+            if let Some(season) = &info.ugc_season {
+                let episodes = season.episodes().len();
+                let position = season
+                    .position(&info.bvid)
+                    .map(|idx| format!(" ({}/{})", idx + 1, episodes))
+                    .unwrap_or_default();
+                author_spans.push(Span::styled(
+                    format!("  📚 合集: {}{}", season.title, position),
+                    Style::default().fg(theme.info),
+                ));
+            }
+            let author = Paragraph::new(Line::from(author_spans));
             frame.render_widget(author, chunks[1]);
 
             // Stats
@@ -286,18 +650,72 @@ impl VideoDetailPage {
             ]));
             frame.render_widget(stats, chunks[2]);
 
-            // Description
-            if let Some(desc) = &info.desc {
-                let char_count = desc.chars().count();
-                let desc_text: String = if char_count > 100 {
-                    desc.chars().take(100).collect::<String>() + "..."
+            // Quality picker, VIP-only entries are grayed out for non-VIP accounts
+            let mut quality_spans = vec![Span::styled(
+                "清晰度: ",
+                Style::default().fg(theme.fg_secondary),
+            )];
+            if self.qualities.is_empty() {
+                quality_spans.push(Span::styled("自动", Style::default().fg(theme.fg_muted)));
+            }
+            for quality in &self.qualities {
+                let restricted = quality.need_vip && !self.is_vip;
+                let style = if self.selected_quality == Some(quality.qn) {
+                    Style::default()
+                        .fg(theme.bilibili_pink)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else if restricted {
+                    Style::default()
+                        .fg(theme.fg_muted)
+                        .add_modifier(Modifier::DIM)
+                } else {
+                    Style::default().fg(theme.fg_secondary)
+                };
+                let label = if restricted {
+                    format!(" {}(大会员) ", quality.label)
+                } else {
+                    format!(" {} ", quality.label)
+                };
+                quality_spans.push(Span::styled(label, style));
+            }
+            if self.intro_skip > 0 {
+                quality_spans.push(Span::styled(
+                    format!(" · 跳过片头 {} 秒", self.intro_skip),
+                    Style::default().fg(theme.info),
+                ));
+            }
+            frame.render_widget(Paragraph::new(Line::from(quality_spans)), chunks[3]);
+
+            // Tags, the selected one is highlighted while the row has focus
+            let mut tag_spans = vec![Span::styled(
+                "标签: ",
+                Style::default().fg(theme.fg_secondary),
+            )];
+            if self.tags.is_empty() {
+                tag_spans.push(Span::styled(
+                    "暂无标签",
+                    Style::default().fg(theme.fg_muted),
+                ));
+            }
+            for (i, tag) in self.tags.iter().enumerate() {
+                let style = if self.focus == DetailFocus::Tags && i == self.selected_tag {
+                    Style::default()
+                        .fg(theme.bilibili_pink)
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
                 } else {
-                    desc.clone()
+                    Style::default().fg(theme.info)
                 };
+                tag_spans.push(Span::styled(format!(" #{} ", tag.tag_name), style));
+            }
+            frame.render_widget(Paragraph::new(Line::from(tag_spans)), chunks[4]);
+
+            // Description
+            if let Some(desc) = &info.desc {
+                let desc_text = truncate_display(desc, 200);
                 let description = Paragraph::new(desc_text)
                     .style(Style::default().fg(theme.fg_secondary))
                     .wrap(Wrap { trim: true });
-                frame.render_widget(description, chunks[3]);
+                frame.render_widget(description, chunks[5]);
             }
         } else {
             let loading = Paragraph::new("加载中...")
@@ -320,7 +738,11 @@ impl VideoDetailPage {
             .border_type(BorderType::Rounded)
             .border_style(border_style)
             .title(Span::styled(
-                " 💬 评论 ",
+                format!(
+                    " 💬 评论 · {} · {} ",
+                    self.comment_sort.label(),
+                    self.comment_page_label()
+                ),
                 Style::default().fg(if is_focused {
                     theme.bilibili_pink
                 } else {
@@ -364,6 +786,12 @@ impl VideoDetailPage {
                         format!("{} ", reply_indicator),
                         Style::default().fg(theme.fg_accent),
                     ),
+                    Span::styled(
+                        if comment.pinned { "置顶 " } else { "" },
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::BOLD),
+                    ),
                     Span::styled(
                         comment.author_name(),
                         Style::default()
@@ -380,7 +808,7 @@ impl VideoDetailPage {
                     ),
                 ]),
                 Line::from(vec![Span::styled(
-                    truncate_str(comment.message(), 60),
+                    truncate_display(comment.message(), inner.width.saturating_sub(2) as usize),
                     Style::default().fg(theme.fg_primary),
                 )]),
                 Line::from(vec![Span::styled(
@@ -396,19 +824,37 @@ impl VideoDetailPage {
 
             // Show replies if expanded
             if is_expanded {
-                if self.loading_replies {
+                if self.loading_replies && self.comment_replies.is_empty() {
                     all_items.push(ListItem::new(vec![Line::from(vec![Span::styled(
                         "  ⏳ 加载回复中...",
                         Style::default().fg(theme.warning),
                     )])]));
                 } else {
-                    for reply in &self.comment_replies {
+                    for (reply_idx, reply) in self.comment_replies.iter().enumerate() {
+                        let reply_selected = is_selected && self.reply_selected == Some(reply_idx);
                         let reply_lines = vec![
                             Line::from(vec![
-                                Span::styled("    ↳ ", Style::default().fg(theme.fg_secondary)),
+                                Span::styled(
+                                    if reply_selected {
+                                        "  ▸ ↳ "
+                                    } else {
+                                        "    ↳ "
+                                    },
+                                    Style::default().fg(if reply_selected {
+                                        theme.fg_accent
+                                    } else {
+                                        theme.fg_secondary
+                                    }),
+                                ),
                                 Span::styled(
                                     reply.author_name(),
-                                    Style::default().fg(Color::Rgb(150, 150, 200)),
+                                    Style::default().fg(Color::Rgb(150, 150, 200)).add_modifier(
+                                        if reply_selected {
+                                            Modifier::BOLD
+                                        } else {
+                                            Modifier::empty()
+                                        },
+                                    ),
                                 ),
                                 Span::styled(
                                     format!("  {}", reply.format_time()),
@@ -418,7 +864,10 @@ impl VideoDetailPage {
                             Line::from(vec![
                                 Span::styled("      ", Style::default()),
                                 Span::styled(
-                                    truncate_str(reply.message(), 55),
+                                    truncate_display(
+                                        reply.message(),
+                                        inner.width.saturating_sub(8) as usize,
+                                    ),
                                     Style::default().fg(theme.fg_primary),
                                 ),
                             ]),
@@ -432,15 +881,35 @@ impl VideoDetailPage {
                         ];
                         all_items.push(ListItem::new(reply_lines));
                     }
+                    if self.loading_replies || self.has_more_replies {
+                        let status = if self.loading_replies {
+                            "⏳ 加载更多回复中..."
+                        } else {
+                            "⏬ 继续向下加载更多回复"
+                        };
+                        all_items.push(ListItem::new(vec![Line::from(vec![Span::styled(
+                            format!(
+                                "      {} ({}/{})",
+                                status,
+                                self.comment_replies.len(),
+                                self.reply_total
+                            ),
+                            Style::default().fg(theme.fg_secondary),
+                        )])]));
+                    }
                 }
             }
         }
 
-        // Calculate scroll and visible items
+        // Calculate scroll and visible items, keeping a selected reply on screen
         let visible_count = (inner.height as usize / item_height).max(1);
+        let selected_item = self.comment_scroll + self.reply_selected.map_or(0, |i| i + 1);
+        let skip = self
+            .comment_scroll
+            .max((selected_item + 1).saturating_sub(visible_count));
         let display_items: Vec<ListItem> = all_items
             .into_iter()
-            .skip(self.comment_scroll)
+            .skip(skip)
             .take(visible_count)
             .collect();
 
@@ -488,14 +957,79 @@ impl VideoDetailPage {
     }
 }
 
+impl VideoDetailPage {
+    /// Footer entries for the keys handled in the current focus
+    fn footer_hints(&self, keys: &Keybindings) -> Vec<KeyHint> {
+        if self.input_mode {
+            return vec![
+                KeyHint::fixed(
+                    "Enter",
+                    if self.reply_target.is_some() {
+                        "发送回复"
+                    } else {
+                        "发送评论"
+                    },
+                ),
+                KeyHint::fixed("Esc", "取消"),
+            ];
+        }
+        let mut hints = vec![help::scrolling(keys), KeyHint::fixed("Tab", "切换")];
+        if !self.failed.is_empty() {
+            hints.insert(0, KeyHint::new(&keys.retry, "重试"));
+        }
+        match self.focus {
+            DetailFocus::Comments if self.thread_open() => {
+                hints.push(KeyHint::new(&keys.confirm, "点赞"));
+                hints.push(KeyHint::new(&keys.reply_comment, "回复"));
+                hints.push(KeyHint::new(&keys.back, "收起回复"));
+            }
+            DetailFocus::Comments => {
+                let opens_thread = self
+                    .comments
+                    .get(self.comment_scroll)
+                    .is_some_and(|c| c.reply_count() > 0);
+                let label = if opens_thread {
+                    "展开回复"
+                } else {
+                    "点赞"
+                };
+                hints.push(KeyHint::new(&keys.confirm, label));
+                hints.push(KeyHint::new(&keys.toggle_replies, "展开/收起"));
+                hints.push(KeyHint::new(&keys.reply_comment, "回复"));
+                hints.push(KeyHint::pair(
+                    &keys.comment_page_down,
+                    &keys.comment_page_up,
+                    "翻页",
+                ));
+                hints.push(KeyHint::new(&keys.cycle_comment_sort, "排序"));
+            }
+            DetailFocus::Related => hints.push(KeyHint::new(&keys.confirm, "打开")),
+            DetailFocus::Tags => {
+                hints.push(KeyHint::pair(&keys.nav_left, &keys.nav_right, "选择标签"));
+                hints.push(KeyHint::new(&keys.confirm, "标签视频"));
+            }
+        }
+        hints.extend([
+            KeyHint::new(&keys.comment, "评论"),
+            KeyHint::new(&keys.play, "播放"),
+            KeyHint::new(&keys.cycle_quality, "清晰度"),
+            KeyHint::new(&keys.skip_intro, "跳过片头"),
+            KeyHint::new(&keys.download, "下载"),
+            KeyHint::new(&keys.open_up_space, "UP主"),
+            KeyHint::new(&keys.back, "返回"),
+        ]);
+        hints
+    }
+}
+
 impl Component for VideoDetailPage {
-    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
         // Adjust layout based on input mode
         let chunks = if self.input_mode {
             Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(6), // Video info
+                    Constraint::Length(8), // Video info
                     Constraint::Min(8),    // Comments + Related
                     Constraint::Length(3), // Input box
                     Constraint::Length(2), // Help
@@ -505,7 +1039,7 @@ impl Component for VideoDetailPage {
             Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(6), // Video info
+                    Constraint::Length(8), // Video info
                     Constraint::Min(10),   // Comments + Related
                     Constraint::Length(2), // Help
                 ])
@@ -526,7 +1060,11 @@ impl Component for VideoDetailPage {
                 );
             frame.render_widget(loading, chunks[1]);
         } else if let Some(error) = &self.error_message {
-            let error_widget = Paragraph::new(format!("❌ {}", error))
+            let mut text = format!("❌ {}", error);
+            if !self.failed.is_empty() {
+                text.push_str(&format!("\n\n按 {} 重试", help::key_label(&keys.retry)));
+            }
+            let error_widget = Paragraph::new(text)
                 .style(Style::default().fg(theme.error))
                 .alignment(Alignment::Center)
                 .block(
@@ -556,7 +1094,10 @@ impl Component for VideoDetailPage {
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.bilibili_pink))
                 .title(Span::styled(
-                    " ✏️ 发表评论 ",
+                    match &self.reply_target {
+                        Some(target) => format!(" ✏️ 回复 @{} ", target.name),
+                        None => " ✏️ 发表评论 ".to_string(),
+                    },
                     Style::default()
                         .fg(theme.bilibili_pink)
                         .add_modifier(Modifier::BOLD),
@@ -575,40 +1116,34 @@ impl Component for VideoDetailPage {
         } else {
             chunks[2]
         };
-        let help_text = if self.input_mode {
-            "[Enter] 发送评论  [Esc] 取消"
-        } else {
-            "[j/k] 滚动  [Tab] 切换  [Enter] 点赞/选择  [c] 评论  [r] 回复  [p] 播放  [q] 返回"
-        };
-        let help = Paragraph::new(help_text)
-            .style(Style::default().fg(theme.fg_secondary))
+        let help = Paragraph::new(help::hints_line(&self.footer_hints(keys), theme))
             .alignment(Alignment::Center);
         frame.render_widget(help, help_chunk);
     }
 
-    fn handle_input(
-        &mut self,
-        key: KeyCode,
-        keys: &crate::storage::Keybindings,
-    ) -> Option<AppAction> {
+    fn handle_input(&mut self, key: KeyCode, keys: &Keybindings) -> Option<AppAction> {
         // Handle input mode for adding comments
         if self.input_mode {
             match key {
                 KeyCode::Esc => {
                     self.input_mode = false;
                     self.input_buffer.clear();
+                    self.reply_target = None;
                     return Some(AppAction::None);
                 }
                 KeyCode::Enter => {
-                    if !self.input_buffer.is_empty() {
+                    if !self.input_buffer.trim().is_empty() {
+                        // Emoji codes like [doge] and @mentions are sent as typed
                         let message = self.input_buffer.clone();
                         self.input_buffer.clear();
                         self.input_mode = false;
+                        let target = self.reply_target.take();
                         return Some(AppAction::AddComment {
                             oid: self.aid,
                             comment_type: 1, // Video comment type
                             message,
-                            root: None,
+                            root: target.as_ref().map(|t| t.root),
+                            parent: target.map(|t| t.parent),
                         });
                     }
                     return Some(AppAction::None);
@@ -625,26 +1160,69 @@ impl Component for VideoDetailPage {
             }
         }
 
+        if keys.matches_back(key) && self.expanded_comment.is_some() {
+            self.collapse_replies();
+            return Some(AppAction::None);
+        }
         if keys.matches_quit(key) || keys.matches_back(key) {
             return Some(AppAction::BackToList);
         }
+        if keys.matches_retry(key) && !self.failed.is_empty() {
+            return Some(AppAction::RetryFailed);
+        }
         if keys.matches_play(key) {
-            let (cid, duration) = if let Some(info) = &self.video_info {
-                (info.cid, info.duration.unwrap_or(0))
+            let (mid, cid, duration) = if let Some(info) = &self.video_info {
+                (info.owner.mid, info.cid, info.duration.unwrap_or(0))
             } else {
-                (0, 0)
+                (0, 0, 0)
             };
             return Some(AppAction::PlayVideo {
                 bvid: self.bvid.clone(),
+                mid,
                 aid: self.aid,
                 cid,
                 duration,
+                quality: self.selected_quality,
             });
         }
+        if keys.matches_cycle_quality(key) {
+            self.cycle_quality();
+            return Some(AppAction::None);
+        }
+        if keys.matches_download(key) {
+            return Some(AppAction::DownloadVideo(self.bvid.clone()));
+        }
+        if keys.matches_skip_intro(key) {
+            return Some(self.video_info.as_ref().map_or(AppAction::None, |info| {
+                AppAction::CycleIntroSkip {
+                    mid: info.owner.mid,
+                    name: info.owner.name.clone(),
+                }
+            }));
+        }
+        if keys.matches_open_up_space(key) {
+            return Some(self.video_info.as_ref().map_or(AppAction::None, |info| {
+                AppAction::OpenUserSpace {
+                    mid: info.owner.mid,
+                    name: info.owner.name.clone(),
+                }
+            }));
+        }
         if keys.matches_comment(key) {
             // Enter comment input mode
             self.input_mode = true;
             self.input_buffer.clear();
+            self.reply_target = None;
+            return Some(AppAction::None);
+        }
+        if keys.matches_reply_comment(key) {
+            if self.focus == DetailFocus::Comments {
+                if let Some(target) = self.selected_reply_target() {
+                    self.input_mode = true;
+                    self.input_buffer.clear();
+                    self.reply_target = Some(target);
+                }
+            }
             return Some(AppAction::None);
         }
         if keys.matches_toggle_replies(key) {
@@ -653,26 +1231,32 @@ impl Component for VideoDetailPage {
             }
             return Some(AppAction::None);
         }
-        // Tab switches focus between Comments and Related (page-specific, not nav)
+        // Tab cycles focus through Comments, Related and Tags (page-specific, not nav)
         if key == KeyCode::Tab {
             self.focus = match self.focus {
                 DetailFocus::Comments => DetailFocus::Related,
-                DetailFocus::Related => DetailFocus::Comments,
+                DetailFocus::Related if !self.tags.is_empty() => DetailFocus::Tags,
+                DetailFocus::Related | DetailFocus::Tags => DetailFocus::Comments,
             };
             return Some(AppAction::None);
         }
+        if self.focus == DetailFocus::Comments {
+            if keys.matches_comment_page_down(key) {
+                return Some(self.comment_page_down().unwrap_or(AppAction::None));
+            }
+            if keys.matches_comment_page_up(key) {
+                self.comment_page_up();
+                return Some(AppAction::None);
+            }
+            if keys.matches_cycle_comment_sort(key) {
+                return Some(AppAction::CycleCommentSort);
+            }
+        }
         if keys.matches_down(key) {
             match self.focus {
                 DetailFocus::Comments => {
-                    if self.comment_scroll + 1 < self.comments.len() {
-                        self.comment_scroll += 1;
-                    }
-                    // Check if near bottom to load more comments
-                    if self.is_near_comments_bottom(10)
-                        && self.has_more_comments
-                        && !self.loading_more_comments
-                    {
-                        return Some(AppAction::LoadMoreComments);
+                    if let Some(action) = self.comment_down() {
+                        return Some(action);
                     }
                 }
                 DetailFocus::Related => {
@@ -680,21 +1264,19 @@ impl Component for VideoDetailPage {
                         self.related_scroll = self.related_card_grid.selected_index;
                     }
                 }
+                DetailFocus::Tags => {}
             }
             return Some(AppAction::None);
         }
         if keys.matches_up(key) {
             match self.focus {
-                DetailFocus::Comments => {
-                    if self.comment_scroll > 0 {
-                        self.comment_scroll -= 1;
-                    }
-                }
+                DetailFocus::Comments => self.comment_up(),
                 DetailFocus::Related => {
                     if self.related_card_grid.move_up() {
                         self.related_scroll = self.related_card_grid.selected_index;
                     }
                 }
+                DetailFocus::Tags => {}
             }
             return Some(AppAction::None);
         }
@@ -702,26 +1284,39 @@ impl Component for VideoDetailPage {
             if self.focus == DetailFocus::Related && self.related_card_grid.move_left() {
                 self.related_scroll = self.related_card_grid.selected_index;
             }
+            if self.focus == DetailFocus::Tags {
+                self.selected_tag = self.selected_tag.saturating_sub(1);
+            }
             return Some(AppAction::None);
         }
         if keys.matches_right(key) {
             if self.focus == DetailFocus::Related && self.related_card_grid.move_right() {
                 self.related_scroll = self.related_card_grid.selected_index;
             }
+            if self.focus == DetailFocus::Tags && self.selected_tag + 1 < self.tags.len() {
+                self.selected_tag += 1;
+            }
             return Some(AppAction::None);
         }
         if keys.matches_confirm(key) {
             match self.focus {
                 DetailFocus::Comments => {
-                    // Like the currently selected comment
-                    if self.comment_scroll < self.comments.len() {
-                        let comment = &self.comments[self.comment_scroll];
-                        return Some(AppAction::LikeComment {
-                            oid: self.aid,
-                            rpid: comment.rpid,
-                            comment_type: 1,
-                        });
+                    let Some(comment) = self.comments.get(self.comment_scroll) else {
+                        return Some(AppAction::None);
+                    };
+                    // Open the reply thread first, then Enter likes the selected entry
+                    if !self.thread_open() && comment.reply_count() > 0 {
+                        return Some(AppAction::ToggleCommentReplies);
                     }
+                    let rpid = match self.reply_selected.filter(|_| self.thread_open()) {
+                        Some(i) => self.comment_replies.get(i).map_or(comment.rpid, |r| r.rpid),
+                        None => comment.rpid,
+                    };
+                    return Some(AppAction::LikeComment {
+                        oid: self.aid,
+                        rpid,
+                        comment_type: 1,
+                    });
                 }
                 DetailFocus::Related => {
                     if let Some(card) = self.related_card_grid.selected_card() {
@@ -731,6 +1326,14 @@ impl Component for VideoDetailPage {
                         }
                     }
                 }
+                DetailFocus::Tags => {
+                    if let Some(tag) = self.tags.get(self.selected_tag) {
+                        return Some(AppAction::OpenTag {
+                            tag_id: tag.tag_id,
+                            name: tag.tag_name.clone(),
+                        });
+                    }
+                }
             }
             return Some(AppAction::None);
         }
@@ -746,14 +1349,8 @@ impl Component for VideoDetailPage {
             MouseEventKind::ScrollDown => {
                 match self.focus {
                     DetailFocus::Comments => {
-                        if self.comment_scroll + 1 < self.comments.len() {
-                            self.comment_scroll += 1;
-                            if self.is_near_comments_bottom(10)
-                                && self.has_more_comments
-                                && !self.loading_more_comments
-                            {
-                                return Some(AppAction::LoadMoreComments);
-                            }
+                        if let Some(action) = self.comment_down() {
+                            return Some(action);
                         }
                     }
                     DetailFocus::Related => {
@@ -761,21 +1358,19 @@ impl Component for VideoDetailPage {
                             self.related_scroll = self.related_card_grid.selected_index;
                         }
                     }
+                    DetailFocus::Tags => {}
                 }
                 None
             }
             MouseEventKind::ScrollUp => {
                 match self.focus {
-                    DetailFocus::Comments => {
-                        if self.comment_scroll > 0 {
-                            self.comment_scroll -= 1;
-                        }
-                    }
+                    DetailFocus::Comments => self.comment_up(),
                     DetailFocus::Related => {
                         if self.related_card_grid.move_up() {
                             self.related_scroll = self.related_card_grid.selected_index;
                         }
                     }
+                    DetailFocus::Tags => {}
                 }
                 None
             }
@@ -855,13 +1450,189 @@ impl Component for VideoDetailPage {
     }
 }
 
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.chars().count() > max_len {
-        s.chars()
-            .take(max_len.saturating_sub(3))
-            .collect::<String>()
-            + "..."
-    } else {
-        s.to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quality(qn: u32, need_vip: bool) -> QualityOption {
+        QualityOption {
+            qn,
+            label: qn.to_string(),
+            need_vip,
+        }
+    }
+
+    #[test]
+    fn test_cycle_quality_skips_vip_only_for_non_vip() {
+        let mut page = VideoDetailPage::new("BV17x411w7KC".to_string(), 170001);
+        page.qualities = vec![quality(116, true), quality(80, false), quality(64, false)];
+        page.selected_quality = Some(80);
+
+        page.cycle_quality();
+        assert_eq!(page.selected_quality, Some(64));
+        page.cycle_quality();
+        assert_eq!(page.selected_quality, Some(80));
+
+        page.is_vip = true;
+        page.selected_quality = Some(64);
+        page.cycle_quality();
+        assert_eq!(page.selected_quality, Some(116));
+    }
+
+    fn comment(rpid: i64, rcount: i32) -> CommentItem {
+        CommentItem {
+            rpid,
+            oid: 170001,
+            mid: 0,
+            parent: 0,
+            count: None,
+            rcount: Some(rcount),
+            floor: None,
+            ctime: None,
+            like: None,
+            member: None,
+            content: None,
+            replies: None,
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_reply_thread_navigation() {
+        let keys = Keybindings::default();
+        let mut page = VideoDetailPage::new("BV17x411w7KC".to_string(), 170001);
+        page.has_more_comments = false;
+        page.comments = vec![comment(1, 2), comment(2, 0)];
+
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::ToggleCommentReplies)
+        ));
+
+        // Simulate the thread having loaded
+        page.expanded_comment = Some(1);
+        page.comment_replies = vec![comment(11, 0), comment(12, 0)];
+        page.reply_total = 2;
+
+        page.handle_input(KeyCode::Down, &keys);
+        assert_eq!(page.reply_selected, Some(0));
+        page.handle_input(KeyCode::Down, &keys);
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::LikeComment { rpid: 12, .. })
+        ));
+
+        // Replying inside the thread targets the selected reply
+        page.handle_input(KeyCode::Char('C'), &keys);
+        assert_eq!(
+            page.reply_target,
+            Some(ReplyTarget {
+                root: 1,
+                parent: 12,
+                name: "匿名".to_string(),
+            })
+        );
+        page.input_buffer = "[doge] @UP".to_string();
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::AddComment {
+                root: Some(1),
+                parent: Some(12),
+                ..
+            })
+        ));
+        assert!(!page.input_mode);
+        assert_eq!(page.reply_target, None);
+
+        // Esc collapses the thread instead of leaving the page
+        assert!(matches!(
+            page.handle_input(KeyCode::Esc, &keys),
+            Some(AppAction::None)
+        ));
+        assert_eq!(page.expanded_comment, None);
+        assert_eq!(page.reply_selected, None);
+        assert_eq!(page.comment_scroll, 0);
+
+        // Stepping past the last reply leaves the thread
+        page.expanded_comment = Some(1);
+        page.comment_replies = vec![comment(11, 0)];
+        page.handle_input(KeyCode::Down, &keys);
+        page.handle_input(KeyCode::Down, &keys);
+        assert_eq!(page.comment_scroll, 1);
+        assert_eq!(page.expanded_comment, None);
+    }
+
+    #[test]
+    fn test_comment_page_jumps() {
+        let keys = Keybindings::default();
+        let mut page = VideoDetailPage::new("BV17x411w7KC".to_string(), 170001);
+        page.loading = false;
+        page.comments = (0..30).map(|i| comment(i, 0)).collect();
+        page.comment_total = 45;
+
+        page.handle_input(KeyCode::PageDown, &keys);
+        assert_eq!(page.comment_scroll, 20);
+        assert_eq!(page.comment_page_label(), "第 2 页 / 共 45 条评论");
+
+        // Past the loaded comments the jump waits for the next page
+        assert!(matches!(
+            page.handle_input(KeyCode::PageDown, &keys),
+            Some(AppAction::LoadMoreComments)
+        ));
+        assert_eq!(page.comment_scroll, 29);
+        assert_eq!(page.comment_jump, Some(40));
+
+        page.handle_input(KeyCode::PageUp, &keys);
+        assert_eq!(page.comment_scroll, 9);
+        page.handle_input(KeyCode::PageUp, &keys);
+        assert_eq!(page.comment_scroll, 0);
+
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('o'), &keys),
+            Some(AppAction::CycleCommentSort)
+        ));
+
+        // A shorter list after switching the sort keeps the cursor in bounds
+        page.comment_scroll = 25;
+        page.set_comments(CommentData {
+            page: None,
+            replies: Some(vec![comment(100, 0), comment(101, 0)]),
+            ..CommentData::default()
+        });
+        assert_eq!(page.comment_scroll, 1);
+        assert_eq!(page.comment_total, 2);
+        assert!(!page.has_more_comments);
+    }
+
+    #[test]
+    fn test_tab_reaches_tags_only_when_present() {
+        let keys = Keybindings::default();
+        let mut page = VideoDetailPage::new("BV1mock0001".to_string(), 1);
+        page.loading = false;
+
+        page.handle_input(KeyCode::Tab, &keys);
+        page.handle_input(KeyCode::Tab, &keys);
+        assert!(page.focus == DetailFocus::Comments);
+
+        page.tags = vec![
+            VideoTag {
+                tag_id: 4010,
+                tag_name: "编程".to_string(),
+            },
+            VideoTag {
+                tag_id: 1628,
+                tag_name: "Rust".to_string(),
+            },
+        ];
+        page.handle_input(KeyCode::Tab, &keys);
+        page.handle_input(KeyCode::Tab, &keys);
+        assert!(page.focus == DetailFocus::Tags);
+        page.handle_input(KeyCode::Char('l'), &keys);
+        page.handle_input(KeyCode::Char('l'), &keys);
+        assert_eq!(page.selected_tag, 1);
+        assert!(matches!(
+            page.handle_input(KeyCode::Enter, &keys),
+            Some(AppAction::OpenTag { tag_id: 1628, ref name }) if name == "Rust"
+        ));
     }
 }
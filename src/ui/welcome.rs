@@ -0,0 +1,175 @@
+//! Welcome screen shown once on the very first launch, before the login page
+
+use super::help::key_label;
+use super::util::centered_fixed;
+use super::{Component, Theme};
+use crate::app::AppAction;
+use crate::storage::Keybindings;
+use ratatui::{
+    crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind},
+    prelude::*,
+    widgets::*,
+};
+
+/// External programs playback and downloads rely on
+pub const DEPENDENCIES: [(&str, &str); 2] = [("mpv", "播放视频"), ("yt-dlp", "解析和下载视频")];
+
+pub struct WelcomePage {
+    /// Whether each of [`DEPENDENCIES`] was found on `PATH`
+    pub installed: Vec<bool>,
+}
+
+impl WelcomePage {
+    pub fn new(installed: Vec<bool>) -> Self {
+        Self { installed }
+    }
+
+    /// Look the dependencies up on `PATH`
+    pub fn detect() -> Self {
+        Self::new(
+            DEPENDENCIES
+                .iter()
+                .map(|(program, _)| crate::player::is_installed(program))
+                .collect(),
+        )
+    }
+
+    fn heading(text: &str, theme: &Theme) -> Line<'static> {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(theme.bilibili_pink)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn key_line(binding: String, label: &str, theme: &Theme) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:<10}", binding),
+                Style::default()
+                    .fg(theme.fg_accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(label.to_string(), Style::default().fg(theme.fg_secondary)),
+        ])
+    }
+}
+
+impl Component for WelcomePage {
+    fn draw(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, keys: &Keybindings) {
+        let text_style = Style::default().fg(theme.fg_secondary);
+        let mut lines = vec![
+            Self::heading("扫码登录", theme),
+            Line::styled(
+                "  用哔哩哔哩手机客户端扫描下一页的二维码，也可以切换为手动填写 Cookie",
+                text_style,
+            ),
+            Line::from(""),
+            Self::heading("外部工具", theme),
+        ];
+        for ((program, purpose), installed) in DEPENDENCIES.iter().zip(&self.installed) {
+            let (status, color) = if *installed {
+                ("已安装", theme.success)
+            } else {
+                ("未找到", theme.error)
+            };
+            lines.push(Line::from(vec![
+                Span::styled("  ● ", Style::default().fg(color)),
+                Span::styled(
+                    format!("{:<8}", program),
+                    Style::default()
+                        .fg(theme.fg_primary)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:<6}", status), Style::default().fg(color)),
+                Span::styled(format!(" {}", purpose), text_style),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Self::heading("常用按键", theme));
+        let nav = format!(
+            "{}{}{}{}",
+            key_label(&keys.nav_left),
+            key_label(&keys.nav_down),
+            key_label(&keys.nav_up),
+            key_label(&keys.nav_right)
+        );
+        for (binding, label) in [
+            (nav, "移动"),
+            (key_label(&keys.confirm), "打开"),
+            (key_label(&keys.back), "返回"),
+            (key_label(&keys.play), "播放"),
+            (key_label(&keys.nav_next_page), "切换页面"),
+            (key_label(&keys.search_focus), "搜索"),
+            (key_label(&keys.open_settings), "设置（可修改按键）"),
+            (key_label(&keys.quit), "退出"),
+        ] {
+            lines.push(Self::key_line(binding, label, theme));
+        }
+        lines.push(Line::from(""));
+        lines.push(
+            Line::styled(
+                "按任意键继续",
+                Style::default()
+                    .fg(theme.fg_muted)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .alignment(Alignment::Center),
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.bilibili_pink))
+            .title(Span::styled(
+                " 欢迎使用 bilibili-tui ",
+                Style::default()
+                    .fg(theme.bilibili_pink)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_alignment(Alignment::Center);
+        let panel = centered_fixed(76, lines.len() as u16 + 4, area);
+        frame.render_widget(Clear, panel);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            panel,
+        );
+    }
+
+    fn handle_input(&mut self, _key: KeyCode, _keys: &Keybindings) -> Option<AppAction> {
+        Some(AppAction::DismissWelcome)
+    }
+
+    fn handle_mouse(&mut self, event: MouseEvent, _area: Rect) -> Option<AppAction> {
+        matches!(event.kind, MouseEventKind::Down(MouseButton::Left))
+            .then_some(AppAction::DismissWelcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::test_utils::{contains, find, render, render_sized};
+
+    #[test]
+    fn test_welcome_marks_dependencies() {
+        let theme = Theme::default();
+        let mut page = WelcomePage::new(vec![true, false]);
+        let buffer = render_sized(&mut page, &theme, 100, 40);
+        assert!(contains(&buffer, "扫码登录"));
+        let found = find(&buffer, "已安装").expect("mpv status is shown");
+        assert_eq!(buffer[found].fg, theme.success);
+        let missing = find(&buffer, "未找到").expect("yt-dlp status is shown");
+        assert_eq!(buffer[missing].fg, theme.error);
+        assert!(contains(&render(&mut page), "欢迎使用"));
+
+        let keys = Keybindings::default();
+        assert!(matches!(
+            page.handle_input(KeyCode::Char('x'), &keys),
+            Some(AppAction::DismissWelcome)
+        ));
+    }
+}
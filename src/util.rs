@@ -0,0 +1,252 @@
+//! Text and URL helpers shared by the pages
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Cut `s` to at most `max_cols` terminal columns, ending with `…` when shortened
+///
+/// CJK characters and most emoji take two columns, so counting chars is not enough. The
+/// cut lands between grapheme clusters, so a ZWJ emoji or a letter with combining marks
+/// is kept or dropped whole.
+pub fn truncate_display(s: &str, max_cols: usize) -> String {
+    if s.width() <= max_cols {
+        return s.to_string();
+    }
+    if max_cols == 0 {
+        return String::new();
+    }
+
+    // Leave one column for the ellipsis
+    let budget = max_cols - 1;
+    let mut used = 0;
+    let mut out = String::new();
+    for grapheme in s.graphemes(true) {
+        let width = grapheme.width();
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        out.push_str(grapheme);
+    }
+    out.push('…');
+    out
+}
+
+/// Wrap `text` into lines of at most `max_cols` terminal columns
+///
+/// Lines break at whitespace where they can. A word wider than a line, like a CJK
+/// sentence without spaces, is broken between grapheme clusters instead.
+pub fn wrap_display(text: &str, max_cols: usize) -> Vec<String> {
+    let max_cols = max_cols.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut used = 0;
+
+    for word in text.split_whitespace() {
+        let width = word.width();
+        let gap = usize::from(!line.is_empty());
+        if used + gap + width <= max_cols {
+            if gap > 0 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            used += gap + width;
+            continue;
+        }
+        if width <= max_cols {
+            lines.push(std::mem::take(&mut line));
+            line.push_str(word);
+            used = width;
+            continue;
+        }
+
+        // Too wide for any line, fill up the current one and carry on below
+        if gap > 0 {
+            if used + gap < max_cols {
+                line.push(' ');
+                used += gap;
+            } else {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+        }
+        for grapheme in word.graphemes(true) {
+            let width = grapheme.width();
+            if used + width > max_cols && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            line.push_str(grapheme);
+            used += width;
+        }
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Give protocol-relative image URLs (`//i0.hdslb.com/...`) an https scheme
+///
+/// The API returns these for covers, avatars and dynamic images, reqwest rejects them.
+pub fn normalize_image_url(url: &str) -> String {
+    let url = url.trim();
+    match url.strip_prefix("//") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
+
+/// `url` with a `t` parameter in whole seconds, so it opens at that moment of the video
+///
+/// Without a position (or at 0) the plain URL is kept.
+pub fn timestamped_url(url: &str, seconds: Option<i64>) -> String {
+    match seconds {
+        Some(t) if t > 0 => {
+            let sep = if url.contains('?') { '&' } else { '?' };
+            format!("{}{}t={}", url, sep, t)
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// Playback position as "mm:ss", or "h:mm:ss" from an hour on
+pub fn format_clock(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (h, m, s) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(0), "00:00");
+        assert_eq!(format_clock(754), "12:34");
+        assert_eq!(format_clock(3725), "1:02:05");
+    }
+
+    #[test]
+    fn test_timestamped_url() {
+        let url = "https://www.bilibili.com/video/BV1xx411c7mD";
+        assert_eq!(
+            timestamped_url(url, Some(123)),
+            "https://www.bilibili.com/video/BV1xx411c7mD?t=123"
+        );
+        assert_eq!(timestamped_url(url, Some(0)), url);
+        assert_eq!(timestamped_url(url, None), url);
+        assert_eq!(
+            timestamped_url(
+                "https://www.bilibili.com/bangumi/play/ep1?from=search",
+                Some(5)
+            ),
+            "https://www.bilibili.com/bangumi/play/ep1?from=search&t=5"
+        );
+    }
+
+    #[test]
+    fn test_normalize_image_url() {
+        assert_eq!(
+            normalize_image_url("//i0.hdslb.com/bfs/archive/a.jpg"),
+            "https://i0.hdslb.com/bfs/archive/a.jpg"
+        );
+        assert_eq!(
+            normalize_image_url("http://i0.hdslb.com/bfs/face/b.jpg"),
+            "http://i0.hdslb.com/bfs/face/b.jpg"
+        );
+        assert_eq!(
+            normalize_image_url("https://i0.hdslb.com/bfs/new_dyn/c.png"),
+            "https://i0.hdslb.com/bfs/new_dyn/c.png"
+        );
+    }
+
+    #[test]
+    fn test_short_strings_are_unchanged() {
+        assert_eq!(truncate_display("hello", 5), "hello");
+        assert_eq!(truncate_display("你好", 4), "你好");
+        assert_eq!(truncate_display("", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_respects_column_budget() {
+        let samples = [
+            "Rust 编程语言入门教程 第一集",
+            "【4K】🎬 电影解说：Inception 盗梦空间",
+            "ascii only title that is rather long",
+            "😀😀😀😀😀😀",
+            "全角ＡＢＣ混合abc",
+        ];
+        for s in samples {
+            for max in 0..=s.width() + 2 {
+                let out = truncate_display(s, max);
+                assert!(out.width() <= max, "{:?} at {} -> {:?}", s, max, out);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_char_is_not_split() {
+        // "你好世界" is 8 columns, 5 columns leave room for two CJK chars and the ellipsis
+        assert_eq!(truncate_display("你好世界", 5), "你好…");
+        assert_eq!(truncate_display("ab你好", 4), "ab…");
+        assert_eq!(truncate_display("你好", 1), "…");
+    }
+
+    #[test]
+    fn test_grapheme_clusters_are_not_split() {
+        let samples = [
+            "👨\u{200d}👩\u{200d}👧 一家人的周末 vlog",
+            "Cafe\u{301} 探店：e\u{301}clair 测评",
+            "🇨🇳🇯🇵 中日对比 Part 2",
+        ];
+        for s in samples {
+            for max in 0..=s.width() + 2 {
+                let out = truncate_display(s, max);
+                assert!(out.width() <= max, "{:?} at {} -> {:?}", s, max, out);
+                let kept = out.strip_suffix('…').unwrap_or(&out);
+                assert!(
+                    s.grapheme_indices(true)
+                        .map(|(i, _)| i)
+                        .chain([s.len()])
+                        .any(|i| i == kept.len()),
+                    "{:?} at {} -> {:?}",
+                    s,
+                    max,
+                    out
+                );
+            }
+        }
+        assert!(
+            !truncate_display("👨\u{200d}👩\u{200d}👧👨\u{200d}👩\u{200d}👧", 3)
+                .contains("\u{200d}…")
+        );
+    }
+
+    #[test]
+    fn test_wrap_breaks_words_and_cjk_by_width() {
+        assert_eq!(
+            wrap_display("hello rust world", 10),
+            ["hello rust", "world"]
+        );
+        assert_eq!(
+            wrap_display("今天天气很好我们去公园玩", 10),
+            ["今天天气很", "好我们去公", "园玩"]
+        );
+        assert_eq!(
+            wrap_display("UP主 发布了新视频：Rust异步编程详解", 12),
+            ["UP主 发布了", "新视频：Rust", "异步编程详解"]
+        );
+        assert_eq!(wrap_display("   ", 10), [""]);
+        for line in wrap_display("【4K】🎬 电影解说：Inception 盗梦空间 完整版", 9)
+        {
+            assert!(line.width() <= 9, "{:?}", line);
+        }
+    }
+}